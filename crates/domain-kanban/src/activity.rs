@@ -0,0 +1,204 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_util::{Entity, Identifier, RepositoryError};
+use serde::{Deserialize, Serialize};
+use shaku::Interface;
+use thiserror::Error;
+
+use crate::{board::BoardId, user::UserId};
+
+pub type ActivityId = Identifier<Activity>;
+
+/// 誰がいつ何をどう変更したかの記録
+/// 追記のみで、一度記録したものは変更しない
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Activity {
+    id: ActivityId,
+    actor: UserId,
+    action: ActivityAction,
+    /// 操作対象のID (`<entity_type>-<ULID>`)
+    target: String,
+    /// 操作対象が属するボード。ボードのフィードに表示するために使う
+    board_id: Option<BoardId>,
+    before: Option<String>,
+    after: Option<String>,
+    occurred_at: DateTime<Utc>,
+}
+
+impl Activity {
+    pub fn new<T: Entity>(actor: UserId, action: ActivityAction, target: &Identifier<T>) -> Self {
+        Self {
+            id: ActivityId::gen(),
+            actor,
+            action,
+            target: target.to_string(),
+            board_id: None,
+            before: None,
+            after: None,
+            occurred_at: Utc::now(),
+        }
+    }
+
+    pub fn on_board(mut self, board_id: BoardId) -> Self {
+        self.board_id = Some(board_id);
+        self
+    }
+
+    pub fn with_change(mut self, before: impl Into<String>, after: impl Into<String>) -> Self {
+        self.before = Some(before.into());
+        self.after = Some(after.into());
+        self
+    }
+
+    pub fn id(&self) -> &ActivityId {
+        &self.id
+    }
+
+    pub fn actor(&self) -> &UserId {
+        &self.actor
+    }
+
+    pub fn action(&self) -> ActivityAction {
+        self.action
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    pub fn board_id(&self) -> Option<&BoardId> {
+        self.board_id.as_ref()
+    }
+
+    pub fn before(&self) -> Option<&str> {
+        self.before.as_deref()
+    }
+
+    pub fn after(&self) -> Option<&str> {
+        self.after.as_deref()
+    }
+
+    pub fn occurred_at(&self) -> &DateTime<Utc> {
+        &self.occurred_at
+    }
+}
+
+impl Entity for Activity {
+    fn entity_type() -> &'static str {
+        "activity"
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityAction {
+    UserRenamed,
+    BoardRenamed,
+    ColumnsReordered,
+    CommentAdded,
+    CommentEdited,
+    CommentDeleted,
+    ChecklistItemAdded,
+    ChecklistItemToggled,
+    ChecklistItemReordered,
+    ChecklistItemDeleted,
+    Archived,
+    Restored,
+}
+
+impl ActivityAction {
+    /// 永続化するときの表現
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UserRenamed => "user_renamed",
+            Self::BoardRenamed => "board_renamed",
+            Self::ColumnsReordered => "columns_reordered",
+            Self::CommentAdded => "comment_added",
+            Self::CommentEdited => "comment_edited",
+            Self::CommentDeleted => "comment_deleted",
+            Self::ChecklistItemAdded => "checklist_item_added",
+            Self::ChecklistItemToggled => "checklist_item_toggled",
+            Self::ChecklistItemReordered => "checklist_item_reordered",
+            Self::ChecklistItemDeleted => "checklist_item_deleted",
+            Self::Archived => "archived",
+            Self::Restored => "restored",
+        }
+    }
+}
+
+impl FromStr for ActivityAction {
+    type Err = UnknownActivityAction;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "user_renamed" => Ok(Self::UserRenamed),
+            "board_renamed" => Ok(Self::BoardRenamed),
+            "columns_reordered" => Ok(Self::ColumnsReordered),
+            "comment_added" => Ok(Self::CommentAdded),
+            "comment_edited" => Ok(Self::CommentEdited),
+            "comment_deleted" => Ok(Self::CommentDeleted),
+            "checklist_item_added" => Ok(Self::ChecklistItemAdded),
+            "checklist_item_toggled" => Ok(Self::ChecklistItemToggled),
+            "checklist_item_reordered" => Ok(Self::ChecklistItemReordered),
+            "checklist_item_deleted" => Ok(Self::ChecklistItemDeleted),
+            "archived" => Ok(Self::Archived),
+            "restored" => Ok(Self::Restored),
+            _ => Err(UnknownActivityAction(s.to_owned())),
+        }
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("不明なアクションです: {0}")]
+pub struct UnknownActivityAction(String);
+
+/// Activityを記録するリポジトリのインターフェース
+#[async_trait]
+pub trait ActivityRepository: Interface {
+    /// Activityを追記する
+    async fn append(&self, activity: Activity) -> Result<(), RepositoryError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn activity_new_test() {
+        let actor = UserId::gen();
+        let board_id = BoardId::gen();
+        let activity = Activity::new(actor.clone(), ActivityAction::BoardRenamed, &board_id)
+            .on_board(board_id.clone())
+            .with_change("before", "after");
+
+        assert_eq!(activity.actor(), &actor);
+        assert_eq!(activity.target(), board_id.to_string());
+        assert_eq!(activity.board_id(), Some(&board_id));
+        assert_eq!(activity.before(), Some("before"));
+        assert_eq!(activity.after(), Some("after"));
+        assert!(activity.id().to_string().starts_with("activity-"));
+    }
+
+    #[test]
+    fn activity_action_round_trip() {
+        for action in [
+            ActivityAction::UserRenamed,
+            ActivityAction::BoardRenamed,
+            ActivityAction::ColumnsReordered,
+            ActivityAction::CommentAdded,
+            ActivityAction::CommentEdited,
+            ActivityAction::CommentDeleted,
+            ActivityAction::ChecklistItemAdded,
+            ActivityAction::ChecklistItemToggled,
+            ActivityAction::ChecklistItemReordered,
+            ActivityAction::ChecklistItemDeleted,
+            ActivityAction::Archived,
+            ActivityAction::Restored,
+        ] {
+            assert_eq!(action.as_str().parse(), Ok(action));
+        }
+        assert!("unknown".parse::<ActivityAction>().is_err());
+    }
+}
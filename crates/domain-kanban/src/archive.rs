@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_util::{InvariantError, InvariantResult, RepositoryError};
+use serde::{Deserialize, Serialize};
+use shaku::Interface;
+
+/// Board, Column, Cardのアーカイブ状態。アーカイブした日時を持つ
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ArchiveState(Option<DateTime<Utc>>);
+
+impl ArchiveState {
+    pub fn archived_at(&self) -> Option<&DateTime<Utc>> {
+        self.0.as_ref()
+    }
+
+    pub fn is_archived(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// `before` より前にアーカイブされたか。削除の対象になる
+    pub fn is_archived_before(&self, before: &DateTime<Utc>) -> bool {
+        self.0.as_ref().map_or(false, |at| at < before)
+    }
+
+    pub fn archive(&mut self) -> InvariantResult<()> {
+        if self.is_archived() {
+            return Err(InvariantError::ViolationError(
+                "すでにアーカイブされています".to_owned(),
+            ));
+        }
+        self.0 = Some(Utc::now());
+        Ok(())
+    }
+
+    pub fn restore(&mut self) -> InvariantResult<()> {
+        if !self.is_archived() {
+            return Err(InvariantError::ViolationError(
+                "アーカイブされていません".to_owned(),
+            ));
+        }
+        self.0 = None;
+        Ok(())
+    }
+}
+
+/// 保持期間を過ぎたアーカイブ済みのBoard, Column, Cardを完全に削除する
+#[async_trait]
+pub trait ArchivePurger: Interface {
+    /// `archived_before` より前にアーカイブされたものを削除する
+    /// 削除するBoardに含まれるColumn, 削除するColumnに含まれるCardも一緒に削除する
+    async fn purge_archived(
+        &self,
+        archived_before: DateTime<Utc>,
+    ) -> Result<PurgeReport, RepositoryError>;
+}
+
+/// 削除した件数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PurgeReport {
+    pub boards: usize,
+    pub columns: usize,
+    pub cards: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_archive_restore() -> InvariantResult<()> {
+        let mut state = ArchiveState::default();
+        assert!(!state.is_archived());
+        assert!(state.restore().is_err());
+
+        state.archive()?;
+        assert!(state.is_archived());
+        assert!(state.archive().is_err());
+
+        state.restore()?;
+        assert_eq!(state.archived_at(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_archived_before() -> InvariantResult<()> {
+        let mut state = ArchiveState::default();
+        let now = Utc::now();
+        assert!(!state.is_archived_before(&now));
+
+        state.archive()?;
+        assert!(state.is_archived_before(&(now + Duration::days(1))));
+        assert!(!state.is_archived_before(&(now - Duration::days(1))));
+        Ok(())
+    }
+}
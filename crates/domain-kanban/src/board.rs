@@ -1,15 +1,25 @@
-use crate::{column::ColumnId, user::UserId};
-use domain_util::{Entity, Identifier, InvariantError, InvariantResult};
+use std::{collections::HashSet, fmt::Display};
+
+use crate::{archive::ArchiveState, column::ColumnId, outbox::OutboxMessage, user::UserId};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_util::{Entity, Identifier, InvariantError, InvariantResult, RepositoryError, Version};
 use invariant_sheild::{invariant_sheild, InvariantSheild};
+use serde::{Deserialize, Serialize};
+use shaku::Interface;
 
 #[allow(unused)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Board {
     id: BoardId,
     title: BoardTitle,
     owner: UserId,
     members: Vec<UserId>,
     column_ids: Vec<ColumnId>,
+    #[serde(default)]
+    archived_at: ArchiveState,
+    #[serde(default)]
+    version: Version,
 }
 
 impl Entity for Board {
@@ -26,6 +36,18 @@ impl Board {
         owner: UserId,
         members: Vec<UserId>,
         column_ids: Vec<ColumnId>,
+    ) -> InvariantResult<Self> {
+        Self::new_with_version(id, title, owner, members, column_ids, Version::initial())
+    }
+
+    /// 保存済みのバージョンを含めてBoardモデルを復元する
+    pub fn new_with_version(
+        id: BoardId,
+        title: BoardTitle,
+        owner: UserId,
+        members: Vec<UserId>,
+        column_ids: Vec<ColumnId>,
+        version: Version,
     ) -> InvariantResult<Self> {
         let result = Self {
             id,
@@ -33,11 +55,106 @@ impl Board {
             owner,
             members,
             column_ids,
+            archived_at: ArchiveState::default(),
+            version,
         };
         result.satisfy_sheilds()
     }
 
-    const MAX_COLUMN_COUNT: usize = 10;
+    pub fn id(&self) -> &BoardId {
+        &self.id
+    }
+
+    pub fn title(&self) -> &BoardTitle {
+        &self.title
+    }
+
+    pub fn owner(&self) -> &UserId {
+        &self.owner
+    }
+
+    pub fn members(&self) -> &[UserId] {
+        &self.members
+    }
+
+    pub fn column_ids(&self) -> &[ColumnId] {
+        &self.column_ids
+    }
+
+    /// 読み込んだ時点のバージョン
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    pub fn update_title(&mut self, title: BoardTitle) {
+        self.title = title;
+    }
+
+    /// カラムを末尾に加える
+    pub fn add_column(&mut self, column_id: ColumnId) -> InvariantResult<()> {
+        if self.column_ids.contains(&column_id) {
+            return Err(InvariantError::ViolationError(format!(
+                "カラム {} はすでにボードにあります",
+                column_id
+            )));
+        }
+        self.column_ids.push(column_id);
+        if let Err(e) = self.column_count_lower_than_max() {
+            self.column_ids.pop();
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// カラムを取り除く。ボードにないカラムは無視する
+    /// 取り除いたカラムがあればtrueを返す
+    pub fn remove_columns(&mut self, column_ids: &[ColumnId]) -> bool {
+        let before = self.column_ids.len();
+        self.column_ids.retain(|id| !column_ids.contains(id));
+        self.column_ids.len() != before
+    }
+
+    /// メンバーに加える。所有者やすでにメンバーのユーザーの場合は何もせずにfalseを返す
+    pub fn add_member(&mut self, user_id: UserId) -> bool {
+        if self.owner == user_id || self.members.contains(&user_id) {
+            return false;
+        }
+        self.members.push(user_id);
+        true
+    }
+
+    /// カラムを `column_ids` の順に並べ替える
+    /// `column_ids` はボードのカラムをちょうど1回ずつ含んでいなければならない
+    pub fn reorder_columns(&mut self, column_ids: Vec<ColumnId>) -> InvariantResult<()> {
+        let requested: HashSet<_> = column_ids.iter().collect();
+        let current: HashSet<_> = self.column_ids.iter().collect();
+        let same_columns = column_ids.len() == self.column_ids.len() && requested == current;
+        if !same_columns {
+            return Err(InvariantError::ViolationError(
+                "ボードのカラムをすべて1回ずつ指定してください".to_owned(),
+            ));
+        }
+        self.column_ids = column_ids;
+        Ok(())
+    }
+
+    pub fn archived_at(&self) -> Option<&DateTime<Utc>> {
+        self.archived_at.archived_at()
+    }
+
+    pub fn is_archived_before(&self, before: &DateTime<Utc>) -> bool {
+        self.archived_at.is_archived_before(before)
+    }
+
+    pub fn archive(&mut self) -> InvariantResult<()> {
+        self.archived_at.archive()
+    }
+
+    pub fn restore(&mut self) -> InvariantResult<()> {
+        self.archived_at.restore()
+    }
+
+    pub const MAX_COLUMN_COUNT: usize = 10;
     #[sheild]
     fn column_count_lower_than_max(&self) -> InvariantResult<()> {
         if self.column_ids.len() > Self::MAX_COLUMN_COUNT {
@@ -51,7 +168,26 @@ impl Board {
 
 pub type BoardId = Identifier<Board>;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// Boardモデルを保存するリポジトリのインターフェース
+#[async_trait]
+pub trait BoardRepository: Interface {
+    /// Boardを保存する
+    /// 保存済みのバージョンが `board.version()` と異なる場合は `RepositoryError::Conflict` を返す
+    async fn save(&self, board: Board) -> Result<(), RepositoryError>;
+    /// Boardを保存し、`messages` を同じトランザクションでアウトボックスに保存する
+    async fn save_with_outbox(
+        &self,
+        board: Board,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError>;
+    /// BoardをIDで検索する
+    async fn find_by_id(&self, id: &BoardId) -> Result<Option<Board>, RepositoryError>;
+    /// 複数のBoardをIDで検索する
+    /// 結果は `ids` と同じ順番に並び、見つからなかったIDの位置は `None` になる
+    async fn find_many(&self, ids: &[BoardId]) -> Result<Vec<Option<Board>>, RepositoryError>;
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct BoardTitle(String);
 
 #[invariant_sheild(InvariantError)]
@@ -73,6 +209,12 @@ impl BoardTitle {
     }
 }
 
+impl Display for BoardTitle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -96,8 +238,109 @@ mod tests {
         assert_eq!(board.owner, owner);
         assert_eq!(board.members, members);
         assert_eq!(board.column_ids, column_ids);
+        assert_eq!(board.version, Version::initial());
+        Ok(())
+    }
+
+    #[test]
+    fn test_board_update_title() -> InvariantResult<()> {
+        let mut board = Board::new(
+            BoardId::gen(),
+            BoardTitle::new("title".to_owned())?,
+            UserId::gen(),
+            vec![],
+            vec![],
+        )?;
+        let new_title = BoardTitle::new("new title".to_owned())?;
+        board.update_title(new_title.clone());
+        assert_eq!(board.title(), &new_title);
+        assert_eq!(board.title().to_string(), "new title");
         Ok(())
     }
+
+    #[test]
+    fn test_board_reorder_columns() -> InvariantResult<()> {
+        let (todo, doing, done) = (ColumnId::gen(), ColumnId::gen(), ColumnId::gen());
+        let mut board = Board::new(
+            BoardId::gen(),
+            BoardTitle::new("title".to_owned())?,
+            UserId::gen(),
+            vec![],
+            vec![todo.clone(), done.clone(), doing.clone()],
+        )?;
+
+        board.reorder_columns(vec![todo.clone(), doing.clone(), done.clone()])?;
+        assert_eq!(
+            board.column_ids(),
+            [todo.clone(), doing.clone(), done.clone()]
+        );
+
+        // 足りない・重複している・ボードにないカラムは並べ替えられない
+        let invalid = [
+            vec![todo.clone(), doing.clone()],
+            vec![todo.clone(), doing.clone(), doing.clone()],
+            vec![todo.clone(), doing.clone(), ColumnId::gen()],
+        ];
+        for column_ids in invalid {
+            assert!(board.reorder_columns(column_ids).is_err());
+        }
+        assert_eq!(board.column_ids(), [todo, doing, done]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_board_add_column_and_member() -> InvariantResult<()> {
+        let owner = UserId::gen();
+        let column_ids: Vec<_> = (0..Board::MAX_COLUMN_COUNT - 1)
+            .map(|_| ColumnId::gen())
+            .collect();
+        let mut board = Board::new(
+            BoardId::gen(),
+            BoardTitle::new("title".to_owned())?,
+            owner.clone(),
+            vec![],
+            column_ids.clone(),
+        )?;
+
+        // 重複するカラムや上限を超えるカラムは加えられない
+        assert!(board.add_column(column_ids[0].clone()).is_err());
+        board.add_column(ColumnId::gen())?;
+        assert!(board.add_column(ColumnId::gen()).is_err());
+        assert_eq!(board.column_ids().len(), Board::MAX_COLUMN_COUNT);
+
+        // ボードにないカラムは無視する
+        assert!(board.remove_columns(&[column_ids[0].clone(), ColumnId::gen()]));
+        assert!(!board.remove_columns(&[column_ids[0].clone()]));
+        assert_eq!(board.column_ids().len(), Board::MAX_COLUMN_COUNT - 1);
+
+        let member = UserId::gen();
+        assert!(board.add_member(member.clone()));
+        assert!(!board.add_member(member.clone()));
+        assert!(!board.add_member(owner));
+        assert_eq!(board.members(), [member]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_board_archive_restore() -> InvariantResult<()> {
+        let mut board = Board::new(
+            BoardId::gen(),
+            BoardTitle::new("title".to_owned())?,
+            UserId::gen(),
+            vec![],
+            vec![],
+        )?;
+        assert!(board.archived_at().is_none());
+
+        board.archive()?;
+        assert!(board.archived_at().is_some());
+        assert!(board.is_archived_before(&(Utc::now() + chrono::Duration::days(1))));
+
+        board.restore()?;
+        assert!(board.archived_at().is_none());
+        Ok(())
+    }
+
     #[test]
     fn test_board_new_with_error() -> InvariantResult<()> {
         let id = BoardId::gen();
@@ -1,26 +1,107 @@
-use domain_util::{Entity, Identifier};
+mod checklist;
+pub use checklist::*;
+
+use std::fmt::Display;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_util::{Entity, Identifier, InvariantError, InvariantResult, RepositoryError, Version};
+use serde::{Deserialize, Serialize};
+use shaku::Interface;
+
+use crate::{archive::ArchiveState, outbox::OutboxMessage};
 
 #[allow(unused)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Column {
     id: ColumnId,
     title: ColumnTitle,
     cards: Vec<Card>,
+    #[serde(default)]
+    archived_at: ArchiveState,
+    #[serde(default)]
+    version: Version,
 }
 
 impl Column {
     pub fn new(title: ColumnTitle) -> Self {
+        Self::new_with_id(ColumnId::gen(), title)
+    }
+
+    pub fn new_with_id(id: ColumnId, title: ColumnTitle) -> Self {
         Self {
-            id: ColumnId::gen(),
+            id,
             title,
             cards: vec![],
+            archived_at: ArchiveState::default(),
+            version: Version::initial(),
         }
     }
 
+    pub fn id(&self) -> &ColumnId {
+        &self.id
+    }
+
+    pub fn title(&self) -> &ColumnTitle {
+        &self.title
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// 読み込んだ時点のバージョン
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    pub fn archived_at(&self) -> Option<&DateTime<Utc>> {
+        self.archived_at.archived_at()
+    }
+
+    pub fn is_archived_before(&self, before: &DateTime<Utc>) -> bool {
+        self.archived_at.is_archived_before(before)
+    }
+
+    pub fn archive(&mut self) -> InvariantResult<()> {
+        self.archived_at.archive()
+    }
+
+    pub fn restore(&mut self) -> InvariantResult<()> {
+        self.archived_at.restore()
+    }
+
+    /// `before` より前にアーカイブされたカードを削除し、削除した数を返す
+    pub fn purge_archived_cards(&mut self, before: &DateTime<Utc>) -> usize {
+        let count = self.cards.len();
+        self.cards.retain(|card| !card.is_archived_before(before));
+        count - self.cards.len()
+    }
+
     pub fn add_card(mut self, title: CardTitle) -> Self {
         self.cards.push(Card::new(title));
         self
     }
 
+    pub fn add_card_with_description(
+        mut self,
+        title: CardTitle,
+        description: CardDescription,
+    ) -> Self {
+        self.cards.push(Card::with_description(title, description));
+        self
+    }
+
+    pub fn add_card_with_id(
+        mut self,
+        id: CardId,
+        title: CardTitle,
+        description: CardDescription,
+    ) -> Self {
+        self.cards.push(Card::new_with_id(id, title, description));
+        self
+    }
+
     pub fn remove_card(mut self, index: usize) -> Self {
         assert!(index < self.cards.len());
 
@@ -42,6 +123,40 @@ impl Column {
 
         self.cards.get_mut(index)
     }
+
+    /// カードをカラムから取り出す。ほかのカラムへ移すときに使う
+    pub fn take_card(&mut self, id: &CardId) -> InvariantResult<Card> {
+        let index = self
+            .cards
+            .iter()
+            .position(|card| &card.id == id)
+            .ok_or_else(|| {
+                InvariantError::ViolationError(format!("カード {} はこのカラムにありません", id))
+            })?;
+        Ok(self.cards.remove(index))
+    }
+
+    /// カードを `index` の位置に入れる。`index` がカード数と同じときは末尾に入れる
+    pub fn insert_card(&mut self, index: usize, card: Card) -> InvariantResult<()> {
+        if index > self.cards.len() {
+            return Err(InvariantError::ViolationError(format!(
+                "カードの位置は{}以下にしてください",
+                self.cards.len()
+            )));
+        }
+        self.cards.insert(index, card);
+        Ok(())
+    }
+
+    /// カラム内のカードをIDで探す
+    pub fn find_card_mut(&mut self, id: &CardId) -> InvariantResult<&mut Card> {
+        self.cards
+            .iter_mut()
+            .find(|card| &card.id == id)
+            .ok_or_else(|| {
+                InvariantError::ViolationError(format!("カード {} はこのカラムにありません", id))
+            })
+    }
 }
 
 impl Entity for Column {
@@ -52,7 +167,25 @@ impl Entity for Column {
 
 pub type ColumnId = Identifier<Column>;
 
+/// Columnモデルを保存するリポジトリのインターフェース
+/// Cardはカラムの一部として保存する
+#[async_trait]
+pub trait ColumnRepository: Interface {
+    /// Columnを保存する
+    /// 保存済みのバージョンが `column.version()` と異なる場合は `RepositoryError::Conflict` を返す
+    async fn save(&self, column: Column) -> Result<(), RepositoryError>;
+    /// Columnを保存し、`messages` を同じトランザクションでアウトボックスに保存する
+    async fn save_with_outbox(
+        &self,
+        column: Column,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError>;
+    /// ColumnをIDで検索する
+    async fn find_by_id(&self, id: &ColumnId) -> Result<Option<Column>, RepositoryError>;
+}
+
 #[allow(unused)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ColumnTitle(String);
 
 impl ColumnTitle {
@@ -61,13 +194,27 @@ impl ColumnTitle {
     }
 }
 
+impl Display for ColumnTitle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[allow(unused)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Card {
     // idは参考用
     id: CardId,
     title: CardTitle,
     description: CardDescription,
     // status: String,
+    #[serde(default)]
+    checklist: Checklist,
+    #[serde(default)]
+    archived_at: ArchiveState,
+    /// 期限。NULLは期限なし
+    #[serde(default)]
+    due_at: Option<DateTime<Utc>>,
 }
 
 impl Card {
@@ -76,16 +223,71 @@ impl Card {
             id: CardId::gen(),
             title,
             description: CardDescription::new("".to_owned()),
+            checklist: Checklist::default(),
+            archived_at: ArchiveState::default(),
+            due_at: None,
         }
     }
     pub fn with_description(title: CardTitle, description: CardDescription) -> Self {
+        Self::new_with_id(CardId::gen(), title, description)
+    }
+
+    pub fn new_with_id(id: CardId, title: CardTitle, description: CardDescription) -> Self {
         Self {
-            id: CardId::gen(),
+            id,
             title,
             description,
+            checklist: Checklist::default(),
+            archived_at: ArchiveState::default(),
+            due_at: None,
         }
     }
 
+    pub fn id(&self) -> &CardId {
+        &self.id
+    }
+
+    pub fn title(&self) -> &CardTitle {
+        &self.title
+    }
+
+    pub fn description(&self) -> &CardDescription {
+        &self.description
+    }
+
+    pub fn checklist(&self) -> &Checklist {
+        &self.checklist
+    }
+
+    pub fn checklist_mut(&mut self) -> &mut Checklist {
+        &mut self.checklist
+    }
+
+    pub fn archived_at(&self) -> Option<&DateTime<Utc>> {
+        self.archived_at.archived_at()
+    }
+
+    pub fn is_archived_before(&self, before: &DateTime<Utc>) -> bool {
+        self.archived_at.is_archived_before(before)
+    }
+
+    pub fn archive(&mut self) -> InvariantResult<()> {
+        self.archived_at.archive()
+    }
+
+    pub fn restore(&mut self) -> InvariantResult<()> {
+        self.archived_at.restore()
+    }
+
+    pub fn due_at(&self) -> Option<&DateTime<Utc>> {
+        self.due_at.as_ref()
+    }
+
+    /// `None` の場合は期限をなくす
+    pub fn set_due_at(&mut self, due_at: Option<DateTime<Utc>>) {
+        self.due_at = due_at;
+    }
+
     pub fn edit_title(mut self, new_title: CardTitle) -> Self {
         self.title = new_title;
         self
@@ -106,6 +308,7 @@ impl Entity for Card {
 pub type CardId = Identifier<Card>;
 
 #[allow(unused)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CardTitle(String);
 
 impl CardTitle {
@@ -114,7 +317,14 @@ impl CardTitle {
     }
 }
 
+impl Display for CardTitle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[allow(unused)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CardDescription(String);
 
 impl CardDescription {
@@ -123,6 +333,12 @@ impl CardDescription {
     }
 }
 
+impl Display for CardDescription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,6 +379,80 @@ mod tests {
         assert!(description.0 == s);
     }
 
+    #[test]
+    fn column_find_card_mut_test() {
+        let TestValues { card_title1, .. } = init();
+        let mut column = Column::new(ColumnTitle::new("todo".to_owned())).add_card(card_title1);
+        let card_id = column.cards()[0].id().clone();
+
+        let text = ChecklistItemText::new("check".to_owned()).unwrap();
+        column
+            .find_card_mut(&card_id)
+            .unwrap()
+            .checklist_mut()
+            .add(text)
+            .unwrap();
+
+        assert_eq!(column.cards()[0].checklist().progress().total, 1);
+        assert!(column.find_card_mut(&CardId::gen()).is_err());
+    }
+
+    #[test]
+    fn column_take_and_insert_card_test() {
+        let TestValues {
+            card_title1,
+            card_title2,
+            ..
+        } = init();
+        let mut src = Column::new(ColumnTitle::new("todo".to_owned())).add_card(card_title1);
+        let mut dst = Column::new(ColumnTitle::new("done".to_owned())).add_card(card_title2);
+        let card_id = src.cards()[0].id().clone();
+
+        let card = src.take_card(&card_id).unwrap();
+        assert!(dst.insert_card(2, card.clone()).is_err());
+        dst.insert_card(0, card).unwrap();
+
+        assert!(src.cards().is_empty());
+        assert_eq!(dst.cards()[0].id(), &card_id);
+        assert_eq!(dst.cards().len(), 2);
+        assert!(src.take_card(&card_id).is_err());
+    }
+
+    #[test]
+    fn column_purge_archived_cards_test() {
+        let TestValues {
+            card_title1,
+            card_title2,
+            ..
+        } = init();
+        let mut column = Column::new(ColumnTitle::new("todo".to_owned()))
+            .add_card(card_title1)
+            .add_card(card_title2);
+        let card_id = column.cards()[0].id().clone();
+        column.find_card_mut(&card_id).unwrap().archive().unwrap();
+
+        let tomorrow = Utc::now() + chrono::Duration::days(1);
+        let yesterday = Utc::now() - chrono::Duration::days(1);
+
+        assert_eq!(column.purge_archived_cards(&yesterday), 0);
+        assert_eq!(column.purge_archived_cards(&tomorrow), 1);
+        assert_eq!(column.cards().len(), 1);
+        assert_ne!(column.cards()[0].id(), &card_id);
+    }
+
+    #[test]
+    fn card_set_due_at_test() {
+        let TestValues { card_title1, .. } = init();
+        let mut card = Card::new(card_title1);
+        let tomorrow = Utc::now() + chrono::Duration::days(1);
+
+        assert!(card.due_at().is_none());
+        card.set_due_at(Some(tomorrow));
+        assert_eq!(card.due_at(), Some(&tomorrow));
+        card.set_due_at(None);
+        assert!(card.due_at().is_none());
+    }
+
     #[test]
     fn card_edit_test() {
         let TestValues {
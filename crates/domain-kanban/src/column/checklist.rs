@@ -0,0 +1,209 @@
+use std::fmt::Display;
+
+use domain_util::{InvariantError, InvariantResult};
+use invariant_sheild::{invariant_sheild, InvariantSheild};
+use serde::{Deserialize, Serialize};
+
+/// カード内の順序付きのチェックリスト
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Checklist(Vec<ChecklistItem>);
+
+#[invariant_sheild(InvariantError)]
+impl Checklist {
+    pub fn new(items: Vec<ChecklistItem>) -> InvariantResult<Self> {
+        Self(items).satisfy_sheilds()
+    }
+
+    pub fn items(&self) -> &[ChecklistItem] {
+        &self.0
+    }
+
+    pub fn progress(&self) -> ChecklistProgress {
+        ChecklistProgress {
+            done: self.0.iter().filter(|item| item.done).count(),
+            total: self.0.len(),
+        }
+    }
+
+    /// 末尾に未完了の項目を追加する
+    pub fn add(&mut self, text: ChecklistItemText) -> InvariantResult<()> {
+        let mut items = self.0.clone();
+        items.push(ChecklistItem::new(text));
+        *self = Self::new(items)?;
+        Ok(())
+    }
+
+    /// 完了状態を反転し、反転後の状態を返す
+    pub fn toggle(&mut self, index: usize) -> InvariantResult<bool> {
+        self.ensure_index(index)?;
+        let item = &mut self.0[index];
+        item.done = !item.done;
+        Ok(item.done)
+    }
+
+    pub fn reorder(&mut self, src_index: usize, dst_index: usize) -> InvariantResult<()> {
+        self.ensure_index(src_index)?;
+        self.ensure_index(dst_index)?;
+        let item = self.0.remove(src_index);
+        self.0.insert(dst_index, item);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, index: usize) -> InvariantResult<ChecklistItem> {
+        self.ensure_index(index)?;
+        Ok(self.0.remove(index))
+    }
+
+    fn ensure_index(&self, index: usize) -> InvariantResult<()> {
+        if index < self.0.len() {
+            Ok(())
+        } else {
+            Err(InvariantError::ViolationError(format!(
+                "チェックリストの{}番目の項目はありません",
+                index
+            )))
+        }
+    }
+
+    pub const MAX_ITEM_COUNT: usize = 50;
+    #[sheild]
+    fn item_count_lower_than_max(&self) -> InvariantResult<()> {
+        if self.0.len() > Self::MAX_ITEM_COUNT {
+            return Err(InvariantError::ViolationError(
+                "チェックリストの項目は50個までです".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    text: ChecklistItemText,
+    done: bool,
+}
+
+impl ChecklistItem {
+    pub fn new(text: ChecklistItemText) -> Self {
+        Self { text, done: false }
+    }
+
+    pub fn text(&self) -> &ChecklistItemText {
+        &self.text
+    }
+
+    pub fn done(&self) -> bool {
+        self.done
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChecklistProgress {
+    pub done: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChecklistItemText(String);
+
+#[invariant_sheild(InvariantError)]
+impl ChecklistItemText {
+    pub fn new(text: String) -> InvariantResult<Self> {
+        Self(text).satisfy_sheilds()
+    }
+
+    #[sheild]
+    fn text_is_not_blank(&self) -> InvariantResult<()> {
+        if self.0.trim().is_empty() {
+            return Err(InvariantError::ViolationError(
+                "項目を入力してください".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+
+    const MAX_LENGTH: usize = 100;
+    #[sheild]
+    fn text_lower_than_max(&self) -> InvariantResult<()> {
+        if self.0.chars().count() > Self::MAX_LENGTH {
+            return Err(InvariantError::ViolationError(
+                "項目は100文字以内にしてください".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Display for ChecklistItemText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> ChecklistItemText {
+        ChecklistItemText::new(s.to_owned()).unwrap()
+    }
+
+    #[test]
+    fn test_checklist_operations() -> InvariantResult<()> {
+        let mut checklist = Checklist::default();
+        checklist.add(text("本を選ぶ"))?;
+        checklist.add(text("棚を買う"))?;
+        checklist.add(text("組み立てる"))?;
+
+        assert!(checklist.toggle(1)?);
+        checklist.reorder(1, 0)?;
+        let removed = checklist.remove(2)?;
+
+        let texts: Vec<_> = checklist
+            .items()
+            .iter()
+            .map(|i| i.text().to_string())
+            .collect();
+        assert_eq!(texts, vec!["棚を買う", "本を選ぶ"]);
+        assert_eq!(removed.text().to_string(), "組み立てる");
+        assert_eq!(
+            checklist.progress(),
+            ChecklistProgress { done: 1, total: 2 }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_checklist_out_of_range() {
+        let mut checklist = Checklist::default();
+        assert!(checklist.toggle(0).is_err());
+        assert!(checklist.reorder(0, 0).is_err());
+        assert!(checklist.remove(0).is_err());
+    }
+
+    #[test]
+    fn test_checklist_max_item_count() -> InvariantResult<()> {
+        let mut checklist = Checklist::default();
+        for i in 0..Checklist::MAX_ITEM_COUNT {
+            checklist.add(text(&i.to_string()))?;
+        }
+
+        let result = checklist.add(text("over"));
+
+        assert_eq!(
+            result,
+            Err(InvariantError::ViolationError(
+                "チェックリストの項目は50個までです".to_owned()
+            ))
+        );
+        assert_eq!(checklist.items().len(), Checklist::MAX_ITEM_COUNT);
+        Ok(())
+    }
+
+    #[test]
+    fn test_checklist_item_text_with_error() {
+        assert!(ChecklistItemText::new(" ".to_owned()).is_err());
+        assert!(ChecklistItemText::new((0..101).map(|_| 'あ').collect()).is_err());
+    }
+}
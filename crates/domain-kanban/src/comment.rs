@@ -0,0 +1,225 @@
+use std::fmt::Display;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_util::{Entity, Identifier, InvariantError, InvariantResult, RepositoryError, Version};
+use invariant_sheild::{invariant_sheild, InvariantSheild};
+use serde::{Deserialize, Serialize};
+use shaku::Interface;
+
+use crate::{column::CardId, outbox::OutboxMessage, user::UserId};
+
+pub type CommentId = Identifier<Comment>;
+
+/// カードへのコメント。編集・削除は投稿者のみできる
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    id: CommentId,
+    card_id: CardId,
+    author: UserId,
+    body: CommentBody,
+    created_at: DateTime<Utc>,
+    edited_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    version: Version,
+}
+
+impl Comment {
+    pub fn new(card_id: CardId, author: UserId, body: CommentBody) -> Self {
+        Self {
+            id: CommentId::gen(),
+            card_id,
+            author,
+            body,
+            created_at: Utc::now(),
+            edited_at: None,
+            version: Version::initial(),
+        }
+    }
+
+    pub fn id(&self) -> &CommentId {
+        &self.id
+    }
+
+    pub fn card_id(&self) -> &CardId {
+        &self.card_id
+    }
+
+    pub fn author(&self) -> &UserId {
+        &self.author
+    }
+
+    pub fn body(&self) -> &CommentBody {
+        &self.body
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn edited_at(&self) -> Option<&DateTime<Utc>> {
+        self.edited_at.as_ref()
+    }
+
+    /// 読み込んだ時点のバージョン
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    pub fn is_written_by(&self, user_id: &UserId) -> bool {
+        &self.author == user_id
+    }
+
+    /// 投稿者以外は編集できない
+    pub fn edit(&mut self, editor: &UserId, body: CommentBody) -> InvariantResult<()> {
+        self.ensure_author(editor)?;
+        self.body = body;
+        self.edited_at = Some(Utc::now());
+        Ok(())
+    }
+
+    /// 投稿者以外が操作しようとしていないかを確認する
+    pub fn ensure_author(&self, user_id: &UserId) -> InvariantResult<()> {
+        if self.is_written_by(user_id) {
+            Ok(())
+        } else {
+            Err(InvariantError::ViolationError(
+                "コメントを変更できるのは投稿者のみです".to_owned(),
+            ))
+        }
+    }
+}
+
+impl Entity for Comment {
+    fn entity_type() -> &'static str {
+        "comment"
+    }
+}
+
+/// Commentモデルを保存するリポジトリのインターフェース
+#[async_trait]
+pub trait CommentRepository: Interface {
+    /// Commentを保存する
+    /// 保存済みのバージョンが `comment.version()` と異なる場合は `RepositoryError::Conflict` を返す
+    async fn save(&self, comment: Comment) -> Result<(), RepositoryError>;
+    /// Commentを保存し、`messages` を同じトランザクションでアウトボックスに保存する
+    async fn save_with_outbox(
+        &self,
+        comment: Comment,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError>;
+    /// CommentをIDで検索する
+    async fn find_by_id(&self, id: &CommentId) -> Result<Option<Comment>, RepositoryError>;
+    /// Commentを削除する
+    async fn delete(&self, id: &CommentId) -> Result<(), RepositoryError>;
+    /// Commentを削除し、`messages` を同じトランザクションでアウトボックスに保存する
+    async fn delete_with_outbox(
+        &self,
+        id: &CommentId,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentBody(String);
+
+#[invariant_sheild(InvariantError)]
+impl CommentBody {
+    pub fn new(body: String) -> InvariantResult<Self> {
+        let result = Self(body);
+        result.satisfy_sheilds()
+    }
+
+    #[sheild]
+    fn body_is_not_blank(&self) -> InvariantResult<()> {
+        if self.0.trim().is_empty() {
+            return Err(InvariantError::ViolationError(
+                "コメントを入力してください".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+
+    const MAX_LENGTH: usize = 1000;
+    // NOTE: 日本語が多いのでバイト数ではなく文字数で数える
+    #[sheild]
+    fn body_lower_than_max(&self) -> InvariantResult<()> {
+        if self.0.chars().count() > Self::MAX_LENGTH {
+            return Err(InvariantError::ViolationError(
+                "コメントは1000文字以内にしてください".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Display for CommentBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comment_body_new() -> InvariantResult<()> {
+        let body = CommentBody::new("いろいろ".to_owned())?;
+        assert_eq!(body.0, "いろいろ");
+
+        // 1000文字ちょうどは許容する
+        let body = CommentBody::new((0..1000).map(|_| 'あ').collect());
+        assert!(body.is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_comment_body_new_with_error() {
+        assert_eq!(
+            CommentBody::new("  ".to_owned()),
+            Err(InvariantError::ViolationError(
+                "コメントを入力してください".to_owned()
+            ))
+        );
+        assert_eq!(
+            CommentBody::new((0..1001).map(|_| 'あ').collect()),
+            Err(InvariantError::ViolationError(
+                "コメントは1000文字以内にしてください".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_comment_edit_by_author() -> InvariantResult<()> {
+        let author = UserId::gen();
+        let mut comment = Comment::new(
+            CardId::gen(),
+            author.clone(),
+            CommentBody::new("before".to_owned())?,
+        );
+        assert!(comment.edited_at().is_none());
+
+        comment.edit(&author, CommentBody::new("after".to_owned())?)?;
+
+        assert_eq!(comment.body().to_string(), "after");
+        assert!(comment.edited_at().is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_comment_edit_by_other_user() -> InvariantResult<()> {
+        let mut comment = Comment::new(
+            CardId::gen(),
+            UserId::gen(),
+            CommentBody::new("before".to_owned())?,
+        );
+
+        let result = comment.edit(&UserId::gen(), CommentBody::new("after".to_owned())?);
+
+        assert!(result.is_err());
+        assert_eq!(comment.body().to_string(), "before");
+        assert!(comment.edited_at().is_none());
+        Ok(())
+    }
+}
@@ -1,3 +1,7 @@
+pub mod activity;
+pub mod archive;
 pub mod board;
 pub mod column;
+pub mod comment;
+pub mod outbox;
 pub mod user;
@@ -0,0 +1,171 @@
+mod relay;
+pub use relay::*;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_util::{Entity, Identifier, RepositoryError};
+use serde::{Deserialize, Serialize};
+use shaku::Interface;
+
+use crate::activity::Activity;
+
+pub type OutboxMessageId = Identifier<OutboxMessage>;
+
+/// 集約と同じトランザクションで保存し、あとでリレーが配送するメッセージ
+/// IDは配送先で重複を除くために使う
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutboxMessage {
+    id: OutboxMessageId,
+    topic: String,
+    /// JSON
+    payload: String,
+    created_at: DateTime<Utc>,
+    /// 配送に失敗した回数
+    #[serde(default)]
+    attempts: u32,
+    /// Noneの場合はすぐに配送する
+    #[serde(default)]
+    next_attempt_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    last_error: Option<String>,
+}
+
+impl Entity for OutboxMessage {
+    fn entity_type() -> &'static str {
+        "outbox"
+    }
+}
+
+impl OutboxMessage {
+    pub const ACTIVITY_TOPIC: &'static str = "activity";
+
+    pub fn new(topic: impl Into<String>, payload: impl Into<String>) -> Self {
+        Self {
+            id: OutboxMessageId::gen(),
+            topic: topic.into(),
+            payload: payload.into(),
+            created_at: Utc::now(),
+            attempts: 0,
+            next_attempt_at: None,
+            last_error: None,
+        }
+    }
+
+    /// Activityを記録するメッセージ
+    pub fn activity(activity: &Activity) -> Result<Self, RepositoryError> {
+        let payload = serde_json::to_string(activity)
+            .map_err(|e| RepositoryError::Serialization(e.to_string()))?;
+        Ok(Self::new(Self::ACTIVITY_TOPIC, payload))
+    }
+
+    pub fn id(&self) -> &OutboxMessageId {
+        &self.id
+    }
+
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    pub fn payload(&self) -> &str {
+        &self.payload
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn next_attempt_at(&self) -> Option<&DateTime<Utc>> {
+        self.next_attempt_at.as_ref()
+    }
+
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    pub fn is_due(&self, now: &DateTime<Utc>) -> bool {
+        self.next_attempt_at.map_or(true, |at| at <= *now)
+    }
+
+    /// 配送の失敗を記録し、`next_attempt_at` に再試行する
+    pub fn record_failure(&mut self, error: impl Into<String>, next_attempt_at: DateTime<Utc>) {
+        self.attempts += 1;
+        self.last_error = Some(error.into());
+        self.next_attempt_at = Some(next_attempt_at);
+    }
+}
+
+/// 再試行をあきらめたメッセージ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeadLetter {
+    #[serde(flatten)]
+    message: OutboxMessage,
+    error: String,
+    dead_at: DateTime<Utc>,
+}
+
+impl DeadLetter {
+    pub fn new(message: OutboxMessage, error: impl Into<String>) -> Self {
+        Self {
+            message,
+            error: error.into(),
+            dead_at: Utc::now(),
+        }
+    }
+
+    pub fn message(&self) -> &OutboxMessage {
+        &self.message
+    }
+
+    pub fn error(&self) -> &str {
+        &self.error
+    }
+
+    pub fn dead_at(&self) -> &DateTime<Utc> {
+        &self.dead_at
+    }
+}
+
+/// リレーがメッセージを読み書きするためのインターフェース
+/// メッセージの追加は、各リポジトリの `save_with_outbox` で集約と一緒に行う
+#[async_trait]
+pub trait OutboxStore: Interface {
+    /// 配送予定日時を過ぎたものを作成順に返す
+    async fn list_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<OutboxMessage>, RepositoryError>;
+    /// 配送できたものを削除する
+    async fn complete(&self, id: &OutboxMessageId) -> Result<(), RepositoryError>;
+    /// 失敗回数と次の配送予定日時を保存する
+    async fn reschedule(&self, message: OutboxMessage) -> Result<(), RepositoryError>;
+    /// アウトボックスから削除し、デッドレターに移す
+    async fn dead_letter(&self, letter: DeadLetter) -> Result<(), RepositoryError>;
+    /// 新しい順に返す
+    async fn list_dead_letters(&self, limit: usize) -> Result<Vec<DeadLetter>, RepositoryError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_record_failure() {
+        let now = Utc::now();
+        let mut message = OutboxMessage::new("topic", "{}");
+        assert!(message.is_due(&now));
+
+        message.record_failure("error", now + Duration::seconds(1));
+
+        assert_eq!(message.attempts(), 1);
+        assert_eq!(message.last_error(), Some("error"));
+        assert!(!message.is_due(&now));
+        assert!(message.is_due(&(now + Duration::seconds(1))));
+    }
+}
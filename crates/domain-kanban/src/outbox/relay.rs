@@ -0,0 +1,276 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use domain_util::RepositoryError;
+
+use super::{DeadLetter, OutboxMessage, OutboxStore};
+use crate::activity::{Activity, ActivityRepository};
+
+/// トピックごとにメッセージを配送する
+/// 同じメッセージが複数回届くことがあるので、メッセージのIDなどで重複を除くこと
+#[async_trait]
+pub trait OutboxHandler: Send + Sync {
+    fn topic(&self) -> &str;
+    async fn handle(&self, message: &OutboxMessage) -> Result<(), String>;
+}
+
+/// 失敗したときの再試行の間隔と回数
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// この回数失敗したらデッドレターに移す
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 8,
+            base_delay: Duration::seconds(1),
+            max_delay: Duration::minutes(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// `attempts` 回目の失敗のあとに待つ時間。失敗するたびに倍にする
+    pub fn delay(&self, attempts: u32) -> Duration {
+        let factor = 2_i32.saturating_pow(attempts.saturating_sub(1));
+        self.base_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+/// 1回分の配送結果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RelayReport {
+    pub delivered: usize,
+    pub retried: usize,
+    pub dead_lettered: usize,
+}
+
+/// アウトボックスのメッセージを少なくとも1回配送する
+pub struct OutboxRelay {
+    store: Box<dyn OutboxStore>,
+    handlers: Vec<Box<dyn OutboxHandler>>,
+    policy: RetryPolicy,
+    batch_size: usize,
+}
+
+impl OutboxRelay {
+    pub fn new(store: Box<dyn OutboxStore>, policy: RetryPolicy) -> Self {
+        Self {
+            store,
+            handlers: vec![],
+            policy,
+            batch_size: 100,
+        }
+    }
+
+    pub fn with_handler(mut self, handler: Box<dyn OutboxHandler>) -> Self {
+        self.handlers.push(handler);
+        self
+    }
+
+    /// 配送予定日時を過ぎたメッセージを配送する
+    /// 配送できたものはアウトボックスから削除するので、削除に失敗した場合は次回もう一度配送する
+    pub async fn relay_once(&self, now: DateTime<Utc>) -> Result<RelayReport, RepositoryError> {
+        let mut report = RelayReport::default();
+        for mut message in self.store.list_due(now, self.batch_size).await? {
+            let Some(handler) = self.handlers.iter().find(|h| h.topic() == message.topic()) else {
+                let error = format!("no handler for topic: {}", message.topic());
+                self.store
+                    .dead_letter(DeadLetter::new(message, error))
+                    .await?;
+                report.dead_lettered += 1;
+                continue;
+            };
+            match handler.handle(&message).await {
+                Ok(()) => {
+                    self.store.complete(message.id()).await?;
+                    report.delivered += 1;
+                }
+                Err(error) if message.attempts() + 1 >= self.policy.max_attempts => {
+                    message.record_failure(error.clone(), now);
+                    self.store
+                        .dead_letter(DeadLetter::new(message, error))
+                        .await?;
+                    report.dead_lettered += 1;
+                }
+                Err(error) => {
+                    let delay = self.policy.delay(message.attempts() + 1);
+                    message.record_failure(error, now + delay);
+                    self.store.reschedule(message).await?;
+                    report.retried += 1;
+                }
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Activityを記録する
+/// 同じIDのActivityがすでに記録されている場合は、配送済みとして扱う
+pub struct ActivityHandler {
+    repository: Box<dyn ActivityRepository>,
+}
+
+impl ActivityHandler {
+    pub fn new(repository: Box<dyn ActivityRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl OutboxHandler for ActivityHandler {
+    fn topic(&self) -> &str {
+        OutboxMessage::ACTIVITY_TOPIC
+    }
+
+    async fn handle(&self, message: &OutboxMessage) -> Result<(), String> {
+        let activity: Activity =
+            serde_json::from_str(message.payload()).map_err(|e| e.to_string())?;
+        match self.repository.append(activity).await {
+            Ok(()) | Err(RepositoryError::Conflict { .. }) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::outbox::OutboxMessageId;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct FakeStore {
+        messages: Mutex<Vec<OutboxMessage>>,
+        dead_letters: Mutex<Vec<DeadLetter>>,
+    }
+
+    #[async_trait]
+    impl OutboxStore for FakeStore {
+        async fn list_due(
+            &self,
+            now: DateTime<Utc>,
+            limit: usize,
+        ) -> Result<Vec<OutboxMessage>, RepositoryError> {
+            let messages = self.messages.lock().unwrap();
+            Ok(messages
+                .iter()
+                .filter(|m| m.is_due(&now))
+                .take(limit)
+                .cloned()
+                .collect())
+        }
+        async fn complete(&self, id: &OutboxMessageId) -> Result<(), RepositoryError> {
+            self.messages.lock().unwrap().retain(|m| m.id() != id);
+            Ok(())
+        }
+        async fn reschedule(&self, message: OutboxMessage) -> Result<(), RepositoryError> {
+            let mut messages = self.messages.lock().unwrap();
+            for m in messages.iter_mut().filter(|m| m.id() == message.id()) {
+                *m = message.clone();
+            }
+            Ok(())
+        }
+        async fn dead_letter(&self, letter: DeadLetter) -> Result<(), RepositoryError> {
+            self.complete(letter.message().id()).await?;
+            self.dead_letters.lock().unwrap().push(letter);
+            Ok(())
+        }
+        async fn list_dead_letters(
+            &self,
+            limit: usize,
+        ) -> Result<Vec<DeadLetter>, RepositoryError> {
+            let dead_letters = self.dead_letters.lock().unwrap();
+            Ok(dead_letters.iter().rev().take(limit).cloned().collect())
+        }
+    }
+
+    struct FailingHandler;
+
+    #[async_trait]
+    impl OutboxHandler for FailingHandler {
+        fn topic(&self) -> &str {
+            "test"
+        }
+        async fn handle(&self, _message: &OutboxMessage) -> Result<(), String> {
+            Err("failed".to_owned())
+        }
+    }
+
+    struct OkHandler;
+
+    #[async_trait]
+    impl OutboxHandler for OkHandler {
+        fn topic(&self) -> &str {
+            "test"
+        }
+        async fn handle(&self, _message: &OutboxMessage) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    fn relay(message: OutboxMessage, handler: Box<dyn OutboxHandler>) -> OutboxRelay {
+        let store = FakeStore::default();
+        store.messages.lock().unwrap().push(message);
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            ..RetryPolicy::default()
+        };
+        OutboxRelay::new(Box::new(store), policy).with_handler(handler)
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay(1), Duration::seconds(1));
+        assert_eq!(policy.delay(2), Duration::seconds(2));
+        assert_eq!(policy.delay(4), Duration::seconds(8));
+        assert_eq!(policy.delay(100), Duration::minutes(10));
+    }
+
+    #[tokio::test]
+    async fn test_relay_delivered() {
+        let relay = relay(OutboxMessage::new("test", "{}"), Box::new(OkHandler));
+
+        let report = relay.relay_once(Utc::now()).await.unwrap();
+
+        assert_eq!(report.delivered, 1);
+        let pending = relay.store.list_due(Utc::now(), 10).await.unwrap();
+        assert!(pending.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_relay_retry_then_dead_letter() {
+        let now = Utc::now();
+        let relay = relay(OutboxMessage::new("test", "{}"), Box::new(FailingHandler));
+
+        let first = relay.relay_once(now).await.unwrap();
+        // 再試行の予定日時より前には配送しない
+        let skipped = relay.relay_once(now).await.unwrap();
+        let second = relay.relay_once(now + Duration::seconds(1)).await.unwrap();
+
+        assert_eq!(first.retried, 1);
+        assert_eq!(skipped, RelayReport::default());
+        assert_eq!(second.dead_lettered, 1);
+        let dead_letters = relay.store.list_dead_letters(10).await.unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].message().attempts(), 2);
+        assert_eq!(dead_letters[0].error(), "failed");
+    }
+
+    #[tokio::test]
+    async fn test_relay_without_handler() {
+        let relay = relay(OutboxMessage::new("unknown", "{}"), Box::new(OkHandler));
+
+        let report = relay.relay_once(Utc::now()).await.unwrap();
+
+        assert_eq!(report.dead_lettered, 1);
+    }
+}
@@ -4,11 +4,13 @@ pub use email::*;
 pub use user_name::*;
 
 use async_trait::async_trait;
-use domain_util::{Entity, Identifier, InvariantError, InvariantResult};
+use domain_util::{Entity, Identifier, InvariantError, InvariantResult, RepositoryError, Version};
 use invariant_sheild::{invariant_sheild, InvariantSheild};
 use serde::{Deserialize, Serialize};
 use shaku::Interface;
 
+use crate::outbox::OutboxMessage;
+
 pub type UserId = Identifier<User>;
 
 #[allow(unused)]
@@ -18,6 +20,8 @@ pub struct User {
     user_id: UserId,
     name: UserName,
     email: Email,
+    #[serde(default)]
+    version: Version,
 }
 
 #[invariant_sheild(InvariantError)]
@@ -31,16 +35,29 @@ impl User {
             user_id,
             name,
             email,
+            version: Version::initial(),
         }
     }
 
     /// UserId, UserName, EmailからUserモデルを作成
     pub fn new_with_id(user_id: UserId, name: UserName, email: Email) -> InvariantResult<Self> {
+        Self::new_with_version(user_id, name, email, Version::initial())
+    }
+
+    /// 保存済みのバージョンを含めてUserモデルを復元する
+    pub fn new_with_version(
+        user_id: UserId,
+        name: UserName,
+        email: Email,
+        version: Version,
+    ) -> InvariantResult<Self> {
         Self {
             user_id,
             name,
             email,
-        }.satisfy_sheilds()
+            version,
+        }
+        .satisfy_sheilds()
     }
 
     pub fn user_id(&self) -> &UserId {
@@ -55,6 +72,11 @@ impl User {
         &self.email
     }
 
+    /// 読み込んだ時点のバージョン
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
     pub fn update_name(&mut self, name: UserName) {
         self.name = name;
     }
@@ -70,9 +92,23 @@ impl Entity for User {
 #[async_trait]
 pub trait UserRepository: Interface {
     /// Userを保存する
-    async fn save(&self, user: User) -> Result<(), String>;
+    /// 保存済みのバージョンが `user.version()` と異なる場合は `RepositoryError::Conflict` を返す
+    async fn save(&self, user: User) -> Result<(), RepositoryError>;
+    /// Userを保存し、`messages` を同じトランザクションでアウトボックスに保存する
+    async fn save_with_outbox(
+        &self,
+        user: User,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError>;
     /// UserをIDで検索する
-    async fn find_by_id(&self, id: &UserId) -> Result<User, String>;
+    async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, RepositoryError>;
+    /// 複数のUserをIDで検索する
+    /// 結果は `ids` と同じ順番に並び、見つからなかったIDの位置は `None` になる
+    async fn find_many(&self, ids: &[UserId]) -> Result<Vec<Option<User>>, RepositoryError>;
+    /// 複数のUserをまとめて保存する
+    /// 保存済みのバージョンが `user.version()` と異なるものがある場合は `RepositoryError::Conflict` を返す
+    /// NOTE: DynamoDBではバージョンを確かめてからBatchWriteItemで保存するので、その間にほかで保存されたものは上書きする。また、アトミックではない
+    async fn save_all(&self, users: Vec<User>) -> Result<(), RepositoryError>;
 }
 
 #[cfg(feature = "dummy")]
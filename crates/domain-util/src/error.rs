@@ -1,8 +1,70 @@
 use thiserror::Error;
 
+use crate::Version;
+
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum InvariantError {
     #[error("不変条件違反: {0}")]
     ViolationError(String),
 }
 pub type InvariantResult<T> = Result<T, InvariantError>;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum RepositoryError {
+    /// 指定したものが保存されていない
+    #[error("見つかりません: {0}")]
+    NotFound(String),
+    /// 保存済みのバージョンが期待したものと異なる。最新を取得しなおせば再試行できる
+    #[error("他の更新と競合しました (expected version: {expected})")]
+    Conflict { expected: Version },
+    /// 保存されていた内容が不変条件を満たさない
+    #[error(transparent)]
+    Invariant(#[from] InvariantError),
+    /// 保存する形式との変換に失敗した
+    #[error("変換に失敗しました: {0}")]
+    Serialization(String),
+    /// スロットリングやタイムアウトなど、時間をおいて再試行すれば成功しうる
+    #[error("一時的なエラー: {0}")]
+    Transient(String),
+    /// 応答がなく、反映されたかどうかわからない(タイムアウトや接続の切断など)
+    /// 読み込みは再試行できるが、書き込みは最新を取得して確かめてからやりなおす
+    #[error("結果が不明なエラー: {0}")]
+    Indeterminate(String),
+    #[error("不明なエラー: {0}")]
+    Other(String),
+}
+
+impl RepositoryError {
+    /// そのまま(または最新を取得しなおして)再試行すれば成功しうるか
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Conflict { .. } | Self::Transient(_))
+    }
+}
+pub type RepositoryResult<T> = Result<T, RepositoryError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_conflict_and_transient_are_retryable() {
+        let conflict = RepositoryError::Conflict {
+            expected: Version::initial(),
+        };
+        assert!(conflict.is_retryable());
+        assert!(RepositoryError::Transient("throttled".to_owned()).is_retryable());
+        assert!(!RepositoryError::Indeterminate("timed out".to_owned()).is_retryable());
+        assert!(!RepositoryError::NotFound("user-01".to_owned()).is_retryable());
+        assert!(!RepositoryError::Other("broken".to_owned()).is_retryable());
+    }
+
+    #[test]
+    fn invariant_error_converts_into_repository_error() {
+        let invariant = InvariantError::ViolationError("empty name".to_owned());
+
+        let error: RepositoryError = invariant.clone().into();
+
+        assert_eq!(error, RepositoryError::Invariant(invariant.clone()));
+        assert_eq!(error.to_string(), invariant.to_string());
+    }
+}
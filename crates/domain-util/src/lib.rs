@@ -1,5 +1,7 @@
 mod error;
 mod identifier;
+mod version;
 
 pub use error::*;
 pub use identifier::*;
+pub use version::*;
@@ -0,0 +1,66 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// 集約の楽観的排他制御に使うバージョン
+/// 未保存の集約は `0` で、保存されるたびに1ずつ増える
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Version(u64);
+
+impl Version {
+    pub fn initial() -> Self {
+        Self(0)
+    }
+
+    pub fn new(value: u64) -> Self {
+        Self(value)
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    /// 保存後のバージョン
+    pub fn next(&self) -> Self {
+        Self(self.0 + 1)
+    }
+
+    pub fn is_initial(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl From<u64> for Version {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initial_version_is_zero() {
+        let version = Version::initial();
+        assert!(version.is_initial());
+        assert_eq!(version.value(), 0);
+        assert_eq!(version, Version::default());
+    }
+
+    #[test]
+    fn next_version_increments() {
+        let version = Version::new(3).next();
+        assert_eq!(version, Version::new(4));
+        assert!(!version.is_initial());
+    }
+}
@@ -0,0 +1,326 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    marker::PhantomData,
+    str::FromStr,
+    sync::Arc,
+};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use domain_kanban::outbox::OutboxMessage;
+use domain_util::{Entity, Identifier, RepositoryError, Version};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_dynamo::{aws_sdk_dynamodb_1::from_item, to_item};
+
+use crate::{
+    batch_get_from, batch_write_to, delete_from, delete_with_outbox_from, get_from, insert_to,
+    save_to, save_with_outbox_to, scan_from,
+    table::{TableDefinition, KEY_ATTRIBUTE},
+    Client, VERSION_ATTRIBUTE,
+};
+
+/// `Identifier<T>` をキーにしてエンティティを1つのテーブルに保存するリポジトリ
+/// キーは常に `Identifier` の `Display` の形式にし、保存するアイテムの `id` 属性もその形式であることを確かめる
+pub struct DynamoRepository<T> {
+    client: Arc<dyn Client>,
+    table: TableDefinition,
+    _entity: PhantomData<fn() -> T>,
+}
+
+impl<T> DynamoRepository<T>
+where
+    T: Entity + Serialize + DeserializeOwned,
+{
+    pub fn new(client: Arc<dyn Client>, table: TableDefinition) -> Self {
+        Self {
+            client,
+            table,
+            _entity: PhantomData,
+        }
+    }
+
+    pub fn table(&self) -> &TableDefinition {
+        &self.table
+    }
+
+    /// IDからキーを作る
+    pub fn key(id: &Identifier<T>) -> HashMap<String, AttributeValue> {
+        HashMap::from([(KEY_ATTRIBUTE.to_owned(), AttributeValue::S(id.to_string()))])
+    }
+
+    /// アイテムのキーをIDとして読み込む
+    pub fn id_of(item: &HashMap<String, AttributeValue>) -> Result<Identifier<T>, RepositoryError> {
+        let key = item
+            .get(KEY_ATTRIBUTE)
+            .and_then(|v| v.as_s().ok())
+            .ok_or_else(|| {
+                RepositoryError::Other(format!("`{}` attribute is missing", KEY_ATTRIBUTE))
+            })?;
+        Identifier::from_str(key)
+            .map_err(|e| RepositoryError::Other(format!("`{}` is not a canonical key: {}", key, e)))
+    }
+
+    /// 見つからない場合は `None` を返す
+    pub async fn find(&self, id: &Identifier<T>) -> Result<Option<T>, RepositoryError> {
+        let client = self.client.client();
+        self.client
+            .resilience()
+            .read(|| get_from(client, self.table.name, Self::key(id)))
+            .await
+    }
+
+    /// 結果は `ids` と同じ順番に並び、見つからなかったIDの位置は `None` になる
+    pub async fn find_many(
+        &self,
+        ids: &[Identifier<T>],
+    ) -> Result<Vec<Option<T>>, RepositoryError> {
+        // 同じキーを1回のリクエストに含めるとエラーになるので、重複を除く
+        let mut requested = HashSet::new();
+        let keys = ids
+            .iter()
+            .filter(|id| requested.insert(*id))
+            .map(Self::key)
+            .collect::<Vec<_>>();
+        let client = self.client.client();
+        let items = self
+            .client
+            .resilience()
+            .read(|| batch_get_from(client, self.table.name, keys.clone()))
+            .await?;
+        let mut found = HashMap::new();
+        for item in items {
+            found.insert(Self::id_of(&item)?, item);
+        }
+        ids.iter()
+            .map(|id| {
+                found
+                    .get(id)
+                    .map(|item| {
+                        from_item(item.clone())
+                            .map_err(|e| RepositoryError::Serialization(e.to_string()))
+                    })
+                    .transpose()
+            })
+            .collect()
+    }
+
+    /// `save` と同じ条件でまとめて保存する。保存されるアイテムのバージョンは `expected.next()` になる
+    /// 競合するものがあった場合は何も保存せず、そのアイテムの `expected` で `RepositoryError::Conflict` を返す
+    /// NOTE: BatchWriteItemは条件式を使えないので、書き込む前にバージョンをまとめて確かめる
+    /// 確かめてから書き込むまでの間にほかで保存されたものは上書きする。また、書き込みはアトミックではない
+    /// 同じIDのものが複数ある場合は、後ろのものを保存する
+    pub async fn save_all(&self, values: Vec<(T, Version)>) -> Result<(), RepositoryError> {
+        let mut items: Vec<(HashMap<String, AttributeValue>, Version)> = vec![];
+        let mut positions = HashMap::new();
+        for (value, expected) in values {
+            let mut item: HashMap<String, AttributeValue> =
+                to_item(value).map_err(|e| RepositoryError::Serialization(e.to_string()))?;
+            item.insert(
+                VERSION_ATTRIBUTE.to_owned(),
+                AttributeValue::N(expected.next().to_string()),
+            );
+            match positions.entry(Self::id_of(&item)?) {
+                Entry::Occupied(position) => items[*position.get()] = (item, expected),
+                Entry::Vacant(position) => {
+                    position.insert(items.len());
+                    items.push((item, expected));
+                }
+            }
+        }
+        let client = self.client.client();
+
+        let keys: Vec<_> = positions.keys().map(Self::key).collect();
+        let stored = self
+            .client
+            .resilience()
+            .read(|| batch_get_from(client, self.table.name, keys.clone()))
+            .await?;
+        for stored in stored {
+            let (_, expected) = &items[positions[&Self::id_of(&stored)?]];
+            // NOTE: version属性がないアイテムはバージョン導入前に保存されたもの
+            let conflicted = stored
+                .get(VERSION_ATTRIBUTE)
+                .and_then(|v| v.as_n().ok())
+                .map_or(false, |version| version != &expected.to_string());
+            if conflicted {
+                return Err(RepositoryError::Conflict {
+                    expected: *expected,
+                });
+            }
+        }
+
+        let items: Vec<_> = items.into_iter().map(|(item, _)| item).collect();
+        self.client
+            .resilience()
+            .write(|| batch_write_to(client, self.table.name, items.clone()))
+            .await
+    }
+
+    /// `expected` のバージョンで保存されている場合のみ上書きする
+    pub async fn save(&self, value: T, expected: Version) -> Result<(), RepositoryError> {
+        self.check_key(&value)?;
+        let client = self.client.client();
+        self.client
+            .resilience()
+            .write(|| save_to(client, self.table.name, &value, expected))
+            .await
+    }
+
+    /// `save` と同じ条件で保存し、`messages` を同じトランザクションでアウトボックスに追加する
+    pub async fn save_with_outbox(
+        &self,
+        value: T,
+        expected: Version,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        self.check_key(&value)?;
+        let client = self.client.client();
+        self.client
+            .resilience()
+            .write(|| save_with_outbox_to(client, self.table.name, &value, expected, &messages))
+            .await
+    }
+
+    /// 新規に追加する。同じIDのアイテムがすでにある場合は `RepositoryError::Conflict` を返す
+    pub async fn insert(&self, value: T) -> Result<(), RepositoryError> {
+        self.check_key(&value)?;
+        let client = self.client.client();
+        self.client
+            .resilience()
+            .write(|| insert_to(client, self.table.name, &value))
+            .await
+    }
+
+    /// 存在しない場合も成功とする
+    pub async fn delete(&self, id: &Identifier<T>) -> Result<(), RepositoryError> {
+        let client = self.client.client();
+        self.client
+            .resilience()
+            .write(|| delete_from(client, self.table.name, Self::key(id)))
+            .await
+    }
+
+    pub async fn delete_with_outbox(
+        &self,
+        id: &Identifier<T>,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        let client = self.client.client();
+        self.client
+            .resilience()
+            .write(|| delete_with_outbox_from(client, self.table.name, Self::key(id), &messages))
+            .await
+    }
+
+    /// テーブル全体を読み込む
+    /// `filter_expression` を指定した場合は、それに一致するアイテムのみを返す
+    pub async fn scan(
+        &self,
+        filter_expression: Option<(&str, HashMap<String, AttributeValue>)>,
+    ) -> Result<Vec<T>, RepositoryError> {
+        let client = self.client.client();
+        self.client
+            .resilience()
+            .read(|| scan_from(client, self.table.name, filter_expression.clone()))
+            .await
+    }
+
+    // シリアライズした `id` 属性が、検索に使うキーと同じ形式になっているか
+    fn check_key(&self, value: &T) -> Result<(), RepositoryError> {
+        let item: HashMap<String, AttributeValue> =
+            to_item(value).map_err(|e| RepositoryError::Serialization(e.to_string()))?;
+        Self::id_of(&item)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::{test_util::async_client_init, ClientImpl};
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Sample {
+        id: Identifier<Sample>,
+        name: String,
+    }
+
+    impl Entity for Sample {
+        fn entity_type() -> &'static str {
+            "sample"
+        }
+    }
+
+    // `id` を `Identifier` の `Display` とは違う形式で保存してしまうエンティティ
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct Legacy {
+        id: String,
+    }
+
+    impl Entity for Legacy {
+        fn entity_type() -> &'static str {
+            "legacy"
+        }
+    }
+
+    const SAMPLES: TableDefinition = TableDefinition {
+        name: "samples",
+        stream: false,
+    };
+
+    #[test]
+    fn test_key_roundtrip() {
+        let id = Identifier::<Sample>::gen();
+
+        let key = DynamoRepository::<Sample>::key(&id);
+
+        assert_eq!(key[KEY_ATTRIBUTE], AttributeValue::S(id.to_string()));
+        assert_eq!(DynamoRepository::<Sample>::id_of(&key).unwrap(), id);
+    }
+
+    #[tokio::test]
+    async fn test_save_find_delete() {
+        // Arrange
+        let (_c, dynamodb_client) = async_client_init().await;
+        SAMPLES
+            .create_if_not_exists(&dynamodb_client)
+            .await
+            .unwrap();
+        let client = Arc::new(ClientImpl::new(dynamodb_client));
+        let repository = DynamoRepository::<Sample>::new(client.clone(), SAMPLES);
+        let sample = Sample {
+            id: Identifier::gen(),
+            name: "sample".to_owned(),
+        };
+
+        // Act
+        repository
+            .save(sample.clone(), Version::initial())
+            .await
+            .unwrap();
+        let found = repository.find(&sample.id).await.unwrap();
+        repository.delete(&sample.id).await.unwrap();
+        let deleted = repository.find(&sample.id).await;
+
+        // Assert
+        assert_eq!(found, Some(sample));
+        assert_eq!(deleted, Ok(None));
+    }
+
+    #[test]
+    fn test_reject_non_canonical_key() {
+        // Arrange
+        let legacy = Legacy {
+            id: "01HXANXCXB0HSWPV9P0TQCJVSD".to_owned(),
+        };
+        let item: HashMap<String, AttributeValue> = to_item(legacy).unwrap();
+
+        // Act
+        let result = DynamoRepository::<Legacy>::id_of(&item);
+
+        // Assert
+        assert!(matches!(result, Err(RepositoryError::Other(_))));
+    }
+}
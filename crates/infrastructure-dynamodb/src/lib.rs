@@ -1,14 +1,27 @@
+mod dynamo_repository;
 mod repository;
+pub mod table;
 #[cfg(test)]
 mod test_util;
 
 use aws_config::{BehaviorVersion, SdkConfig as AwsSdkConfig};
-use aws_sdk_dynamodb::{types::AttributeValue, Client as DynamoDbClient};
-use serde::{Deserialize, Serialize};
+use aws_sdk_dynamodb::{
+    error::{ProvideErrorMetadata, SdkError},
+    operation::transact_write_items::TransactWriteItemsError,
+    types::{
+        AttributeValue, Delete, KeysAndAttributes, Put, PutRequest, TransactWriteItem, WriteRequest,
+    },
+    Client as DynamoDbClient,
+};
+use domain_kanban::outbox::OutboxMessage;
+use domain_util::{RepositoryError, Version};
+use resilience::{Resilience, ResiliencePolicy};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_dynamo::{aws_sdk_dynamodb_1::from_item, to_item};
 use shaku::{Component, Interface};
-use std::{collections::HashMap, fmt::Debug};
+use std::{collections::HashMap, fmt::Debug, sync::Arc, time::Duration};
 
+pub use dynamo_repository::DynamoRepository;
 pub use repository::Module as RepositoryModule;
 
 pub async fn default_sdk_config() -> AwsSdkConfig {
@@ -21,59 +34,404 @@ pub fn dynamo_db_client(config: &AwsSdkConfig) -> DynamoDbClient {
 
 pub trait Client: Interface + Debug {
     fn client(&self) -> &DynamoDbClient;
+    /// DynamoDBへの呼び出しで共有するタイムアウト・再試行・サーキットブレーカー
+    fn resilience(&self) -> &Resilience;
 }
 
 #[derive(Debug, Clone, Component)]
 #[shaku(interface = Client)]
 pub struct ClientImpl {
     client: DynamoDbClient,
+    resilience: Arc<Resilience>,
+}
+
+impl ClientImpl {
+    /// デフォルトのポリシーで呼び出す
+    pub fn new(client: DynamoDbClient) -> Self {
+        Self {
+            client,
+            resilience: Arc::new(Resilience::new("dynamodb", ResiliencePolicy::default())),
+        }
+    }
 }
 
 impl Client for ClientImpl {
     fn client(&self) -> &DynamoDbClient {
         &self.client
     }
+    fn resilience(&self) -> &Resilience {
+        &self.resilience
+    }
+}
+
+/// 時間をおいて再試行すれば成功しうるサービスエラーのコード
+const TRANSIENT_ERROR_CODES: [&str; 3] = [
+    "ProvisionedThroughputExceededException",
+    "RequestLimitExceeded",
+    "ThrottlingException",
+];
+
+// SDKのエラーを、再試行できるかどうかがわかるリポジトリのエラーにする
+fn sdk_error<E, R>(e: SdkError<E, R>) -> RepositoryError
+where
+    E: ProvideErrorMetadata,
+{
+    // 送信の途中で失敗した・応答を受け取れなかった場合や、サーバー内部のエラーは、反映されたかわからない
+    let indeterminate = match &e {
+        SdkError::TimeoutError(_) | SdkError::DispatchFailure(_) | SdkError::ResponseError(_) => {
+            true
+        }
+        SdkError::ServiceError(service_error) => {
+            service_error.err().code() == Some("InternalServerError")
+        }
+        _ => false,
+    };
+    let transient = match &e {
+        SdkError::ServiceError(service_error) => service_error
+            .err()
+            .code()
+            .map_or(false, |code| TRANSIENT_ERROR_CODES.contains(&code)),
+        _ => false,
+    };
+    if indeterminate {
+        RepositoryError::Indeterminate(e.to_string())
+    } else if transient {
+        RepositoryError::Transient(e.to_string())
+    } else {
+        RepositoryError::Other(e.to_string())
+    }
+}
+
+fn serialization_error(e: serde_dynamo::Error) -> RepositoryError {
+    RepositoryError::Serialization(e.to_string())
 }
 
+/// 集約のバージョンを保持する属性名
+const VERSION_ATTRIBUTE: &str = "version";
+
+/// `expected` のバージョンで保存されている場合のみ上書きする（未保存なら新規に追加する）
+/// 保存されるアイテムのバージョンは `expected.next()` になる
 async fn save_to(
     client: &DynamoDbClient,
     table_name: impl Into<String>,
     value: impl Serialize,
-) -> Result<(), String> {
-    // TODO
-    let item = to_item(value).map_err(|e| e.to_string())?;
+    expected: Version,
+) -> Result<(), RepositoryError> {
+    let mut item = to_item(value).map_err(serialization_error)?;
+    item.insert(
+        VERSION_ATTRIBUTE.to_owned(),
+        AttributeValue::N(expected.next().to_string()),
+    );
     let save_request = client
         .put_item()
         .table_name(table_name)
-        .set_item(Some(item));
-    // TODO
-    save_request.send().await.map_err(|e| e.to_string())?;
+        .set_item(Some(item))
+        // NOTE: version属性がないアイテムはバージョン導入前に保存されたもの
+        .condition_expression("attribute_not_exists(#version) OR #version = :expected")
+        .expression_attribute_names("#version", VERSION_ATTRIBUTE)
+        .expression_attribute_values(":expected", AttributeValue::N(expected.to_string()));
+    save_request.send().await.map_err(|e| {
+        let conflicted = e
+            .as_service_error()
+            .map_or(false, |e| e.is_conditional_check_failed_exception());
+        if conflicted {
+            RepositoryError::Conflict { expected }
+        } else {
+            sdk_error(e)
+        }
+    })?;
     Ok(())
 }
 
+/// `save_to` と同じ条件で保存し、`messages` を同じトランザクションでアウトボックスに追加する
+async fn save_with_outbox_to(
+    client: &DynamoDbClient,
+    table_name: impl Into<String>,
+    value: impl Serialize,
+    expected: Version,
+    messages: &[OutboxMessage],
+) -> Result<(), RepositoryError> {
+    let mut item = to_item(value).map_err(serialization_error)?;
+    item.insert(
+        VERSION_ATTRIBUTE.to_owned(),
+        AttributeValue::N(expected.next().to_string()),
+    );
+    let put = Put::builder()
+        .table_name(table_name)
+        .set_item(Some(item))
+        .condition_expression("attribute_not_exists(#version) OR #version = :expected")
+        .expression_attribute_names("#version", VERSION_ATTRIBUTE)
+        .expression_attribute_values(":expected", AttributeValue::N(expected.to_string()))
+        .build()
+        .map_err(|e| RepositoryError::Other(e.to_string()))?;
+    let mut items = vec![TransactWriteItem::builder().put(put).build()];
+    items.extend(outbox_puts(messages)?);
+    transact_write(client, items, expected).await
+}
+
+/// キーに一致するアイテムを削除し、`messages` を同じトランザクションでアウトボックスに追加する
+async fn delete_with_outbox_from<K: Into<HashMap<String, AttributeValue>>>(
+    client: &DynamoDbClient,
+    table_name: impl Into<String>,
+    keys: K,
+    messages: &[OutboxMessage],
+) -> Result<(), RepositoryError> {
+    let delete = Delete::builder()
+        .table_name(table_name)
+        .set_key(Some(keys.into()))
+        .build()
+        .map_err(|e| RepositoryError::Other(e.to_string()))?;
+    let mut items = vec![TransactWriteItem::builder().delete(delete).build()];
+    items.extend(outbox_puts(messages)?);
+    transact_write(client, items, Version::initial()).await
+}
+
+fn outbox_puts(messages: &[OutboxMessage]) -> Result<Vec<TransactWriteItem>, RepositoryError> {
+    messages
+        .iter()
+        .map(|message| {
+            let item = to_item(message).map_err(serialization_error)?;
+            let put = Put::builder()
+                .table_name(table::OUTBOX.name)
+                .set_item(Some(item))
+                .condition_expression("attribute_not_exists(id)")
+                .build()
+                .map_err(|e| RepositoryError::Other(e.to_string()))?;
+            Ok(TransactWriteItem::builder().put(put).build())
+        })
+        .collect()
+}
+
+/// 条件を満たさないアイテムがあった場合は `RepositoryError::Conflict` を返す
+async fn transact_write(
+    client: &DynamoDbClient,
+    items: Vec<TransactWriteItem>,
+    expected: Version,
+) -> Result<(), RepositoryError> {
+    let transact_request = client
+        .transact_write_items()
+        .set_transact_items(Some(items));
+    transact_request.send().await.map_err(|e| {
+        if is_canceled_by(&e, "ConditionalCheckFailed") {
+            RepositoryError::Conflict { expected }
+        } else if is_canceled_by(&e, "TransactionConflict") {
+            // ほかのトランザクションが同じアイテムを書き込んでいた。時間をおけば成功しうる
+            RepositoryError::Transient(e.to_string())
+        } else {
+            sdk_error(e)
+        }
+    })?;
+    Ok(())
+}
+
+// トランザクションの競合は、例外のコードではなく取り消された理由のコードでわかる
+fn is_canceled_by<R>(e: &SdkError<TransactWriteItemsError, R>, code: &str) -> bool {
+    e.as_service_error().map_or(false, |e| match e {
+        TransactWriteItemsError::TransactionCanceledException(e) => e
+            .cancellation_reasons()
+            .iter()
+            .any(|r| r.code() == Some(code)),
+        _ => false,
+    })
+}
+
+/// 新規に追加する。同じIDのアイテムがすでにある場合は `RepositoryError::Conflict` を返す
+async fn insert_to(
+    client: &DynamoDbClient,
+    table_name: impl Into<String>,
+    value: impl Serialize,
+) -> Result<(), RepositoryError> {
+    let item = to_item(value).map_err(serialization_error)?;
+    let insert_request = client
+        .put_item()
+        .table_name(table_name)
+        .set_item(Some(item))
+        .condition_expression("attribute_not_exists(id)");
+    insert_request.send().await.map_err(|e| {
+        let conflicted = e
+            .as_service_error()
+            .map_or(false, |e| e.is_conditional_check_failed_exception());
+        if conflicted {
+            RepositoryError::Conflict {
+                expected: Version::initial(),
+            }
+        } else {
+            sdk_error(e)
+        }
+    })?;
+    Ok(())
+}
+
+/// キーに一致するアイテムを読み込む。見つからない場合は `None` を返す
 async fn get_from<
-    'a,
     T: Into<String>,
     K: Into<HashMap<String, AttributeValue>>,
-    R: Deserialize<'a>,
+    R: DeserializeOwned,
 >(
     client: &DynamoDbClient,
     table_name: T,
     keys: K,
-) -> Result<R, String> {
+) -> Result<Option<R>, RepositoryError> {
     let get_request = client
         .get_item()
         .table_name(table_name)
         .set_key(Some(keys.into()));
-    // TODO
-    let item = get_request.send().await.map_err(|e| e.to_string())?;
-    dbg!(&item);
-    let item = item.item.ok_or_else(|| "e".to_string())?;
-    let result = from_item(item).map_err(|e| e.to_string())?;
-    // TODO
+    let output = get_request.send().await.map_err(sdk_error)?;
+    output
+        .item
+        .map(|item| from_item(item).map_err(serialization_error))
+        .transpose()
+}
+
+/// テーブル全体をページングしながら読み込む
+/// `filter_expression` を指定した場合は、それに一致するアイテムのみを返す
+async fn scan_from<T: Into<String>, R: DeserializeOwned>(
+    client: &DynamoDbClient,
+    table_name: T,
+    filter_expression: Option<(&str, HashMap<String, AttributeValue>)>,
+) -> Result<Vec<R>, RepositoryError> {
+    let table_name = table_name.into();
+    let mut result = vec![];
+    let mut exclusive_start_key = None;
+    loop {
+        let mut scan_request = client
+            .scan()
+            .table_name(&table_name)
+            .set_exclusive_start_key(exclusive_start_key);
+        if let Some((expression, values)) = &filter_expression {
+            scan_request = scan_request
+                .filter_expression(*expression)
+                .set_expression_attribute_values(Some(values.clone()));
+        }
+        let output = scan_request.send().await.map_err(sdk_error)?;
+        for item in output.items.unwrap_or_default() {
+            let value = from_item(item).map_err(serialization_error)?;
+            result.push(value);
+        }
+        exclusive_start_key = output.last_evaluated_key;
+        if exclusive_start_key.is_none() {
+            return Ok(result);
+        }
+    }
+}
+
+/// BatchGetItemで1回に読み込めるキーの数
+const BATCH_GET_LIMIT: usize = 100;
+/// BatchWriteItemで1回に書き込めるアイテムの数
+const BATCH_WRITE_LIMIT: usize = 25;
+/// 未処理のキー・アイテムを再試行する回数
+const BATCH_MAX_ATTEMPTS: u32 = 8;
+/// 最初の再試行までの待ち時間。再試行するたびに倍にする
+const BATCH_RETRY_BASE_DELAY: Duration = Duration::from_millis(50);
+
+/// キーに一致するアイテムをまとめて読み込む。見つからなかったキーのアイテムは含まない
+/// 結果の順番はキーの順番と一致しない。同じキーを重複して渡さないこと
+async fn batch_get_from(
+    client: &DynamoDbClient,
+    table_name: &str,
+    keys: Vec<HashMap<String, AttributeValue>>,
+) -> Result<Vec<HashMap<String, AttributeValue>>, RepositoryError> {
+    let mut result = vec![];
+    for chunk in keys.chunks(BATCH_GET_LIMIT) {
+        let mut pending = chunk.to_vec();
+        let mut attempts = 0;
+        while !pending.is_empty() {
+            wait_for_retry(table_name, attempts).await?;
+            let keys_and_attributes = KeysAndAttributes::builder()
+                .set_keys(Some(pending))
+                .build()
+                .map_err(|e| RepositoryError::Other(e.to_string()))?;
+            let output = client
+                .batch_get_item()
+                .request_items(table_name, keys_and_attributes)
+                .send()
+                .await
+                .map_err(sdk_error)?;
+            if let Some(mut responses) = output.responses {
+                result.extend(responses.remove(table_name).unwrap_or_default());
+            }
+            pending = output
+                .unprocessed_keys
+                .and_then(|mut unprocessed| unprocessed.remove(table_name))
+                .map(|k| k.keys)
+                .unwrap_or_default();
+            attempts += 1;
+        }
+    }
     Ok(result)
 }
 
+/// アイテムをまとめて上書き保存する。条件式は使えないので、バージョンは確認しない
+/// `BATCH_WRITE_LIMIT` 件ずつ書き込むので、途中で失敗した場合はそれより前のアイテムは保存されたままになる
+/// 同じキーのアイテムを重複して渡さないこと
+async fn batch_write_to(
+    client: &DynamoDbClient,
+    table_name: &str,
+    items: Vec<HashMap<String, AttributeValue>>,
+) -> Result<(), RepositoryError> {
+    let mut requests = items
+        .into_iter()
+        .map(|item| {
+            let put = PutRequest::builder()
+                .set_item(Some(item))
+                .build()
+                .map_err(|e| RepositoryError::Other(e.to_string()))?;
+            Ok(WriteRequest::builder().put_request(put).build())
+        })
+        .collect::<Result<Vec<_>, RepositoryError>>()?;
+    while !requests.is_empty() {
+        let mut pending: Vec<_> = requests
+            .drain(..requests.len().min(BATCH_WRITE_LIMIT))
+            .collect();
+        let mut attempts = 0;
+        while !pending.is_empty() {
+            wait_for_retry(table_name, attempts).await?;
+            let output = client
+                .batch_write_item()
+                .request_items(table_name, pending)
+                .send()
+                .await
+                .map_err(sdk_error)?;
+            pending = output
+                .unprocessed_items
+                .and_then(|mut unprocessed| unprocessed.remove(table_name))
+                .unwrap_or_default();
+            attempts += 1;
+        }
+    }
+    Ok(())
+}
+
+// 未処理のものが残ったときは、間隔をあけてから再試行する
+async fn wait_for_retry(table_name: &str, attempts: u32) -> Result<(), RepositoryError> {
+    if attempts == 0 {
+        return Ok(());
+    }
+    // 未処理が残るのはスループットが足りないときなので、時間をおけば成功しうる
+    if attempts >= BATCH_MAX_ATTEMPTS {
+        return Err(RepositoryError::Transient(format!(
+            "unprocessed items remain in {} after {} attempts",
+            table_name, attempts
+        )));
+    }
+    tokio::time::sleep(BATCH_RETRY_BASE_DELAY * 2_u32.pow(attempts - 1)).await;
+    Ok(())
+}
+
+/// キーに一致するアイテムを削除する。存在しない場合も成功とする
+async fn delete_from<T: Into<String>, K: Into<HashMap<String, AttributeValue>>>(
+    client: &DynamoDbClient,
+    table_name: T,
+    keys: K,
+) -> Result<(), RepositoryError> {
+    let delete_request = client
+        .delete_item()
+        .table_name(table_name)
+        .set_key(Some(keys.into()));
+    delete_request.send().await.map_err(sdk_error)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -124,13 +482,60 @@ mod tests {
         };
 
         // Act
-        save_to(&client, table_name, test.clone()).await.unwrap();
+        save_to(&client, table_name, test.clone(), Version::initial())
+            .await
+            .unwrap();
         let search_key = to_attribute_value("test_id").unwrap();
-        let result: Test = get_from(&client, table_name, [(String::from("id"), search_key)])
+        let result: Option<Test> =
+            get_from(&client, table_name, [(String::from("id"), search_key)])
+                .await
+                .unwrap();
+        let missing_key = to_attribute_value("missing_id").unwrap();
+        let missing: Option<Test> =
+            get_from(&client, table_name, [(String::from("id"), missing_key)])
+                .await
+                .unwrap();
+
+        // Assert
+        assert_eq!(result, Some(test));
+        assert_eq!(missing, None);
+    }
+
+    #[tokio::test]
+    async fn test_save_with_stale_version() {
+        // Arrange
+        let (_c, client) = async_client_init().await;
+        let table_name = "test_table";
+        create_table(&client, table_name, &ID_ONLY_TABLE)
             .await
             .unwrap();
 
+        let test = Test {
+            id: "test_id".to_string(),
+            name: Name {
+                first: "first".to_string(),
+                last: "last".to_string(),
+                middle: None,
+            },
+            count: 1,
+            age: 18,
+            age_value: AgeValue(18),
+        };
+        save_to(&client, table_name, test.clone(), Version::initial())
+            .await
+            .unwrap();
+
+        // Act
+        let stale = save_to(&client, table_name, test.clone(), Version::initial()).await;
+        let latest = save_to(&client, table_name, test.clone(), Version::new(1)).await;
+
         // Assert
-        assert_eq!(result, test);
+        assert_eq!(
+            stale,
+            Err(RepositoryError::Conflict {
+                expected: Version::initial()
+            })
+        );
+        assert_eq!(latest, Ok(()));
     }
 }
@@ -1,9 +1,21 @@
+mod activity;
+mod archive;
+mod board;
+mod column;
+mod comment;
+mod outbox;
 mod user;
 
 shaku::module! {
     pub Module {
         components = [super::ClientImpl],
         providers = [
+            activity::ActivityRepositoryImpl,
+            archive::ArchivePurgerImpl,
+            board::BoardRepositoryImpl,
+            column::ColumnRepositoryImpl,
+            comment::CommentRepositoryImpl,
+            outbox::OutboxStoreImpl,
             user::UserRepositoryImpl,
         ]
     }
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain_kanban::activity::{Activity, ActivityRepository};
+use domain_util::RepositoryError;
+use shaku::Provider;
+
+use crate::{table::ACTIVITIES, Client, DynamoRepository};
+
+/// ActivityRepositoryの実装
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = ActivityRepository)]
+pub struct ActivityRepositoryImpl {
+    #[shaku(inject)]
+    client: Arc<dyn Client>,
+}
+
+impl ActivityRepositoryImpl {
+    fn repository(&self) -> DynamoRepository<Activity> {
+        DynamoRepository::new(self.client.clone(), ACTIVITIES)
+    }
+}
+
+#[async_trait]
+impl ActivityRepository for ActivityRepositoryImpl {
+    async fn append(&self, activity: Activity) -> Result<(), RepositoryError> {
+        // 追記のみなので、同じIDで上書きしない
+        self.repository().insert(activity).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain_kanban::{activity::ActivityAction, user::UserId};
+    use testcontainers_modules::{localstack::LocalStack, testcontainers::ContainerAsync};
+
+    use crate::{test_util::async_client_init, ClientImpl};
+
+    use super::*;
+
+    async fn arrange_repository() -> (ContainerAsync<LocalStack>, ActivityRepositoryImpl) {
+        let (c, dynamodb_client) = async_client_init().await;
+        ACTIVITIES
+            .create_if_not_exists(&dynamodb_client)
+            .await
+            .unwrap();
+
+        let client = Arc::new(ClientImpl::new(dynamodb_client));
+        (c, ActivityRepositoryImpl { client })
+    }
+
+    #[tokio::test]
+    async fn test_append() {
+        // Arrange
+        let (_c, activity_repository) = arrange_repository().await;
+        let actor = UserId::gen();
+        let activity = Activity::new(actor.clone(), ActivityAction::UserRenamed, &actor)
+            .with_change("before", "after");
+
+        // Act
+        activity_repository.append(activity.clone()).await.unwrap();
+        let duplicated = activity_repository.append(activity.clone()).await;
+
+        // Assert
+        assert!(matches!(duplicated, Err(RepositoryError::Conflict { .. })));
+        let stored = activity_repository
+            .repository()
+            .find(activity.id())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.id(), activity.id());
+        assert_eq!(stored.actor(), &actor);
+        assert_eq!(stored.action(), ActivityAction::UserRenamed);
+        assert_eq!(stored.after(), Some("after"));
+    }
+}
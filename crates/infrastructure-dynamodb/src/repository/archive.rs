@@ -0,0 +1,274 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::{DateTime, Utc};
+use domain_kanban::{
+    archive::{ArchivePurger, PurgeReport},
+    board::Board,
+    column::{Column, ColumnId},
+};
+use domain_util::RepositoryError;
+use shaku::Provider;
+
+use crate::{
+    table::{BOARDS, COLUMNS},
+    Client, DynamoRepository,
+};
+
+/// ArchivePurgerの実装
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = ArchivePurger)]
+pub struct ArchivePurgerImpl {
+    #[shaku(inject)]
+    client: Arc<dyn Client>,
+}
+
+#[async_trait]
+impl ArchivePurger for ArchivePurgerImpl {
+    async fn purge_archived(
+        &self,
+        archived_before: DateTime<Utc>,
+    ) -> Result<PurgeReport, RepositoryError> {
+        let board_repository = DynamoRepository::<Board>::new(self.client.clone(), BOARDS);
+        let column_repository = DynamoRepository::<Column>::new(self.client.clone(), COLUMNS);
+        let mut report = PurgeReport::default();
+
+        let columns = column_repository.scan(None).await?;
+        let mut columns: HashMap<ColumnId, Column> =
+            columns.into_iter().map(|c| (c.id().clone(), c)).collect();
+        // アーカイブされたことがあるボードのみを読み込む
+        let archived_filter = (
+            "attribute_type(archived_at, :string)",
+            HashMap::from([(":string".to_owned(), AttributeValue::S("S".to_owned()))]),
+        );
+        let boards = board_repository.scan(Some(archived_filter)).await?;
+
+        for board in boards
+            .iter()
+            .filter(|b| b.is_archived_before(&archived_before))
+        {
+            for column_id in board.column_ids() {
+                if let Some(column) = columns.remove(column_id) {
+                    report.cards += column.cards().len();
+                }
+                column_repository.delete(column_id).await?;
+                report.columns += 1;
+            }
+            board_repository.delete(board.id()).await?;
+            report.boards += 1;
+        }
+
+        // 残ったボードに入っているカラムは、ボードから取り除いてから削除する
+        let expired_column_ids: Vec<_> = columns
+            .values()
+            .filter(|c| c.is_archived_before(&archived_before))
+            .map(|c| c.id().clone())
+            .collect();
+        let detached = detach_columns(&board_repository, &expired_column_ids).await?;
+
+        for (column_id, mut column) in columns {
+            if column.is_archived_before(&archived_before) {
+                if !detached.contains(&column_id) {
+                    continue;
+                }
+                report.cards += column.cards().len();
+                column_repository.delete(&column_id).await?;
+                report.columns += 1;
+                continue;
+            }
+            let purged = column.purge_archived_cards(&archived_before);
+            if purged == 0 {
+                continue;
+            }
+            let expected = column.version();
+            match column_repository.save(column, expected).await {
+                Ok(()) => report.cards += purged,
+                // 読み込んだあとに更新されたカラムは、次回の実行で削除する
+                Err(RepositoryError::Conflict { .. }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(report)
+    }
+}
+
+// カラムをボードの `column_ids` から取り除き、どのボードにも残っていないカラムのIDを返す
+// 読み込んだあとに更新されたボードのカラムは、次回の実行で削除する
+async fn detach_columns(
+    board_repository: &DynamoRepository<Board>,
+    column_ids: &[ColumnId],
+) -> Result<HashSet<ColumnId>, RepositoryError> {
+    let mut detached: HashSet<_> = column_ids.iter().cloned().collect();
+    if column_ids.is_empty() {
+        return Ok(detached);
+    }
+    for mut board in board_repository.scan(None).await? {
+        let attached: Vec<_> = board
+            .column_ids()
+            .iter()
+            .filter(|id| column_ids.contains(id))
+            .cloned()
+            .collect();
+        if !board.remove_columns(&attached) {
+            continue;
+        }
+        let expected = board.version();
+        match board_repository.save(board, expected).await {
+            Ok(()) => {}
+            Err(RepositoryError::Conflict { .. }) => {
+                for column_id in &attached {
+                    detached.remove(column_id);
+                }
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(detached)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use domain_kanban::{
+        board::{BoardId, BoardRepository, BoardTitle},
+        column::{CardTitle, ColumnRepository, ColumnTitle},
+        user::UserId,
+    };
+    use testcontainers_modules::{localstack::LocalStack, testcontainers::ContainerAsync};
+
+    use crate::{
+        repository::{board::BoardRepositoryImpl, column::ColumnRepositoryImpl},
+        test_util::async_client_init,
+        ClientImpl,
+    };
+
+    use super::*;
+
+    async fn arrange() -> (
+        ContainerAsync<LocalStack>,
+        ArchivePurgerImpl,
+        BoardRepositoryImpl,
+        ColumnRepositoryImpl,
+    ) {
+        let (c, dynamodb_client) = async_client_init().await;
+        for table in [BOARDS, COLUMNS] {
+            table.create_if_not_exists(&dynamodb_client).await.unwrap();
+        }
+
+        let client = Arc::new(ClientImpl::new(dynamodb_client));
+        (
+            c,
+            ArchivePurgerImpl {
+                client: client.clone(),
+            },
+            BoardRepositoryImpl {
+                client: client.clone(),
+            },
+            ColumnRepositoryImpl { client },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_purge_archived() {
+        // Arrange
+        let (_c, purger, board_repository, column_repository) = arrange().await;
+        // アーカイブされたボードとそのカラム
+        let archived_column = Column::new(ColumnTitle::new("done".to_owned()))
+            .add_card(CardTitle::new("card".to_owned()));
+        let mut archived_board = Board::new(
+            BoardId::gen(),
+            BoardTitle::new("old".to_owned()).unwrap(),
+            UserId::gen(),
+            vec![],
+            vec![archived_column.id().clone()],
+        )
+        .unwrap();
+        archived_board.archive().unwrap();
+        // アーカイブされたカードを含むカラム
+        let mut column = Column::new(ColumnTitle::new("todo".to_owned()))
+            .add_card(CardTitle::new("archived".to_owned()))
+            .add_card(CardTitle::new("active".to_owned()));
+        let archived_card_id = column.cards()[0].id().clone();
+        column
+            .find_card_mut(&archived_card_id)
+            .unwrap()
+            .archive()
+            .unwrap();
+        // アーカイブされていないボードの、アーカイブされたカラム
+        let mut detached_column = Column::new(ColumnTitle::new("old".to_owned()));
+        detached_column.archive().unwrap();
+        let board = Board::new(
+            BoardId::gen(),
+            BoardTitle::new("active".to_owned()).unwrap(),
+            UserId::gen(),
+            vec![],
+            vec![column.id().clone(), detached_column.id().clone()],
+        )
+        .unwrap();
+        board_repository.save(archived_board.clone()).await.unwrap();
+        board_repository.save(board.clone()).await.unwrap();
+        column_repository
+            .save(detached_column.clone())
+            .await
+            .unwrap();
+        column_repository
+            .save(archived_column.clone())
+            .await
+            .unwrap();
+        column_repository.save(column.clone()).await.unwrap();
+
+        // Act
+        let not_expired = purger
+            .purge_archived(Utc::now() - Duration::days(1))
+            .await
+            .unwrap();
+        let expired = purger
+            .purge_archived(Utc::now() + Duration::days(1))
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(not_expired, PurgeReport::default());
+        assert_eq!(
+            expired,
+            PurgeReport {
+                boards: 1,
+                columns: 2,
+                cards: 2,
+            }
+        );
+        assert!(board_repository
+            .find_by_id(archived_board.id())
+            .await
+            .unwrap()
+            .is_none());
+        assert!(column_repository
+            .find_by_id(archived_column.id())
+            .await
+            .unwrap()
+            .is_none());
+        let stored = column_repository
+            .find_by_id(column.id())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.cards().len(), 1);
+        assert_ne!(stored.cards()[0].id(), &archived_card_id);
+        assert!(column_repository
+            .find_by_id(detached_column.id())
+            .await
+            .unwrap()
+            .is_none());
+        let stored_board = board_repository
+            .find_by_id(board.id())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored_board.column_ids(), [column.id().clone()]);
+        assert_eq!(stored_board.version(), board.version().next().next());
+    }
+}
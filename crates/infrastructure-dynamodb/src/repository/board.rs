@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain_kanban::{
+    board::{Board, BoardId, BoardRepository},
+    outbox::OutboxMessage,
+};
+use domain_util::RepositoryError;
+use shaku::Provider;
+
+use crate::{table::BOARDS, Client, DynamoRepository};
+
+/// BoardRepositoryの実装
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = BoardRepository)]
+pub struct BoardRepositoryImpl {
+    #[shaku(inject)]
+    pub(super) client: Arc<dyn Client>,
+}
+
+impl BoardRepositoryImpl {
+    fn repository(&self) -> DynamoRepository<Board> {
+        DynamoRepository::new(self.client.clone(), BOARDS)
+    }
+}
+
+#[async_trait]
+impl BoardRepository for BoardRepositoryImpl {
+    async fn save(&self, board: Board) -> Result<(), RepositoryError> {
+        let expected = board.version();
+        self.repository().save(board, expected).await
+    }
+    async fn save_with_outbox(
+        &self,
+        board: Board,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        let expected = board.version();
+        self.repository()
+            .save_with_outbox(board, expected, messages)
+            .await
+    }
+    async fn find_by_id(&self, id: &BoardId) -> Result<Option<Board>, RepositoryError> {
+        self.repository().find(id).await
+    }
+    async fn find_many(&self, ids: &[BoardId]) -> Result<Vec<Option<Board>>, RepositoryError> {
+        self.repository().find_many(ids).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain_kanban::{board::BoardTitle, column::ColumnId, user::UserId};
+    use domain_util::Version;
+    use testcontainers_modules::{localstack::LocalStack, testcontainers::ContainerAsync};
+
+    use crate::{test_util::async_client_init, ClientImpl};
+
+    use super::*;
+
+    async fn arrange_repository() -> (ContainerAsync<LocalStack>, BoardRepositoryImpl) {
+        let (c, dynamodb_client) = async_client_init().await;
+        BOARDS.create_if_not_exists(&dynamodb_client).await.unwrap();
+
+        let client = Arc::new(ClientImpl::new(dynamodb_client));
+        (c, BoardRepositoryImpl { client })
+    }
+
+    fn board() -> Board {
+        Board::new(
+            BoardId::gen(),
+            BoardTitle::new("yarukoto".to_owned()).unwrap(),
+            UserId::gen(),
+            vec![UserId::gen()],
+            vec![ColumnId::gen(), ColumnId::gen()],
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_save_find() {
+        // Arrange
+        let (_c, board_repository) = arrange_repository().await;
+        let board = board();
+
+        // Act
+        board_repository.save(board.clone()).await.unwrap();
+        let result = board_repository
+            .find_by_id(board.id())
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Assert
+        assert_eq!(result.id(), board.id());
+        assert_eq!(result.title(), board.title());
+        assert_eq!(result.owner(), board.owner());
+        assert_eq!(result.members(), board.members());
+        assert_eq!(result.column_ids(), board.column_ids());
+        assert_eq!(result.version(), Version::new(1));
+    }
+
+    #[tokio::test]
+    async fn test_save_find_many() {
+        // Arrange
+        let (_c, board_repository) = arrange_repository().await;
+        let boards = [board(), board()];
+        for board in &boards {
+            board_repository.save(board.clone()).await.unwrap();
+        }
+        let ids = [
+            boards[1].id().clone(),
+            BoardId::gen(),
+            boards[0].id().clone(),
+        ];
+
+        // Act
+        let result = board_repository.find_many(&ids).await.unwrap();
+
+        // Assert
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].as_ref().unwrap().id(), boards[1].id());
+        assert!(result[1].is_none());
+        assert_eq!(result[2].as_ref().unwrap().id(), boards[0].id());
+    }
+
+    #[tokio::test]
+    async fn test_save_conflict() {
+        // Arrange
+        let (_c, board_repository) = arrange_repository().await;
+        let board = board();
+        board_repository.save(board.clone()).await.unwrap();
+
+        // 2人が同じバージョンを読み込んで更新する
+        let mut first = board_repository
+            .find_by_id(board.id())
+            .await
+            .unwrap()
+            .unwrap();
+        let mut second = first.clone();
+        first.update_title(BoardTitle::new("first".to_owned()).unwrap());
+        second.update_title(BoardTitle::new("second".to_owned()).unwrap());
+
+        // Act
+        let first_result = board_repository.save(first).await;
+        let second_result = board_repository.save(second).await;
+
+        // Assert
+        assert_eq!(first_result, Ok(()));
+        assert_eq!(
+            second_result,
+            Err(RepositoryError::Conflict {
+                expected: Version::new(1)
+            })
+        );
+        let stored = board_repository
+            .find_by_id(board.id())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.title().to_string(), "first");
+    }
+}
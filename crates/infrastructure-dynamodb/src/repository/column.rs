@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain_kanban::{
+    column::{Column, ColumnId, ColumnRepository},
+    outbox::OutboxMessage,
+};
+use domain_util::RepositoryError;
+use shaku::Provider;
+
+use crate::{table::COLUMNS, Client, DynamoRepository};
+
+/// ColumnRepositoryの実装
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = ColumnRepository)]
+pub struct ColumnRepositoryImpl {
+    #[shaku(inject)]
+    pub(super) client: Arc<dyn Client>,
+}
+
+impl ColumnRepositoryImpl {
+    fn repository(&self) -> DynamoRepository<Column> {
+        DynamoRepository::new(self.client.clone(), COLUMNS)
+    }
+}
+
+#[async_trait]
+impl ColumnRepository for ColumnRepositoryImpl {
+    async fn save(&self, column: Column) -> Result<(), RepositoryError> {
+        let expected = column.version();
+        self.repository().save(column, expected).await
+    }
+    async fn save_with_outbox(
+        &self,
+        column: Column,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        let expected = column.version();
+        self.repository()
+            .save_with_outbox(column, expected, messages)
+            .await
+    }
+    async fn find_by_id(&self, id: &ColumnId) -> Result<Option<Column>, RepositoryError> {
+        self.repository().find(id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain_kanban::column::{CardTitle, ChecklistItemText, ColumnTitle};
+    use domain_util::Version;
+    use testcontainers_modules::{localstack::LocalStack, testcontainers::ContainerAsync};
+
+    use crate::{test_util::async_client_init, ClientImpl};
+
+    use super::*;
+
+    async fn arrange_repository() -> (ContainerAsync<LocalStack>, ColumnRepositoryImpl) {
+        let (c, dynamodb_client) = async_client_init().await;
+        COLUMNS
+            .create_if_not_exists(&dynamodb_client)
+            .await
+            .unwrap();
+
+        let client = Arc::new(ClientImpl::new(dynamodb_client));
+        (c, ColumnRepositoryImpl { client })
+    }
+
+    #[tokio::test]
+    async fn test_save_find_with_checklist() {
+        // Arrange
+        let (_c, column_repository) = arrange_repository().await;
+        let mut column = Column::new(ColumnTitle::new("todo".to_owned()))
+            .add_card(CardTitle::new("本棚".to_owned()));
+        let card_id = column.cards()[0].id().clone();
+        let checklist = column.find_card_mut(&card_id).unwrap().checklist_mut();
+        checklist
+            .add(ChecklistItemText::new("棚板".to_owned()).unwrap())
+            .unwrap();
+        checklist
+            .add(ChecklistItemText::new("ネジ".to_owned()).unwrap())
+            .unwrap();
+        checklist.toggle(1).unwrap();
+
+        // Act
+        column_repository.save(column.clone()).await.unwrap();
+        let result = column_repository
+            .find_by_id(column.id())
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Assert
+        assert_eq!(result.id(), column.id());
+        assert_eq!(result.cards()[0].id(), &card_id);
+        assert_eq!(result.cards()[0].checklist(), column.cards()[0].checklist());
+        assert_eq!(result.version(), Version::new(1));
+    }
+}
@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain_kanban::{
+    comment::{Comment, CommentId, CommentRepository},
+    outbox::OutboxMessage,
+};
+use domain_util::RepositoryError;
+use shaku::Provider;
+
+use crate::{table::COMMENTS, Client, DynamoRepository};
+
+/// CommentRepositoryの実装
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = CommentRepository)]
+pub struct CommentRepositoryImpl {
+    #[shaku(inject)]
+    client: Arc<dyn Client>,
+}
+
+impl CommentRepositoryImpl {
+    fn repository(&self) -> DynamoRepository<Comment> {
+        DynamoRepository::new(self.client.clone(), COMMENTS)
+    }
+}
+
+#[async_trait]
+impl CommentRepository for CommentRepositoryImpl {
+    async fn save(&self, comment: Comment) -> Result<(), RepositoryError> {
+        let expected = comment.version();
+        self.repository().save(comment, expected).await
+    }
+    async fn save_with_outbox(
+        &self,
+        comment: Comment,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        let expected = comment.version();
+        self.repository()
+            .save_with_outbox(comment, expected, messages)
+            .await
+    }
+    async fn find_by_id(&self, id: &CommentId) -> Result<Option<Comment>, RepositoryError> {
+        self.repository().find(id).await
+    }
+    async fn delete(&self, id: &CommentId) -> Result<(), RepositoryError> {
+        self.repository().delete(id).await
+    }
+    async fn delete_with_outbox(
+        &self,
+        id: &CommentId,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        self.repository().delete_with_outbox(id, messages).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain_kanban::{column::CardId, comment::CommentBody, user::UserId};
+    use domain_util::Version;
+    use testcontainers_modules::{localstack::LocalStack, testcontainers::ContainerAsync};
+
+    use crate::{test_util::async_client_init, ClientImpl};
+
+    use super::*;
+
+    async fn arrange_repository() -> (ContainerAsync<LocalStack>, CommentRepositoryImpl) {
+        let (c, dynamodb_client) = async_client_init().await;
+        COMMENTS
+            .create_if_not_exists(&dynamodb_client)
+            .await
+            .unwrap();
+
+        let client = Arc::new(ClientImpl::new(dynamodb_client));
+        (c, CommentRepositoryImpl { client })
+    }
+
+    #[tokio::test]
+    async fn test_save_find_delete() {
+        // Arrange
+        let (_c, comment_repository) = arrange_repository().await;
+        let author = UserId::gen();
+        let comment = Comment::new(
+            CardId::gen(),
+            author.clone(),
+            CommentBody::new("いいね".to_owned()).unwrap(),
+        );
+
+        // Act
+        comment_repository.save(comment.clone()).await.unwrap();
+        let mut stored = comment_repository
+            .find_by_id(comment.id())
+            .await
+            .unwrap()
+            .unwrap();
+        stored
+            .edit(&author, CommentBody::new("よくない".to_owned()).unwrap())
+            .unwrap();
+        comment_repository.save(stored).await.unwrap();
+        let edited = comment_repository
+            .find_by_id(comment.id())
+            .await
+            .unwrap()
+            .unwrap();
+        comment_repository.delete(comment.id()).await.unwrap();
+        let deleted = comment_repository.find_by_id(comment.id()).await.unwrap();
+
+        // Assert
+        assert_eq!(edited.card_id(), comment.card_id());
+        assert_eq!(edited.author(), &author);
+        assert_eq!(edited.body().to_string(), "よくない");
+        assert!(edited.edited_at().is_some());
+        assert_eq!(edited.version(), Version::new(2));
+        assert!(deleted.is_none());
+    }
+}
@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::types::{Delete, Put, TransactWriteItem};
+use chrono::{DateTime, Utc};
+use domain_kanban::outbox::{DeadLetter, OutboxMessage, OutboxMessageId, OutboxStore};
+use domain_util::{RepositoryError, Version};
+use serde_dynamo::to_item;
+use shaku::Provider;
+
+use crate::{
+    scan_from, sdk_error, serialization_error,
+    table::{OUTBOX, OUTBOX_DEAD_LETTERS},
+    transact_write, Client, DynamoRepository,
+};
+
+/// OutboxStoreの実装
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = OutboxStore)]
+pub struct OutboxStoreImpl {
+    #[shaku(inject)]
+    client: Arc<dyn Client>,
+}
+
+impl OutboxStoreImpl {
+    fn repository(&self) -> DynamoRepository<OutboxMessage> {
+        DynamoRepository::new(self.client.clone(), OUTBOX)
+    }
+}
+
+#[async_trait]
+impl OutboxStore for OutboxStoreImpl {
+    async fn list_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<OutboxMessage>, RepositoryError> {
+        let mut messages = self.repository().scan(None).await?;
+        messages.retain(|m| m.is_due(&now));
+        messages.sort_by_key(|m| *m.created_at());
+        messages.truncate(limit);
+        Ok(messages)
+    }
+    async fn complete(&self, id: &OutboxMessageId) -> Result<(), RepositoryError> {
+        self.repository().delete(id).await
+    }
+    async fn reschedule(&self, message: OutboxMessage) -> Result<(), RepositoryError> {
+        let item = to_item(message).map_err(serialization_error)?;
+        // 配送済みで削除されたものは作り直さない
+        let reschedule_request = self
+            .client
+            .client()
+            .put_item()
+            .table_name(OUTBOX.name)
+            .set_item(Some(item))
+            .condition_expression("attribute_exists(id)");
+        let reschedule = || async {
+            reschedule_request.clone().send().await.map_err(|e| {
+                let conflicted = e
+                    .as_service_error()
+                    .map_or(false, |e| e.is_conditional_check_failed_exception());
+                if conflicted {
+                    RepositoryError::Conflict {
+                        expected: Version::initial(),
+                    }
+                } else {
+                    sdk_error(e)
+                }
+            })
+        };
+        self.client.resilience().write(reschedule).await?;
+        Ok(())
+    }
+    async fn dead_letter(&self, letter: DeadLetter) -> Result<(), RepositoryError> {
+        let delete = Delete::builder()
+            .table_name(OUTBOX.name)
+            .set_key(Some(DynamoRepository::key(letter.message().id())))
+            .build()
+            .map_err(|e| RepositoryError::Other(e.to_string()))?;
+        let item = to_item(letter).map_err(serialization_error)?;
+        let put = Put::builder()
+            .table_name(OUTBOX_DEAD_LETTERS.name)
+            .set_item(Some(item))
+            .build()
+            .map_err(|e| RepositoryError::Other(e.to_string()))?;
+        let items = vec![
+            TransactWriteItem::builder().delete(delete).build(),
+            TransactWriteItem::builder().put(put).build(),
+        ];
+        let client = self.client.client();
+        self.client
+            .resilience()
+            .write(|| transact_write(client, items.clone(), Version::initial()))
+            .await
+    }
+    async fn list_dead_letters(&self, limit: usize) -> Result<Vec<DeadLetter>, RepositoryError> {
+        let client = self.client.client();
+        let mut letters: Vec<DeadLetter> = self
+            .client
+            .resilience()
+            .read(|| scan_from(client, OUTBOX_DEAD_LETTERS.name, None))
+            .await?;
+        letters.sort_by_key(|l| std::cmp::Reverse(*l.dead_at()));
+        letters.truncate(limit);
+        Ok(letters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain_kanban::{
+        activity::{Activity, ActivityAction},
+        user::{User, UserRepository},
+    };
+    use fake::{Fake, Faker};
+    use testcontainers_modules::{localstack::LocalStack, testcontainers::ContainerAsync};
+
+    use crate::{
+        repository::user::UserRepositoryImpl, table::USERS, test_util::async_client_init,
+        ClientImpl,
+    };
+
+    use super::*;
+
+    async fn arrange() -> (
+        ContainerAsync<LocalStack>,
+        OutboxStoreImpl,
+        UserRepositoryImpl,
+    ) {
+        let (c, dynamodb_client) = async_client_init().await;
+        for table in [USERS, OUTBOX, OUTBOX_DEAD_LETTERS] {
+            table.create_if_not_exists(&dynamodb_client).await.unwrap();
+        }
+
+        let client = Arc::new(ClientImpl::new(dynamodb_client));
+        let outbox_store = OutboxStoreImpl {
+            client: client.clone(),
+        };
+        (c, outbox_store, UserRepositoryImpl { client })
+    }
+
+    #[tokio::test]
+    async fn test_save_with_outbox() {
+        // Arrange
+        let (_c, outbox_store, user_repository) = arrange().await;
+        let user: User = Faker.fake();
+        let activity = Activity::new(
+            user.user_id().clone(),
+            ActivityAction::UserRenamed,
+            user.user_id(),
+        );
+        let message = OutboxMessage::activity(&activity).unwrap();
+
+        // Act
+        user_repository
+            .save_with_outbox(user.clone(), vec![message.clone()])
+            .await
+            .unwrap();
+        // 同じバージョンで保存しようとした場合は、メッセージも追加しない
+        let stale = user_repository
+            .save_with_outbox(user, vec![OutboxMessage::activity(&activity).unwrap()])
+            .await;
+        let due = outbox_store.list_due(Utc::now(), 10).await.unwrap();
+
+        // Assert
+        assert!(matches!(stale, Err(RepositoryError::Conflict { .. })));
+        assert_eq!(due, vec![message]);
+    }
+
+    #[tokio::test]
+    async fn test_dead_letter() {
+        // Arrange
+        let (_c, outbox_store, user_repository) = arrange().await;
+        let user: User = Faker.fake();
+        let message = OutboxMessage::new("unknown", "{}");
+        user_repository
+            .save_with_outbox(user, vec![message.clone()])
+            .await
+            .unwrap();
+
+        // Act
+        outbox_store
+            .dead_letter(DeadLetter::new(message.clone(), "no handler"))
+            .await
+            .unwrap();
+        let due = outbox_store.list_due(Utc::now(), 10).await.unwrap();
+        let dead_letters = outbox_store.list_dead_letters(10).await.unwrap();
+
+        // Assert
+        assert!(due.is_empty());
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].message(), &message);
+        assert_eq!(dead_letters[0].error(), "no handler");
+    }
+}
@@ -1,67 +1,80 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use aws_sdk_dynamodb::types::AttributeValue;
-use domain_kanban::user::{User, UserId, UserRepository};
-use serde_dynamo::to_attribute_value;
+use domain_kanban::{
+    outbox::OutboxMessage,
+    user::{User, UserId, UserRepository},
+};
+use domain_util::RepositoryError;
 use shaku::Provider;
 
-use crate::{get_from, save_to, Client};
+use crate::{table::USERS, Client, DynamoRepository};
 
 /// UserRepositoryの実装
 #[derive(Debug, Clone, Provider)]
 #[shaku(interface = UserRepository)]
 pub struct UserRepositoryImpl {
     #[shaku(inject)]
-    client: Arc<dyn Client>,
+    pub(super) client: Arc<dyn Client>,
 }
 
-const TABLE_NAME: &'static str = "users";
+impl UserRepositoryImpl {
+    fn repository(&self) -> DynamoRepository<User> {
+        DynamoRepository::new(self.client.clone(), USERS)
+    }
+}
 
 #[async_trait]
 impl UserRepository for UserRepositoryImpl {
-    async fn save(&self, user: User) -> Result<(), String> {
-        // clientを使ってuserをusersテーブルに上書き保存する。なければ新規に追加する。
-        save_to(self.client.client(), TABLE_NAME, user).await?;
-        Ok(())
+    async fn save(&self, user: User) -> Result<(), RepositoryError> {
+        // userをusersテーブルに上書き保存する。なければ新規に追加する。
+        let expected = user.version();
+        self.repository().save(user, expected).await
+    }
+    async fn save_with_outbox(
+        &self,
+        user: User,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        let expected = user.version();
+        self.repository()
+            .save_with_outbox(user, expected, messages)
+            .await
+    }
+    async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, RepositoryError> {
+        self.repository().find(id).await
+    }
+    async fn find_many(&self, ids: &[UserId]) -> Result<Vec<Option<User>>, RepositoryError> {
+        self.repository().find_many(ids).await
     }
-    async fn find_by_id(&self, id: &UserId) -> Result<User, String> {
-        // TODO
-        let id_key: AttributeValue =
-            to_attribute_value(id.to_string()).map_err(|e| e.to_string())?;
-        dbg!(&id_key);
-        let result = get_from(
-            self.client.client(),
-            TABLE_NAME,
-            [(String::from("id"), id_key)],
-        )
-        .await?;
-        Ok(result)
+    async fn save_all(&self, users: Vec<User>) -> Result<(), RepositoryError> {
+        let values = users
+            .into_iter()
+            .map(|user| {
+                let expected = user.version();
+                (user, expected)
+            })
+            .collect();
+        self.repository().save_all(values).await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use domain_kanban::user::UserName;
+    use domain_util::Version;
     use fake::{Fake, Faker};
     use testcontainers_modules::{localstack::LocalStack, testcontainers::ContainerAsync};
 
-    use crate::{
-        test_util::{async_client_init, create_table, ID_ONLY_TABLE},
-        ClientImpl,
-    };
+    use crate::{test_util::async_client_init, ClientImpl};
 
     use super::*;
 
     async fn arrange_repository() -> (ContainerAsync<LocalStack>, UserRepositoryImpl) {
         let (c, dynamodb_client) = async_client_init().await;
-        create_table(&dynamodb_client, TABLE_NAME, &ID_ONLY_TABLE)
-            .await
-            .unwrap();
+        USERS.create_if_not_exists(&dynamodb_client).await.unwrap();
 
-        let client = Arc::new(ClientImpl {
-            client: dynamodb_client,
-        });
+        let client = Arc::new(ClientImpl::new(dynamodb_client));
         (c, UserRepositoryImpl { client })
     }
 
@@ -75,8 +88,11 @@ mod tests {
 
         // Act
         user_repository.save(user.clone()).await.unwrap();
-        // XXX: serdeでserializeしたときは "01HXANXCXB0HSWPV9P0TQCJVSD" になっていて、 user_id.to_string()では "user-01HXANXCXB0HSWPV9P0TQCJVSD" になっているため検索できずエラーになる
-        let result = user_repository.find_by_id(&user.user_id()).await.unwrap();
+        let result = user_repository
+            .find_by_id(&user.user_id())
+            .await
+            .unwrap()
+            .unwrap();
 
         // Assert
         assert_eq!(result.user_id(), user.user_id());
@@ -94,20 +110,113 @@ mod tests {
         user_repository.save(user.clone()).await.unwrap();
 
         // Act
-        let mut new_user = user.clone();
+        let mut new_user = user_repository.find_by_id(&user_id).await.unwrap().unwrap();
         let new_name: UserName = Faker.fake();
         new_user.update_name(new_name.clone());
 
         assert_eq!(new_user.user_id(), &user_id);
         assert_eq!(new_user.user_name(), &new_name);
+        assert_eq!(new_user.version(), Version::new(1));
 
         user_repository.save(new_user.clone()).await.unwrap();
 
-        let result = user_repository.find_by_id(&user.user_id()).await.unwrap();
+        let result = user_repository
+            .find_by_id(&user.user_id())
+            .await
+            .unwrap()
+            .unwrap();
 
         // Assert
         assert_eq!(result.user_id(), new_user.user_id());
         assert_eq!(result.user_name(), new_user.user_name());
         assert_eq!(result.email(), new_user.email());
+        assert_eq!(result.version(), Version::new(2));
+    }
+
+    #[tokio::test]
+    async fn test_save_conflict() {
+        // Arrange
+        let (_c, user_repository) = arrange_repository().await;
+        let user: User = Faker.fake();
+        user_repository.save(user.clone()).await.unwrap();
+
+        // Act
+        // 読み込み前のバージョンのまま上書きしようとする
+        let mut stale_user = user.clone();
+        stale_user.update_name(Faker.fake());
+        let result = user_repository.save(stale_user).await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(RepositoryError::Conflict {
+                expected: Version::initial()
+            })
+        );
+        let stored = user_repository
+            .find_by_id(user.user_id())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.user_name(), user.user_name());
+    }
+
+    #[tokio::test]
+    async fn test_save_all_find_many() {
+        // Arrange
+        let (_c, user_repository) = arrange_repository().await;
+        // BatchGetItem(100件)・BatchWriteItem(25件)の1回の上限を超える数を扱う
+        let users: Vec<User> = (0..120).map(|_| Faker.fake()).collect();
+        let missing = UserId::gen();
+        let mut ids: Vec<_> = users.iter().rev().map(|u| u.user_id().clone()).collect();
+        ids.insert(10, missing);
+        ids.push(users[0].user_id().clone());
+
+        // Act
+        user_repository.save_all(users.clone()).await.unwrap();
+        let result = user_repository.find_many(&ids).await.unwrap();
+
+        // Assert
+        assert_eq!(result.len(), ids.len());
+        assert!(result[10].is_none());
+        for (id, found) in ids.iter().zip(&result) {
+            if let Some(found) = found {
+                assert_eq!(found.user_id(), id);
+                assert_eq!(found.version(), Version::new(1));
+            }
+        }
+        assert_eq!(result.iter().filter(|u| u.is_some()).count(), 121);
+    }
+
+    #[tokio::test]
+    async fn test_save_all_conflict() {
+        // Arrange
+        let (_c, user_repository) = arrange_repository().await;
+        let user: User = Faker.fake();
+        user_repository.save(user.clone()).await.unwrap();
+        let new_user: User = Faker.fake();
+
+        // Act
+        // 読み込み前のバージョンのまま、ほかのユーザーとまとめて上書きしようとする
+        let mut stale_user = user.clone();
+        stale_user.update_name(Faker.fake());
+        let result = user_repository
+            .save_all(vec![new_user.clone(), stale_user])
+            .await;
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(RepositoryError::Conflict {
+                expected: Version::initial()
+            })
+        );
+        // 競合するものがあれば、ほかのユーザーも保存しない
+        let found = user_repository
+            .find_many(&[user.user_id().clone(), new_user.user_id().clone()])
+            .await
+            .unwrap();
+        assert_eq!(found[0].as_ref().unwrap().user_name(), user.user_name());
+        assert!(found[1].is_none());
     }
 }
@@ -0,0 +1,199 @@
+use std::time::Duration;
+
+use aws_sdk_dynamodb::{
+    types::{
+        AttributeDefinition, BillingMode, KeySchemaElement, KeyType, ScalarAttributeType,
+        StreamSpecification, StreamViewType, TableStatus,
+    },
+    Client as DynamoDbClient,
+};
+use domain_util::RepositoryError;
+
+use crate::sdk_error;
+
+/// 全テーブル共通のパーティションキー
+/// 値は `Identifier` の `Display` の形式(`user-01H...`)にする
+pub const KEY_ATTRIBUTE: &str = "id";
+
+/// DynamoDBのテーブル定義
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableDefinition {
+    pub name: &'static str,
+    /// DynamoDB Streamsでリードモデルに反映するテーブルか
+    pub stream: bool,
+}
+
+pub const USERS: TableDefinition = TableDefinition {
+    name: "users",
+    stream: true,
+};
+pub const BOARDS: TableDefinition = TableDefinition {
+    name: "boards",
+    stream: true,
+};
+pub const COLUMNS: TableDefinition = TableDefinition {
+    name: "columns",
+    stream: true,
+};
+pub const COMMENTS: TableDefinition = TableDefinition {
+    name: "comments",
+    stream: true,
+};
+pub const ACTIVITIES: TableDefinition = TableDefinition {
+    name: "activities",
+    stream: false,
+};
+pub const OUTBOX: TableDefinition = TableDefinition {
+    name: "outbox",
+    stream: false,
+};
+pub const OUTBOX_DEAD_LETTERS: TableDefinition = TableDefinition {
+    name: "outbox_dead_letters",
+    stream: false,
+};
+
+/// アプリケーションが使う全てのテーブル
+pub const TABLES: [TableDefinition; 7] = [
+    USERS,
+    BOARDS,
+    COLUMNS,
+    COMMENTS,
+    ACTIVITIES,
+    OUTBOX,
+    OUTBOX_DEAD_LETTERS,
+];
+
+impl TableDefinition {
+    /// テーブルがなければ作成し、使えるようになるまで待つ
+    /// 作成した場合はtrueを返す。すでにある場合は何もしない
+    pub async fn create_if_not_exists(
+        &self,
+        client: &DynamoDbClient,
+    ) -> Result<bool, RepositoryError> {
+        if self.exists(client).await? {
+            return Ok(false);
+        }
+        let create_request = client
+            .create_table()
+            .billing_mode(BillingMode::PayPerRequest)
+            .table_name(self.name)
+            .key_schema(
+                KeySchemaElement::builder()
+                    .attribute_name(KEY_ATTRIBUTE)
+                    .key_type(KeyType::Hash)
+                    .build()
+                    .map_err(|e| RepositoryError::Other(e.to_string()))?,
+            )
+            .attribute_definitions(
+                AttributeDefinition::builder()
+                    .attribute_name(KEY_ATTRIBUTE)
+                    .attribute_type(ScalarAttributeType::S)
+                    .build()
+                    .map_err(|e| RepositoryError::Other(e.to_string()))?,
+            )
+            .set_stream_specification(self.stream_specification()?);
+        match create_request.send().await {
+            Ok(_) => {}
+            // 同時に起動した別のプロセスが作成した
+            Err(e)
+                if e.as_service_error()
+                    .map_or(false, |e| e.is_resource_in_use_exception()) =>
+            {
+                return Ok(false)
+            }
+            Err(e) => return Err(sdk_error(e)),
+        }
+        self.wait_until_active(client).await?;
+        Ok(true)
+    }
+
+    async fn exists(&self, client: &DynamoDbClient) -> Result<bool, RepositoryError> {
+        match client.describe_table().table_name(self.name).send().await {
+            Ok(_) => Ok(true),
+            Err(e)
+                if e.as_service_error()
+                    .map_or(false, |e| e.is_resource_not_found_exception()) =>
+            {
+                Ok(false)
+            }
+            Err(e) => Err(sdk_error(e)),
+        }
+    }
+
+    async fn wait_until_active(&self, client: &DynamoDbClient) -> Result<(), RepositoryError> {
+        for _ in 0..60 {
+            let output = client
+                .describe_table()
+                .table_name(self.name)
+                .send()
+                .await
+                .map_err(sdk_error)?;
+            let status = output.table().and_then(|t| t.table_status());
+            if status == Some(&TableStatus::Active) {
+                return Ok(());
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        Err(RepositoryError::Other(format!(
+            "table {} did not become active",
+            self.name
+        )))
+    }
+
+    fn stream_specification(&self) -> Result<Option<StreamSpecification>, RepositoryError> {
+        if !self.stream {
+            return Ok(None);
+        }
+        let specification = StreamSpecification::builder()
+            .stream_enabled(true)
+            .stream_view_type(StreamViewType::NewImage)
+            .build()
+            .map_err(|e| RepositoryError::Other(e.to_string()))?;
+        Ok(Some(specification))
+    }
+}
+
+/// 全てのテーブルを作成する。何度実行してもよい
+/// 作成したテーブルの名前を返す
+pub async fn bootstrap(client: &DynamoDbClient) -> Result<Vec<&'static str>, RepositoryError> {
+    let mut created = vec![];
+    for table in TABLES {
+        if table.create_if_not_exists(client).await? {
+            created.push(table.name);
+        }
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::async_client_init;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_bootstrap_is_idempotent() {
+        // Arrange
+        let (_c, client) = async_client_init().await;
+
+        // Act
+        let first = bootstrap(&client).await.unwrap();
+        let second = bootstrap(&client).await.unwrap();
+
+        // Assert
+        let all: Vec<_> = TABLES.iter().map(|t| t.name).collect();
+        assert_eq!(first, all);
+        assert!(second.is_empty());
+        let users = client
+            .describe_table()
+            .table_name(USERS.name)
+            .send()
+            .await
+            .unwrap();
+        let stream_enabled = users
+            .table()
+            .and_then(|t| t.stream_specification())
+            .map(|s| s.stream_enabled());
+        assert_eq!(stream_enabled, Some(true));
+    }
+}
@@ -0,0 +1,270 @@
+// Postgres・DynamoDBを使わずに動かすための、メモリ上のバックエンド
+// QueryModuleとRepositoryModuleで同じStoreを共有するので、リポジトリで保存した内容がそのままクエリに反映される
+mod query;
+mod repository;
+pub mod sample;
+
+pub use query::Module as QueryModule;
+pub use repository::Module as RepositoryModule;
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use domain_kanban::{
+    activity::Activity,
+    board::{Board, BoardId, BoardTitle},
+    column::{CardDescription, CardTitle, Column, ColumnId, ColumnTitle},
+    comment::{Comment, CommentId},
+    outbox::{DeadLetter, OutboxMessage},
+    user::{Email, User, UserId, UserName},
+};
+use domain_util::{RepositoryError, Version};
+use serde::{de::DeserializeOwned, Serialize};
+use shaku::{Component, Interface};
+
+/// 各集約をIDごとに保持する
+#[derive(Debug, Default)]
+pub struct Tables {
+    users: HashMap<UserId, User>,
+    boards: HashMap<BoardId, Board>,
+    columns: HashMap<ColumnId, Column>,
+    comments: HashMap<CommentId, Comment>,
+    activities: Vec<Activity>,
+    outbox: Vec<OutboxMessage>,
+    dead_letters: Vec<DeadLetter>,
+}
+
+impl Tables {
+    /// `sample.rs` のデータを入れた状態で作る
+    pub fn seeded() -> Self {
+        let data = sample::data();
+        let mut tables = Self::default();
+        for u in &data.users {
+            let user = User::new_with_id(
+                u.id.clone(),
+                UserName::new(u.name.clone()).unwrap(),
+                Email::new(u.email.clone()).unwrap(),
+            )
+            .unwrap();
+            tables.users.insert(u.id.clone(), user);
+        }
+        for b in &data.boards {
+            let board = Board::new(
+                b.id.clone(),
+                BoardTitle::new(b.title.clone()).unwrap(),
+                b.owner_id.clone(),
+                vec![],
+                b.column_ids.clone(),
+            )
+            .unwrap();
+            tables.boards.insert(b.id.clone(), board);
+        }
+        for c in &data.columns {
+            let column = c.cards.iter().fold(
+                Column::new_with_id(c.id.clone(), ColumnTitle::new(c.title.clone())),
+                |column, card| {
+                    column.add_card_with_id(
+                        card.id.clone(),
+                        CardTitle::new(card.title.clone()),
+                        CardDescription::new(card.description.clone()),
+                    )
+                },
+            );
+            tables.columns.insert(c.id.clone(), column);
+        }
+        tables
+    }
+}
+
+pub trait Store: Interface + Debug {
+    fn tables(&self) -> &RwLock<Tables>;
+
+    fn read(&self) -> RwLockReadGuard<'_, Tables> {
+        self.tables().read().expect("store lock poisoned")
+    }
+    fn write(&self) -> RwLockWriteGuard<'_, Tables> {
+        self.tables().write().expect("store lock poisoned")
+    }
+}
+
+#[derive(Debug, Clone, Component)]
+#[shaku(interface = Store)]
+pub struct StoreImpl {
+    tables: Arc<RwLock<Tables>>,
+}
+
+impl Store for StoreImpl {
+    fn tables(&self) -> &RwLock<Tables> {
+        &self.tables
+    }
+}
+
+/// 同じStoreを共有するQueryModuleとRepositoryModuleを作る
+pub fn modules(tables: Tables) -> (QueryModule, RepositoryModule) {
+    let tables = Arc::new(RwLock::new(tables));
+    let query_module = QueryModule::builder()
+        .with_component_parameters::<StoreImpl>(StoreImplParameters {
+            tables: Arc::clone(&tables),
+        })
+        .build();
+    let repository_module = RepositoryModule::builder()
+        .with_component_parameters::<StoreImpl>(StoreImplParameters { tables })
+        .build();
+    (query_module, repository_module)
+}
+
+/// `expected` のバージョンで保存されている場合のみ保存する
+/// DynamoDBの実装と同じく、保存されるバージョンは `expected.next()` になる
+fn save_versioned<K, T>(
+    table: &mut HashMap<K, T>,
+    key: K,
+    value: T,
+    stored_version: fn(&T) -> Version,
+    expected: Version,
+) -> Result<(), RepositoryError>
+where
+    K: std::hash::Hash + Eq,
+    T: Serialize + DeserializeOwned,
+{
+    // NOTE: 未保存の場合はバージョンによらず追加する
+    let current = table.get(&key).map(stored_version);
+    if current.map_or(false, |current| current != expected) {
+        return Err(RepositoryError::Conflict { expected });
+    }
+    table.insert(key, with_version(value, expected.next())?);
+    Ok(())
+}
+
+// versionはモデルの外から書き換えられないので、シリアライズしなおして設定する
+fn with_version<T: Serialize + DeserializeOwned>(
+    value: T,
+    version: Version,
+) -> Result<T, RepositoryError> {
+    let to_error = |e: serde_json::Error| RepositoryError::Serialization(e.to_string());
+    let mut json = serde_json::to_value(value).map_err(to_error)?;
+    json["version"] = serde_json::to_value(version).map_err(to_error)?;
+    serde_json::from_value(json).map_err(to_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use domain_kanban::user::UserRepository;
+    use query_resolver::{
+        ArchivedFilter, BoardCriteria, BoardQuery, CardsQuery, SearchHitKind, SearchPage,
+        SearchQuery, UsersQuery,
+    };
+    use shaku::HasProvider;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_seeded_queries() {
+        // Arrange
+        let (query_module, _) = modules(Tables::seeded());
+        let board_query: Box<dyn BoardQuery> = query_module.provide().unwrap();
+        let cards_query: Box<dyn CardsQuery> = query_module.provide().unwrap();
+        let board = &sample::data().boards[0];
+
+        // Act
+        let boards = board_query.all(&BoardCriteria::default()).await.unwrap();
+        let card = cards_query
+            .find_by_order(&board.column_ids[0], &0, ArchivedFilter::Exclude)
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(boards.len(), sample::data().boards.len());
+        let column = sample::data()
+            .columns
+            .iter()
+            .find(|c| c.id == board.column_ids[0])
+            .unwrap();
+        assert_eq!(card.title, column.cards[0].title);
+    }
+
+    #[tokio::test]
+    async fn test_seeded_cards_keep_sample_ids() {
+        // Arrange
+        let (query_module, _) = modules(Tables::seeded());
+        let cards_query: Box<dyn CardsQuery> = query_module.provide().unwrap();
+        let column = &sample::data().columns[0];
+        let card = &column.cards[1];
+
+        // Act
+        let result = cards_query.list_by_ids(&[card.id.clone()]).await.unwrap();
+
+        // Assert
+        let view = &result[&card.id];
+        assert_eq!(view.title, card.title);
+        assert_eq!(view.column_id, column.id.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_saved_user_is_visible_to_query() {
+        // Arrange
+        let (query_module, repository_module) = modules(Tables::default());
+        let users_query: Box<dyn UsersQuery> = query_module.provide().unwrap();
+        let user_repository: Box<dyn UserRepository> = repository_module.provide().unwrap();
+        let user = User::new(
+            UserName::new("memory".to_owned()).unwrap(),
+            Email::new("memory@example.com".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        // Act
+        user_repository.save(user.clone()).await.unwrap();
+        let result = users_query.find_by_id(user.user_id()).await.unwrap();
+
+        // Assert
+        assert_eq!(result.name, "memory");
+        assert_eq!(result.email, "memory@example.com");
+        assert_eq!(result.version, 1);
+    }
+
+    #[tokio::test]
+    async fn test_save_conflict() {
+        // Arrange
+        let (_, repository_module) = modules(Tables::default());
+        let user_repository: Box<dyn UserRepository> = repository_module.provide().unwrap();
+        let user = User::new(
+            UserName::new("memory".to_owned()).unwrap(),
+            Email::new("memory@example.com".to_owned()).unwrap(),
+        )
+        .unwrap();
+        user_repository.save(user.clone()).await.unwrap();
+
+        // Act
+        let stale = user_repository.save(user.clone()).await;
+
+        // Assert
+        assert_eq!(
+            stale,
+            Err(RepositoryError::Conflict {
+                expected: Version::initial()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_japanese_title() {
+        // Arrange
+        let (query_module, _) = modules(Tables::seeded());
+        let search_query: Box<dyn SearchQuery> = query_module.provide().unwrap();
+        let page = SearchPage {
+            query: "ゴミ".to_owned(),
+            first: 10,
+            offset: 0,
+        };
+
+        // Act
+        let hits = search_query.search(&page).await.unwrap();
+
+        // Assert
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, SearchHitKind::Card);
+        assert_eq!(hits[0].id, "card-01HBFZE37QQPEATF1DDMD3T7X7");
+    }
+}
@@ -0,0 +1,22 @@
+mod activity;
+mod board;
+mod card;
+mod column;
+mod comment;
+mod search;
+mod user;
+
+shaku::module! {
+    pub Module {
+        components = [super::StoreImpl],
+        providers = [
+            activity::ActivityQueryImpl,
+            board::BoardQueryImpl,
+            card::CardsQueryImpl,
+            column::ColumnsQueryImpl,
+            comment::CommentsQueryImpl,
+            search::SearchQueryImpl,
+            user::UsersQueryImpl,
+        ]
+    }
+}
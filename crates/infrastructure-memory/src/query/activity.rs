@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use domain_kanban::{activity::Activity, board::BoardId, user::UserId};
+use query_resolver::{ActivityPage, ActivityQuery, ActivityView};
+use shaku::Provider;
+
+use crate::Store;
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = ActivityQuery)]
+pub struct ActivityQueryImpl {
+    #[shaku(inject)]
+    store: Arc<dyn Store>,
+}
+
+impl ActivityQueryImpl {
+    fn list(
+        &self,
+        page: &ActivityPage,
+        predicate: impl Fn(&Activity) -> bool,
+    ) -> Vec<ActivityView> {
+        let tables = self.store.read();
+        let after = page.after.as_ref().map(ToString::to_string);
+        // NOTE: IDはULIDなので、IDの順序が記録された順序になる
+        let mut activities: Vec<_> = tables
+            .activities
+            .iter()
+            .filter(|&a| predicate(a))
+            .filter(|a| {
+                after
+                    .as_ref()
+                    .map_or(true, |after| &a.id().to_string() < after)
+            })
+            .filter(|a| page.actions.is_empty() || page.actions.contains(&a.action()))
+            .collect();
+        activities.sort_by_key(|a| std::cmp::Reverse(a.id().to_string()));
+        activities
+            .into_iter()
+            .take(page.first)
+            .map(to_view)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl ActivityQuery for ActivityQueryImpl {
+    async fn list_by_board(
+        &self,
+        board_id: &BoardId,
+        page: &ActivityPage,
+    ) -> Result<Vec<ActivityView>> {
+        Ok(self.list(page, |a| a.board_id() == Some(board_id)))
+    }
+
+    async fn list_by_actor(
+        &self,
+        actor_id: &UserId,
+        page: &ActivityPage,
+    ) -> Result<Vec<ActivityView>> {
+        Ok(self.list(page, |a| a.actor() == actor_id))
+    }
+}
+
+fn to_view(activity: &Activity) -> ActivityView {
+    ActivityView {
+        id: activity.id().to_string(),
+        actor_id: activity.actor().to_string(),
+        action: activity.action().as_str().to_owned(),
+        target_id: activity.target().to_owned(),
+        board_id: activity.board_id().map(ToString::to_string),
+        before: activity.before().map(ToOwned::to_owned),
+        after: activity.after().map(ToOwned::to_owned),
+        occurred_at: *activity.occurred_at(),
+    }
+}
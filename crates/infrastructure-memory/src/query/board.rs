@@ -0,0 +1,94 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use domain_kanban::{
+    board::{Board, BoardId},
+    column::ColumnId,
+    user::UserId,
+};
+use query_resolver::{BoardCriteria, BoardQuery, BoardView};
+use shaku::Provider;
+
+use crate::Store;
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = BoardQuery)]
+pub struct BoardQueryImpl {
+    #[shaku(inject)]
+    store: Arc<dyn Store>,
+}
+
+#[async_trait]
+impl BoardQuery for BoardQueryImpl {
+    async fn find_by_id(&self, id: &BoardId) -> Result<BoardView> {
+        let tables = self.store.read();
+        let board = tables
+            .boards
+            .get(id)
+            .ok_or_else(|| anyhow!("board not found: {id}"))?;
+        Ok(to_view(board))
+    }
+
+    async fn list_by_ids(&self, ids: &[BoardId]) -> Result<HashMap<BoardId, BoardView>> {
+        let tables = self.store.read();
+        let result = ids
+            .iter()
+            .filter_map(|id| tables.boards.get(id))
+            .map(|b| (b.id().clone(), to_view(b)))
+            .collect();
+        Ok(result)
+    }
+
+    async fn all(&self, criteria: &BoardCriteria) -> Result<Vec<BoardView>> {
+        let tables = self.store.read();
+        let mut result: Vec<_> = tables
+            .boards
+            .values()
+            .map(|b| (to_view(b), b.members()))
+            .filter(|(view, members)| criteria.matches(view, members))
+            .map(|(view, _)| view)
+            .collect();
+        criteria.sort(&mut result);
+        Ok(result)
+    }
+
+    async fn list_by_column_ids(&self, ids: &[ColumnId]) -> Result<HashMap<ColumnId, BoardView>> {
+        let tables = self.store.read();
+        let result = tables
+            .boards
+            .values()
+            .flat_map(|b| b.column_ids().iter().map(move |c| (c, b)))
+            .filter(|(c, _)| ids.contains(c))
+            .map(|(c, b)| (c.clone(), to_view(b)))
+            .collect();
+        Ok(result)
+    }
+
+    async fn list_by_member_ids(&self, ids: &[UserId]) -> Result<HashMap<UserId, Vec<BoardView>>> {
+        let tables = self.store.read();
+        let mut boards: Vec<_> = tables.boards.values().collect();
+        boards.sort_by_key(|b| b.id().to_string());
+        let mut result: HashMap<UserId, Vec<BoardView>> = HashMap::new();
+        for board in boards {
+            for member in board.members().iter().filter(|m| ids.contains(m)) {
+                result
+                    .entry(member.clone())
+                    .or_default()
+                    .push(to_view(board));
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn to_view(board: &Board) -> BoardView {
+    BoardView {
+        id: board.id().to_string(),
+        title: board.title().to_string(),
+        owner_id: board.owner().to_string(),
+        column_ids: board.column_ids().iter().map(ToString::to_string).collect(),
+        version: board.version().value(),
+        archived_at: board.archived_at().cloned(),
+    }
+}
@@ -0,0 +1,120 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use domain_kanban::column::{Card, CardId, ColumnId};
+use query_resolver::{ArchivedFilter, CardCriteria, CardView, CardsQuery, ChecklistItemView};
+use shaku::Provider;
+
+use crate::{Store, Tables};
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = CardsQuery)]
+pub struct CardsQueryImpl {
+    #[shaku(inject)]
+    store: Arc<dyn Store>,
+}
+
+#[async_trait]
+impl CardsQuery for CardsQueryImpl {
+    async fn find_by_order(
+        &self,
+        column_id: &ColumnId,
+        order: &usize,
+        archived: ArchivedFilter,
+    ) -> Result<CardView> {
+        let tables = self.store.read();
+        let card = filtered_cards(&tables, column_id, archived)?
+            .nth(*order)
+            .ok_or_else(|| anyhow!("card not found: {column_id} [{order}]"))?;
+        Ok(to_view(column_id, card))
+    }
+
+    async fn list_by_orders(
+        &self,
+        column_id: &ColumnId,
+        orders: &[usize],
+        archived: ArchivedFilter,
+    ) -> Result<HashMap<usize, CardView>> {
+        let tables = self.store.read();
+        let result = filtered_cards(&tables, column_id, archived)?
+            .enumerate()
+            .filter(|(i, _)| orders.contains(i))
+            .map(|(i, c)| (i, to_view(column_id, c)))
+            .collect();
+        Ok(result)
+    }
+
+    async fn list_by_column_ids(
+        &self,
+        ids: &[ColumnId],
+        criteria: &CardCriteria,
+    ) -> Result<HashMap<ColumnId, Vec<CardView>>> {
+        let tables = self.store.read();
+        let mut result = HashMap::new();
+        for column in ids.iter().filter_map(|id| tables.columns.get(id)) {
+            let mut cards: Vec<_> = column
+                .cards()
+                .iter()
+                .map(|c| to_view(column.id(), c))
+                .filter(|c| criteria.matches(c))
+                .collect();
+            if cards.is_empty() {
+                continue;
+            }
+            criteria.sort(&mut cards);
+            result.insert(column.id().clone(), cards);
+        }
+        Ok(result)
+    }
+
+    async fn list_by_ids(&self, ids: &[CardId]) -> Result<HashMap<CardId, CardView>> {
+        let tables = self.store.read();
+        let result = tables
+            .columns
+            .values()
+            .flat_map(|column| column.cards().iter().map(move |c| (column.id(), c)))
+            .filter(|(_, c)| ids.contains(c.id()))
+            .map(|(column_id, c)| (c.id().clone(), to_view(column_id, c)))
+            .collect();
+        Ok(result)
+    }
+}
+
+// カラム内のカードを表示順のまま `archived` で絞り込む
+fn filtered_cards<'a>(
+    tables: &'a Tables,
+    column_id: &ColumnId,
+    archived: ArchivedFilter,
+) -> Result<impl Iterator<Item = &'a Card>> {
+    let column = tables
+        .columns
+        .get(column_id)
+        .ok_or_else(|| anyhow!("column not found: {column_id}"))?;
+    let cards = column
+        .cards()
+        .iter()
+        .filter(move |c| archived.includes_archived() || c.archived_at().is_none());
+    Ok(cards)
+}
+
+fn to_view(column_id: &ColumnId, card: &Card) -> CardView {
+    let checklist = card
+        .checklist()
+        .items()
+        .iter()
+        .map(|i| ChecklistItemView {
+            text: i.text().to_string(),
+            done: i.done(),
+        })
+        .collect();
+    CardView {
+        id: card.id().to_string(),
+        title: card.title().to_string(),
+        description: card.description().to_string(),
+        column_id: column_id.to_string(),
+        checklist,
+        archived_at: card.archived_at().cloned(),
+        due_at: card.due_at().cloned(),
+    }
+}
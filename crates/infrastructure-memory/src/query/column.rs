@@ -0,0 +1,53 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use domain_kanban::column::{Column, ColumnId};
+use query_resolver::{ColumnView, ColumnsQuery};
+use shaku::Provider;
+
+use crate::Store;
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = ColumnsQuery)]
+pub struct ColumnsQueryImpl {
+    #[shaku(inject)]
+    store: Arc<dyn Store>,
+}
+
+#[async_trait]
+impl ColumnsQuery for ColumnsQueryImpl {
+    async fn find_by_id(&self, id: &ColumnId) -> Result<ColumnView> {
+        let tables = self.store.read();
+        let column = tables
+            .columns
+            .get(id)
+            .ok_or_else(|| anyhow!("column not found: {id}"))?;
+        Ok(to_view(column))
+    }
+
+    async fn list_by_ids(&self, ids: &[ColumnId]) -> Result<HashMap<ColumnId, ColumnView>> {
+        let tables = self.store.read();
+        let result = ids
+            .iter()
+            .filter_map(|id| tables.columns.get(id))
+            .map(|c| (c.id().clone(), to_view(c)))
+            .collect();
+        Ok(result)
+    }
+}
+
+fn to_view(column: &Column) -> ColumnView {
+    let archived_card_cnt = column
+        .cards()
+        .iter()
+        .filter(|c| c.archived_at().is_some())
+        .count();
+    ColumnView {
+        id: column.id().to_string(),
+        title: column.title().to_string(),
+        card_cnt: column.cards().len() - archived_card_cnt,
+        archived_card_cnt,
+        archived_at: column.archived_at().cloned(),
+    }
+}
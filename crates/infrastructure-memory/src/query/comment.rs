@@ -0,0 +1,48 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use domain_kanban::column::CardId;
+use query_resolver::{CommentView, CommentsQuery};
+use shaku::Provider;
+
+use crate::Store;
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = CommentsQuery)]
+pub struct CommentsQueryImpl {
+    #[shaku(inject)]
+    store: Arc<dyn Store>,
+}
+
+#[async_trait]
+impl CommentsQuery for CommentsQueryImpl {
+    async fn list_by_card_ids(
+        &self,
+        card_ids: &[CardId],
+    ) -> Result<HashMap<CardId, Vec<CommentView>>> {
+        let tables = self.store.read();
+        let mut comments: Vec<_> = tables
+            .comments
+            .values()
+            .filter(|c| card_ids.contains(c.card_id()))
+            .collect();
+        comments.sort_by_key(|c| (*c.created_at(), c.id().to_string()));
+
+        let mut result: HashMap<CardId, Vec<CommentView>> = HashMap::new();
+        for c in comments {
+            result
+                .entry(c.card_id().clone())
+                .or_default()
+                .push(CommentView {
+                    id: c.id().to_string(),
+                    card_id: c.card_id().to_string(),
+                    author_id: c.author().to_string(),
+                    body: c.body().to_string(),
+                    created_at: *c.created_at(),
+                    edited_at: c.edited_at().cloned(),
+                });
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use query_resolver::{
+    paginate_hits, substring_score, SearchHit, SearchHitKind, SearchPage, SearchQuery,
+};
+use shaku::Provider;
+
+use crate::Store;
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = SearchQuery)]
+pub struct SearchQueryImpl {
+    #[shaku(inject)]
+    store: Arc<dyn Store>,
+}
+
+#[async_trait]
+impl SearchQuery for SearchQueryImpl {
+    async fn search(&self, page: &SearchPage) -> Result<Vec<SearchHit>> {
+        let tables = self.store.read();
+        let query = page.query.as_str();
+        let hit = |kind, id: String, score| SearchHit { kind, id, score };
+
+        let boards = tables
+            .boards
+            .values()
+            .filter(|b| b.archived_at().is_none())
+            .filter_map(|b| {
+                let score = substring_score(query, &b.title().to_string(), None)?;
+                Some(hit(SearchHitKind::Board, b.id().to_string(), score))
+            });
+        let columns = tables
+            .columns
+            .values()
+            .filter(|c| c.archived_at().is_none())
+            .filter_map(|c| {
+                let score = substring_score(query, &c.title().to_string(), None)?;
+                Some(hit(SearchHitKind::Column, c.id().to_string(), score))
+            });
+        let cards = tables
+            .columns
+            .values()
+            .flat_map(|c| c.cards())
+            .filter(|c| c.archived_at().is_none())
+            .filter_map(|c| {
+                let description = c.description().to_string();
+                let score = substring_score(query, &c.title().to_string(), Some(&description))?;
+                Some(hit(SearchHitKind::Card, c.id().to_string(), score))
+            });
+        let hits = boards.chain(columns).chain(cards).collect();
+        Ok(paginate_hits(hits, page))
+    }
+}
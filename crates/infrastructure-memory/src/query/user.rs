@@ -0,0 +1,67 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use domain_kanban::user::{User, UserId};
+use query_resolver::{UserCriteria, UserView, UsersQuery};
+use shaku::Provider;
+
+use crate::{Store, Tables};
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = UsersQuery)]
+pub struct UsersQueryImpl {
+    #[shaku(inject)]
+    store: Arc<dyn Store>,
+}
+
+#[async_trait]
+impl UsersQuery for UsersQueryImpl {
+    async fn find_by_id(&self, id: &UserId) -> Result<UserView> {
+        let tables = self.store.read();
+        let user = tables
+            .users
+            .get(id)
+            .ok_or_else(|| anyhow!("user not found: {id}"))?;
+        Ok(to_view(&tables, user))
+    }
+
+    async fn list_by_ids(&self, ids: &[UserId]) -> Result<HashMap<UserId, UserView>> {
+        let tables = self.store.read();
+        let result = ids
+            .iter()
+            .filter_map(|id| tables.users.get(id))
+            .map(|u| (u.user_id().clone(), to_view(&tables, u)))
+            .collect();
+        Ok(result)
+    }
+
+    async fn all(&self, criteria: &UserCriteria) -> Result<Vec<UserView>> {
+        let tables = self.store.read();
+        let mut result: Vec<_> = tables
+            .users
+            .values()
+            .map(|u| to_view(&tables, u))
+            .filter(|u| criteria.matches(u))
+            .collect();
+        criteria.sort(&mut result);
+        Ok(result)
+    }
+}
+
+fn to_view(tables: &Tables, user: &User) -> UserView {
+    // NOTE: user_board_relationsの代わりに、ボードの所有者から求める
+    let owned_board_ids = tables
+        .boards
+        .values()
+        .filter(|b| b.owner() == user.user_id())
+        .map(|b| b.id().to_string())
+        .collect();
+    UserView {
+        id: user.user_id().to_string(),
+        name: user.user_name().to_string(),
+        email: user.email().to_string(),
+        owned_board_ids,
+        version: user.version().value(),
+    }
+}
@@ -0,0 +1,22 @@
+mod activity;
+mod archive;
+mod board;
+mod column;
+mod comment;
+mod outbox;
+mod user;
+
+shaku::module! {
+    pub Module {
+        components = [super::StoreImpl],
+        providers = [
+            activity::ActivityRepositoryImpl,
+            archive::ArchivePurgerImpl,
+            board::BoardRepositoryImpl,
+            column::ColumnRepositoryImpl,
+            comment::CommentRepositoryImpl,
+            outbox::OutboxStoreImpl,
+            user::UserRepositoryImpl,
+        ]
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain_kanban::activity::{Activity, ActivityRepository};
+use domain_util::{RepositoryError, Version};
+use shaku::Provider;
+
+use crate::Store;
+
+/// ActivityRepositoryの実装
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = ActivityRepository)]
+pub struct ActivityRepositoryImpl {
+    #[shaku(inject)]
+    store: Arc<dyn Store>,
+}
+
+#[async_trait]
+impl ActivityRepository for ActivityRepositoryImpl {
+    async fn append(&self, activity: Activity) -> Result<(), RepositoryError> {
+        let mut tables = self.store.write();
+        // 追記のみなので、同じIDで上書きしない
+        if tables.activities.iter().any(|a| a.id() == activity.id()) {
+            return Err(RepositoryError::Conflict {
+                expected: Version::initial(),
+            });
+        }
+        tables.activities.push(activity);
+        Ok(())
+    }
+}
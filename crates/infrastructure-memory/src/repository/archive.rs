@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_kanban::archive::{ArchivePurger, PurgeReport};
+use domain_util::RepositoryError;
+use shaku::Provider;
+
+use crate::{with_version, Store};
+
+/// ArchivePurgerの実装
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = ArchivePurger)]
+pub struct ArchivePurgerImpl {
+    #[shaku(inject)]
+    store: Arc<dyn Store>,
+}
+
+#[async_trait]
+impl ArchivePurger for ArchivePurgerImpl {
+    async fn purge_archived(
+        &self,
+        archived_before: DateTime<Utc>,
+    ) -> Result<PurgeReport, RepositoryError> {
+        let mut tables = self.store.write();
+        let mut report = PurgeReport::default();
+
+        let purged_boards: Vec<_> = tables
+            .boards
+            .values()
+            .filter(|b| b.is_archived_before(&archived_before))
+            .cloned()
+            .collect();
+        for board in purged_boards {
+            for column_id in board.column_ids() {
+                if let Some(column) = tables.columns.remove(column_id) {
+                    report.cards += column.cards().len();
+                    report.columns += 1;
+                }
+            }
+            tables.boards.remove(board.id());
+            report.boards += 1;
+        }
+
+        tables.columns.retain(|_, c| {
+            let purged = c.is_archived_before(&archived_before);
+            if purged {
+                report.cards += c.cards().len();
+                report.columns += 1;
+            }
+            !purged
+        });
+        for column in tables.columns.values_mut() {
+            let purged = column.purge_archived_cards(&archived_before);
+            if purged == 0 {
+                continue;
+            }
+            // 読み込み済みのカラムで上書きされないよう、バージョンを進める
+            let version = column.version().next();
+            *column = with_version(column.clone(), version)?;
+            report.cards += purged;
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain_kanban::{
+    board::{Board, BoardId, BoardRepository},
+    outbox::OutboxMessage,
+};
+use domain_util::RepositoryError;
+use shaku::Provider;
+
+use crate::{save_versioned, Store};
+
+/// BoardRepositoryの実装
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = BoardRepository)]
+pub struct BoardRepositoryImpl {
+    #[shaku(inject)]
+    store: Arc<dyn Store>,
+}
+
+#[async_trait]
+impl BoardRepository for BoardRepositoryImpl {
+    async fn save(&self, board: Board) -> Result<(), RepositoryError> {
+        let expected = board.version();
+        let key = board.id().clone();
+        let mut tables = self.store.write();
+        save_versioned(&mut tables.boards, key, board, Board::version, expected)
+    }
+    async fn save_with_outbox(
+        &self,
+        board: Board,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        let expected = board.version();
+        let key = board.id().clone();
+        let mut tables = self.store.write();
+        save_versioned(&mut tables.boards, key, board, Board::version, expected)?;
+        tables.outbox.extend(messages);
+        Ok(())
+    }
+    async fn find_by_id(&self, id: &BoardId) -> Result<Option<Board>, RepositoryError> {
+        let tables = self.store.read();
+        Ok(tables.boards.get(id).cloned())
+    }
+    async fn find_many(&self, ids: &[BoardId]) -> Result<Vec<Option<Board>>, RepositoryError> {
+        let tables = self.store.read();
+        let result = ids
+            .iter()
+            .map(|id| tables.boards.get(id).cloned())
+            .collect();
+        Ok(result)
+    }
+}
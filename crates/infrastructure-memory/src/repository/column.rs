@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain_kanban::{
+    column::{Column, ColumnId, ColumnRepository},
+    outbox::OutboxMessage,
+};
+use domain_util::RepositoryError;
+use shaku::Provider;
+
+use crate::{save_versioned, Store};
+
+/// ColumnRepositoryの実装
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = ColumnRepository)]
+pub struct ColumnRepositoryImpl {
+    #[shaku(inject)]
+    store: Arc<dyn Store>,
+}
+
+#[async_trait]
+impl ColumnRepository for ColumnRepositoryImpl {
+    async fn save(&self, column: Column) -> Result<(), RepositoryError> {
+        let expected = column.version();
+        let key = column.id().clone();
+        let mut tables = self.store.write();
+        save_versioned(&mut tables.columns, key, column, Column::version, expected)
+    }
+    async fn save_with_outbox(
+        &self,
+        column: Column,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        let expected = column.version();
+        let key = column.id().clone();
+        let mut tables = self.store.write();
+        save_versioned(&mut tables.columns, key, column, Column::version, expected)?;
+        tables.outbox.extend(messages);
+        Ok(())
+    }
+    async fn find_by_id(&self, id: &ColumnId) -> Result<Option<Column>, RepositoryError> {
+        let tables = self.store.read();
+        Ok(tables.columns.get(id).cloned())
+    }
+}
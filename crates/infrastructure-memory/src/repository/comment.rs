@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain_kanban::{
+    comment::{Comment, CommentId, CommentRepository},
+    outbox::OutboxMessage,
+};
+use domain_util::RepositoryError;
+use shaku::Provider;
+
+use crate::{save_versioned, Store};
+
+/// CommentRepositoryの実装
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = CommentRepository)]
+pub struct CommentRepositoryImpl {
+    #[shaku(inject)]
+    store: Arc<dyn Store>,
+}
+
+#[async_trait]
+impl CommentRepository for CommentRepositoryImpl {
+    async fn save(&self, comment: Comment) -> Result<(), RepositoryError> {
+        let expected = comment.version();
+        let key = comment.id().clone();
+        let mut tables = self.store.write();
+        save_versioned(
+            &mut tables.comments,
+            key,
+            comment,
+            Comment::version,
+            expected,
+        )
+    }
+    async fn save_with_outbox(
+        &self,
+        comment: Comment,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        let expected = comment.version();
+        let key = comment.id().clone();
+        let mut tables = self.store.write();
+        save_versioned(
+            &mut tables.comments,
+            key,
+            comment,
+            Comment::version,
+            expected,
+        )?;
+        tables.outbox.extend(messages);
+        Ok(())
+    }
+    async fn find_by_id(&self, id: &CommentId) -> Result<Option<Comment>, RepositoryError> {
+        let tables = self.store.read();
+        Ok(tables.comments.get(id).cloned())
+    }
+    async fn delete(&self, id: &CommentId) -> Result<(), RepositoryError> {
+        self.store.write().comments.remove(id);
+        Ok(())
+    }
+    async fn delete_with_outbox(
+        &self,
+        id: &CommentId,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        let mut tables = self.store.write();
+        tables.comments.remove(id);
+        tables.outbox.extend(messages);
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_kanban::outbox::{DeadLetter, OutboxMessage, OutboxMessageId, OutboxStore};
+use domain_util::{RepositoryError, Version};
+use shaku::Provider;
+
+use crate::Store;
+
+/// OutboxStoreの実装
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = OutboxStore)]
+pub struct OutboxStoreImpl {
+    #[shaku(inject)]
+    store: Arc<dyn Store>,
+}
+
+#[async_trait]
+impl OutboxStore for OutboxStoreImpl {
+    async fn list_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<OutboxMessage>, RepositoryError> {
+        let tables = self.store.read();
+        // NOTE: 追加した順に並んでいる
+        let result = tables
+            .outbox
+            .iter()
+            .filter(|m| m.is_due(&now))
+            .take(limit)
+            .cloned()
+            .collect();
+        Ok(result)
+    }
+    async fn complete(&self, id: &OutboxMessageId) -> Result<(), RepositoryError> {
+        self.store.write().outbox.retain(|m| m.id() != id);
+        Ok(())
+    }
+    async fn reschedule(&self, message: OutboxMessage) -> Result<(), RepositoryError> {
+        let mut tables = self.store.write();
+        // 配送済みで削除されたものは作り直さない
+        let stored = tables
+            .outbox
+            .iter_mut()
+            .find(|m| m.id() == message.id())
+            .ok_or(RepositoryError::Conflict {
+                expected: Version::initial(),
+            })?;
+        *stored = message;
+        Ok(())
+    }
+    async fn dead_letter(&self, letter: DeadLetter) -> Result<(), RepositoryError> {
+        let mut tables = self.store.write();
+        tables.outbox.retain(|m| m.id() != letter.message().id());
+        tables.dead_letters.push(letter);
+        Ok(())
+    }
+    async fn list_dead_letters(&self, limit: usize) -> Result<Vec<DeadLetter>, RepositoryError> {
+        let tables = self.store.read();
+        let result = tables
+            .dead_letters
+            .iter()
+            .rev()
+            .take(limit)
+            .cloned()
+            .collect();
+        Ok(result)
+    }
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain_kanban::{
+    outbox::OutboxMessage,
+    user::{User, UserId, UserRepository},
+};
+use domain_util::RepositoryError;
+use shaku::Provider;
+
+use crate::{save_versioned, with_version, Store};
+
+/// UserRepositoryの実装
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = UserRepository)]
+pub struct UserRepositoryImpl {
+    #[shaku(inject)]
+    store: Arc<dyn Store>,
+}
+
+#[async_trait]
+impl UserRepository for UserRepositoryImpl {
+    async fn save(&self, user: User) -> Result<(), RepositoryError> {
+        let expected = user.version();
+        let key = user.user_id().clone();
+        let mut tables = self.store.write();
+        save_versioned(&mut tables.users, key, user, User::version, expected)
+    }
+    async fn save_with_outbox(
+        &self,
+        user: User,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        let expected = user.version();
+        let key = user.user_id().clone();
+        let mut tables = self.store.write();
+        save_versioned(&mut tables.users, key, user, User::version, expected)?;
+        tables.outbox.extend(messages);
+        Ok(())
+    }
+    async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, RepositoryError> {
+        let tables = self.store.read();
+        Ok(tables.users.get(id).cloned())
+    }
+    async fn find_many(&self, ids: &[UserId]) -> Result<Vec<Option<User>>, RepositoryError> {
+        let tables = self.store.read();
+        let result = ids.iter().map(|id| tables.users.get(id).cloned()).collect();
+        Ok(result)
+    }
+    async fn save_all(&self, users: Vec<User>) -> Result<(), RepositoryError> {
+        let mut tables = self.store.write();
+        // 競合するものがあれば何も保存しない
+        for user in &users {
+            let expected = user.version();
+            let current = tables.users.get(user.user_id()).map(User::version);
+            if current.map_or(false, |current| current != expected) {
+                return Err(RepositoryError::Conflict { expected });
+            }
+        }
+        for user in users {
+            let version = user.version().next();
+            let key = user.user_id().clone();
+            tables.users.insert(key, with_version(user, version)?);
+        }
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ use std::str::FromStr;
 use std::sync::OnceLock;
 
 use domain_kanban::board::BoardId;
-use domain_kanban::column::ColumnId;
+use domain_kanban::column::{CardId, ColumnId};
 use domain_kanban::user::UserId;
 
 pub static DATA: OnceLock<Data> = OnceLock::new();
@@ -77,19 +77,15 @@ impl Column {
 
 #[derive(Debug, Clone)]
 pub struct Card {
-    pub id: String,
+    pub id: CardId,
     pub title: String,
     pub description: String,
 }
 
 impl Card {
-    fn new(
-        id: impl Into<String>,
-        title: impl Into<String>,
-        description: impl Into<String>,
-    ) -> Self {
+    fn new(id: &str, title: impl Into<String>, description: impl Into<String>) -> Self {
         Self {
-            id: id.into(),
+            id: CardId::from_str(id).unwrap(),
             title: title.into(),
             description: description.into(),
         }
@@ -101,6 +97,12 @@ pub struct Data {
     pub boards: Vec<Board>,
     pub columns: Vec<Column>,
 }
+impl Default for Data {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Data {
     pub fn new() -> Self {
         let user_ids = vec![
@@ -148,41 +150,41 @@ impl Data {
                 column_ids[0].clone(),
                 "TODO",
                 vec![
-                    Card::new("c0", "掃除", ""),
-                    Card::new("c1", "洗濯", ""),
-                    Card::new("c2", "食事", ""),
+                    Card::new("card-01HBFZE37M0SKHS01HFYHV2YCX", "掃除", ""),
+                    Card::new("card-01HBFZE37N7TRMCT2JAYYXCG7V", "洗濯", ""),
+                    Card::new("card-01HBFZE37PQJ4J7E61X4WJ8NPY", "食事", ""),
                 ],
             ),
             Column::new(
                 column_ids[1].clone(),
                 "doing",
-                vec![Card::new("c3", "ゴミ出し", "")],
+                vec![Card::new("card-01HBFZE37QQPEATF1DDMD3T7X7", "ゴミ出し", "")],
             ),
             Column::new(
                 column_ids[2].clone(),
                 "DONE",
                 vec![
-                    Card::new("c14", "買い物", ""),
-                    Card::new("c15", "洗い物", ""),
+                    Card::new("card-01HBFZE382AWD6RC3N26SBJGVT", "買い物", ""),
+                    Card::new("card-01HBFZE3836WF2FKC1613J54W9", "洗い物", ""),
                 ],
             ),
             Column::new(
                 column_ids[3].clone(),
                 "wish",
                 vec![
-                    Card::new("c4", "ランタン", ""),
-                    Card::new("c5", "本棚", "いろいろ"),
+                    Card::new("card-01HBFZE37R2S0RQ7946KJ6BRAG", "ランタン", ""),
+                    Card::new("card-01HBFZE37S10H6R0RHK6BJN139", "本棚", "いろいろ"),
                 ],
             ),
             Column::new(
                 column_ids[4].clone(),
                 "bought",
                 vec![
-                    Card::new("c13", "石鹸", ""),
-                    Card::new("c12", "常備薬", ""),
-                    Card::new("c11", "米", ""),
-                    Card::new("c9", "センサーライト", ""),
-                    Card::new("c10", "飲み物", ""),
+                    Card::new("card-01HBFZE38153FWWJHBQQ142CRB", "石鹸", ""),
+                    Card::new("card-01HBFZE38006MXX2KQ9F7KDNCB", "常備薬", ""),
+                    Card::new("card-01HBFZE37Z8WVSDPGXNHSFYQ9A", "米", ""),
+                    Card::new("card-01HBFZE37XPE5ACQPKH6VMT3XH", "センサーライト", ""),
+                    Card::new("card-01HBFZE37Y71Q6PSD6MJ5R2J7P", "飲み物", ""),
                 ],
             ),
             Column::new(column_ids[5].clone(), "pending", vec![]),
@@ -190,9 +192,9 @@ impl Data {
                 column_ids[6].clone(),
                 "challenge",
                 vec![
-                    Card::new("c8", "ゴハッチュウ", ""),
-                    Card::new("c7", "ダソッキー", ""),
-                    Card::new("c6", "床下三兄弟", ""),
+                    Card::new("card-01HBFZE37W6BKRZBE1MR3CP3XK", "ゴハッチュウ", ""),
+                    Card::new("card-01HBFZE37VK8ER2QQPT4XRZAGR", "ダソッキー", ""),
+                    Card::new("card-01HBFZE37T6ZW1FZ39B83T1JKE", "床下三兄弟", ""),
                 ],
             ),
             Column::new(column_ids[7].clone(), "got", vec![]),
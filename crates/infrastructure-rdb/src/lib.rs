@@ -1,12 +1,20 @@
+mod purge;
 mod query;
-mod sample;
+mod repository;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(test)]
+mod test_util;
 
 pub use query::Module as QueryModule;
+pub use repository::Module as RepositoryModule;
 
 use anyhow::Result;
+use domain_util::RepositoryError;
+use resilience::{Resilience, ResiliencePolicy};
 use shaku::{Component, Interface};
 use sqlx::{postgres::PgPoolOptions, PgPool};
-use std::fmt::Debug;
+use std::{fmt::Debug, future::Future, sync::Arc};
 
 pub struct Configuration {
     max_connections: u32,
@@ -44,18 +52,23 @@ impl Default for Configuration {
 
 pub trait Pool: Interface + Debug {
     fn pool(&self) -> &PgPool;
+    /// Postgresへの呼び出しで共有するタイムアウト・再試行・サーキットブレーカー
+    fn resilience(&self) -> &Resilience;
 }
 
 #[derive(Debug, Clone, Component)]
 #[shaku(interface = Pool)]
 pub struct PgPoolImpl {
     pool: PgPool,
+    resilience: Arc<Resilience>,
 }
 
 impl PgPoolImpl {
+    /// デフォルトのポリシーで呼び出す
     pub async fn from_configuration(conf: Configuration) -> Result<Self> {
         let slf = Self {
             pool: conf.connect().await?,
+            resilience: Arc::new(Resilience::new("postgres", ResiliencePolicy::default())),
         };
         Ok(slf)
     }
@@ -65,4 +78,56 @@ impl Pool for PgPoolImpl {
     fn pool(&self) -> &PgPool {
         &self.pool
     }
+    fn resilience(&self) -> &Resilience {
+        &self.resilience
+    }
+}
+
+/// 読み込みのクエリを、タイムアウト・再試行・サーキットブレーカーを適用して実行する
+async fn read<T, F, Fut>(pool: &dyn Pool, mut query: F) -> Result<T, RepositoryError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = sqlx::Result<T>>,
+{
+    let call = || {
+        let fetch = query();
+        async move { fetch.await.map_err(repository_error) }
+    };
+    pool.resilience().read(call).await
+}
+
+/// 1文で完結する書き込みを、タイムアウト・サーキットブレーカーを適用して実行する
+/// タイムアウトした場合は反映されたかわからないので、再試行しない
+async fn write<T, F, Fut>(pool: &dyn Pool, mut query: F) -> Result<T, RepositoryError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = sqlx::Result<T>>,
+{
+    let call = || {
+        let execute = query();
+        async move { execute.await.map_err(repository_error) }
+    };
+    pool.resilience().write(call).await
+}
+
+// sqlxのエラーを、再試行できるかどうかがわかるリポジトリのエラーにする
+fn repository_error(e: sqlx::Error) -> RepositoryError {
+    // 送信中・応答待ちに接続が切れた場合は、反映されたかわからない
+    if let sqlx::Error::Io(_) = e {
+        return RepositoryError::Indeterminate(e.to_string());
+    }
+    let transient = match &e {
+        // 接続を取得できなかった場合は、まだ何も送っていない
+        sqlx::Error::PoolTimedOut => true,
+        // serialization_failure と deadlock_detected はトランザクションをやりなおせば成功しうる
+        sqlx::Error::Database(db) => matches!(db.code().as_deref(), Some("40001" | "40P01")),
+        _ => false,
+    };
+    match e {
+        _ if transient => RepositoryError::Transient(e.to_string()),
+        sqlx::Error::ColumnDecode { .. } | sqlx::Error::Decode(_) => {
+            RepositoryError::Serialization(e.to_string())
+        }
+        _ => RepositoryError::Other(e.to_string()),
+    }
 }
@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_kanban::archive::{ArchivePurger, PurgeReport};
+use domain_util::RepositoryError;
+use shaku::Provider;
+use sqlx::query;
+
+use crate::{repository_error, Pool};
+
+/// リードモデル側のArchivePurgerの実装
+/// コメント・チェックリストはカードの削除に合わせてcascadeで削除される
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = ArchivePurger)]
+pub struct ArchivePurgerImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[async_trait]
+impl ArchivePurger for ArchivePurgerImpl {
+    async fn purge_archived(
+        &self,
+        archived_before: DateTime<Utc>,
+    ) -> Result<PurgeReport, RepositoryError> {
+        let purge = || async { self.purge(archived_before).await.map_err(repository_error) };
+        self.pool.resilience().write(purge).await
+    }
+}
+
+impl ArchivePurgerImpl {
+    async fn purge(&self, archived_before: DateTime<Utc>) -> sqlx::Result<PurgeReport> {
+        let mut tx = self.pool.pool().begin().await?;
+
+        let board_ids: Vec<String> = query!(
+            r#"
+            select id from boards where archived_at < $1
+            "#,
+            archived_before
+        )
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+
+        // 削除するボードのカラムも削除する
+        let column_ids: Vec<String> = query!(
+            r#"
+            select c.id
+            from columns c
+            where c.archived_at < $1
+                or c.id in (
+                    select column_id from board_column_relations where board_id = any($2)
+                )
+            "#,
+            archived_before,
+            &board_ids
+        )
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+
+        // カラムから取り除かれたカードも、保持期間を過ぎたら削除する
+        let cards = query!(
+            r#"
+            delete from cards
+            where archived_at < $1 or detached_at < $1 or column_id = any($2)
+            "#,
+            archived_before,
+            &column_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        query!(
+            r#"
+            delete from board_column_relations
+            where board_id = any($1) or column_id = any($2)
+            "#,
+            &board_ids,
+            &column_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+        query!(
+            r#"
+            delete from columns where id = any($1)
+            "#,
+            &column_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+        query!(
+            r#"
+            delete from user_board_relations where board_id = any($1)
+            "#,
+            &board_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+        query!(
+            r#"
+            delete from boards where id = any($1)
+            "#,
+            &board_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        let result = PurgeReport {
+            boards: board_ids.len(),
+            columns: column_ids.len(),
+            cards: cards.rows_affected() as usize,
+        };
+        Ok(result)
+    }
+}
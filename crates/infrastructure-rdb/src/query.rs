@@ -2,19 +2,27 @@ use anyhow::Result;
 
 use crate::{Configuration, PgPoolImpl, Pool};
 
+mod activity;
 mod board;
 mod card;
 mod column;
+mod comment;
+mod search;
 mod user;
 
 shaku::module! {
     pub Module {
         components = [super::PgPoolImpl],
         providers = [
+            activity::ActivityQueryImpl,
             board::BoardQueryImpl,
             card::CardsQueryImpl,
             column::ColumnsQueryImpl,
+            comment::CommentsQueryImpl,
+            search::SearchQueryImpl,
             user::UsersQueryImpl,
+            // NOTE: クエリではないが、リードモデルのテーブルを扱うのでここに置く
+            super::purge::ArchivePurgerImpl,
         ]
     }
 }
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use domain_kanban::{board::BoardId, user::UserId};
+use query_resolver::{ActivityPage, ActivityQuery, ActivityView};
+use shaku::Provider;
+use sqlx::query;
+
+use crate::{read, Pool};
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = ActivityQuery)]
+pub struct ActivityQueryImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[async_trait]
+impl ActivityQuery for ActivityQueryImpl {
+    async fn list_by_board(
+        &self,
+        board_id: &BoardId,
+        page: &ActivityPage,
+    ) -> Result<Vec<ActivityView>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let (after, actions, limit) = page_params(page)?;
+        let activities = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                -- NOTE: IDはULIDなので、IDの順序が記録された順序になる
+                select a.id, a.actor_id, a.action, a.target_id, a.board_id,
+                    a.before_value, a.after_value, a.occurred_at
+                from activities a
+                where a.board_id = $1
+                    and ($2::varchar is null or a.id < $2)
+                    and (cardinality($3::varchar[]) = 0 or a.action = any($3))
+                order by a.id desc
+                limit $4
+                "#,
+                board_id.to_string(),
+                after,
+                &actions,
+                limit,
+            )
+            .fetch_all(executor)
+        })
+        .await?;
+
+        let result = activities
+            .into_iter()
+            .map(|a| ActivityView {
+                id: a.id,
+                actor_id: a.actor_id,
+                action: a.action,
+                target_id: a.target_id,
+                board_id: a.board_id,
+                before: a.before_value,
+                after: a.after_value,
+                occurred_at: a.occurred_at,
+            })
+            .collect();
+        Ok(result)
+    }
+
+    async fn list_by_actor(
+        &self,
+        actor_id: &UserId,
+        page: &ActivityPage,
+    ) -> Result<Vec<ActivityView>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let (after, actions, limit) = page_params(page)?;
+        let activities = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select a.id, a.actor_id, a.action, a.target_id, a.board_id,
+                    a.before_value, a.after_value, a.occurred_at
+                from activities a
+                where a.actor_id = $1
+                    and ($2::varchar is null or a.id < $2)
+                    and (cardinality($3::varchar[]) = 0 or a.action = any($3))
+                order by a.id desc
+                limit $4
+                "#,
+                actor_id.to_string(),
+                after,
+                &actions,
+                limit,
+            )
+            .fetch_all(executor)
+        })
+        .await?;
+
+        let result = activities
+            .into_iter()
+            .map(|a| ActivityView {
+                id: a.id,
+                actor_id: a.actor_id,
+                action: a.action,
+                target_id: a.target_id,
+                board_id: a.board_id,
+                before: a.before_value,
+                after: a.after_value,
+                occurred_at: a.occurred_at,
+            })
+            .collect();
+        Ok(result)
+    }
+}
+
+// ActivityPageをSQLのパラメータにする
+fn page_params(page: &ActivityPage) -> Result<(Option<String>, Vec<String>, i64)> {
+    let after = page.after.as_ref().map(ToString::to_string);
+    let actions = page.actions.iter().map(|a| a.as_str().to_owned()).collect();
+    let limit = i64::try_from(page.first)?;
+    Ok((after, actions, limit))
+}
@@ -2,13 +2,14 @@ use std::{collections::HashMap, str::FromStr, sync::Arc};
 
 use anyhow::Result;
 use async_trait::async_trait;
-use domain_kanban::board::BoardId;
+use chrono::{DateTime, Utc};
+use domain_kanban::{board::BoardId, column::ColumnId, user::UserId};
 use itertools::Itertools;
-use query_resolver::{BoardQuery, BoardView};
+use query_resolver::{BoardCriteria, BoardQuery, BoardView};
 use shaku::Provider;
 use sqlx::query;
 
-use crate::Pool;
+use crate::{read, Pool};
 
 #[derive(Debug, Clone, Provider)]
 #[shaku(interface=BoardQuery)]
@@ -24,27 +25,32 @@ impl BoardQuery for BoardQueryImpl {
         let executor = pool;
 
         let id_string = id.to_string();
-        let board = query!(
-            r#"
-            select b.id, b.title, ubr.user_id as owner_id
-            from boards b
-                inner join user_board_relations ubr on b.id = ubr.board_id
-            where b.id = $1
-            "#,
-            &id_string
-        )
-        .fetch_one(executor)
+        let board = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select b.id, b.title, ubr.user_id as owner_id, b.version, b.archived_at
+                from boards b
+                    inner join user_board_relations ubr on b.id = ubr.board_id
+                where b.id = $1
+                "#,
+                &id_string
+            )
+            .fetch_one(executor)
+        })
         .await?;
 
-        let column_ids: Vec<_> = query!(
-            r#"
-                select column_id
-                from board_column_relations
-                where board_id = $1
-            "#,
-            &id_string
-        )
-        .fetch_all(executor)
+        let column_ids: Vec<_> = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                    select column_id
+                    from board_column_relations
+                    where board_id = $1
+                    order by position
+                "#,
+                &id_string
+            )
+            .fetch_all(executor)
+        })
         .await?;
         let column_ids = column_ids.into_iter().map(|r| r.column_id).collect();
 
@@ -53,6 +59,8 @@ impl BoardQuery for BoardQueryImpl {
             title: board.title,
             owner_id: board.owner_id,
             column_ids,
+            version: board.version.try_into()?,
+            archived_at: board.archived_at,
         };
 
         Ok(result)
@@ -63,62 +71,106 @@ impl BoardQuery for BoardQueryImpl {
         let executor = pool;
         let ids_string: Vec<_> = ids.iter().map(ToString::to_string).collect();
 
-        let boards = query!(
-            r#"
-            select b.id, b.title, ubr.user_id as owner_id
-            from boards b
-                inner join user_board_relations ubr on b.id = ubr.board_id
-            where b.id = any($1)
-            "#,
-            &ids_string
-        )
-        .fetch_all(executor)
+        let boards = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select b.id, b.title, ubr.user_id as owner_id, b.version, b.archived_at
+                from boards b
+                    inner join user_board_relations ubr on b.id = ubr.board_id
+                where b.id = any($1)
+                "#,
+                &ids_string
+            )
+            .fetch_all(executor)
+        })
         .await?;
 
-        let column_ids: Vec<_> = query!(
-            r#"
-                select board_id, column_id
-                from board_column_relations
-                where board_id = any($1)
-            "#,
-            &ids_string
-        )
-        .fetch_all(executor)
+        let column_ids: Vec<_> = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                    select board_id, column_id
+                    from board_column_relations
+                    where board_id = any($1)
+                    order by board_id, position
+                "#,
+                &ids_string
+            )
+            .fetch_all(executor)
+        })
         .await?;
 
+        // NOTE: into_group_map は読み込んだ順を保つので、ボードごとに並び順のまま入る
         let mut column_id_map: HashMap<_, _> = column_ids
             .into_iter()
             .map(|r| (r.board_id, r.column_id))
             .into_group_map();
 
-        let result = boards
+        boards
             .into_iter()
-            .map(|b| to_view_kv(b.id, b.title, b.owner_id, &mut column_id_map))
-            .collect();
-        Ok(result)
+            .map(|b| {
+                to_view_kv(
+                    b.id,
+                    b.title,
+                    b.owner_id,
+                    b.version,
+                    b.archived_at,
+                    &mut column_id_map,
+                )
+            })
+            .collect()
     }
 
-    async fn all(&self) -> Result<Vec<BoardView>> {
+    async fn all(&self, criteria: &BoardCriteria) -> Result<Vec<BoardView>> {
         let pool = self.pool.pool();
         let executor = pool;
 
-        let boards = query!(
-            r#"
-            select b.id, b.title, ubr.user_id as owner_id
-            from boards b
-                inner join user_board_relations ubr on b.id = ubr.board_id
-            "#,
-        )
-        .fetch_all(executor)
+        let owner_id = criteria.owner_id.as_ref().map(ToString::to_string);
+        let member_id = criteria.member_id.as_ref().map(ToString::to_string);
+        let sort_key = criteria.sort.key.as_str();
+        let boards = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select b.id, b.title, ubr.user_id as owner_id, b.version, b.archived_at
+                from boards b
+                    inner join user_board_relations ubr on b.id = ubr.board_id
+                where ($1 or b.archived_at is null)
+                    and ($2::varchar is null or strpos(b.title, $2) > 0)
+                    and ($3::varchar is null or ubr.user_id = $3)
+                    and ($4::varchar is null or exists (
+                        select 1
+                        from board_members bm
+                        where bm.board_id = b.id and bm.user_id = $4
+                    ))
+                order by
+                    case when $5 = 'title' and not $6 then b.title collate "C" end asc,
+                    case when $5 = 'title' and $6 then b.title collate "C" end desc,
+                    case when not $6 then b.id end asc,
+                    case when $6 then b.id end desc
+                "#,
+                criteria.archived.includes_archived(),
+                criteria.title_contains.as_deref(),
+                owner_id.as_deref(),
+                member_id.as_deref(),
+                sort_key,
+                criteria.sort.direction.is_desc(),
+            )
+            .fetch_all(executor)
+        })
         .await?;
 
-        let column_ids: Vec<_> = query!(
-            r#"
-                select board_id, column_id
-                from board_column_relations
-            "#,
-        )
-        .fetch_all(executor)
+        let ids_string: Vec<_> = boards.iter().map(|b| b.id.clone()).collect();
+        let column_ids: Vec<_> = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                    select board_id, column_id
+                    from board_column_relations
+                    where board_id = any($1)
+                    order by board_id, position
+                "#,
+                &ids_string
+            )
+            .fetch_all(executor)
+        })
         .await?;
 
         let mut column_id_map: HashMap<_, _> = column_ids
@@ -126,10 +178,94 @@ impl BoardQuery for BoardQueryImpl {
             .map(|r| (r.board_id, r.column_id))
             .into_group_map();
 
-        let result = boards
+        boards
             .into_iter()
-            .map(|b| to_view(b.id, b.title, b.owner_id, &mut column_id_map))
-            .collect();
+            .map(|b| {
+                to_view(
+                    b.id,
+                    b.title,
+                    b.owner_id,
+                    b.version,
+                    b.archived_at,
+                    &mut column_id_map,
+                )
+            })
+            .collect()
+    }
+
+    async fn list_by_column_ids(&self, ids: &[ColumnId]) -> Result<HashMap<ColumnId, BoardView>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+        let ids_string: Vec<_> = ids.iter().map(ToString::to_string).collect();
+
+        let relations = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                    select board_id, column_id
+                    from board_column_relations
+                    where column_id = any($1)
+                "#,
+                &ids_string
+            )
+            .fetch_all(executor)
+        })
+        .await?;
+
+        let board_ids = relations
+            .iter()
+            .map(|r| r.board_id.as_str())
+            .unique()
+            .map(BoardId::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        let boards = self.list_by_ids(&board_ids).await?;
+
+        let mut result = HashMap::new();
+        for r in relations {
+            let board_id = BoardId::from_str(&r.board_id)?;
+            if let Some(board) = boards.get(&board_id) {
+                result.insert(ColumnId::from_str(&r.column_id)?, board.clone());
+            }
+        }
+        Ok(result)
+    }
+
+    async fn list_by_member_ids(&self, ids: &[UserId]) -> Result<HashMap<UserId, Vec<BoardView>>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+        let ids_string: Vec<_> = ids.iter().map(ToString::to_string).collect();
+
+        let members = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                    select board_id, user_id
+                    from board_members
+                    where user_id = any($1)
+                    order by user_id, board_id
+                "#,
+                &ids_string
+            )
+            .fetch_all(executor)
+        })
+        .await?;
+
+        let board_ids = members
+            .iter()
+            .map(|m| m.board_id.as_str())
+            .unique()
+            .map(BoardId::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        let boards = self.list_by_ids(&board_ids).await?;
+
+        let mut result: HashMap<UserId, Vec<BoardView>> = HashMap::new();
+        for m in members {
+            let board_id = BoardId::from_str(&m.board_id)?;
+            if let Some(board) = boards.get(&board_id) {
+                result
+                    .entry(UserId::from_str(&m.user_id)?)
+                    .or_default()
+                    .push(board.clone());
+            }
+        }
         Ok(result)
     }
 }
@@ -138,23 +274,32 @@ fn to_view(
     id: String,
     title: String,
     owner_id: String,
+    version: i64,
+    archived_at: Option<DateTime<Utc>>,
     column_id_map: &mut HashMap<String, Vec<String>>,
-) -> BoardView {
+) -> Result<BoardView> {
     let column_ids = column_id_map.remove(&id).unwrap_or_else(|| vec![]);
-    BoardView {
+    let result = BoardView {
         id,
         title,
         owner_id,
         column_ids,
-    }
+        version: version.try_into()?,
+        archived_at,
+    };
+    Ok(result)
 }
 
 fn to_view_kv(
     id: String,
     title: String,
     owner_id: String,
+    version: i64,
+    archived_at: Option<DateTime<Utc>>,
     column_id_map: &mut HashMap<String, Vec<String>>,
-) -> (BoardId, BoardView) {
+) -> Result<(BoardId, BoardView)> {
     let key = FromStr::from_str(&id).unwrap();
-    (key, to_view(id, title, owner_id, column_id_map))
+    let view = to_view(id, title, owner_id, version, archived_at, column_id_map)?;
+    let result = (key, view);
+    Ok(result)
 }
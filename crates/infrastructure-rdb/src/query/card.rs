@@ -2,12 +2,13 @@ use std::{collections::HashMap, str::FromStr, sync::Arc};
 
 use anyhow::Result;
 use async_trait::async_trait;
-use domain_kanban::column::ColumnId;
-use query_resolver::{CardView, CardsQuery};
+use chrono::{DateTime, Utc};
+use domain_kanban::column::{CardId, ColumnId};
+use query_resolver::{ArchivedFilter, CardCriteria, CardView, CardsQuery, ChecklistItemView};
 use shaku::Provider;
 use sqlx::query;
 
-use crate::Pool;
+use crate::{read, Pool};
 
 #[derive(Debug, Clone, Provider)]
 #[shaku(interface = CardsQuery)]
@@ -16,36 +17,86 @@ pub struct CardsQueryImpl {
     pool: Arc<dyn Pool>,
 }
 
-impl CardsQueryImpl {}
+impl CardsQueryImpl {
+    // カードごとのチェックリストを表示順にまとめて取得する
+    async fn list_checklists(
+        &self,
+        card_ids: &[String],
+    ) -> Result<HashMap<String, Vec<ChecklistItemView>>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let items = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select i.card_id, i.text, i.done
+                from checklist_items i
+                where i.card_id = any($1)
+                order by i.card_id, i.position
+                "#,
+                card_ids
+            )
+            .fetch_all(executor)
+        })
+        .await?;
+
+        let mut result: HashMap<String, Vec<ChecklistItemView>> = HashMap::new();
+        for i in items {
+            result
+                .entry(i.card_id)
+                .or_default()
+                .push(ChecklistItemView {
+                    text: i.text,
+                    done: i.done,
+                });
+        }
+        Ok(result)
+    }
+}
 
 #[async_trait]
 impl CardsQuery for CardsQueryImpl {
-    async fn find_by_order(&self, column_id: &ColumnId, order: &usize) -> Result<CardView> {
+    async fn find_by_order(
+        &self,
+        column_id: &ColumnId,
+        order: &usize,
+        archived: ArchivedFilter,
+    ) -> Result<CardView> {
         let pool = self.pool.pool();
         let executor = pool;
 
         let column_id_string = column_id.to_string();
-        let card = query!(
-            r#"
-            select c.id, c.title, c.description
-            from cards c
-            where c.column_id = $1
-            -- 暫定sort
-            order by id
-            limit 1
-            offset $2
-            "#,
-            &column_id_string,
-            i64::try_from(*order)?
-        )
-        .fetch_one(executor)
+        let offset = i64::try_from(*order)?;
+        let card = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select c.id, c.title, c.description, c.column_id, c.archived_at, c.due_at
+                from cards c
+                where c.column_id = $1 and c.detached_at is null
+                    and ($3 or c.archived_at is null)
+                order by c.position, c.id
+                limit 1
+                offset $2
+                "#,
+                &column_id_string,
+                offset,
+                archived.includes_archived(),
+            )
+            .fetch_one(executor)
+        })
         .await?;
 
-        let result = CardView {
-            id: card.id,
-            title: card.title,
-            description: card.description.unwrap_or_else(|| "".into()),
-        };
+        let mut checklists = self.list_checklists(&[card.id.clone()]).await?;
+        let checklist = checklists.remove(&card.id).unwrap_or_default();
+        let result = to_view(
+            card.id,
+            card.title,
+            card.description,
+            card.column_id,
+            checklist,
+            card.archived_at,
+            card.due_at,
+        );
         Ok(result)
     }
 
@@ -53,6 +104,7 @@ impl CardsQuery for CardsQueryImpl {
         &self,
         column_id: &ColumnId,
         orders: &[usize],
+        archived: ArchivedFilter,
     ) -> Result<HashMap<usize, CardView>> {
         let pool = self.pool.pool();
         let executor = pool;
@@ -63,41 +115,165 @@ impl CardsQuery for CardsQueryImpl {
         let min_order = orders_i64.iter().min().unwrap_or(&0);
         let max_order = orders_i64.iter().max().unwrap_or(&0);
         let length = 1 + max_order - min_order;
-        let cards = query!(
-            r#"
-            select c.id, c.title, c.description
-            from cards c
-            where c.column_id = $1
-            -- 暫定sort
-            order by id
-            limit $3
-            offset $2
-            "#,
-            &column_id_string,
-            min_order,
-            length,
-        )
-        .fetch_all(executor)
+        let cards = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select c.id, c.title, c.description, c.column_id, c.archived_at, c.due_at
+                from cards c
+                where c.column_id = $1 and c.detached_at is null
+                    and ($4 or c.archived_at is null)
+                order by c.position, c.id
+                limit $3
+                offset $2
+                "#,
+                &column_id_string,
+                min_order,
+                length,
+                archived.includes_archived(),
+            )
+            .fetch_all(executor)
+        })
         .await?;
 
+        let card_ids: Vec<_> = cards.iter().map(|c| c.id.clone()).collect();
+        let mut checklists = self.list_checklists(&card_ids).await?;
         let result = cards
             .into_iter()
             .enumerate()
             .map(|(i, c)| {
+                let checklist = checklists.remove(&c.id).unwrap_or_default();
                 (
                     i + (*min_order as usize),
-                    to_view(c.id, c.title, c.description),
+                    to_view(
+                        c.id,
+                        c.title,
+                        c.description,
+                        c.column_id,
+                        checklist,
+                        c.archived_at,
+                        c.due_at,
+                    ),
                 )
             })
             .filter(|(k, _)| orders.contains(k))
             .collect();
         Ok(result)
     }
+
+    async fn list_by_column_ids(
+        &self,
+        ids: &[ColumnId],
+        criteria: &CardCriteria,
+    ) -> Result<HashMap<ColumnId, Vec<CardView>>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let id_strings: Vec<_> = ids.iter().map(ToString::to_string).collect();
+        let archived = criteria.status.map(|s| s.is_archived());
+        let sort_key = criteria.sort.key.as_str();
+        let cards = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select c.id, c.title, c.description, c.column_id, c.archived_at, c.due_at
+                from cards c
+                where c.column_id = any($1) and c.detached_at is null
+                    and ($2::varchar is null or strpos(c.title, $2) > 0)
+                    and ($3::boolean is null or $3 = (c.archived_at is not null))
+                    and ($6::timestamptz is null or c.due_at < $6)
+                -- 並び順はカラム内の位置の順
+                order by
+                    case when $4 = 'title' and not $5 then c.title collate "C" end asc,
+                    case when $4 = 'title' and $5 then c.title collate "C" end desc,
+                    case when $4 = 'position' and $5 then c.position end desc,
+                    c.position asc,
+                    c.id asc
+                "#,
+                &id_strings,
+                criteria.title_contains.as_deref(),
+                archived,
+                sort_key,
+                criteria.sort.direction.is_desc(),
+                criteria.due_before,
+            )
+            .fetch_all(executor)
+        })
+        .await?;
+
+        let card_ids: Vec<_> = cards.iter().map(|c| c.id.clone()).collect();
+        let mut checklists = self.list_checklists(&card_ids).await?;
+        let mut result: HashMap<ColumnId, Vec<CardView>> = HashMap::new();
+        for c in cards {
+            let column_id = ColumnId::from_str(&c.column_id)?;
+            let checklist = checklists.remove(&c.id).unwrap_or_default();
+            let view = to_view(
+                c.id,
+                c.title,
+                c.description,
+                c.column_id,
+                checklist,
+                c.archived_at,
+                c.due_at,
+            );
+            result.entry(column_id).or_default().push(view);
+        }
+        Ok(result)
+    }
+
+    async fn list_by_ids(&self, ids: &[CardId]) -> Result<HashMap<CardId, CardView>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let id_strings: Vec<_> = ids.iter().map(ToString::to_string).collect();
+        let cards = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select c.id, c.title, c.description, c.column_id, c.archived_at, c.due_at
+                from cards c
+                where c.id = any($1) and c.detached_at is null
+                "#,
+                &id_strings,
+            )
+            .fetch_all(executor)
+        })
+        .await?;
+
+        let card_ids: Vec<_> = cards.iter().map(|c| c.id.clone()).collect();
+        let mut checklists = self.list_checklists(&card_ids).await?;
+        let mut result = HashMap::new();
+        for c in cards {
+            let id = CardId::from_str(&c.id)?;
+            let checklist = checklists.remove(&c.id).unwrap_or_default();
+            let view = to_view(
+                c.id,
+                c.title,
+                c.description,
+                c.column_id,
+                checklist,
+                c.archived_at,
+                c.due_at,
+            );
+            result.insert(id, view);
+        }
+        Ok(result)
+    }
 }
-fn to_view(id: String, title: String, description: Option<String>) -> CardView {
+
+fn to_view(
+    id: String,
+    title: String,
+    description: Option<String>,
+    column_id: String,
+    checklist: Vec<ChecklistItemView>,
+    archived_at: Option<DateTime<Utc>>,
+    due_at: Option<DateTime<Utc>>,
+) -> CardView {
     CardView {
         id,
         title,
         description: description.unwrap_or_else(|| "".into()),
+        column_id,
+        checklist,
+        archived_at,
+        due_at,
     }
 }
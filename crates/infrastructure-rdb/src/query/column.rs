@@ -2,12 +2,13 @@ use std::{collections::HashMap, str::FromStr, sync::Arc};
 
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use domain_kanban::column::ColumnId;
 use query_resolver::{ColumnView, ColumnsQuery};
 use shaku::Provider;
 use sqlx::query;
 
-use crate::Pool;
+use crate::{read, Pool};
 
 #[derive(Debug, Clone, Provider)]
 #[shaku(interface = ColumnsQuery)]
@@ -23,20 +24,30 @@ impl ColumnsQuery for ColumnsQueryImpl {
         let executor = pool;
 
         let id_string = id.to_string();
-        let column = query!(
+        let column = read(self.pool.as_ref(), || {
+query!(
             r#"
-            select c.id, c.title, count(distinct ca.id) as "card_cnt"
-            from columns c
-                left outer join cards ca on c.id = ca.column_id
-            where c.id = $1
-            group by 1, 2
-            "#,
+                select c.id, c.title, c.archived_at,
+                    count(distinct ca.id) filter (where ca.archived_at is null) as "card_cnt",
+                    count(distinct ca.id) filter (where ca.archived_at is not null) as "archived_card_cnt"
+                from columns c
+                    left outer join cards ca on c.id = ca.column_id and ca.detached_at is null
+                where c.id = $1
+                group by 1, 2, 3
+                "#,
             &id_string
         )
         .fetch_one(executor)
-        .await?;
+})
+.await?;
 
-        let result = to_view(column.id, column.title, column.card_cnt)?;
+        let result = to_view(
+            column.id,
+            column.title,
+            column.archived_at,
+            column.card_cnt,
+            column.archived_card_cnt,
+        )?;
         Ok(result)
     }
 
@@ -45,40 +56,70 @@ impl ColumnsQuery for ColumnsQueryImpl {
         let executor = pool;
         let ids_string: Vec<_> = ids.iter().map(ToString::to_string).collect();
 
-        let columns = query!(
+        let columns = read(self.pool.as_ref(), || {
+query!(
             r#"
-            -- NOTE: `count(...) as "card_cnt!: usize"` は、usizeにdecodeできないので不可。i64で帰ってきたのをTryFromするしかない
-            select c.id, c.title, count(distinct ca.id) as "card_cnt"
-            from columns c
-                left outer join cards ca on c.id = ca.column_id
-            where c.id = any($1)
-            group by 1, 2
-            "#,
+                -- NOTE: `count(...) as "card_cnt!: usize"` は、usizeにdecodeできないので不可。i64で帰ってきたのをTryFromするしかない
+                select c.id, c.title, c.archived_at,
+                    count(distinct ca.id) filter (where ca.archived_at is null) as "card_cnt",
+                    count(distinct ca.id) filter (where ca.archived_at is not null) as "archived_card_cnt"
+                from columns c
+                    left outer join cards ca on c.id = ca.column_id and ca.detached_at is null
+                where c.id = any($1)
+                group by 1, 2, 3
+                "#,
             &ids_string
         )
         .fetch_all(executor)
-        .await?;
+})
+.await?;
 
         let result: Result<Vec<_>> = columns
             .into_iter()
-            .map(|column| to_view_kv(column.id, column.title, column.card_cnt))
+            .map(|column| {
+                to_view_kv(
+                    column.id,
+                    column.title,
+                    column.archived_at,
+                    column.card_cnt,
+                    column.archived_card_cnt,
+                )
+            })
             .collect();
         Ok(HashMap::from_iter(result?.into_iter()))
     }
 }
 
-fn to_view(id: String, title: String, card_cnt: Option<i64>) -> Result<ColumnView> {
+fn to_view(
+    id: String,
+    title: String,
+    archived_at: Option<DateTime<Utc>>,
+    card_cnt: Option<i64>,
+    archived_card_cnt: Option<i64>,
+) -> Result<ColumnView> {
     let card_cnt = card_cnt.unwrap_or(0).try_into()?;
+    let archived_card_cnt = archived_card_cnt.unwrap_or(0).try_into()?;
     let result = ColumnView {
         id,
         title,
         card_cnt,
+        archived_card_cnt,
+        archived_at,
     };
     Ok(result)
 }
 
-fn to_view_kv(id: String, title: String, card_cnt: Option<i64>) -> Result<(ColumnId, ColumnView)> {
+fn to_view_kv(
+    id: String,
+    title: String,
+    archived_at: Option<DateTime<Utc>>,
+    card_cnt: Option<i64>,
+    archived_card_cnt: Option<i64>,
+) -> Result<(ColumnId, ColumnView)> {
     let key = FromStr::from_str(&id).unwrap();
-    let result = (key, to_view(id, title, card_cnt)?);
+    let result = (
+        key,
+        to_view(id, title, archived_at, card_cnt, archived_card_cnt)?,
+    );
     Ok(result)
 }
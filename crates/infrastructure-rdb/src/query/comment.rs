@@ -0,0 +1,57 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use domain_kanban::column::CardId;
+use query_resolver::{CommentView, CommentsQuery};
+use shaku::Provider;
+use sqlx::query;
+
+use crate::{read, Pool};
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = CommentsQuery)]
+pub struct CommentsQueryImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[async_trait]
+impl CommentsQuery for CommentsQueryImpl {
+    async fn list_by_card_ids(
+        &self,
+        card_ids: &[CardId],
+    ) -> Result<HashMap<CardId, Vec<CommentView>>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+        let ids_string: Vec<_> = card_ids.iter().map(ToString::to_string).collect();
+
+        let comments = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select c.id, c.card_id, c.author_id, c.body, c.created_at, c.edited_at
+                from comments c
+                where c.card_id = any($1)
+                order by c.created_at, c.id
+                "#,
+                &ids_string
+            )
+            .fetch_all(executor)
+        })
+        .await?;
+
+        let mut result: HashMap<CardId, Vec<CommentView>> = HashMap::new();
+        for c in comments {
+            let key = FromStr::from_str(&c.card_id)?;
+            result.entry(key).or_default().push(CommentView {
+                id: c.id,
+                card_id: c.card_id,
+                author_id: c.author_id,
+                body: c.body,
+                created_at: c.created_at,
+                edited_at: c.edited_at,
+            });
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use query_resolver::{SearchHit, SearchPage, SearchQuery};
+use shaku::Provider;
+use sqlx::query;
+
+use crate::{read, Pool};
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = SearchQuery)]
+pub struct SearchQueryImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[async_trait]
+impl SearchQuery for SearchQueryImpl {
+    async fn search(&self, page: &SearchPage) -> Result<Vec<SearchHit>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let pattern = like_pattern(&page.query);
+        let limit = i64::try_from(page.first)?;
+        let offset = i64::try_from(page.offset)?;
+        let hits = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                -- 部分一致(trigramの索引)と単語の一致(全文検索の索引)のどちらかに当たるもの
+                -- タイトルの部分一致を説明の部分一致より上にし、似ている度合いと全文検索の順位を足す
+                select h.kind as "kind!", h.id as "id!", h.score as "score!"
+                from (
+                    select 'board' as kind, b.id,
+                        (case when b.title ilike $2 then 1.0 else 0.0 end
+                            + similarity(b.title, $1)
+                            + ts_rank(to_tsvector('simple', b.title), plainto_tsquery('simple', $1))
+                        )::float8 as score
+                    from boards b
+                    where b.archived_at is null
+                        and (b.title ilike $2
+                            or to_tsvector('simple', b.title) @@ plainto_tsquery('simple', $1))
+                    union all
+                    select 'column' as kind, c.id,
+                        (case when c.title ilike $2 then 1.0 else 0.0 end
+                            + similarity(c.title, $1)
+                            + ts_rank(to_tsvector('simple', c.title), plainto_tsquery('simple', $1))
+                        )::float8 as score
+                    from columns c
+                    where c.archived_at is null
+                        and (c.title ilike $2
+                            or to_tsvector('simple', c.title) @@ plainto_tsquery('simple', $1))
+                    union all
+                    select 'card' as kind, c.id,
+                        (case
+                            when c.title ilike $2 then 1.0
+                            when c.description ilike $2 then 0.5
+                            else 0.0
+                        end
+                            + similarity(c.title, $1)
+                            + ts_rank(
+                                to_tsvector('simple', c.title || ' ' || coalesce(c.description, '')),
+                                plainto_tsquery('simple', $1)
+                            )
+                        )::float8 as score
+                    from cards c
+                    where c.archived_at is null and c.detached_at is null
+                        and (c.title ilike $2
+                            or c.description ilike $2
+                            or to_tsvector('simple', c.title || ' ' || coalesce(c.description, ''))
+                                @@ plainto_tsquery('simple', $1))
+                ) h
+                order by h.score desc, h.id
+                limit $3
+                offset $4
+                "#,
+                &page.query,
+                &pattern,
+                limit,
+                offset,
+            )
+            .fetch_all(executor)
+        })
+        .await?;
+
+        hits.into_iter()
+            .map(|h| {
+                Ok(SearchHit {
+                    kind: h.kind.parse()?,
+                    id: h.id,
+                    score: h.score,
+                })
+            })
+            .collect()
+    }
+}
+
+// 検索語をそのまま部分一致させるLIKEのパターンにする
+fn like_pattern(query: &str) -> String {
+    let escaped = query
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_like_pattern_escapes_wildcards() {
+        assert_eq!(like_pattern("ゴミ出し"), "%ゴミ出し%");
+        assert_eq!(like_pattern(r"100%_a\b"), r"%100\%\_a\\b%");
+    }
+}
@@ -4,11 +4,11 @@ use anyhow::Result;
 use async_trait::async_trait;
 use domain_kanban::user::UserId;
 use itertools::Itertools;
-use query_resolver::{UserView, UsersQuery};
+use query_resolver::{UserCriteria, UserView, UsersQuery};
 use shaku::Provider;
 use sqlx::query;
 
-use crate::Pool;
+use crate::{read, Pool};
 
 #[derive(Debug, Clone, Provider)]
 #[shaku(interface = UsersQuery)]
@@ -24,26 +24,30 @@ impl UsersQuery for UsersQueryImpl {
         let executor = pool;
 
         let id_string = id.to_string();
-        let user = query!(
-            r#"
-            select u.id, u.name, u.email
-            from users u
-            where u.id = $1
-            "#,
-            &id_string
-        )
-        .fetch_one(executor)
+        let user = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select u.id, u.name, u.email, u.version
+                from users u
+                where u.id = $1
+                "#,
+                &id_string
+            )
+            .fetch_one(executor)
+        })
         .await?;
 
-        let owned_board_ids: Vec<_> = query!(
-            r#"
-                select board_id
-                from user_board_relations
-                where user_id = $1
-            "#,
-            &id_string
-        )
-        .fetch_all(executor)
+        let owned_board_ids: Vec<_> = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                    select board_id
+                    from user_board_relations
+                    where user_id = $1
+                "#,
+                &id_string
+            )
+            .fetch_all(executor)
+        })
         .await?;
         let owned_board_ids = owned_board_ids.into_iter().map(|r| r.board_id).collect();
 
@@ -52,6 +56,7 @@ impl UsersQuery for UsersQueryImpl {
             name: user.name,
             email: user.email,
             owned_board_ids,
+            version: user.version.try_into()?,
         };
 
         Ok(result)
@@ -62,26 +67,30 @@ impl UsersQuery for UsersQueryImpl {
         let executor = pool;
         let ids_string: Vec<_> = ids.iter().map(ToString::to_string).collect();
 
-        let users = query!(
-            r#"
-            select u.id, u.name, u.email
-            from users u
-            where u.id = any($1)
-            "#,
-            &ids_string
-        )
-        .fetch_all(executor)
+        let users = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select u.id, u.name, u.email, u.version
+                from users u
+                where u.id = any($1)
+                "#,
+                &ids_string
+            )
+            .fetch_all(executor)
+        })
         .await?;
 
-        let owned_board_ids: Vec<_> = query!(
-            r#"
-                select user_id, board_id
-                from user_board_relations
-                where user_id = any($1)
-            "#,
-            &ids_string
-        )
-        .fetch_all(executor)
+        let owned_board_ids: Vec<_> = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                    select user_id, board_id
+                    from user_board_relations
+                    where user_id = any($1)
+                "#,
+                &ids_string
+            )
+            .fetch_all(executor)
+        })
         .await?;
 
         let mut owned_board_map: HashMap<_, _> = owned_board_ids
@@ -89,32 +98,55 @@ impl UsersQuery for UsersQueryImpl {
             .map(|r| (r.user_id, r.board_id))
             .into_group_map();
 
-        let result = users
+        users
             .into_iter()
-            .map(|u| to_view_kv(u.id, u.name, u.email, &mut owned_board_map))
-            .collect();
-        Ok(result)
+            .map(|u| to_view_kv(u.id, u.name, u.email, u.version, &mut owned_board_map))
+            .collect()
     }
-    async fn all(&self) -> Result<Vec<UserView>> {
+    async fn all(&self, criteria: &UserCriteria) -> Result<Vec<UserView>> {
         let pool = self.pool.pool();
         let executor = pool;
 
-        let users = query!(
-            r#"
-            select u.id, u.name, u.email
-            from users u
-            "#
-        )
-        .fetch_all(executor)
+        let sort_key = criteria.sort.key.as_str();
+        let users = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select u.id, u.name, u.email, u.version
+                from users u
+                where ($1::varchar is null or starts_with(u.name, $1))
+                    and ($2::varchar is null or (
+                        strpos(u.email, '@') > 0
+                        and lower(substr(u.email, strpos(u.email, '@') + 1)) = lower($2)
+                    ))
+                order by
+                    case when $3 = 'name' and not $4 then u.name collate "C" end asc,
+                    case when $3 = 'name' and $4 then u.name collate "C" end desc,
+                    case when $3 = 'email' and not $4 then u.email collate "C" end asc,
+                    case when $3 = 'email' and $4 then u.email collate "C" end desc,
+                    case when not $4 then u.id end asc,
+                    case when $4 then u.id end desc
+                "#,
+                criteria.name_prefix.as_deref(),
+                criteria.email_domain.as_deref(),
+                sort_key,
+                criteria.sort.direction.is_desc(),
+            )
+            .fetch_all(executor)
+        })
         .await?;
 
-        let owned_board_ids: Vec<_> = query!(
-            r#"
-                select user_id, board_id
-                from user_board_relations
-            "#
-        )
-        .fetch_all(executor)
+        let ids_string: Vec<_> = users.iter().map(|u| u.id.clone()).collect();
+        let owned_board_ids: Vec<_> = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                    select user_id, board_id
+                    from user_board_relations
+                    where user_id = any($1)
+                "#,
+                &ids_string
+            )
+            .fetch_all(executor)
+        })
         .await?;
 
         let mut owned_board_map: HashMap<_, _> = owned_board_ids
@@ -122,11 +154,10 @@ impl UsersQuery for UsersQueryImpl {
             .map(|r| (r.user_id, r.board_id))
             .into_group_map();
 
-        let result = users
+        users
             .into_iter()
-            .map(|u| to_view(u.id, u.name, u.email, &mut owned_board_map))
-            .collect();
-        Ok(result)
+            .map(|u| to_view(u.id, u.name, u.email, u.version, &mut owned_board_map))
+            .collect()
     }
 }
 
@@ -134,23 +165,28 @@ fn to_view(
     id: String,
     name: String,
     email: String,
+    version: i64,
     owned_board_map: &mut HashMap<String, Vec<String>>,
-) -> UserView {
+) -> Result<UserView> {
     let owned_board_ids = owned_board_map.remove(&id).unwrap_or_else(|| vec![]);
-    UserView {
+    let result = UserView {
         id,
         name,
         email,
         owned_board_ids,
-    }
+        version: version.try_into()?,
+    };
+    Ok(result)
 }
 
 fn to_view_kv(
     id: String,
     name: String,
     email: String,
+    version: i64,
     owned_board_map: &mut HashMap<String, Vec<String>>,
-) -> (UserId, UserView) {
+) -> Result<(UserId, UserView)> {
     let key = FromStr::from_str(&id).unwrap();
-    (key, to_view(id, name, email, owned_board_map))
+    let result = (key, to_view(id, name, email, version, owned_board_map)?);
+    Ok(result)
 }
@@ -0,0 +1,35 @@
+mod activity;
+mod board;
+mod column;
+mod comment;
+mod outbox;
+mod user;
+
+use domain_util::{RepositoryError, Version};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+// NOTE: Postgresのみで動かす場合のリポジトリ
+// 書き込み先がリードモデルと同じテーブルなので、保存した内容がそのままクエリに反映される
+shaku::module! {
+    pub Module {
+        components = [super::PgPoolImpl],
+        providers = [
+            activity::ActivityRepositoryImpl,
+            board::BoardRepositoryImpl,
+            column::ColumnRepositoryImpl,
+            comment::CommentRepositoryImpl,
+            outbox::OutboxStoreImpl,
+            user::UserRepositoryImpl,
+        ]
+    }
+}
+
+fn to_i64(version: Version) -> Result<i64, RepositoryError> {
+    i64::try_from(version.value()).map_err(|e| RepositoryError::Other(e.to_string()))
+}
+
+// NOTE: 集約やOutboxMessageは外から各フィールドを指定して作れないので、JSONから復元する
+fn from_json<T: DeserializeOwned>(value: Value) -> Result<T, RepositoryError> {
+    serde_json::from_value(value).map_err(|e| RepositoryError::Serialization(e.to_string()))
+}
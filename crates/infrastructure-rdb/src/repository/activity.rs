@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain_kanban::activity::{Activity, ActivityRepository};
+use domain_util::{RepositoryError, Version};
+use shaku::Provider;
+use sqlx::query;
+
+use crate::{write, Pool};
+
+/// ActivityRepositoryのPostgresでの実装
+/// ActivityQueryと同じ `activities` テーブルに追記するので、配送するとすぐにクエリに反映される
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = ActivityRepository)]
+pub struct ActivityRepositoryImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[async_trait]
+impl ActivityRepository for ActivityRepositoryImpl {
+    async fn append(&self, activity: Activity) -> Result<(), RepositoryError> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        // 追記のみなので、同じIDで上書きしない
+        let result = write(self.pool.as_ref(), || {
+            query!(
+                r#"
+                insert into activities
+                    (id, actor_id, action, target_id, board_id, before_value, after_value, occurred_at)
+                values ($1, $2, $3, $4, $5, $6, $7, $8)
+                on conflict (id) do nothing
+                "#,
+                activity.id().to_string(),
+                activity.actor().to_string(),
+                activity.action().as_str(),
+                activity.target(),
+                activity.board_id().map(ToString::to_string),
+                activity.before(),
+                activity.after(),
+                *activity.occurred_at(),
+            )
+            .execute(executor)
+        })
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::Conflict {
+                expected: Version::initial(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use domain_kanban::{
+        activity::ActivityAction,
+        outbox::{ActivityHandler, OutboxMessage, OutboxRelay, RetryPolicy},
+        user::{Email, User, UserId, UserName, UserRepository},
+    };
+    use query_resolver::{ActivityPage, ActivityQuery};
+    use shaku::HasProvider;
+
+    use super::*;
+    use crate::{
+        repository::{outbox::OutboxStoreImpl, user::UserRepositoryImpl},
+        test_util::async_pool_init,
+        QueryModule,
+    };
+
+    #[tokio::test]
+    async fn test_append_is_visible_to_activity_query() {
+        // Arrange
+        let (_c, pool) = async_pool_init().await;
+        let user_repository = UserRepositoryImpl {
+            pool: Arc::new(pool.clone()),
+        };
+        let relay = OutboxRelay::new(
+            Box::new(OutboxStoreImpl {
+                pool: Arc::new(pool.clone()),
+            }),
+            RetryPolicy::default(),
+        )
+        .with_handler(Box::new(ActivityHandler::new(Box::new(
+            ActivityRepositoryImpl {
+                pool: Arc::new(pool.clone()),
+            },
+        ))));
+        let query_module = QueryModule::new_with_pool(Box::new(pool));
+        let activity_query: Box<dyn ActivityQuery> = query_module.provide().unwrap();
+
+        let user = User::new(
+            UserName::new("alice".to_owned()).unwrap(),
+            Email::new("alice@example.com".to_owned()).unwrap(),
+        )
+        .unwrap();
+        let actor = user.user_id().clone();
+        user_repository.save(user).await.unwrap();
+        let mut renamed = user_repository.find_by_id(&actor).await.unwrap().unwrap();
+        renamed.update_name(UserName::new("alice2".to_owned()).unwrap());
+        let activity = Activity::new(actor.clone(), ActivityAction::UserRenamed, &actor)
+            .with_change("alice", "alice2");
+
+        // Act
+        // renameUserと同じく、集約と一緒にアウトボックスに保存してから配送する
+        user_repository
+            .save_with_outbox(renamed, vec![OutboxMessage::activity(&activity).unwrap()])
+            .await
+            .unwrap();
+        let report = relay.relay_once(Utc::now()).await.unwrap();
+        let page = ActivityPage {
+            first: 10,
+            after: None,
+            actions: vec![],
+        };
+        let activities = activity_query.list_by_actor(&actor, &page).await.unwrap();
+
+        // Assert
+        assert_eq!(report.delivered, 1);
+        assert_eq!(activities.len(), 1);
+        assert_eq!(activities[0].id, activity.id().to_string());
+        assert_eq!(activities[0].action, "user_renamed");
+        assert_eq!(activities[0].before.as_deref(), Some("alice"));
+        assert_eq!(activities[0].after.as_deref(), Some("alice2"));
+    }
+
+    #[tokio::test]
+    async fn test_append_duplicated() {
+        // Arrange
+        let (_c, pool) = async_pool_init().await;
+        let activity_repository = ActivityRepositoryImpl {
+            pool: Arc::new(pool),
+        };
+        let actor = UserId::gen();
+        let activity = Activity::new(actor.clone(), ActivityAction::UserRenamed, &actor);
+
+        // Act
+        activity_repository.append(activity.clone()).await.unwrap();
+        let duplicated = activity_repository.append(activity).await;
+
+        // Assert
+        assert!(matches!(duplicated, Err(RepositoryError::Conflict { .. })));
+    }
+}
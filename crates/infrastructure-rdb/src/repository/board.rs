@@ -0,0 +1,369 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use domain_kanban::{
+    board::{Board, BoardId, BoardRepository},
+    outbox::OutboxMessage,
+};
+use domain_util::RepositoryError;
+use serde_json::json;
+use shaku::Provider;
+use sqlx::query;
+
+use super::{from_json, outbox::insert_outbox, to_i64};
+use crate::{read, repository_error, Pool};
+
+/// BoardRepositoryのPostgresでの実装
+/// 所有者・メンバー・カラムの並び順は、リードモデルと同じ関連テーブルに保存する
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = BoardRepository)]
+pub struct BoardRepositoryImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[async_trait]
+impl BoardRepository for BoardRepositoryImpl {
+    async fn save(&self, board: Board) -> Result<(), RepositoryError> {
+        self.save_with_outbox(board, vec![]).await
+    }
+
+    async fn save_with_outbox(
+        &self,
+        board: Board,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        self.pool
+            .resilience()
+            .write(|| self.try_save_with_outbox(&board, &messages))
+            .await
+    }
+
+    async fn find_by_id(&self, id: &BoardId) -> Result<Option<Board>, RepositoryError> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        // メンバーの並び順は保存しないので、IDの順に復元する
+        let id_string = id.to_string();
+        let board = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select b.id, b.title, b.version, b.archived_at, r.user_id as owner_id,
+                    array(
+                        select m.user_id from board_members m
+                        where m.board_id = b.id
+                        order by m.user_id
+                    ) as "member_ids!",
+                    array(
+                        select bc.column_id from board_column_relations bc
+                        where bc.board_id = b.id
+                        order by bc.position
+                    ) as "column_ids!"
+                from boards b
+                join user_board_relations r on r.board_id = b.id
+                where b.id = $1
+                "#,
+                &id_string
+            )
+            .fetch_optional(executor)
+        })
+        .await?;
+
+        board
+            .map(|b| {
+                from_json(json!({
+                    "id": b.id,
+                    "title": b.title,
+                    "owner": b.owner_id,
+                    "members": b.member_ids,
+                    "column_ids": b.column_ids,
+                    "archived_at": b.archived_at,
+                    "version": b.version,
+                }))
+            })
+            .transpose()
+    }
+
+    async fn find_many(&self, ids: &[BoardId]) -> Result<Vec<Option<Board>>, RepositoryError> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        // メンバーの並び順は保存しないので、IDの順に復元する
+        let ids_string: Vec<_> = ids.iter().map(ToString::to_string).collect();
+        let boards = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select b.id, b.title, b.version, b.archived_at, r.user_id as owner_id,
+                    array(
+                        select m.user_id from board_members m
+                        where m.board_id = b.id
+                        order by m.user_id
+                    ) as "member_ids!",
+                    array(
+                        select bc.column_id from board_column_relations bc
+                        where bc.board_id = b.id
+                        order by bc.position
+                    ) as "column_ids!"
+                from boards b
+                join user_board_relations r on r.board_id = b.id
+                where b.id = any($1)
+                "#,
+                &ids_string
+            )
+            .fetch_all(executor)
+        })
+        .await?;
+
+        let mut found = HashMap::new();
+        for b in boards {
+            let board: Board = from_json(json!({
+                "id": b.id,
+                "title": b.title,
+                "owner": b.owner_id,
+                "members": b.member_ids,
+                "column_ids": b.column_ids,
+                "archived_at": b.archived_at,
+                "version": b.version,
+            }))?;
+            found.insert(board.id().clone(), board);
+        }
+        Ok(ids.iter().map(|id| found.get(id).cloned()).collect())
+    }
+}
+
+impl BoardRepositoryImpl {
+    // 同じトランザクションをやりなおせるように、値は参照で受け取る
+    async fn try_save_with_outbox(
+        &self,
+        board: &Board,
+        messages: &[OutboxMessage],
+    ) -> Result<(), RepositoryError> {
+        let mut tx = self.pool.pool().begin().await.map_err(repository_error)?;
+
+        // `expected` のバージョンで保存されている場合のみ上書きする（未保存なら新規に追加する）
+        let expected = board.version();
+        let id = board.id().to_string();
+        let result = query!(
+            r#"
+            insert into boards (id, title, version, archived_at)
+            values ($1, $2, $3, $4)
+            on conflict (id) do update
+                set title = excluded.title,
+                    version = excluded.version,
+                    archived_at = excluded.archived_at
+                where boards.version = $5
+            "#,
+            &id,
+            board.title().to_string(),
+            to_i64(expected.next())?,
+            board.archived_at().cloned(),
+            to_i64(expected)?,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(repository_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::Conflict { expected });
+        }
+
+        query!("delete from user_board_relations where board_id = $1", &id)
+            .execute(&mut *tx)
+            .await
+            .map_err(repository_error)?;
+        query!(
+            "insert into user_board_relations (user_id, board_id) values ($1, $2)",
+            board.owner().to_string(),
+            &id,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(repository_error)?;
+
+        let member_ids: Vec<_> = board.members().iter().map(ToString::to_string).collect();
+        query!("delete from board_members where board_id = $1", &id)
+            .execute(&mut *tx)
+            .await
+            .map_err(repository_error)?;
+        query!(
+            r#"
+            insert into board_members (board_id, user_id)
+            select distinct $1, m.user_id
+            from unnest($2::varchar[]) as m(user_id)
+            "#,
+            &id,
+            &member_ids,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(repository_error)?;
+
+        // カラムは先に保存されている必要がある（board_column_relationsの外部キー）
+        let column_ids: Vec<_> = board.column_ids().iter().map(ToString::to_string).collect();
+        query!(
+            "delete from board_column_relations where board_id = $1",
+            &id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(repository_error)?;
+        query!(
+            r#"
+            insert into board_column_relations (board_id, column_id, position)
+            select $1, c.column_id, c.position - 1
+            from unnest($2::varchar[]) with ordinality as c(column_id, position)
+            on conflict (column_id) do update
+                set board_id = excluded.board_id,
+                    position = excluded.position
+            "#,
+            &id,
+            &column_ids,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(repository_error)?;
+
+        insert_outbox(&mut tx, messages)
+            .await
+            .map_err(repository_error)?;
+        tx.commit().await.map_err(repository_error)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain_kanban::{
+        board::BoardTitle,
+        column::{Column, ColumnRepository, ColumnTitle},
+        user::{Email, User, UserName, UserRepository},
+    };
+    use domain_util::Version;
+
+    use super::*;
+    use crate::{
+        repository::{column::ColumnRepositoryImpl, user::UserRepositoryImpl},
+        test_util::async_pool_init,
+        PgPoolImpl,
+    };
+
+    async fn arrange_user(pool: &PgPoolImpl, name: &str) -> User {
+        let user_repository = UserRepositoryImpl {
+            pool: Arc::new(pool.clone()),
+        };
+        let user = User::new(
+            UserName::new(name.to_owned()).unwrap(),
+            Email::new(format!("{name}@example.com")).unwrap(),
+        )
+        .unwrap();
+        user_repository.save(user.clone()).await.unwrap();
+        user
+    }
+
+    #[tokio::test]
+    async fn test_save_and_find() {
+        // Arrange
+        let (_c, pool) = async_pool_init().await;
+        let owner = arrange_user(&pool, "alice").await;
+        let member = arrange_user(&pool, "bob").await;
+        let column_repository = ColumnRepositoryImpl {
+            pool: Arc::new(pool.clone()),
+        };
+        let todo = Column::new(ColumnTitle::new("todo".to_owned()));
+        let done = Column::new(ColumnTitle::new("done".to_owned()));
+        column_repository.save(todo.clone()).await.unwrap();
+        column_repository.save(done.clone()).await.unwrap();
+        let board_repository = BoardRepositoryImpl {
+            pool: Arc::new(pool),
+        };
+        let board = Board::new(
+            BoardId::gen(),
+            BoardTitle::new("yarukoto".to_owned()).unwrap(),
+            owner.user_id().clone(),
+            vec![member.user_id().clone()],
+            vec![done.id().clone(), todo.id().clone()],
+        )
+        .unwrap();
+
+        // Act
+        board_repository.save(board.clone()).await.unwrap();
+        let mut stored = board_repository
+            .find_by_id(board.id())
+            .await
+            .unwrap()
+            .unwrap();
+        stored
+            .reorder_columns(vec![todo.id().clone(), done.id().clone()])
+            .unwrap();
+        stored.archive().unwrap();
+        board_repository.save(stored).await.unwrap();
+        let updated = board_repository
+            .find_by_id(board.id())
+            .await
+            .unwrap()
+            .unwrap();
+        let missing = board_repository.find_by_id(&BoardId::gen()).await.unwrap();
+        let found = board_repository
+            .find_many(&[BoardId::gen(), board.id().clone()])
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(updated.title().to_string(), "yarukoto");
+        assert_eq!(updated.owner(), owner.user_id());
+        assert_eq!(updated.members(), &[member.user_id().clone()]);
+        assert_eq!(
+            updated.column_ids(),
+            &[todo.id().clone(), done.id().clone()]
+        );
+        assert!(updated.archived_at().is_some());
+        assert_eq!(updated.version(), Version::new(2));
+        assert!(missing.is_none());
+        assert!(found[0].is_none());
+        assert_eq!(found[1].as_ref().unwrap().version(), Version::new(2));
+    }
+
+    #[tokio::test]
+    async fn test_save_conflict() {
+        // Arrange
+        let (_c, pool) = async_pool_init().await;
+        let owner = arrange_user(&pool, "alice").await;
+        let board_repository = BoardRepositoryImpl {
+            pool: Arc::new(pool),
+        };
+        let board = Board::new(
+            BoardId::gen(),
+            BoardTitle::new("yarukoto".to_owned()).unwrap(),
+            owner.user_id().clone(),
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        board_repository.save(board.clone()).await.unwrap();
+        let mut first = board_repository
+            .find_by_id(board.id())
+            .await
+            .unwrap()
+            .unwrap();
+        let mut second = first.clone();
+
+        // Act
+        first.update_title(BoardTitle::new("first".to_owned()).unwrap());
+        board_repository.save(first).await.unwrap();
+        second.update_title(BoardTitle::new("second".to_owned()).unwrap());
+        let result = board_repository.save(second).await;
+        let stored = board_repository
+            .find_by_id(board.id())
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(RepositoryError::Conflict { expected }) if expected == Version::new(1)
+        ));
+        assert_eq!(stored.title().to_string(), "first");
+        assert_eq!(stored.version(), Version::new(2));
+    }
+}
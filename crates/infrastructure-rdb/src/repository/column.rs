@@ -0,0 +1,352 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use domain_kanban::{
+    column::{Column, ColumnId, ColumnRepository},
+    outbox::OutboxMessage,
+};
+use domain_util::RepositoryError;
+use serde_json::{json, Value};
+use shaku::Provider;
+use sqlx::query;
+
+use super::{from_json, outbox::insert_outbox, to_i64};
+use crate::{read, repository_error, Pool};
+
+/// ColumnRepositoryのPostgresでの実装
+/// カードとチェックリストは、リードモデルと同じテーブルにカラム内の並び順つきで保存する
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = ColumnRepository)]
+pub struct ColumnRepositoryImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[async_trait]
+impl ColumnRepository for ColumnRepositoryImpl {
+    async fn save(&self, column: Column) -> Result<(), RepositoryError> {
+        self.save_with_outbox(column, vec![]).await
+    }
+
+    async fn save_with_outbox(
+        &self,
+        column: Column,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        self.pool
+            .resilience()
+            .write(|| self.try_save_with_outbox(&column, &messages))
+            .await
+    }
+
+    async fn find_by_id(&self, id: &ColumnId) -> Result<Option<Column>, RepositoryError> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let id_string = id.to_string();
+        let column = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select c.id, c.title, c.version, c.archived_at
+                from columns c
+                where c.id = $1
+                "#,
+                &id_string
+            )
+            .fetch_optional(executor)
+        })
+        .await?;
+        let Some(column) = column else {
+            return Ok(None);
+        };
+
+        let cards = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select c.id, c.title, c.description, c.archived_at, c.due_at
+                from cards c
+                where c.column_id = $1 and c.detached_at is null
+                order by c.position, c.id
+                "#,
+                &id_string
+            )
+            .fetch_all(executor)
+        })
+        .await?;
+
+        let card_ids: Vec<_> = cards.iter().map(|c| c.id.clone()).collect();
+        let items = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select i.card_id, i.text, i.done
+                from checklist_items i
+                where i.card_id = any($1)
+                order by i.card_id, i.position
+                "#,
+                &card_ids
+            )
+            .fetch_all(executor)
+        })
+        .await?;
+        let mut checklists: HashMap<String, Vec<Value>> = HashMap::new();
+        for i in items {
+            checklists
+                .entry(i.card_id)
+                .or_default()
+                .push(json!({ "text": i.text, "done": i.done }));
+        }
+
+        let cards: Vec<_> = cards
+            .into_iter()
+            .map(|c| {
+                let checklist = checklists.remove(&c.id).unwrap_or_default();
+                json!({
+                    "id": c.id,
+                    "title": c.title,
+                    "description": c.description.unwrap_or_default(),
+                    "checklist": checklist,
+                    "archived_at": c.archived_at,
+                    "due_at": c.due_at,
+                })
+            })
+            .collect();
+        let column = from_json(json!({
+            "id": column.id,
+            "title": column.title,
+            "cards": cards,
+            "archived_at": column.archived_at,
+            "version": column.version,
+        }))?;
+        Ok(Some(column))
+    }
+}
+
+impl ColumnRepositoryImpl {
+    // 同じトランザクションをやりなおせるように、値は参照で受け取る
+    async fn try_save_with_outbox(
+        &self,
+        column: &Column,
+        messages: &[OutboxMessage],
+    ) -> Result<(), RepositoryError> {
+        let mut tx = self.pool.pool().begin().await.map_err(repository_error)?;
+
+        // `expected` のバージョンで保存されている場合のみ上書きする（未保存なら新規に追加する）
+        let expected = column.version();
+        let id = column.id().to_string();
+        let result = query!(
+            r#"
+            insert into columns (id, title, version, archived_at)
+            values ($1, $2, $3, $4)
+            on conflict (id) do update
+                set title = excluded.title,
+                    version = excluded.version,
+                    archived_at = excluded.archived_at
+                where columns.version = $5
+            "#,
+            &id,
+            column.title().to_string(),
+            to_i64(expected.next())?,
+            column.archived_at().cloned(),
+            to_i64(expected)?,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(repository_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::Conflict { expected });
+        }
+
+        // カラムから取り除かれたカードは削除せず、取り除かれた日時を記録する
+        // ほかのカラムへ移したカードは、移した先をどちらの順で保存してもコメントが消えないようにする
+        // 保持期間を過ぎたものはArchivePurgerが削除する
+        let card_ids: Vec<_> = column.cards().iter().map(|c| c.id().to_string()).collect();
+        query!(
+            r#"
+            update cards set detached_at = now()
+            where column_id = $1 and not (id = any($2)) and detached_at is null
+            "#,
+            &id,
+            &card_ids,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(repository_error)?;
+        query!(
+            "delete from checklist_items where card_id = any($1)",
+            &card_ids
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(repository_error)?;
+        for (position, card) in column.cards().iter().enumerate() {
+            let card_id = card.id().to_string();
+            query!(
+                r#"
+                insert into cards (id, title, description, column_id, archived_at, position, due_at)
+                values ($1, $2, $3, $4, $5, $6, $7)
+                on conflict (id) do update
+                    set title = excluded.title,
+                        description = excluded.description,
+                        column_id = excluded.column_id,
+                        archived_at = excluded.archived_at,
+                        position = excluded.position,
+                        due_at = excluded.due_at,
+                        detached_at = null
+                "#,
+                &card_id,
+                card.title().to_string(),
+                card.description().to_string(),
+                &id,
+                card.archived_at().cloned(),
+                to_i32(position)?,
+                card.due_at().cloned(),
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(repository_error)?;
+            for (position, item) in card.checklist().items().iter().enumerate() {
+                query!(
+                    r#"
+                    insert into checklist_items (card_id, position, text, done)
+                    values ($1, $2, $3, $4)
+                    "#,
+                    &card_id,
+                    to_i32(position)?,
+                    item.text().to_string(),
+                    item.done(),
+                )
+                .execute(&mut *tx)
+                .await
+                .map_err(repository_error)?;
+            }
+        }
+
+        insert_outbox(&mut tx, messages)
+            .await
+            .map_err(repository_error)?;
+        tx.commit().await.map_err(repository_error)?;
+        Ok(())
+    }
+}
+
+fn to_i32(position: usize) -> Result<i32, RepositoryError> {
+    i32::try_from(position).map_err(|e| RepositoryError::Other(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use domain_kanban::column::{CardDescription, CardTitle, ChecklistItemText, ColumnTitle};
+    use domain_util::Version;
+
+    use super::*;
+    use crate::test_util::async_pool_init;
+
+    #[tokio::test]
+    async fn test_save_and_find() {
+        // Arrange
+        let (_c, pool) = async_pool_init().await;
+        let column_repository = ColumnRepositoryImpl {
+            pool: Arc::new(pool),
+        };
+        let column = Column::new(ColumnTitle::new("todo".to_owned()))
+            .add_card(CardTitle::new("first".to_owned()))
+            .add_card_with_description(
+                CardTitle::new("second".to_owned()),
+                CardDescription::new("desc".to_owned()),
+            )
+            .add_card(CardTitle::new("third".to_owned()));
+
+        // Act
+        column_repository.save(column.clone()).await.unwrap();
+        let stored = column_repository
+            .find_by_id(column.id())
+            .await
+            .unwrap()
+            .unwrap();
+        // IDの順ではなく、カラム内の並び順で復元されることを確かめる
+        let mut stored = stored.rerank_card(2, 0).remove_card(1);
+        let card = stored.get_card_mut(1).unwrap();
+        card.checklist_mut()
+            .add(ChecklistItemText::new("a".to_owned()).unwrap())
+            .unwrap();
+        card.checklist_mut()
+            .add(ChecklistItemText::new("b".to_owned()).unwrap())
+            .unwrap();
+        card.checklist_mut().toggle(1).unwrap();
+        card.archive().unwrap();
+        let due_at = "2026-10-31T09:00:00Z".parse().unwrap();
+        card.set_due_at(Some(due_at));
+        column_repository.save(stored).await.unwrap();
+        let updated = column_repository
+            .find_by_id(column.id())
+            .await
+            .unwrap()
+            .unwrap();
+        let missing = column_repository
+            .find_by_id(&ColumnId::gen())
+            .await
+            .unwrap();
+
+        // Assert
+        let titles: Vec<_> = updated
+            .cards()
+            .iter()
+            .map(|c| c.title().to_string())
+            .collect();
+        assert_eq!(titles, vec!["third", "second"]);
+        let second = &updated.cards()[1];
+        assert_eq!(second.id(), column.cards()[1].id());
+        assert_eq!(second.description().to_string(), "desc");
+        assert!(second.archived_at().is_some());
+        assert_eq!(second.due_at(), Some(&due_at));
+        assert!(updated.cards()[0].due_at().is_none());
+        let items: Vec<_> = second
+            .checklist()
+            .items()
+            .iter()
+            .map(|i| (i.text().to_string(), i.done()))
+            .collect();
+        assert_eq!(items, vec![("a".to_owned(), false), ("b".to_owned(), true)]);
+        assert_eq!(updated.version(), Version::new(2));
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_save_conflict() {
+        // Arrange
+        let (_c, pool) = async_pool_init().await;
+        let column_repository = ColumnRepositoryImpl {
+            pool: Arc::new(pool),
+        };
+        let column = Column::new(ColumnTitle::new("todo".to_owned()));
+        column_repository.save(column.clone()).await.unwrap();
+        let stored = column_repository
+            .find_by_id(column.id())
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Act
+        column_repository
+            .save(stored.clone().add_card(CardTitle::new("first".to_owned())))
+            .await
+            .unwrap();
+        let result = column_repository
+            .save(stored.add_card(CardTitle::new("second".to_owned())))
+            .await;
+        let updated = column_repository
+            .find_by_id(column.id())
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(RepositoryError::Conflict { expected }) if expected == Version::new(1)
+        ));
+        assert_eq!(updated.cards().len(), 1);
+        assert_eq!(updated.cards()[0].title().to_string(), "first");
+    }
+}
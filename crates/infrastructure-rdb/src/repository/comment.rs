@@ -0,0 +1,280 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain_kanban::{
+    comment::{Comment, CommentId, CommentRepository},
+    outbox::OutboxMessage,
+};
+use domain_util::RepositoryError;
+use serde_json::json;
+use shaku::Provider;
+use sqlx::query;
+
+use super::{from_json, outbox::insert_outbox, to_i64};
+use crate::{read, repository_error, Pool};
+
+/// CommentRepositoryのPostgresでの実装
+/// カードと投稿者は先に保存されている必要がある（commentsの外部キー）
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = CommentRepository)]
+pub struct CommentRepositoryImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[async_trait]
+impl CommentRepository for CommentRepositoryImpl {
+    async fn save(&self, comment: Comment) -> Result<(), RepositoryError> {
+        self.save_with_outbox(comment, vec![]).await
+    }
+
+    async fn save_with_outbox(
+        &self,
+        comment: Comment,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        self.pool
+            .resilience()
+            .write(|| self.try_save_with_outbox(&comment, &messages))
+            .await
+    }
+
+    async fn find_by_id(&self, id: &CommentId) -> Result<Option<Comment>, RepositoryError> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let id_string = id.to_string();
+        let comment = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select c.id, c.card_id, c.author_id, c.body, c.created_at, c.edited_at, c.version
+                from comments c
+                where c.id = $1
+                "#,
+                &id_string
+            )
+            .fetch_optional(executor)
+        })
+        .await?;
+
+        comment
+            .map(|c| {
+                from_json(json!({
+                    "id": c.id,
+                    "card_id": c.card_id,
+                    "author": c.author_id,
+                    "body": c.body,
+                    "created_at": c.created_at,
+                    "edited_at": c.edited_at,
+                    "version": c.version,
+                }))
+            })
+            .transpose()
+    }
+
+    async fn delete(&self, id: &CommentId) -> Result<(), RepositoryError> {
+        self.delete_with_outbox(id, vec![]).await
+    }
+
+    async fn delete_with_outbox(
+        &self,
+        id: &CommentId,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        self.pool
+            .resilience()
+            .write(|| self.try_delete_with_outbox(id, &messages))
+            .await
+    }
+}
+
+impl CommentRepositoryImpl {
+    // 同じトランザクションをやりなおせるように、値は参照で受け取る
+    async fn try_save_with_outbox(
+        &self,
+        comment: &Comment,
+        messages: &[OutboxMessage],
+    ) -> Result<(), RepositoryError> {
+        let mut tx = self.pool.pool().begin().await.map_err(repository_error)?;
+
+        // `expected` のバージョンで保存されている場合のみ上書きする（未保存なら新規に追加する）
+        let expected = comment.version();
+        let result = query!(
+            r#"
+            insert into comments (id, card_id, author_id, body, created_at, edited_at, version)
+            values ($1, $2, $3, $4, $5, $6, $7)
+            on conflict (id) do update
+                set body = excluded.body,
+                    edited_at = excluded.edited_at,
+                    version = excluded.version
+                where comments.version = $8
+            "#,
+            comment.id().to_string(),
+            comment.card_id().to_string(),
+            comment.author().to_string(),
+            comment.body().to_string(),
+            *comment.created_at(),
+            comment.edited_at().cloned(),
+            to_i64(expected.next())?,
+            to_i64(expected)?,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(repository_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::Conflict { expected });
+        }
+        insert_outbox(&mut tx, messages)
+            .await
+            .map_err(repository_error)?;
+        tx.commit().await.map_err(repository_error)?;
+        Ok(())
+    }
+
+    async fn try_delete_with_outbox(
+        &self,
+        id: &CommentId,
+        messages: &[OutboxMessage],
+    ) -> Result<(), RepositoryError> {
+        let mut tx = self.pool.pool().begin().await.map_err(repository_error)?;
+
+        query!("delete from comments where id = $1", id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(repository_error)?;
+        insert_outbox(&mut tx, messages)
+            .await
+            .map_err(repository_error)?;
+        tx.commit().await.map_err(repository_error)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use domain_kanban::{
+        column::{CardTitle, Column, ColumnRepository, ColumnTitle},
+        comment::CommentBody,
+        user::{Email, User, UserName, UserRepository},
+    };
+    use domain_util::Version;
+
+    use super::*;
+    use crate::{
+        repository::{column::ColumnRepositoryImpl, user::UserRepositoryImpl},
+        test_util::async_pool_init,
+        PgPoolImpl,
+    };
+
+    // コメントを付けるカードと投稿者を保存する
+    async fn arrange_comment(pool: &PgPoolImpl) -> (User, Comment) {
+        let user_repository = UserRepositoryImpl {
+            pool: Arc::new(pool.clone()),
+        };
+        let column_repository = ColumnRepositoryImpl {
+            pool: Arc::new(pool.clone()),
+        };
+        let author = User::new(
+            UserName::new("alice".to_owned()).unwrap(),
+            Email::new("alice@example.com".to_owned()).unwrap(),
+        )
+        .unwrap();
+        user_repository.save(author.clone()).await.unwrap();
+        let column = Column::new(ColumnTitle::new("todo".to_owned()))
+            .add_card(CardTitle::new("first".to_owned()));
+        column_repository.save(column.clone()).await.unwrap();
+
+        let comment = Comment::new(
+            column.cards()[0].id().clone(),
+            author.user_id().clone(),
+            CommentBody::new("いいね".to_owned()).unwrap(),
+        );
+        (author, comment)
+    }
+
+    #[tokio::test]
+    async fn test_save_find_delete() {
+        // Arrange
+        let (_c, pool) = async_pool_init().await;
+        let (author, comment) = arrange_comment(&pool).await;
+        let comment_repository = CommentRepositoryImpl {
+            pool: Arc::new(pool),
+        };
+
+        // Act
+        comment_repository.save(comment.clone()).await.unwrap();
+        let mut stored = comment_repository
+            .find_by_id(comment.id())
+            .await
+            .unwrap()
+            .unwrap();
+        stored
+            .edit(
+                author.user_id(),
+                CommentBody::new("よくない".to_owned()).unwrap(),
+            )
+            .unwrap();
+        comment_repository.save(stored).await.unwrap();
+        let edited = comment_repository
+            .find_by_id(comment.id())
+            .await
+            .unwrap()
+            .unwrap();
+        comment_repository.delete(comment.id()).await.unwrap();
+        let deleted = comment_repository.find_by_id(comment.id()).await.unwrap();
+
+        // Assert
+        assert_eq!(edited.card_id(), comment.card_id());
+        assert_eq!(edited.author(), author.user_id());
+        assert_eq!(edited.body().to_string(), "よくない");
+        assert!(edited.edited_at().is_some());
+        assert_eq!(edited.version(), Version::new(2));
+        assert!(deleted.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_save_conflict() {
+        // Arrange
+        let (_c, pool) = async_pool_init().await;
+        let (author, comment) = arrange_comment(&pool).await;
+        let comment_repository = CommentRepositoryImpl {
+            pool: Arc::new(pool),
+        };
+        comment_repository.save(comment.clone()).await.unwrap();
+        let mut first = comment_repository
+            .find_by_id(comment.id())
+            .await
+            .unwrap()
+            .unwrap();
+        let mut second = first.clone();
+
+        // Act
+        first
+            .edit(
+                author.user_id(),
+                CommentBody::new("first".to_owned()).unwrap(),
+            )
+            .unwrap();
+        comment_repository.save(first).await.unwrap();
+        second
+            .edit(
+                author.user_id(),
+                CommentBody::new("second".to_owned()).unwrap(),
+            )
+            .unwrap();
+        let result = comment_repository.save(second).await;
+        let stored = comment_repository
+            .find_by_id(comment.id())
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(RepositoryError::Conflict { expected }) if expected == Version::new(1)
+        ));
+        assert_eq!(stored.body().to_string(), "first");
+    }
+}
@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_kanban::outbox::{DeadLetter, OutboxMessage, OutboxMessageId, OutboxStore};
+use domain_util::RepositoryError;
+use serde_json::json;
+use shaku::Provider;
+use sqlx::{query, PgConnection};
+
+use super::from_json;
+use crate::{read, repository_error, write, Pool};
+
+/// OutboxStoreのPostgresでの実装
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = OutboxStore)]
+pub struct OutboxStoreImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+/// 集約を保存するトランザクションの中でメッセージを追加する
+pub(super) async fn insert_outbox(
+    conn: &mut PgConnection,
+    messages: &[OutboxMessage],
+) -> sqlx::Result<()> {
+    for message in messages {
+        query!(
+            r#"
+            insert into outbox (id, topic, payload, created_at)
+            values ($1, $2, $3, $4)
+            "#,
+            message.id().to_string(),
+            message.topic(),
+            message.payload(),
+            *message.created_at(),
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl OutboxStore for OutboxStoreImpl {
+    async fn list_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<OutboxMessage>, RepositoryError> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let limit = i64::try_from(limit).map_err(|e| RepositoryError::Other(e.to_string()))?;
+        let messages = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select o.id, o.topic, o.payload, o.created_at, o.attempts, o.next_attempt_at, o.last_error
+                from outbox o
+                where o.next_attempt_at is null or o.next_attempt_at <= $1
+                order by o.created_at
+                limit $2
+                "#,
+                now,
+                limit,
+            )
+            .fetch_all(executor)
+        })
+        .await?;
+
+        messages
+            .into_iter()
+            .map(|m| {
+                from_json(json!({
+                    "id": m.id,
+                    "topic": m.topic,
+                    "payload": m.payload,
+                    "created_at": m.created_at,
+                    "attempts": m.attempts,
+                    "next_attempt_at": m.next_attempt_at,
+                    "last_error": m.last_error,
+                }))
+            })
+            .collect()
+    }
+
+    async fn complete(&self, id: &OutboxMessageId) -> Result<(), RepositoryError> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let id = id.to_string();
+        write(self.pool.as_ref(), || {
+            query!("delete from outbox where id = $1", &id).execute(executor)
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn reschedule(&self, message: OutboxMessage) -> Result<(), RepositoryError> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        // 配送済みで削除されたものは作り直さない
+        let id = message.id().to_string();
+        let attempts =
+            i32::try_from(message.attempts()).map_err(|e| RepositoryError::Other(e.to_string()))?;
+        write(self.pool.as_ref(), || {
+            query!(
+                r#"
+                update outbox
+                set attempts = $2, next_attempt_at = $3, last_error = $4
+                where id = $1
+                "#,
+                &id,
+                attempts,
+                message.next_attempt_at().copied(),
+                message.last_error(),
+            )
+            .execute(executor)
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn dead_letter(&self, letter: DeadLetter) -> Result<(), RepositoryError> {
+        self.pool
+            .resilience()
+            .write(|| self.try_dead_letter(&letter))
+            .await
+    }
+
+    async fn list_dead_letters(&self, limit: usize) -> Result<Vec<DeadLetter>, RepositoryError> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let limit = i64::try_from(limit).map_err(|e| RepositoryError::Other(e.to_string()))?;
+        let letters = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select d.id, d.topic, d.payload, d.created_at, d.attempts, d.last_error, d.error, d.dead_at
+                from outbox_dead_letters d
+                order by d.dead_at desc
+                limit $1
+                "#,
+                limit,
+            )
+            .fetch_all(executor)
+        })
+        .await?;
+
+        letters
+            .into_iter()
+            .map(|d| {
+                from_json(json!({
+                    "id": d.id,
+                    "topic": d.topic,
+                    "payload": d.payload,
+                    "created_at": d.created_at,
+                    "attempts": d.attempts,
+                    "last_error": d.last_error,
+                    "error": d.error,
+                    "dead_at": d.dead_at,
+                }))
+            })
+            .collect()
+    }
+}
+
+impl OutboxStoreImpl {
+    async fn try_dead_letter(&self, letter: &DeadLetter) -> Result<(), RepositoryError> {
+        let mut tx = self.pool.pool().begin().await.map_err(repository_error)?;
+
+        let message = letter.message();
+        let id = message.id().to_string();
+        query!("delete from outbox where id = $1", &id)
+            .execute(&mut *tx)
+            .await
+            .map_err(repository_error)?;
+        query!(
+            r#"
+            insert into outbox_dead_letters
+                (id, topic, payload, created_at, attempts, last_error, error, dead_at)
+            values ($1, $2, $3, $4, $5, $6, $7, $8)
+            on conflict (id) do nothing
+            "#,
+            &id,
+            message.topic(),
+            message.payload(),
+            *message.created_at(),
+            i32::try_from(message.attempts()).map_err(|e| RepositoryError::Other(e.to_string()))?,
+            message.last_error(),
+            letter.error(),
+            *letter.dead_at(),
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(repository_error)?;
+
+        tx.commit().await.map_err(repository_error)?;
+        Ok(())
+    }
+}
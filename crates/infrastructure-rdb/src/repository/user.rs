@@ -0,0 +1,300 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use async_trait::async_trait;
+use domain_kanban::{
+    outbox::OutboxMessage,
+    user::{Email, User, UserId, UserName, UserRepository},
+};
+use domain_util::{RepositoryError, Version};
+use shaku::Provider;
+use sqlx::query;
+
+use super::{outbox::insert_outbox, to_i64};
+use crate::{read, repository_error, Pool};
+
+/// UserRepositoryのPostgresでの実装
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = UserRepository)]
+pub struct UserRepositoryImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[async_trait]
+impl UserRepository for UserRepositoryImpl {
+    async fn save(&self, user: User) -> Result<(), RepositoryError> {
+        self.save_with_outbox(user, vec![]).await
+    }
+
+    async fn save_with_outbox(
+        &self,
+        user: User,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        self.pool
+            .resilience()
+            .write(|| self.try_save_with_outbox(&user, &messages))
+            .await
+    }
+
+    async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, RepositoryError> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let id_string = id.to_string();
+        let user = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select u.id, u.name, u.email, u.version
+                from users u
+                where u.id = $1
+                "#,
+                &id_string
+            )
+            .fetch_optional(executor)
+        })
+        .await?;
+
+        user.map(|u| to_user(u.id, u.name, u.email, u.version))
+            .transpose()
+    }
+
+    async fn find_many(&self, ids: &[UserId]) -> Result<Vec<Option<User>>, RepositoryError> {
+        let pool = self.pool.pool();
+        let executor = pool;
+        let ids_string: Vec<_> = ids.iter().map(ToString::to_string).collect();
+
+        let users = read(self.pool.as_ref(), || {
+            query!(
+                r#"
+                select u.id, u.name, u.email, u.version
+                from users u
+                where u.id = any($1)
+                "#,
+                &ids_string
+            )
+            .fetch_all(executor)
+        })
+        .await?;
+
+        let mut found = HashMap::new();
+        for u in users {
+            let user = to_user(u.id, u.name, u.email, u.version)?;
+            found.insert(user.user_id().clone(), user);
+        }
+        Ok(ids.iter().map(|id| found.get(id).cloned()).collect())
+    }
+
+    async fn save_all(&self, users: Vec<User>) -> Result<(), RepositoryError> {
+        self.pool
+            .resilience()
+            .write(|| self.try_save_all(&users))
+            .await
+    }
+}
+
+impl UserRepositoryImpl {
+    // 同じトランザクションをやりなおせるように、値は参照で受け取る
+    async fn try_save_with_outbox(
+        &self,
+        user: &User,
+        messages: &[OutboxMessage],
+    ) -> Result<(), RepositoryError> {
+        let mut tx = self.pool.pool().begin().await.map_err(repository_error)?;
+
+        // `expected` のバージョンで保存されている場合のみ上書きする（未保存なら新規に追加する）
+        let expected = user.version();
+        let result = query!(
+            r#"
+            insert into users (id, name, email, version)
+            values ($1, $2, $3, $4)
+            on conflict (id) do update
+                set name = excluded.name,
+                    email = excluded.email,
+                    version = excluded.version
+                where users.version = $5
+            "#,
+            user.user_id().to_string(),
+            user.user_name().to_string(),
+            user.email().to_string(),
+            to_i64(expected.next())?,
+            to_i64(expected)?,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(repository_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::Conflict { expected });
+        }
+        insert_outbox(&mut tx, messages)
+            .await
+            .map_err(repository_error)?;
+        tx.commit().await.map_err(repository_error)?;
+        Ok(())
+    }
+
+    async fn try_save_all(&self, users: &[User]) -> Result<(), RepositoryError> {
+        // 競合するものがあればロールバックして、何も保存しない
+        let mut tx = self.pool.pool().begin().await.map_err(repository_error)?;
+        for user in users {
+            let expected = user.version();
+            let result = query!(
+                r#"
+                insert into users (id, name, email, version)
+                values ($1, $2, $3, $4)
+                on conflict (id) do update
+                    set name = excluded.name,
+                        email = excluded.email,
+                        version = excluded.version
+                    where users.version = $5
+                "#,
+                user.user_id().to_string(),
+                user.user_name().to_string(),
+                user.email().to_string(),
+                to_i64(expected.next())?,
+                to_i64(expected)?,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(repository_error)?;
+            if result.rows_affected() == 0 {
+                return Err(RepositoryError::Conflict { expected });
+            }
+        }
+        tx.commit().await.map_err(repository_error)?;
+        Ok(())
+    }
+}
+
+// 保存されている値がドメインの不変条件を満たさない場合は `RepositoryError::Invariant` にする
+fn to_user(id: String, name: String, email: String, version: i64) -> Result<User, RepositoryError> {
+    let version =
+        u64::try_from(version).map_err(|e| RepositoryError::Serialization(e.to_string()))?;
+    let user = User::new_with_version(
+        UserId::from_str(&id).map_err(|e| RepositoryError::Serialization(e.to_string()))?,
+        UserName::new(name)?,
+        Email::new(email)?,
+        Version::new(version),
+    )?;
+    Ok(user)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::async_pool_init;
+
+    #[tokio::test]
+    async fn test_save_and_find() {
+        // Arrange
+        let (_c, pool) = async_pool_init().await;
+        let user_repository = UserRepositoryImpl {
+            pool: Arc::new(pool),
+        };
+        let user = User::new(
+            UserName::new("alice".to_owned()).unwrap(),
+            Email::new("alice@example.com".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        // Act
+        user_repository.save(user.clone()).await.unwrap();
+        let mut stored = user_repository
+            .find_by_id(user.user_id())
+            .await
+            .unwrap()
+            .unwrap();
+        stored.update_name(UserName::new("alice2".to_owned()).unwrap());
+        user_repository.save(stored).await.unwrap();
+        let found = user_repository
+            .find_many(&[user.user_id().clone(), UserId::gen()])
+            .await
+            .unwrap();
+
+        // Assert
+        let updated = found[0].as_ref().unwrap();
+        assert_eq!(updated.user_name().to_string(), "alice2");
+        assert_eq!(updated.email().to_string(), "alice@example.com");
+        assert_eq!(updated.version(), Version::new(2));
+        assert!(found[1].is_none());
+    }
+
+    #[tokio::test]
+    async fn test_save_conflict() {
+        // Arrange
+        let (_c, pool) = async_pool_init().await;
+        let user_repository = UserRepositoryImpl {
+            pool: Arc::new(pool),
+        };
+        let user = User::new(
+            UserName::new("alice".to_owned()).unwrap(),
+            Email::new("alice@example.com".to_owned()).unwrap(),
+        )
+        .unwrap();
+        user_repository.save(user.clone()).await.unwrap();
+        let mut first = user_repository
+            .find_by_id(user.user_id())
+            .await
+            .unwrap()
+            .unwrap();
+        let mut second = first.clone();
+
+        // Act
+        first.update_name(UserName::new("first".to_owned()).unwrap());
+        user_repository.save(first).await.unwrap();
+        second.update_name(UserName::new("second".to_owned()).unwrap());
+        let result = user_repository.save(second).await;
+        let stored = user_repository
+            .find_by_id(user.user_id())
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(RepositoryError::Conflict { expected }) if expected == Version::new(1)
+        ));
+        assert_eq!(stored.user_name().to_string(), "first");
+    }
+
+    #[tokio::test]
+    async fn test_save_all_conflict() {
+        // Arrange
+        let (_c, pool) = async_pool_init().await;
+        let user_repository = UserRepositoryImpl {
+            pool: Arc::new(pool),
+        };
+        let user = User::new(
+            UserName::new("alice".to_owned()).unwrap(),
+            Email::new("alice@example.com".to_owned()).unwrap(),
+        )
+        .unwrap();
+        user_repository.save(user.clone()).await.unwrap();
+        let new_user = User::new(
+            UserName::new("bob".to_owned()).unwrap(),
+            Email::new("bob@example.com".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        // Act
+        let mut stale_user = user.clone();
+        stale_user.update_name(UserName::new("stale".to_owned()).unwrap());
+        let result = user_repository
+            .save_all(vec![new_user.clone(), stale_user])
+            .await;
+        let found = user_repository
+            .find_many(&[user.user_id().clone(), new_user.user_id().clone()])
+            .await
+            .unwrap();
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(RepositoryError::Conflict { expected }) if expected == Version::initial()
+        ));
+        assert_eq!(found[0].as_ref().unwrap().user_name().to_string(), "alice");
+        assert!(found[1].is_none());
+    }
+}
@@ -0,0 +1,443 @@
+//! リードモデルをSQLiteで扱う実装
+//! Postgresを起動せずに動かすデモや組み込み用途向け
+
+use std::{fmt::Debug, str::FromStr};
+
+use anyhow::Result;
+use shaku::{Component, Interface};
+use sqlx::{
+    migrate::Migrator,
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    SqlitePool,
+};
+
+mod activity;
+mod board;
+mod card;
+mod column;
+mod comment;
+mod search;
+mod user;
+
+// crates/migrate/migrations と同じテーブルをSQLite向けに作る
+static MIGRATOR: Migrator = sqlx::migrate!("../migrate/sqlite_migrations");
+
+shaku::module! {
+    pub Module {
+        components = [SqlitePoolImpl],
+        providers = [
+            activity::ActivityQueryImpl,
+            activity::ActivityRepositoryImpl,
+            board::BoardQueryImpl,
+            card::CardsQueryImpl,
+            column::ColumnsQueryImpl,
+            comment::CommentsQueryImpl,
+            search::SearchQueryImpl,
+            user::UsersQueryImpl,
+        ]
+    }
+}
+
+impl Module {
+    // SqlitePoolを受け取ってOverrideしたModuleを返すメソッド
+    pub fn new_with_pool(pool: SqlitePool) -> Self {
+        Self::builder()
+            .with_component_parameters::<SqlitePoolImpl>(SqlitePoolImplParameters { pool })
+            .build()
+    }
+}
+
+pub struct Configuration {
+    max_connections: u32,
+    uri: String,
+}
+
+impl Configuration {
+    pub fn new(max_connections: u32, uri: String) -> Self {
+        Self {
+            max_connections,
+            uri,
+        }
+    }
+
+    /// ファイルがなければ作成し、マイグレーションを適用してから返す
+    pub async fn connect(self) -> Result<SqlitePool> {
+        let options = SqliteConnectOptions::from_str(&self.uri)?
+            .create_if_missing(true)
+            .foreign_keys(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(self.max_connections)
+            .connect_with(options)
+            .await?;
+        MIGRATOR.run(&pool).await?;
+        Ok(pool)
+    }
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Self {
+            max_connections: 5,
+            uri: "sqlite://kanban.db".to_owned(),
+        }
+    }
+}
+
+pub trait Pool: Interface + Debug {
+    fn pool(&self) -> &SqlitePool;
+}
+
+#[derive(Debug, Clone, Component)]
+#[shaku(interface = Pool)]
+pub struct SqlitePoolImpl {
+    pool: SqlitePool,
+}
+
+impl Pool for SqlitePoolImpl {
+    fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+}
+
+// SQLiteには配列のバインドがないので、`any($1)` の代わりにJSON配列を渡して `json_each` で展開する
+fn json_array<T: ToString>(values: &[T]) -> Result<String> {
+    let values: Vec<_> = values.iter().map(ToString::to_string).collect();
+    Ok(serde_json::to_string(&values)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use domain_kanban::{
+        activity::{Activity, ActivityAction, ActivityRepository},
+        board::BoardId,
+        column::{CardId, ColumnId},
+        user::UserId,
+    };
+    use domain_util::RepositoryError;
+    use query_resolver::{
+        ActivityPage, ActivityQuery, ArchivedFilter, BoardCriteria, BoardQuery, CardCriteria,
+        CardSort, CardSortKey, CardStatus, CardsQuery, ColumnsQuery, SearchHitKind, SearchPage,
+        SearchQuery, SortDirection, UserCriteria, UserSort, UserSortKey, UsersQuery,
+    };
+    use shaku::HasProvider;
+
+    use super::*;
+
+    // インメモリのDBは接続ごとに別になるので、接続を1つにする
+    async fn arrange_module() -> (Module, SqlitePool) {
+        let pool = Configuration::new(1, "sqlite::memory:".to_owned())
+            .connect()
+            .await
+            .unwrap();
+        sqlx::query(
+            r#"
+            insert into users (id, name, email, version)
+                values
+                    ('user-01HBCCGK3MG5HA7GJG25BGV6PJ', 'alice', 'alice@example.com', 2),
+                    ('user-01HBCCGK3MG5HA7GJG25BGV6PB', 'bob', 'bob@example.com', 1);
+            insert into boards (id, title, version)
+                values ('board-01HBCCGK3MG5HA7GJG25BGV6PK', 'yarukoto', 1);
+            insert into user_board_relations (user_id, board_id)
+                values ('user-01HBCCGK3MG5HA7GJG25BGV6PJ', 'board-01HBCCGK3MG5HA7GJG25BGV6PK');
+            insert into board_members (board_id, user_id)
+                values ('board-01HBCCGK3MG5HA7GJG25BGV6PK', 'user-01HBCCGK3MG5HA7GJG25BGV6PB');
+            insert into columns (id, title)
+                values
+                    ('column-01HBCCGK3MG5HA7GJG25BGV6PM', 'todo'),
+                    ('column-01HBCCGK3MG5HA7GJG25BGV6PA', 'done');
+            insert into board_column_relations (board_id, column_id, position)
+                values
+                    ('board-01HBCCGK3MG5HA7GJG25BGV6PK', 'column-01HBCCGK3MG5HA7GJG25BGV6PM', 0),
+                    ('board-01HBCCGK3MG5HA7GJG25BGV6PK', 'column-01HBCCGK3MG5HA7GJG25BGV6PA', 1);
+            insert into cards (id, title, description, column_id, archived_at, position, due_at)
+                values
+                    ('card-01HBCCGK3MG5HA7GJG25BGV6Q1', 'first', null, 'column-01HBCCGK3MG5HA7GJG25BGV6PM', null, 0, '2026-10-20T09:00:00Z'),
+                    ('card-01HBCCGK3MG5HA7GJG25BGV6Q2', 'archived', 'old', 'column-01HBCCGK3MG5HA7GJG25BGV6PM', '2026-10-01T00:00:00Z', 1, null),
+                    ('card-01HBCCGK3MG5HA7GJG25BGV6Q3', 'third', 'desc', 'column-01HBCCGK3MG5HA7GJG25BGV6PM', null, 2, '2026-11-01T00:00:00Z');
+            insert into checklist_items (card_id, position, text, done)
+                values ('card-01HBCCGK3MG5HA7GJG25BGV6Q3', 1, 'b', false), ('card-01HBCCGK3MG5HA7GJG25BGV6Q3', 0, 'a', true);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        (Module::new_with_pool(pool.clone()), pool)
+    }
+
+    #[tokio::test]
+    async fn test_users_and_boards() {
+        // Arrange
+        let (module, _pool) = arrange_module().await;
+        let users_query: Box<dyn UsersQuery> = module.provide().unwrap();
+        let board_query: Box<dyn BoardQuery> = module.provide().unwrap();
+        let user_id: UserId = "user-01HBCCGK3MG5HA7GJG25BGV6PJ".parse().unwrap();
+        let board_id: BoardId = "board-01HBCCGK3MG5HA7GJG25BGV6PK".parse().unwrap();
+
+        // Act
+        let users = users_query
+            .list_by_ids(&[user_id.clone(), UserId::gen()])
+            .await
+            .unwrap();
+        let board = board_query.find_by_id(&board_id).await.unwrap();
+
+        // Assert
+        assert_eq!(users.len(), 1);
+        let user = &users[&user_id];
+        assert_eq!(user.name, "alice");
+        assert_eq!(user.owned_board_ids, vec![board_id.to_string()]);
+        assert_eq!(user.version, 2);
+        assert_eq!(board.owner_id, user_id.to_string());
+        // IDの順ではなく、並び順に返す
+        assert_eq!(
+            board.column_ids,
+            vec![
+                "column-01HBCCGK3MG5HA7GJG25BGV6PM",
+                "column-01HBCCGK3MG5HA7GJG25BGV6PA"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_boards_by_column_and_member() {
+        // Arrange
+        let (module, _pool) = arrange_module().await;
+        let board_query: Box<dyn BoardQuery> = module.provide().unwrap();
+        let column_id: ColumnId = "column-01HBCCGK3MG5HA7GJG25BGV6PA".parse().unwrap();
+        let owner_id: UserId = "user-01HBCCGK3MG5HA7GJG25BGV6PJ".parse().unwrap();
+        let member_id: UserId = "user-01HBCCGK3MG5HA7GJG25BGV6PB".parse().unwrap();
+
+        // Act
+        let by_column = board_query
+            .list_by_column_ids(&[column_id.clone(), ColumnId::gen()])
+            .await
+            .unwrap();
+        let by_member = board_query
+            .list_by_member_ids(&[owner_id.clone(), member_id.clone()])
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(by_column.len(), 1);
+        assert_eq!(by_column[&column_id].title, "yarukoto");
+        // 所有しているだけのボードは含めない
+        assert!(!by_member.contains_key(&owner_id));
+        let titles: Vec<_> = by_member[&member_id].iter().map(|b| &b.title).collect();
+        assert_eq!(titles, vec!["yarukoto"]);
+    }
+
+    #[tokio::test]
+    async fn test_columns_and_cards() {
+        // Arrange
+        let (module, _pool) = arrange_module().await;
+        let columns_query: Box<dyn ColumnsQuery> = module.provide().unwrap();
+        let cards_query: Box<dyn CardsQuery> = module.provide().unwrap();
+        let column_id: ColumnId = "column-01HBCCGK3MG5HA7GJG25BGV6PM".parse().unwrap();
+
+        // Act
+        let column = columns_query.find_by_id(&column_id).await.unwrap();
+        let cards = cards_query
+            .list_by_orders(&column_id, &[0, 1], ArchivedFilter::Exclude)
+            .await
+            .unwrap();
+        let archived = cards_query
+            .find_by_order(&column_id, &1, ArchivedFilter::Include)
+            .await
+            .unwrap();
+        let card_id: CardId = "card-01HBCCGK3MG5HA7GJG25BGV6Q3".parse().unwrap();
+        let by_ids = cards_query
+            .list_by_ids(&[card_id.clone(), CardId::gen()])
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(column.card_cnt, 2);
+        assert_eq!(column.archived_card_cnt, 1);
+        assert_eq!(cards[&0].title, "first");
+        assert_eq!(cards[&0].description, "");
+        assert_eq!(cards[&1].title, "third");
+        let checklist: Vec<_> = cards[&1]
+            .checklist
+            .iter()
+            .map(|i| (i.text.as_str(), i.done))
+            .collect();
+        assert_eq!(checklist, vec![("a", true), ("b", false)]);
+        assert_eq!(archived.title, "archived");
+        assert!(archived.archived_at.is_some());
+        assert_eq!(by_ids.len(), 1);
+        assert_eq!(by_ids[&card_id], cards[&1]);
+        assert_eq!(by_ids[&card_id].column_id, column_id.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_filter_and_sort() {
+        // Arrange
+        let (module, _pool) = arrange_module().await;
+        let users_query: Box<dyn UsersQuery> = module.provide().unwrap();
+        let board_query: Box<dyn BoardQuery> = module.provide().unwrap();
+        let cards_query: Box<dyn CardsQuery> = module.provide().unwrap();
+        let member_id: UserId = "user-01HBCCGK3MG5HA7GJG25BGV6PB".parse().unwrap();
+        let column_id: ColumnId = "column-01HBCCGK3MG5HA7GJG25BGV6PM".parse().unwrap();
+
+        // Act
+        let users = users_query
+            .all(&UserCriteria {
+                email_domain: Some("EXAMPLE.com".to_owned()),
+                sort: UserSort {
+                    key: UserSortKey::Name,
+                    direction: SortDirection::Desc,
+                },
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let prefixed = users_query
+            .all(&UserCriteria {
+                name_prefix: Some("al".to_owned()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let boards = board_query
+            .all(&BoardCriteria {
+                title_contains: Some("ruko".to_owned()),
+                member_id: Some(member_id.clone()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let owned_by_member = board_query
+            .all(&BoardCriteria {
+                owner_id: Some(member_id),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let cards = cards_query
+            .list_by_column_ids(
+                &[column_id.clone()],
+                &CardCriteria {
+                    status: Some(CardStatus::Active),
+                    sort: CardSort {
+                        key: CardSortKey::Position,
+                        direction: SortDirection::Desc,
+                    },
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let by_title = cards_query
+            .list_by_column_ids(
+                &[column_id.clone()],
+                &CardCriteria {
+                    title_contains: Some("i".to_owned()),
+                    sort: CardSort {
+                        key: CardSortKey::Title,
+                        direction: SortDirection::Asc,
+                    },
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let due = cards_query
+            .list_by_column_ids(
+                &[column_id.clone()],
+                &CardCriteria {
+                    due_before: Some("2026-10-25T00:00:00+09:00".parse().unwrap()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        let names: Vec<_> = users.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["bob", "alice"]);
+        let names: Vec<_> = prefixed.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["alice"]);
+        let titles: Vec<_> = boards.iter().map(|b| b.title.as_str()).collect();
+        assert_eq!(titles, vec!["yarukoto"]);
+        assert_eq!(boards[0].column_ids.len(), 2);
+        assert!(owned_by_member.is_empty());
+        let titles: Vec<_> = cards[&column_id].iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["third", "first"]);
+        let titles: Vec<_> = by_title[&column_id]
+            .iter()
+            .map(|c| c.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["archived", "first", "third"]);
+        let titles: Vec<_> = due[&column_id].iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["first"]);
+        assert_eq!(
+            due[&column_id][0].due_at,
+            Some("2026-10-20T09:00:00Z".parse().unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search() {
+        // Arrange
+        let (module, _pool) = arrange_module().await;
+        let search_query: Box<dyn SearchQuery> = module.provide().unwrap();
+        let page = |query: &str| SearchPage {
+            query: query.to_owned(),
+            first: 10,
+            offset: 0,
+        };
+
+        // Act
+        let columns = search_query.search(&page("do")).await.unwrap();
+        let by_description = search_query.search(&page("DESC")).await.unwrap();
+        // アーカイブされたカードは説明が一致しても含めない
+        let archived = search_query.search(&page("old")).await.unwrap();
+
+        // Assert
+        let hits: Vec<_> = columns.iter().map(|h| (h.kind, h.id.as_str())).collect();
+        assert_eq!(
+            hits,
+            vec![
+                (SearchHitKind::Column, "column-01HBCCGK3MG5HA7GJG25BGV6PA"),
+                (SearchHitKind::Column, "column-01HBCCGK3MG5HA7GJG25BGV6PM"),
+            ]
+        );
+        assert_eq!(by_description.len(), 1);
+        assert_eq!(by_description[0].id, "card-01HBCCGK3MG5HA7GJG25BGV6Q3");
+        assert!(archived.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_append_activity_is_visible_to_activity_query() {
+        // Arrange
+        let (module, _pool) = arrange_module().await;
+        let activity_repository: Box<dyn ActivityRepository> = module.provide().unwrap();
+        let activity_query: Box<dyn ActivityQuery> = module.provide().unwrap();
+        let actor: UserId = "user-01HBCCGK3MG5HA7GJG25BGV6PJ".parse().unwrap();
+        let board_id: BoardId = "board-01HBCCGK3MG5HA7GJG25BGV6PK".parse().unwrap();
+        let activity = Activity::new(actor, ActivityAction::BoardRenamed, &board_id)
+            .on_board(board_id.clone())
+            .with_change("yarukoto", "todo");
+        let page = ActivityPage {
+            first: 10,
+            after: None,
+            actions: vec![],
+        };
+
+        // Act
+        activity_repository.append(activity.clone()).await.unwrap();
+        let duplicated = activity_repository.append(activity.clone()).await;
+        let activities = activity_query
+            .list_by_board(&board_id, &page)
+            .await
+            .unwrap();
+
+        // Assert
+        assert!(matches!(duplicated, Err(RepositoryError::Conflict { .. })));
+        assert_eq!(activities.len(), 1);
+        assert_eq!(activities[0].id, activity.id().to_string());
+        assert_eq!(activities[0].action, "board_renamed");
+        assert_eq!(activities[0].before.as_deref(), Some("yarukoto"));
+        assert_eq!(activities[0].after.as_deref(), Some("todo"));
+        assert_eq!(activities[0].occurred_at, *activity.occurred_at());
+    }
+}
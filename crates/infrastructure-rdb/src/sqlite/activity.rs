@@ -0,0 +1,168 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_kanban::{
+    activity::{Activity, ActivityRepository},
+    board::BoardId,
+    user::UserId,
+};
+use domain_util::{RepositoryError, Version};
+use query_resolver::{ActivityPage, ActivityQuery, ActivityView};
+use shaku::Provider;
+use sqlx::{query, query_as, FromRow};
+
+use super::{json_array, Pool};
+use crate::repository_error;
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = ActivityQuery)]
+pub struct ActivityQueryImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[derive(FromRow)]
+struct ActivityRow {
+    id: String,
+    actor_id: String,
+    action: String,
+    target_id: String,
+    board_id: Option<String>,
+    before_value: Option<String>,
+    after_value: Option<String>,
+    occurred_at: DateTime<Utc>,
+}
+
+#[async_trait]
+impl ActivityQuery for ActivityQueryImpl {
+    async fn list_by_board(
+        &self,
+        board_id: &BoardId,
+        page: &ActivityPage,
+    ) -> Result<Vec<ActivityView>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let (after, actions, limit) = page_params(page)?;
+        let activities: Vec<ActivityRow> = query_as(
+            r#"
+            -- NOTE: IDはULIDなので、IDの順序が記録された順序になる
+            select a.id, a.actor_id, a.action, a.target_id, a.board_id,
+                a.before_value, a.after_value, a.occurred_at
+            from activities a
+            where a.board_id = ?1
+                and (?2 is null or a.id < ?2)
+                and (json_array_length(?3) = 0 or a.action in (select value from json_each(?3)))
+            order by a.id desc
+            limit ?4
+            "#,
+        )
+        .bind(board_id.to_string())
+        .bind(after)
+        .bind(actions)
+        .bind(limit)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(activities.into_iter().map(to_view).collect())
+    }
+
+    async fn list_by_actor(
+        &self,
+        actor_id: &UserId,
+        page: &ActivityPage,
+    ) -> Result<Vec<ActivityView>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let (after, actions, limit) = page_params(page)?;
+        let activities: Vec<ActivityRow> = query_as(
+            r#"
+            select a.id, a.actor_id, a.action, a.target_id, a.board_id,
+                a.before_value, a.after_value, a.occurred_at
+            from activities a
+            where a.actor_id = ?1
+                and (?2 is null or a.id < ?2)
+                and (json_array_length(?3) = 0 or a.action in (select value from json_each(?3)))
+            order by a.id desc
+            limit ?4
+            "#,
+        )
+        .bind(actor_id.to_string())
+        .bind(after)
+        .bind(actions)
+        .bind(limit)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(activities.into_iter().map(to_view).collect())
+    }
+}
+
+/// ActivityRepositoryのSQLiteでの実装
+/// ActivityQueryと同じ `activities` テーブルに追記するので、配送するとすぐにクエリに反映される
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = ActivityRepository)]
+pub struct ActivityRepositoryImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[async_trait]
+impl ActivityRepository for ActivityRepositoryImpl {
+    async fn append(&self, activity: Activity) -> Result<(), RepositoryError> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        // 追記のみなので、同じIDで上書きしない
+        let result = query(
+            r#"
+            insert into activities
+                (id, actor_id, action, target_id, board_id, before_value, after_value, occurred_at)
+            values (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            on conflict (id) do nothing
+            "#,
+        )
+        .bind(activity.id().to_string())
+        .bind(activity.actor().to_string())
+        .bind(activity.action().as_str())
+        .bind(activity.target())
+        .bind(activity.board_id().map(ToString::to_string))
+        .bind(activity.before())
+        .bind(activity.after())
+        .bind(*activity.occurred_at())
+        .execute(executor)
+        .await
+        .map_err(repository_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::Conflict {
+                expected: Version::initial(),
+            });
+        }
+        Ok(())
+    }
+}
+
+// ActivityPageをSQLのパラメータにする。アクションはJSON配列で渡す
+fn page_params(page: &ActivityPage) -> Result<(Option<String>, String, i64)> {
+    let after = page.after.as_ref().map(ToString::to_string);
+    let actions: Vec<_> = page.actions.iter().map(|a| a.as_str()).collect();
+    let limit = i64::try_from(page.first)?;
+    Ok((after, json_array(&actions)?, limit))
+}
+
+fn to_view(row: ActivityRow) -> ActivityView {
+    ActivityView {
+        id: row.id,
+        actor_id: row.actor_id,
+        action: row.action,
+        target_id: row.target_id,
+        board_id: row.board_id,
+        before: row.before_value,
+        after: row.after_value,
+        occurred_at: row.occurred_at,
+    }
+}
@@ -0,0 +1,232 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_kanban::{board::BoardId, column::ColumnId, user::UserId};
+use itertools::Itertools;
+use query_resolver::{BoardCriteria, BoardQuery, BoardView};
+use shaku::Provider;
+use sqlx::{query_as, FromRow};
+
+use super::{json_array, Pool};
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = BoardQuery)]
+pub struct BoardQueryImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[derive(FromRow)]
+struct BoardRow {
+    id: String,
+    title: String,
+    owner_id: String,
+    version: i64,
+    archived_at: Option<DateTime<Utc>>,
+}
+
+#[derive(FromRow)]
+struct BoardColumnRow {
+    board_id: String,
+    column_id: String,
+}
+
+#[derive(FromRow)]
+struct BoardMemberRow {
+    board_id: String,
+    user_id: String,
+}
+
+#[async_trait]
+impl BoardQuery for BoardQueryImpl {
+    async fn find_by_id(&self, id: &BoardId) -> Result<BoardView> {
+        let mut boards = self.list_by_ids(&[id.clone()]).await?;
+        boards
+            .remove(id)
+            .ok_or_else(|| anyhow::anyhow!("board not found: {}", id))
+    }
+
+    async fn list_by_ids(&self, ids: &[BoardId]) -> Result<HashMap<BoardId, BoardView>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+        let ids_json = json_array(ids)?;
+
+        let boards: Vec<BoardRow> = query_as(
+            r#"
+            select b.id, b.title, ubr.user_id as owner_id, b.version, b.archived_at
+            from boards b
+                inner join user_board_relations ubr on b.id = ubr.board_id
+            where b.id in (select value from json_each(?1))
+            "#,
+        )
+        .bind(&ids_json)
+        .fetch_all(executor)
+        .await?;
+
+        let column_ids: Vec<BoardColumnRow> = query_as(
+            r#"
+                select board_id, column_id
+                from board_column_relations
+                where board_id in (select value from json_each(?1))
+                order by board_id, position
+            "#,
+        )
+        .bind(&ids_json)
+        .fetch_all(executor)
+        .await?;
+
+        let mut column_id_map = to_column_id_map(column_ids);
+        boards
+            .into_iter()
+            .map(|b| {
+                let key = FromStr::from_str(&b.id)?;
+                Ok((key, to_view(b, &mut column_id_map)?))
+            })
+            .collect()
+    }
+
+    async fn all(&self, criteria: &BoardCriteria) -> Result<Vec<BoardView>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let boards: Vec<BoardRow> = query_as(
+            r#"
+            select b.id, b.title, ubr.user_id as owner_id, b.version, b.archived_at
+            from boards b
+                inner join user_board_relations ubr on b.id = ubr.board_id
+            where (?1 or b.archived_at is null)
+                and (?2 is null or instr(b.title, ?2) > 0)
+                and (?3 is null or ubr.user_id = ?3)
+                and (?4 is null or exists (
+                    select 1
+                    from board_members bm
+                    where bm.board_id = b.id and bm.user_id = ?4
+                ))
+            order by
+                case when ?5 = 'title' and not ?6 then b.title end asc,
+                case when ?5 = 'title' and ?6 then b.title end desc,
+                case when not ?6 then b.id end asc,
+                case when ?6 then b.id end desc
+            "#,
+        )
+        .bind(criteria.archived.includes_archived())
+        .bind(criteria.title_contains.as_deref())
+        .bind(criteria.owner_id.as_ref().map(ToString::to_string))
+        .bind(criteria.member_id.as_ref().map(ToString::to_string))
+        .bind(criteria.sort.key.as_str())
+        .bind(criteria.sort.direction.is_desc())
+        .fetch_all(executor)
+        .await?;
+
+        let ids: Vec<_> = boards.iter().map(|b| b.id.as_str()).collect();
+        let column_ids: Vec<BoardColumnRow> = query_as(
+            r#"
+                select board_id, column_id
+                from board_column_relations
+                where board_id in (select value from json_each(?1))
+                order by board_id, position
+            "#,
+        )
+        .bind(json_array(&ids)?)
+        .fetch_all(executor)
+        .await?;
+
+        let mut column_id_map = to_column_id_map(column_ids);
+        boards
+            .into_iter()
+            .map(|b| to_view(b, &mut column_id_map))
+            .collect()
+    }
+
+    async fn list_by_column_ids(&self, ids: &[ColumnId]) -> Result<HashMap<ColumnId, BoardView>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let relations: Vec<BoardColumnRow> = query_as(
+            r#"
+                select board_id, column_id
+                from board_column_relations
+                where column_id in (select value from json_each(?1))
+            "#,
+        )
+        .bind(json_array(ids)?)
+        .fetch_all(executor)
+        .await?;
+
+        let board_ids = relations
+            .iter()
+            .map(|r| r.board_id.as_str())
+            .unique()
+            .map(BoardId::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        let boards = self.list_by_ids(&board_ids).await?;
+
+        let mut result = HashMap::new();
+        for r in relations {
+            let board_id = BoardId::from_str(&r.board_id)?;
+            if let Some(board) = boards.get(&board_id) {
+                result.insert(ColumnId::from_str(&r.column_id)?, board.clone());
+            }
+        }
+        Ok(result)
+    }
+
+    async fn list_by_member_ids(&self, ids: &[UserId]) -> Result<HashMap<UserId, Vec<BoardView>>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let members: Vec<BoardMemberRow> = query_as(
+            r#"
+                select board_id, user_id
+                from board_members
+                where user_id in (select value from json_each(?1))
+                order by user_id, board_id
+            "#,
+        )
+        .bind(json_array(ids)?)
+        .fetch_all(executor)
+        .await?;
+
+        let board_ids = members
+            .iter()
+            .map(|m| m.board_id.as_str())
+            .unique()
+            .map(BoardId::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        let boards = self.list_by_ids(&board_ids).await?;
+
+        let mut result: HashMap<UserId, Vec<BoardView>> = HashMap::new();
+        for m in members {
+            let board_id = BoardId::from_str(&m.board_id)?;
+            if let Some(board) = boards.get(&board_id) {
+                result
+                    .entry(UserId::from_str(&m.user_id)?)
+                    .or_default()
+                    .push(board.clone());
+            }
+        }
+        Ok(result)
+    }
+}
+
+// 読み込んだ順(ボード内での並び順)を保つ
+fn to_column_id_map(rows: Vec<BoardColumnRow>) -> HashMap<String, Vec<String>> {
+    rows.into_iter()
+        .map(|r| (r.board_id, r.column_id))
+        .into_group_map()
+}
+
+fn to_view(row: BoardRow, column_id_map: &mut HashMap<String, Vec<String>>) -> Result<BoardView> {
+    let column_ids = column_id_map.remove(&row.id).unwrap_or_default();
+    let result = BoardView {
+        id: row.id,
+        title: row.title,
+        owner_id: row.owner_id,
+        column_ids,
+        version: row.version.try_into()?,
+        archived_at: row.archived_at,
+    };
+    Ok(result)
+}
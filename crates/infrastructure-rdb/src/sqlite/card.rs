@@ -0,0 +1,240 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_kanban::column::{CardId, ColumnId};
+use query_resolver::{ArchivedFilter, CardCriteria, CardView, CardsQuery, ChecklistItemView};
+use shaku::Provider;
+use sqlx::{query_as, FromRow};
+
+use super::{json_array, Pool};
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = CardsQuery)]
+pub struct CardsQueryImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[derive(FromRow)]
+struct CardRow {
+    id: String,
+    title: String,
+    description: Option<String>,
+    column_id: String,
+    archived_at: Option<DateTime<Utc>>,
+    due_at: Option<DateTime<Utc>>,
+}
+
+#[derive(FromRow)]
+struct ChecklistItemRow {
+    card_id: String,
+    text: String,
+    done: bool,
+}
+
+impl CardsQueryImpl {
+    // カラム内の `offset` 番目から `limit` 件のカードを取得する
+    async fn list_cards(
+        &self,
+        column_id: &ColumnId,
+        offset: i64,
+        limit: i64,
+        archived: ArchivedFilter,
+    ) -> Result<Vec<CardRow>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let cards = query_as(
+            r#"
+            select c.id, c.title, c.description, c.column_id, c.archived_at, c.due_at
+            from cards c
+            where c.column_id = ?1
+                and (?4 or c.archived_at is null)
+            order by c.position, c.id
+            limit ?3
+            offset ?2
+            "#,
+        )
+        .bind(column_id.to_string())
+        .bind(offset)
+        .bind(limit)
+        .bind(archived.includes_archived())
+        .fetch_all(executor)
+        .await?;
+        Ok(cards)
+    }
+
+    // カードごとのチェックリストを表示順にまとめて取得する
+    async fn list_checklists(
+        &self,
+        card_ids: &[String],
+    ) -> Result<HashMap<String, Vec<ChecklistItemView>>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let items: Vec<ChecklistItemRow> = query_as(
+            r#"
+            select i.card_id, i.text, i.done
+            from checklist_items i
+            where i.card_id in (select value from json_each(?1))
+            order by i.card_id, i.position
+            "#,
+        )
+        .bind(json_array(card_ids)?)
+        .fetch_all(executor)
+        .await?;
+
+        let mut result: HashMap<String, Vec<ChecklistItemView>> = HashMap::new();
+        for i in items {
+            result
+                .entry(i.card_id)
+                .or_default()
+                .push(ChecklistItemView {
+                    text: i.text,
+                    done: i.done,
+                });
+        }
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl CardsQuery for CardsQueryImpl {
+    async fn find_by_order(
+        &self,
+        column_id: &ColumnId,
+        order: &usize,
+        archived: ArchivedFilter,
+    ) -> Result<CardView> {
+        let card = self
+            .list_cards(column_id, i64::try_from(*order)?, 1, archived)
+            .await?
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("card not found: {} {}", column_id, order))?;
+
+        let mut checklists = self.list_checklists(&[card.id.clone()]).await?;
+        let checklist = checklists.remove(&card.id).unwrap_or_default();
+        Ok(to_view(card, checklist))
+    }
+
+    async fn list_by_orders(
+        &self,
+        column_id: &ColumnId,
+        orders: &[usize],
+        archived: ArchivedFilter,
+    ) -> Result<HashMap<usize, CardView>> {
+        let min_order = orders.iter().min().copied().unwrap_or(0);
+        let max_order = orders.iter().max().copied().unwrap_or(0);
+        let length = 1 + max_order - min_order;
+        let cards = self
+            .list_cards(
+                column_id,
+                i64::try_from(min_order)?,
+                i64::try_from(length)?,
+                archived,
+            )
+            .await?;
+
+        let card_ids: Vec<_> = cards.iter().map(|c| c.id.clone()).collect();
+        let mut checklists = self.list_checklists(&card_ids).await?;
+        let result = cards
+            .into_iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let checklist = checklists.remove(&c.id).unwrap_or_default();
+                (i + min_order, to_view(c, checklist))
+            })
+            .filter(|(k, _)| orders.contains(k))
+            .collect();
+        Ok(result)
+    }
+
+    async fn list_by_column_ids(
+        &self,
+        ids: &[ColumnId],
+        criteria: &CardCriteria,
+    ) -> Result<HashMap<ColumnId, Vec<CardView>>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let cards: Vec<CardRow> = query_as(
+            r#"
+            select c.id, c.title, c.description, c.column_id, c.archived_at, c.due_at
+            from cards c
+            where c.column_id in (select value from json_each(?1))
+                and (?2 is null or instr(c.title, ?2) > 0)
+                and (?3 is null or ?3 = (c.archived_at is not null))
+                -- 日時の書式が揃っているとは限らないので、文字列ではなく日時として比べる
+                and (?6 is null or julianday(c.due_at) < julianday(?6))
+            -- 並び順はカラム内の位置の順
+            order by
+                case when ?4 = 'title' and not ?5 then c.title end asc,
+                case when ?4 = 'title' and ?5 then c.title end desc,
+                case when ?4 = 'position' and ?5 then c.position end desc,
+                c.position asc,
+                c.id asc
+            "#,
+        )
+        .bind(json_array(ids)?)
+        .bind(criteria.title_contains.as_deref())
+        .bind(criteria.status.map(|s| s.is_archived()))
+        .bind(criteria.sort.key.as_str())
+        .bind(criteria.sort.direction.is_desc())
+        .bind(criteria.due_before)
+        .fetch_all(executor)
+        .await?;
+
+        let card_ids: Vec<_> = cards.iter().map(|c| c.id.clone()).collect();
+        let mut checklists = self.list_checklists(&card_ids).await?;
+        let mut result: HashMap<ColumnId, Vec<CardView>> = HashMap::new();
+        for c in cards {
+            let column_id = ColumnId::from_str(&c.column_id)?;
+            let checklist = checklists.remove(&c.id).unwrap_or_default();
+            result
+                .entry(column_id)
+                .or_default()
+                .push(to_view(c, checklist));
+        }
+        Ok(result)
+    }
+
+    async fn list_by_ids(&self, ids: &[CardId]) -> Result<HashMap<CardId, CardView>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let cards: Vec<CardRow> = query_as(
+            r#"
+            select c.id, c.title, c.description, c.column_id, c.archived_at, c.due_at
+            from cards c
+            where c.id in (select value from json_each(?1))
+            "#,
+        )
+        .bind(json_array(ids)?)
+        .fetch_all(executor)
+        .await?;
+
+        let card_ids: Vec<_> = cards.iter().map(|c| c.id.clone()).collect();
+        let mut checklists = self.list_checklists(&card_ids).await?;
+        let mut result = HashMap::new();
+        for c in cards {
+            let id = CardId::from_str(&c.id)?;
+            let checklist = checklists.remove(&c.id).unwrap_or_default();
+            result.insert(id, to_view(c, checklist));
+        }
+        Ok(result)
+    }
+}
+
+fn to_view(row: CardRow, checklist: Vec<ChecklistItemView>) -> CardView {
+    CardView {
+        id: row.id,
+        title: row.title,
+        description: row.description.unwrap_or_default(),
+        column_id: row.column_id,
+        checklist,
+        archived_at: row.archived_at,
+        due_at: row.due_at,
+    }
+}
@@ -0,0 +1,76 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_kanban::column::ColumnId;
+use query_resolver::{ColumnView, ColumnsQuery};
+use shaku::Provider;
+use sqlx::{query_as, FromRow};
+
+use super::{json_array, Pool};
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = ColumnsQuery)]
+pub struct ColumnsQueryImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[derive(FromRow)]
+struct ColumnRow {
+    id: String,
+    title: String,
+    archived_at: Option<DateTime<Utc>>,
+    card_cnt: i64,
+    archived_card_cnt: i64,
+}
+
+#[async_trait]
+impl ColumnsQuery for ColumnsQueryImpl {
+    async fn find_by_id(&self, id: &ColumnId) -> Result<ColumnView> {
+        let mut columns = self.list_by_ids(&[id.clone()]).await?;
+        columns
+            .remove(id)
+            .ok_or_else(|| anyhow::anyhow!("column not found: {}", id))
+    }
+
+    async fn list_by_ids(&self, ids: &[ColumnId]) -> Result<HashMap<ColumnId, ColumnView>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let columns: Vec<ColumnRow> = query_as(
+            r#"
+            select c.id, c.title, c.archived_at,
+                count(distinct ca.id) filter (where ca.archived_at is null) as card_cnt,
+                count(distinct ca.id) filter (where ca.archived_at is not null) as archived_card_cnt
+            from columns c
+                left outer join cards ca on c.id = ca.column_id
+            where c.id in (select value from json_each(?1))
+            group by 1, 2, 3
+            "#,
+        )
+        .bind(json_array(ids)?)
+        .fetch_all(executor)
+        .await?;
+
+        columns
+            .into_iter()
+            .map(|c| {
+                let key = FromStr::from_str(&c.id)?;
+                Ok((key, to_view(c)?))
+            })
+            .collect()
+    }
+}
+
+fn to_view(row: ColumnRow) -> Result<ColumnView> {
+    let result = ColumnView {
+        id: row.id,
+        title: row.title,
+        card_cnt: row.card_cnt.try_into()?,
+        archived_card_cnt: row.archived_card_cnt.try_into()?,
+        archived_at: row.archived_at,
+    };
+    Ok(result)
+}
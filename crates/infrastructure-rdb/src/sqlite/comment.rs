@@ -0,0 +1,65 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_kanban::column::CardId;
+use query_resolver::{CommentView, CommentsQuery};
+use shaku::Provider;
+use sqlx::{query_as, FromRow};
+
+use super::{json_array, Pool};
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = CommentsQuery)]
+pub struct CommentsQueryImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[derive(FromRow)]
+struct CommentRow {
+    id: String,
+    card_id: String,
+    author_id: String,
+    body: String,
+    created_at: DateTime<Utc>,
+    edited_at: Option<DateTime<Utc>>,
+}
+
+#[async_trait]
+impl CommentsQuery for CommentsQueryImpl {
+    async fn list_by_card_ids(
+        &self,
+        card_ids: &[CardId],
+    ) -> Result<HashMap<CardId, Vec<CommentView>>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let comments: Vec<CommentRow> = query_as(
+            r#"
+            select c.id, c.card_id, c.author_id, c.body, c.created_at, c.edited_at
+            from comments c
+            where c.card_id in (select value from json_each(?1))
+            order by c.created_at, c.id
+            "#,
+        )
+        .bind(json_array(card_ids)?)
+        .fetch_all(executor)
+        .await?;
+
+        let mut result: HashMap<CardId, Vec<CommentView>> = HashMap::new();
+        for c in comments {
+            let key = FromStr::from_str(&c.card_id)?;
+            result.entry(key).or_default().push(CommentView {
+                id: c.id,
+                card_id: c.card_id,
+                author_id: c.author_id,
+                body: c.body,
+                created_at: c.created_at,
+                edited_at: c.edited_at,
+            });
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use query_resolver::{paginate_hits, substring_score, SearchHit, SearchPage, SearchQuery};
+use shaku::Provider;
+use sqlx::{query_as, FromRow};
+
+use super::Pool;
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = SearchQuery)]
+pub struct SearchQueryImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[derive(FromRow)]
+struct CandidateRow {
+    kind: String,
+    id: String,
+    title: String,
+    description: Option<String>,
+}
+
+#[async_trait]
+impl SearchQuery for SearchQueryImpl {
+    async fn search(&self, page: &SearchPage) -> Result<Vec<SearchHit>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        // 全文検索の索引は使わず、部分一致するものを集めてから関連度で並べる
+        let candidates: Vec<CandidateRow> = query_as(
+            r#"
+            select 'board' as kind, b.id, b.title, null as description
+            from boards b
+            where b.archived_at is null
+                and instr(lower(b.title), lower(?1)) > 0
+            union all
+            select 'column' as kind, c.id, c.title, null as description
+            from columns c
+            where c.archived_at is null
+                and instr(lower(c.title), lower(?1)) > 0
+            union all
+            select 'card' as kind, c.id, c.title, c.description
+            from cards c
+            where c.archived_at is null
+                and (instr(lower(c.title), lower(?1)) > 0
+                    or instr(lower(coalesce(c.description, '')), lower(?1)) > 0)
+            "#,
+        )
+        .bind(&page.query)
+        .fetch_all(executor)
+        .await?;
+
+        let mut hits = Vec::new();
+        for c in candidates {
+            let description = c.description.as_deref();
+            if let Some(score) = substring_score(&page.query, &c.title, description) {
+                hits.push(SearchHit {
+                    kind: c.kind.parse()?,
+                    id: c.id,
+                    score,
+                });
+            }
+        }
+        Ok(paginate_hits(hits, page))
+    }
+}
@@ -0,0 +1,145 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use domain_kanban::user::UserId;
+use itertools::Itertools;
+use query_resolver::{UserCriteria, UserView, UsersQuery};
+use shaku::Provider;
+use sqlx::{query_as, FromRow};
+
+use super::{json_array, Pool};
+
+#[derive(Debug, Clone, Provider)]
+#[shaku(interface = UsersQuery)]
+pub struct UsersQueryImpl {
+    #[shaku(inject)]
+    pool: Arc<dyn Pool>,
+}
+
+#[derive(FromRow)]
+struct UserRow {
+    id: String,
+    name: String,
+    email: String,
+    version: i64,
+}
+
+#[derive(FromRow)]
+struct OwnedBoardRow {
+    user_id: String,
+    board_id: String,
+}
+
+#[async_trait]
+impl UsersQuery for UsersQueryImpl {
+    async fn find_by_id(&self, id: &UserId) -> Result<UserView> {
+        let mut users = self.list_by_ids(&[id.clone()]).await?;
+        users
+            .remove(id)
+            .ok_or_else(|| anyhow::anyhow!("user not found: {}", id))
+    }
+
+    async fn list_by_ids(&self, ids: &[UserId]) -> Result<HashMap<UserId, UserView>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+        let ids_json = json_array(ids)?;
+
+        let users: Vec<UserRow> = query_as(
+            r#"
+            select u.id, u.name, u.email, u.version
+            from users u
+            where u.id in (select value from json_each(?1))
+            "#,
+        )
+        .bind(&ids_json)
+        .fetch_all(executor)
+        .await?;
+
+        let owned_board_ids: Vec<OwnedBoardRow> = query_as(
+            r#"
+                select user_id, board_id
+                from user_board_relations
+                where user_id in (select value from json_each(?1))
+            "#,
+        )
+        .bind(&ids_json)
+        .fetch_all(executor)
+        .await?;
+
+        let mut owned_board_map = to_owned_board_map(owned_board_ids);
+        users
+            .into_iter()
+            .map(|u| {
+                let key = FromStr::from_str(&u.id)?;
+                Ok((key, to_view(u, &mut owned_board_map)?))
+            })
+            .collect()
+    }
+
+    async fn all(&self, criteria: &UserCriteria) -> Result<Vec<UserView>> {
+        let pool = self.pool.pool();
+        let executor = pool;
+
+        let users: Vec<UserRow> = query_as(
+            r#"
+            select u.id, u.name, u.email, u.version
+            from users u
+            where (?1 is null or substr(u.name, 1, length(?1)) = ?1)
+                and (?2 is null or (
+                    instr(u.email, '@') > 0
+                    and lower(substr(u.email, instr(u.email, '@') + 1)) = lower(?2)
+                ))
+            order by
+                case when ?3 = 'name' and not ?4 then u.name end asc,
+                case when ?3 = 'name' and ?4 then u.name end desc,
+                case when ?3 = 'email' and not ?4 then u.email end asc,
+                case when ?3 = 'email' and ?4 then u.email end desc,
+                case when not ?4 then u.id end asc,
+                case when ?4 then u.id end desc
+            "#,
+        )
+        .bind(criteria.name_prefix.as_deref())
+        .bind(criteria.email_domain.as_deref())
+        .bind(criteria.sort.key.as_str())
+        .bind(criteria.sort.direction.is_desc())
+        .fetch_all(executor)
+        .await?;
+
+        let ids: Vec<_> = users.iter().map(|u| u.id.as_str()).collect();
+        let owned_board_ids: Vec<OwnedBoardRow> = query_as(
+            r#"
+                select user_id, board_id
+                from user_board_relations
+                where user_id in (select value from json_each(?1))
+            "#,
+        )
+        .bind(json_array(&ids)?)
+        .fetch_all(executor)
+        .await?;
+
+        let mut owned_board_map = to_owned_board_map(owned_board_ids);
+        users
+            .into_iter()
+            .map(|u| to_view(u, &mut owned_board_map))
+            .collect()
+    }
+}
+
+fn to_owned_board_map(rows: Vec<OwnedBoardRow>) -> HashMap<String, Vec<String>> {
+    rows.into_iter()
+        .map(|r| (r.user_id, r.board_id))
+        .into_group_map()
+}
+
+fn to_view(row: UserRow, owned_board_map: &mut HashMap<String, Vec<String>>) -> Result<UserView> {
+    let owned_board_ids = owned_board_map.remove(&row.id).unwrap_or_default();
+    let result = UserView {
+        id: row.id,
+        name: row.name,
+        email: row.email,
+        owned_board_ids,
+        version: row.version.try_into()?,
+    };
+    Ok(result)
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use resilience::{Resilience, ResiliencePolicy};
+use testcontainers_modules::{
+    postgres::Postgres,
+    testcontainers::{runners::AsyncRunner, ContainerAsync, RunnableImage},
+};
+
+use crate::{Configuration, PgPoolImpl};
+
+/// マイグレーションを適用したPostgresを起動する
+pub async fn async_pool_init() -> (ContainerAsync<Postgres>, PgPoolImpl) {
+    // create docker instance
+    let image = RunnableImage::from(Postgres::default());
+    let container = image.start().await;
+
+    let host_ip = container.get_host().await;
+    let host_port = container.get_host_port_ipv4(5432).await;
+    let uri = format!("postgres://postgres:postgres@{host_ip}:{host_port}/postgres");
+    let pool = Configuration::new(5, uri).connect().await.unwrap();
+    sqlx::migrate!("../migrate/migrations")
+        .run(&pool)
+        .await
+        .unwrap();
+
+    let pool = PgPoolImpl {
+        pool,
+        resilience: Arc::new(Resilience::new("postgres", ResiliencePolicy::default())),
+    };
+    (container, pool)
+}
@@ -0,0 +1,92 @@
+//! `operations/*.graphql` に書いた操作から、型付きのクエリ・ミューテーションを生成する
+//! スキーマはビルドのたびに `GraphQL::sdl()` から書き出すので、サーバーとずれた操作はビルドが通らない
+use std::{
+    env,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use async_graphql_parser::{
+    parse_query,
+    types::{DocumentOperations, OperationType},
+};
+use graphql_client_codegen::{
+    generate_module_token_stream, CodegenMode, GraphQLClientCodegenOptions,
+};
+use heck::ToSnakeCase;
+use presentation_graphql::GraphQL;
+
+const OPERATIONS_DIR: &str = "operations";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+    let schema_path = out_dir.join("schema.graphql");
+    fs::write(&schema_path, GraphQL::sdl())?;
+
+    println!("cargo:rerun-if-changed={OPERATIONS_DIR}");
+    let mut paths = fs::read_dir(OPERATIONS_DIR)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.retain(|p| p.extension().is_some_and(|ext| ext == "graphql"));
+    paths.sort();
+
+    let mut generated = String::new();
+    for path in &paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+        generated.push_str(&types(path, &schema_path)?);
+        generated.push_str(&methods(path)?);
+    }
+    fs::write(out_dir.join("operations.rs"), generated)?;
+    Ok(())
+}
+
+// 変数とレスポンスの型はgraphql_clientで作る
+fn types(path: &Path, schema_path: &Path) -> Result<String, Box<dyn Error>> {
+    let mut options = GraphQLClientCodegenOptions::new(CodegenMode::Cli);
+    options.set_module_visibility(syn::parse_quote!(pub));
+    options.set_variables_derives("Debug, Clone".to_owned());
+    options.set_response_derives("Debug, Clone, PartialEq".to_owned());
+    let tokens = generate_module_token_stream(path.to_path_buf(), schema_path, options)?;
+    Ok(tokens.to_string())
+}
+
+// 操作ごとに、型を指定して `Client::execute` を呼ぶメソッドを作る
+// NOTE: メソッド名はgraphql_clientが作るモジュール名と同じく、操作名のsnake_caseにする
+fn methods(path: &Path) -> Result<String, Box<dyn Error>> {
+    let document = parse_query(fs::read_to_string(path)?)?;
+    let DocumentOperations::Multiple(operations) = document.operations else {
+        return Err(format!("{}: operations must be named", path.display()).into());
+    };
+    let mut operations: Vec<_> = operations
+        .iter()
+        .map(|(name, op)| (name.to_string(), &op.node.ty))
+        .collect();
+    operations.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut result = String::new();
+    for (name, ty) in operations {
+        let kind = match ty {
+            OperationType::Query => "query",
+            OperationType::Mutation => "mutation",
+            OperationType::Subscription => {
+                return Err(format!("{name}: subscriptions are not supported").into())
+            }
+        };
+        let module = name.to_snake_case();
+        result.push_str(&format!(
+            r#"
+impl<T: crate::Transport> crate::Client<T> {{
+    #[doc = "`{kind} {name}`"]
+    pub async fn {module}(
+        &self,
+        variables: {module}::Variables,
+    ) -> crate::Result<{module}::ResponseData> {{
+        self.execute::<{name}>(variables).await
+    }}
+}}
+"#
+        ));
+    }
+    Ok(result)
+}
@@ -0,0 +1,148 @@
+use graphql_client::{GraphQLQuery, Response};
+
+use crate::{ClientError, HttpRequest, Result, Transport};
+
+// NOTE: サーバー(presentation-axum)が認証を導入するまでは、操作するユーザーのIDをヘッダーで送る
+const CURRENT_USER_HEADER: &str = "x-user-id";
+
+/// 操作ごとのメソッドは `operations/*.graphql` から生成する
+pub struct Client<T> {
+    transport: T,
+    current_user: Option<String>,
+}
+
+impl<T: Transport> Client<T> {
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            current_user: None,
+        }
+    }
+
+    /// ミューテーションを行うユーザーを指定する
+    pub fn with_current_user(mut self, user_id: impl Into<String>) -> Self {
+        self.current_user = Some(user_id.into());
+        self
+    }
+
+    /// 生成した操作の型を指定して送る
+    pub async fn execute<Q: GraphQLQuery>(
+        &self,
+        variables: Q::Variables,
+    ) -> Result<Q::ResponseData> {
+        let request = self.request(&Q::build_query(variables))?;
+        let response = self
+            .transport
+            .send(request)
+            .await
+            .map_err(ClientError::Transport)?;
+        if !(200..300).contains(&response.status) {
+            return Err(ClientError::Status {
+                status: response.status,
+                body: String::from_utf8_lossy(&response.body).into_owned(),
+            });
+        }
+
+        let response: Response<Q::ResponseData> = serde_json::from_slice(&response.body)?;
+        match (response.data, response.errors) {
+            (_, Some(errors)) if !errors.is_empty() => Err(ClientError::GraphQL(errors)),
+            (Some(data), _) => Ok(data),
+            (None, _) => Err(ClientError::NoData),
+        }
+    }
+
+    fn request(&self, body: &impl serde::Serialize) -> Result<HttpRequest> {
+        let mut headers = vec![("content-type", "application/json".to_owned())];
+        if let Some(user_id) = &self.current_user {
+            headers.push((CURRENT_USER_HEADER, user_id.clone()));
+        }
+        Ok(HttpRequest {
+            headers,
+            body: serde_json::to_vec(body)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::{operations::get_user, HttpResponse};
+
+    // 受け取ったリクエストを覚えて、決まったレスポンスを返す
+    struct StubTransport {
+        status: u16,
+        body: &'static str,
+        sent: std::sync::Mutex<Vec<HttpRequest>>,
+    }
+
+    #[async_trait]
+    impl Transport for StubTransport {
+        async fn send(&self, request: HttpRequest) -> anyhow::Result<HttpResponse> {
+            self.sent.lock().unwrap().push(request);
+            Ok(HttpResponse {
+                status: self.status,
+                body: self.body.as_bytes().to_vec(),
+            })
+        }
+    }
+
+    fn client(status: u16, body: &'static str) -> Client<StubTransport> {
+        Client::new(StubTransport {
+            status,
+            body,
+            sent: Default::default(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_execute_sends_current_user_and_variables() {
+        // Arrange
+        let client = client(200, r#"{"data":{"user":null}}"#).with_current_user("user-01");
+        let variables = get_user::Variables {
+            id: "user-02".to_owned(),
+        };
+
+        // Act
+        let result = client.get_user(variables).await.unwrap();
+
+        // Assert
+        assert_eq!(result.user, None);
+        let sent = client.transport.sent.lock().unwrap();
+        assert!(sent[0]
+            .headers
+            .contains(&(CURRENT_USER_HEADER, "user-01".to_owned())));
+        let body: serde_json::Value = serde_json::from_slice(&sent[0].body).unwrap();
+        assert_eq!(body["operationName"], "GetUser");
+        assert_eq!(body["variables"]["id"], "user-02");
+    }
+
+    #[tokio::test]
+    async fn test_execute_errors() {
+        let graphql = client(
+            200,
+            r#"{"data":null,"errors":[{"message":"見つかりません"}]}"#,
+        );
+        let status = client(500, "internal error");
+
+        let graphql = graphql
+            .get_user(get_user::Variables {
+                id: "user-01".to_owned(),
+            })
+            .await;
+        let status = status
+            .get_user(get_user::Variables {
+                id: "user-01".to_owned(),
+            })
+            .await;
+
+        assert!(
+            matches!(graphql, Err(ClientError::GraphQL(errors)) if errors[0].message == "見つかりません")
+        );
+        assert!(matches!(
+            status,
+            Err(ClientError::Status { status: 500, .. })
+        ));
+    }
+}
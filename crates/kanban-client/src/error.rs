@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// 送信できなかった
+    #[error("送信に失敗しました: {0}")]
+    Transport(anyhow::Error),
+    /// 2xx以外のステータスが返った
+    #[error("HTTPステータスが成功ではありません ({status}): {body}")]
+    Status { status: u16, body: String },
+    /// レスポンスがGraphQLのレスポンスとして読めない
+    #[error("レスポンスを読めません: {0}")]
+    Decode(#[from] serde_json::Error),
+    /// GraphQLのエラーが返った。一部のデータが返っていても、エラーとして扱う
+    #[error("GraphQLのエラー: {}", join_messages(.0))]
+    GraphQL(Vec<graphql_client::Error>),
+    #[error("レスポンスにデータがありません")]
+    NoData,
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+fn join_messages(errors: &[graphql_client::Error]) -> String {
+    errors
+        .iter()
+        .map(|e| e.message.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,25 @@
+//! GraphQL APIの型付きクライアント
+//!
+//! 操作は `operations/*.graphql` に書き、ビルド時に `GraphQL::sdl()` のスキーマと合わせて
+//! 変数・レスポンスの型と `Client` のメソッドを生成する。送り方は `Transport` で差し替えられる
+mod client;
+mod error;
+mod transport;
+
+pub use client::Client;
+pub use error::{ClientError, Result};
+pub use transport::{HttpRequest, HttpResponse, Transport};
+
+#[cfg(feature = "reqwest")]
+pub use transport::ReqwestTransport;
+
+/// 生成した操作。`operations::get_user::Variables` のように操作名のモジュールから型を使う
+#[allow(clippy::all, warnings)]
+pub mod operations {
+    // スキーマの独自スカラー
+    // NOTE: 生成したモジュールからは `super::<スカラー名>` で参照される
+    pub type Id = String;
+    pub type DateTime = chrono::DateTime<chrono::Utc>;
+
+    include!(concat!(env!("OUT_DIR"), "/operations.rs"));
+}
@@ -0,0 +1,65 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// GraphQLのリクエストをHTTPで送る
+/// NOTE: 送り先や認証はトランスポートが決める。クライアントはヘッダーと本文だけを渡す
+#[async_trait]
+pub trait Transport: Send + Sync {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+}
+
+/// POSTで送るリクエスト
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+#[cfg(feature = "reqwest")]
+pub use self::reqwest_transport::ReqwestTransport;
+
+#[cfg(feature = "reqwest")]
+mod reqwest_transport {
+    use super::*;
+
+    /// reqwestで `endpoint` にPOSTする
+    #[derive(Debug, Clone)]
+    pub struct ReqwestTransport {
+        client: reqwest::Client,
+        endpoint: String,
+    }
+
+    impl ReqwestTransport {
+        pub fn new(endpoint: impl Into<String>) -> Self {
+            Self::with_client(reqwest::Client::new(), endpoint)
+        }
+
+        /// タイムアウトなどを設定したクライアントで送る
+        pub fn with_client(client: reqwest::Client, endpoint: impl Into<String>) -> Self {
+            Self {
+                client,
+                endpoint: endpoint.into(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Transport for ReqwestTransport {
+        async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+            let mut builder = self.client.post(&self.endpoint);
+            for (name, value) in request.headers {
+                builder = builder.header(name, value);
+            }
+            let response = builder.body(request.body).send().await?;
+            let status = response.status().as_u16();
+            let body = response.bytes().await?.to_vec();
+            Ok(HttpResponse { status, body })
+        }
+    }
+}
@@ -0,0 +1,239 @@
+// ネットワークを使わず、presentation-axumのRouterに直接リクエストを流してクライアントを試す
+use anyhow::Result;
+use async_trait::async_trait;
+use axum::{body::Body, http::Request, Router};
+use chrono::Utc;
+use domain_kanban::{
+    activity::ActivityRepository,
+    outbox::{ActivityHandler, OutboxRelay, OutboxStore, RetryPolicy},
+};
+use infrastructure_memory::{modules, sample, Tables};
+use kanban_client::{
+    operations::{
+        add_checklist_item, get_board, get_board_activity, get_user, list_users, rename_user,
+        search,
+    },
+    Client, ClientError, HttpRequest, HttpResponse, Transport,
+};
+use presentation_axum::{router, GraphQL, Modules};
+use shaku::HasProvider;
+use tower::ServiceExt;
+
+struct RouterTransport(Router);
+
+#[async_trait]
+impl Transport for RouterTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let mut builder = Request::post("/");
+        for (name, value) in request.headers {
+            builder = builder.header(name, value);
+        }
+        let response = self
+            .0
+            .clone()
+            .oneshot(builder.body(Body::from(request.body))?)
+            .await?;
+        let status = response.status().as_u16();
+        let body = hyper::body::to_bytes(response.into_body()).await?.to_vec();
+        Ok(HttpResponse { status, body })
+    }
+}
+
+// サンプルデータを入れたメモリ上のバックエンドで動かす
+fn client() -> Client<RouterTransport> {
+    client_and_relay().0
+}
+
+// アウトボックスのリレーも同じストアで動かす
+fn client_and_relay() -> (Client<RouterTransport>, OutboxRelay) {
+    let (query_module, repository_module) = modules(Tables::seeded());
+    let store: Box<dyn OutboxStore> = repository_module.provide().unwrap();
+    let activity_repository: Box<dyn ActivityRepository> = repository_module.provide().unwrap();
+    let relay = OutboxRelay::new(store, RetryPolicy::default())
+        .with_handler(Box::new(ActivityHandler::new(activity_repository)));
+    let m = Modules::new(Box::new(query_module), Box::new(repository_module));
+    let gql = GraphQL::new(tokio::spawn, m);
+    (Client::new(RouterTransport(router(gql))), relay)
+}
+
+#[tokio::test]
+async fn test_get_board() {
+    // Arrange
+    let client = client();
+    let board = &sample::data().boards[0];
+
+    // Act
+    let result = client
+        .get_board(get_board::Variables {
+            id: board.id.to_string(),
+            include_archived: false,
+        })
+        .await
+        .unwrap();
+
+    // Assert
+    let result = result.board.unwrap();
+    assert_eq!(result.title, board.title);
+    assert_eq!(result.owner.unwrap().id, board.owner_id.to_string());
+    let column_ids: Vec<_> = result.columns.into_iter().map(|c| c.id).collect();
+    let expected: Vec<_> = board.column_ids.iter().map(ToString::to_string).collect();
+    assert_eq!(column_ids, expected);
+}
+
+#[tokio::test]
+async fn test_list_users_in_order() {
+    // Arrange
+    let client = client();
+    let variables = list_users::Variables {
+        filter: list_users::UserFilter {
+            name_prefix: None,
+            email_domain: None,
+        },
+        order_by: Some(list_users::UserOrder {
+            field: list_users::UserOrderField::NAME,
+            direction: list_users::SortDirection::DESC,
+        }),
+    };
+
+    // Act
+    let result = client.list_users(variables).await.unwrap();
+
+    // Assert
+    let names: Vec<_> = result.users_all.iter().map(|u| u.name.clone()).collect();
+    let mut expected: Vec<_> = sample::data()
+        .users
+        .iter()
+        .map(|u| u.name.clone())
+        .collect();
+    expected.sort_by(|a, b| b.cmp(a));
+    assert_eq!(names, expected);
+}
+
+#[tokio::test]
+async fn test_rename_user() {
+    // Arrange
+    let user = &sample::data().users[0];
+    let client = client().with_current_user(user.id.to_string());
+    let current = client
+        .get_user(get_user::Variables {
+            id: user.id.to_string(),
+        })
+        .await
+        .unwrap()
+        .user
+        .unwrap();
+
+    // Act
+    let result = client
+        .rename_user(rename_user::Variables {
+            id: user.id.to_string(),
+            name: "renamed".to_owned(),
+            expected_version: current.version,
+        })
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(result.rename_user.name, "renamed");
+    assert_eq!(result.rename_user.version, current.version + 1);
+    let renamed = client
+        .get_user(get_user::Variables {
+            id: user.id.to_string(),
+        })
+        .await
+        .unwrap()
+        .user
+        .unwrap();
+    assert_eq!(renamed.name, "renamed");
+}
+
+#[tokio::test]
+async fn test_mutation_without_current_user() {
+    // Arrange
+    let client = client();
+    let user = &sample::data().users[0];
+
+    // Act
+    let result = client
+        .rename_user(rename_user::Variables {
+            id: user.id.to_string(),
+            name: "renamed".to_owned(),
+            expected_version: 0,
+        })
+        .await;
+
+    // Assert
+    assert!(matches!(result, Err(ClientError::GraphQL(_))));
+}
+
+#[tokio::test]
+async fn test_search() {
+    // Arrange
+    let client = client();
+    let card = &sample::data().columns[0].cards[0];
+
+    // Act
+    let result = client
+        .search(search::Variables {
+            query: card.title.clone(),
+            first: 10,
+            after: None,
+        })
+        .await
+        .unwrap();
+
+    // Assert
+    let found = result.search.edges.iter().any(|edge| {
+        matches!(&edge.node, search::SearchSearchEdgesNode::Card(c) if c.id == card.id.to_string())
+    });
+    assert!(found);
+}
+
+#[tokio::test]
+async fn test_checklist_activity_is_on_board() {
+    // Arrange
+    let (client, relay) = client_and_relay();
+    let data = sample::data();
+    let user = &data.users[0];
+    let client = client.with_current_user(user.id.to_string());
+    let board = &data.boards[0];
+    let column = &data.columns[0];
+    let card = &column.cards[0];
+
+    // Act
+    client
+        .add_checklist_item(add_checklist_item::Variables {
+            column_id: column.id.to_string(),
+            card_id: card.id.to_string(),
+            text: "買い物".to_owned(),
+            // シードしたカラムはバージョン0
+            expected_version: 0,
+        })
+        .await
+        .unwrap();
+    relay.relay_once(Utc::now()).await.unwrap();
+    let result = client
+        .get_board_activity(get_board_activity::Variables {
+            id: board.id.to_string(),
+            first: 10,
+        })
+        .await
+        .unwrap();
+
+    // Assert
+    let activities: Vec<_> = result
+        .board
+        .unwrap()
+        .activity
+        .edges
+        .into_iter()
+        .map(|edge| (edge.node.action, edge.node.target_id))
+        .collect();
+    assert_eq!(
+        activities,
+        vec![(
+            get_board_activity::ActivityAction::CHECKLIST_ITEM_ADDED,
+            card.id.to_string()
+        )]
+    );
+}
@@ -0,0 +1,23 @@
+use std::env;
+
+use anyhow::{bail, Context, Result};
+
+/// 環境変数を0以上の整数として読み込む。未設定の場合は `default` を返す
+pub(crate) fn read_env(key: &str, default: u64) -> Result<u64> {
+    match env::var(key) {
+        Ok(value) => value
+            .parse()
+            .with_context(|| format!("{} must be a non-negative integer: {}", key, value)),
+        Err(_) => Ok(default),
+    }
+}
+
+/// `read_env` と同じく読み込むが、0は受け付けない
+/// `tokio::time::interval` は間隔が0だとpanicするので、実行する間隔はこれで読み込む
+pub(crate) fn read_positive_env(key: &str, default: u64) -> Result<u64> {
+    let value = read_env(key, default)?;
+    if value == 0 {
+        bail!("{} must be a positive integer", key);
+    }
+    Ok(value)
+}
@@ -1,51 +1,239 @@
-use anyhow::Result;
+mod config;
+mod outbox;
+mod projection;
+mod purge;
+mod query_cache;
+mod resilience_policy;
+
+use std::env;
+
+use anyhow::{anyhow, Result};
+use aws_config::SdkConfig;
+use config::read_env;
+use domain_kanban::archive::ArchivePurger;
 use infrastructure_dynamodb::{
-    default_sdk_config, dynamo_db_client, ClientImpl, ClientImplParameters, RepositoryModule,
+    default_sdk_config, dynamo_db_client, table, ClientImpl, ClientImplParameters, RepositoryModule,
 };
-use infrastructure_rdb::{Configuration, PgPoolImpl, PgPoolImplParameters, QueryModule};
+use infrastructure_memory::Tables;
+use infrastructure_rdb::{
+    sqlite::{Configuration as SqliteConfiguration, Module as SqliteQueryModule},
+    Configuration, PgPoolImpl, PgPoolImplParameters, QueryModule,
+    RepositoryModule as RdbRepositoryModule,
+};
+use outbox::{outbox_relay, relay_periodically, OutboxConfig};
 use presentation_axum::{App, Modules};
+use projection::{project_periodically, ProjectionConfig};
+use projector::{DynamoDbStreamFeed, PostgresSink, Projector};
+use purge::{purge_archived_periodically, PurgeConfig};
+use query_cache::{query_cache_from_env, InvalidateOnProjection};
+use query_resolver::cache::{CachedQueryModule, InvalidatingRepositoryModule, QueryCache};
+use resilience::Resilience;
+use resilience_policy::{report_interval_from_env, report_periodically, resilience_from_env};
+use shaku::HasProvider;
+use sqlx::PgPool;
+use std::sync::Arc;
 use tokio::spawn;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     logger_init();
-    let m = module().await?;
+    // KANBAN_BACKEND=memory のときは、Postgres・DynamoDBを使わずにサンプルデータで動かす
+    // KANBAN_BACKEND=sqlite のときは、リードモデルをPostgresではなくSQLiteから読み込む
+    // KANBAN_BACKEND=postgres のときは、DynamoDBを使わずに書き込み側もPostgresにする
+    let m = match env::var("KANBAN_BACKEND").as_deref() {
+        Ok("memory") => memory_modules()?,
+        Ok("sqlite") => sqlite_modules().await?,
+        Ok("postgres") => postgres_modules().await?,
+        _ => modules().await?,
+    };
     App::new()?.run(spawn, m).await?;
     Ok(())
 }
 
+async fn modules() -> Result<Modules> {
+    let pool = Configuration::default().connect().await?;
+    let sdk_config = default_sdk_config().await;
+    bootstrap_tables(&sdk_config).await?;
+    // タイムアウト・再試行・サーキットブレーカーはバックエンドごとに共有する
+    let rdb_resilience = resilience_from_env("postgres", "RDB")?;
+    let dynamodb_resilience = resilience_from_env("dynamodb", "DYNAMODB")?;
+    spawn(report_periodically(
+        vec![rdb_resilience.clone(), dynamodb_resilience.clone()],
+        report_interval_from_env()?,
+    ));
+    let query_module = query_module(pool.clone(), rdb_resilience.clone());
+    let repository_module = repository_module(&sdk_config, dynamodb_resilience);
+    let query_cache = query_cache_from_env()?;
+
+    // 書き込み側から先に削除する
+    let purgers = vec![
+        archive_purger(repository_module.as_ref())?,
+        archive_purger(query_module.as_ref())?,
+    ];
+    spawn(purge_archived_periodically(
+        purgers,
+        PurgeConfig::from_env()?,
+    ));
+
+    // 集約と一緒に保存したメッセージを配送する
+    // ActivityQueryはPostgresの `activities` を読むので、ActivityはPostgresに追記する
+    let outbox_config = OutboxConfig::from_env()?;
+    let activity_module = rdb_repository_module(pool.clone(), rdb_resilience);
+    let relay = outbox_relay(
+        repository_module.as_ref(),
+        activity_module.as_ref(),
+        &outbox_config,
+    )?;
+    spawn(relay_periodically(relay, outbox_config));
+
+    // 書き込み側の変更をリードモデルに反映する
+    let projection_config = ProjectionConfig::from_env()?;
+    if projection_config.enabled() {
+        // 保存したときに捨てたあとで、反映前のビューがキャッシュされることがあるので、反映したときにも捨てる
+        let projector = Projector::new(
+            Box::new(DynamoDbStreamFeed::new(&sdk_config)),
+            PostgresSink::new(pool),
+        )
+        .with_observer(Box::new(InvalidateOnProjection(Arc::clone(&query_cache))));
+        spawn(project_periodically(projector, projection_config));
+    }
+
+    let query_module = CachedQueryModule::new(*query_module, Arc::clone(&query_cache));
+    let repository_module = InvalidatingRepositoryModule::new(*repository_module, query_cache);
+    Ok(Modules::new(
+        Box::new(query_module),
+        Box::new(repository_module),
+    ))
+}
+
+async fn postgres_modules() -> Result<Modules> {
+    let pool = Configuration::default().connect().await?;
+    let rdb_resilience = resilience_from_env("postgres", "RDB")?;
+    spawn(report_periodically(
+        vec![rdb_resilience.clone()],
+        report_interval_from_env()?,
+    ));
+    let query_module = query_module(pool.clone(), rdb_resilience.clone());
+    let repository_module = rdb_repository_module(pool, rdb_resilience);
+
+    // クエリとリポジトリでテーブルを共有しているので、片方から削除すればよい
+    spawn(purge_archived_periodically(
+        vec![archive_purger(query_module.as_ref())?],
+        PurgeConfig::from_env()?,
+    ));
+    let outbox_config = OutboxConfig::from_env()?;
+    let relay = outbox_relay(
+        repository_module.as_ref(),
+        repository_module.as_ref(),
+        &outbox_config,
+    )?;
+    spawn(relay_periodically(relay, outbox_config));
+    // NOTE: 保存した内容がそのままリードモデルになるので、リードモデルへの反映はしない
+
+    let query_cache = query_cache_from_env()?;
+    let query_module = CachedQueryModule::new(*query_module, Arc::clone(&query_cache));
+    let repository_module = InvalidatingRepositoryModule::new(*repository_module, query_cache);
+    Ok(Modules::new(
+        Box::new(query_module),
+        Box::new(repository_module),
+    ))
+}
+
+async fn sqlite_modules() -> Result<Modules> {
+    let uri = env::var("SQLITE_DATABASE_URL").unwrap_or_else(|_| "sqlite://kanban.db".to_owned());
+    let pool = SqliteConfiguration::new(5, uri).connect().await?;
+    let query_module = SqliteQueryModule::new_with_pool(pool);
+    let sdk_config = default_sdk_config().await;
+    bootstrap_tables(&sdk_config).await?;
+    let dynamodb_resilience = resilience_from_env("dynamodb", "DYNAMODB")?;
+    spawn(report_periodically(
+        vec![dynamodb_resilience.clone()],
+        report_interval_from_env()?,
+    ));
+    let repository_module = repository_module(&sdk_config, dynamodb_resilience);
+
+    spawn(purge_archived_periodically(
+        vec![archive_purger(repository_module.as_ref())?],
+        PurgeConfig::from_env()?,
+    ));
+    // ActivityQueryはSQLiteの `activities` を読むので、ActivityはSQLiteに追記する
+    let outbox_config = OutboxConfig::from_env()?;
+    let relay = outbox_relay(repository_module.as_ref(), &query_module, &outbox_config)?;
+    spawn(relay_periodically(relay, outbox_config));
+    // NOTE: リードモデルへの反映はPostgresにしか対応していないので、SQLiteには反映しない
+    tracing::warn!("projection is not supported with the sqlite backend");
+
+    let query_cache = query_cache_from_env()?;
+    let query_module = CachedQueryModule::new(query_module, Arc::clone(&query_cache));
+    let repository_module = InvalidatingRepositoryModule::new(*repository_module, query_cache);
+    Ok(Modules::new(
+        Box::new(query_module),
+        Box::new(repository_module),
+    ))
+}
+
+fn memory_modules() -> Result<Modules> {
+    let (query_module, repository_module) = infrastructure_memory::modules(Tables::seeded());
+    // クエリとリポジトリでストアを共有しているので、片方から削除すればよい
+    spawn(purge_archived_periodically(
+        vec![archive_purger(&repository_module)?],
+        PurgeConfig::from_env()?,
+    ));
+    let outbox_config = OutboxConfig::from_env()?;
+    let relay = outbox_relay(&repository_module, &repository_module, &outbox_config)?;
+    spawn(relay_periodically(relay, outbox_config));
+
+    let query_cache = query_cache_from_env()?;
+    let query_module = CachedQueryModule::new(query_module, Arc::clone(&query_cache));
+    let repository_module = InvalidatingRepositoryModule::new(repository_module, query_cache);
+    Ok(Modules::new(
+        Box::new(query_module),
+        Box::new(repository_module),
+    ))
+}
+
+// DYNAMODB_BOOTSTRAP_TABLES=1 のときは、起動時に足りないテーブルを作成する
+async fn bootstrap_tables(sdk_config: &SdkConfig) -> Result<()> {
+    if read_env("DYNAMODB_BOOTSTRAP_TABLES", 0)? == 0 {
+        return Ok(());
+    }
+    let created = table::bootstrap(&dynamo_db_client(sdk_config)).await?;
+    tracing::info!(?created, "bootstrapped dynamodb tables");
+    Ok(())
+}
+
 fn logger_init() {
     // install global collector configured based on RUST_LOG env var.
     tracing_subscriber::fmt::init();
 }
 
-async fn module() -> Result<Modules> {
-    let query_module = query_module().await?;
-    let repository_module = repository_module().await?;
-
-    let module = Modules::new(query_module, repository_module);
-
-    Ok(module)
+fn archive_purger(module: &impl HasProvider<dyn ArchivePurger>) -> Result<Box<dyn ArchivePurger>> {
+    module.provide().map_err(|e| anyhow!(e.to_string()))
 }
 
-async fn query_module() -> Result<Box<QueryModule>> {
-    let parameters = PgPoolImplParameters {
-        pool: Configuration::default().connect().await?,
-    };
+fn query_module(pool: PgPool, resilience: Arc<Resilience>) -> Box<QueryModule> {
+    let parameters = PgPoolImplParameters { pool, resilience };
     let query_module: QueryModule = QueryModule::builder()
         .with_component_parameters::<PgPoolImpl>(parameters)
         .build();
-    let query_module = Box::new(query_module);
-    Ok(query_module)
+    Box::new(query_module)
+}
+
+fn rdb_repository_module(pool: PgPool, resilience: Arc<Resilience>) -> Box<RdbRepositoryModule> {
+    let parameters = PgPoolImplParameters { pool, resilience };
+    let repository_module: RdbRepositoryModule = RdbRepositoryModule::builder()
+        .with_component_parameters::<PgPoolImpl>(parameters)
+        .build();
+    Box::new(repository_module)
 }
 
-async fn repository_module() -> Result<Box<RepositoryModule>> {
+fn repository_module(sdk_config: &SdkConfig, resilience: Arc<Resilience>) -> Box<RepositoryModule> {
     let parameters = ClientImplParameters {
-        client: dynamo_db_client(&default_sdk_config().await),
+        client: dynamo_db_client(sdk_config),
+        resilience,
     };
     let repository_module = RepositoryModule::builder()
         .with_component_parameters::<ClientImpl>(parameters)
         .build();
-    let repository_module = Box::new(repository_module);
-    Ok(repository_module)
+    Box::new(repository_module)
 }
@@ -0,0 +1,79 @@
+use std::time::Duration as StdDuration;
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use domain_kanban::{
+    activity::ActivityRepository,
+    outbox::{ActivityHandler, OutboxRelay, OutboxStore, RelayReport, RetryPolicy},
+};
+use shaku::HasProvider;
+use tokio::time::interval;
+
+use crate::config::{read_env, read_positive_env};
+
+/// アウトボックスのメッセージを配送する間隔と、デッドレターに移すまでの試行回数
+pub struct OutboxConfig {
+    interval: StdDuration,
+    max_attempts: u32,
+}
+
+impl OutboxConfig {
+    const INTERVAL_MILLIS_ENV: &'static str = "OUTBOX_RELAY_INTERVAL_MILLIS";
+    const MAX_ATTEMPTS_ENV: &'static str = "OUTBOX_MAX_ATTEMPTS";
+
+    /// 環境変数から読み込む。未設定の場合は1秒ごとに実行し、8回失敗したらデッドレターに移す
+    pub fn from_env() -> Result<Self> {
+        let interval_millis = read_positive_env(Self::INTERVAL_MILLIS_ENV, 1000)?;
+        let max_attempts = read_env(Self::MAX_ATTEMPTS_ENV, 8)?;
+        let result = Self {
+            interval: StdDuration::from_millis(interval_millis),
+            max_attempts: max_attempts.try_into()?,
+        };
+        Ok(result)
+    }
+}
+
+/// リポジトリと同じストアのアウトボックスを配送するリレーを作る
+/// Activityは `activity_module` のリポジトリに追記するので、ActivityQueryが読むストアのものを渡す
+pub fn outbox_relay<M, A>(
+    module: &M,
+    activity_module: &A,
+    config: &OutboxConfig,
+) -> Result<OutboxRelay>
+where
+    M: HasProvider<dyn OutboxStore>,
+    A: HasProvider<dyn ActivityRepository>,
+{
+    let store: Box<dyn OutboxStore> = module.provide().map_err(|e| anyhow!(e.to_string()))?;
+    let activity_repository: Box<dyn ActivityRepository> = activity_module
+        .provide()
+        .map_err(|e| anyhow!(e.to_string()))?;
+    let policy = RetryPolicy {
+        max_attempts: config.max_attempts,
+        ..RetryPolicy::default()
+    };
+    let relay = OutboxRelay::new(store, policy)
+        .with_handler(Box::new(ActivityHandler::new(activity_repository)));
+    Ok(relay)
+}
+
+/// 定期的にアウトボックスのメッセージを配送する
+/// 失敗しても次の実行で再試行する
+pub async fn relay_periodically(relay: OutboxRelay, config: OutboxConfig) {
+    let mut interval = interval(config.interval);
+    loop {
+        interval.tick().await;
+        match relay.relay_once(Utc::now()).await {
+            Ok(report) if report == RelayReport::default() => {
+                tracing::debug!("no outbox messages to relay")
+            }
+            Ok(report) => tracing::info!(
+                delivered = report.delivered,
+                retried = report.retried,
+                dead_lettered = report.dead_lettered,
+                "relayed outbox messages"
+            ),
+            Err(e) => tracing::error!(error = %e, "failed to relay outbox messages"),
+        }
+    }
+}
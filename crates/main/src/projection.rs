@@ -0,0 +1,54 @@
+use std::time::Duration as StdDuration;
+
+use anyhow::Result;
+use projector::{ProjectionReport, Projector};
+use tokio::time::interval;
+
+use crate::config::{read_env, read_positive_env};
+
+/// 書き込み側の変更をリードモデルに反映する間隔
+pub struct ProjectionConfig {
+    enabled: bool,
+    interval: StdDuration,
+}
+
+impl ProjectionConfig {
+    const ENABLED_ENV: &'static str = "PROJECTION_ENABLED";
+    const INTERVAL_MILLIS_ENV: &'static str = "PROJECTION_INTERVAL_MILLIS";
+
+    /// 環境変数から読み込む。未設定の場合は有効にし、1秒ごとに実行する
+    pub fn from_env() -> Result<Self> {
+        let enabled = read_env(Self::ENABLED_ENV, 1)? != 0;
+        let interval_millis = read_positive_env(Self::INTERVAL_MILLIS_ENV, 1000)?;
+        let result = Self {
+            enabled,
+            interval: StdDuration::from_millis(interval_millis),
+        };
+        Ok(result)
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+/// 定期的に変更を反映し、反映の遅れをログに出す
+/// 失敗しても次の実行で、チェックポイントの続きから再試行する
+pub async fn project_periodically(projector: Projector, config: ProjectionConfig) {
+    let mut interval = interval(config.interval);
+    loop {
+        interval.tick().await;
+        match projector.project_once().await {
+            Ok(report) if report == ProjectionReport::default() => {
+                tracing::debug!("no changes to project")
+            }
+            Ok(report) => tracing::info!(
+                applied = report.applied,
+                deferred_shards = report.deferred_shards,
+                lag_millis = report.lag.num_milliseconds(),
+                "projected changes"
+            ),
+            Err(e) => tracing::error!(error = %e, "failed to project changes"),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::time::Duration as StdDuration;
+
+use anyhow::{bail, Result};
+use chrono::{Duration, Utc};
+use domain_kanban::archive::ArchivePurger;
+use tokio::time::interval;
+
+use crate::config::{read_env, read_positive_env};
+
+/// アーカイブしたものを削除するまでの保持期間と、削除を実行する間隔
+pub struct PurgeConfig {
+    retention: Duration,
+    interval: StdDuration,
+}
+
+impl PurgeConfig {
+    const RETENTION_DAYS_ENV: &'static str = "ARCHIVE_RETENTION_DAYS";
+    const INTERVAL_SECS_ENV: &'static str = "ARCHIVE_PURGE_INTERVAL_SECS";
+    // 削除の基準日時を計算できるように、保持期間は100年までにする
+    const MAX_RETENTION_DAYS: u64 = 365 * 100;
+
+    /// 環境変数から読み込む。未設定の場合は30日間保持し、1時間ごとに実行する
+    pub fn from_env() -> Result<Self> {
+        let retention_days = read_env(Self::RETENTION_DAYS_ENV, 30)?;
+        if retention_days > Self::MAX_RETENTION_DAYS {
+            bail!(
+                "{} must be at most {}: {}",
+                Self::RETENTION_DAYS_ENV,
+                Self::MAX_RETENTION_DAYS,
+                retention_days
+            );
+        }
+        let interval_secs = read_positive_env(Self::INTERVAL_SECS_ENV, 60 * 60)?;
+        let result = Self {
+            retention: Duration::days(retention_days.try_into()?),
+            interval: StdDuration::from_secs(interval_secs),
+        };
+        Ok(result)
+    }
+}
+
+/// 保持期間を過ぎたアーカイブ済みのものを定期的に削除する
+/// 失敗しても次の実行で再試行する
+pub async fn purge_archived_periodically(
+    purgers: Vec<Box<dyn ArchivePurger>>,
+    config: PurgeConfig,
+) {
+    let mut interval = interval(config.interval);
+    loop {
+        interval.tick().await;
+        let archived_before = Utc::now() - config.retention;
+        for purger in &purgers {
+            match purger.purge_archived(archived_before).await {
+                Ok(report) => tracing::info!(?report, %archived_before, "purged archived items"),
+                Err(e) => tracing::error!(error = %e, "failed to purge archived items"),
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use projector::{Change, ChangeObserver};
+use query_resolver::cache::{CacheConfig, QueryCache};
+
+use crate::config::read_env;
+
+/// 環境変数から読み込む。未設定の場合はビューの種類ごとに10000件まで、30秒キャッシュする
+pub fn query_cache_from_env() -> Result<Arc<QueryCache>> {
+    let default = CacheConfig::default();
+    let config = CacheConfig {
+        capacity: read_env("QUERY_CACHE_CAPACITY", default.capacity.try_into()?)?.try_into()?,
+        ttl: Duration::from_secs(read_env("QUERY_CACHE_TTL_SECS", default.ttl.as_secs())?),
+    };
+    tracing::info!(?config, "query cache");
+    Ok(Arc::new(QueryCache::new(config)))
+}
+
+/// リードモデルに反映した集約のキャッシュを捨てる
+pub struct InvalidateOnProjection(pub Arc<QueryCache>);
+
+impl ChangeObserver for InvalidateOnProjection {
+    fn applied(&self, change: &Change) {
+        let cache = &self.0;
+        match change {
+            Change::UserSaved(user) => cache.invalidate_user(user.user_id()),
+            Change::UserRemoved(id) => cache.invalidate_user(id),
+            Change::BoardSaved(board) => {
+                cache.invalidate_board(board.id());
+                cache.invalidate_user(board.owner());
+            }
+            Change::BoardRemoved(id) => cache.invalidate_board(id),
+            Change::ColumnSaved(column) => cache.invalidate_column(column.id()),
+            Change::ColumnRemoved(id) => cache.invalidate_column(id),
+            // コメントはキャッシュしていない
+            Change::CommentSaved(_) | Change::CommentRemoved(_) => {}
+        }
+    }
+}
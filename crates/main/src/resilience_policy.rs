@@ -0,0 +1,69 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use resilience::{BreakerPolicy, Resilience, ResiliencePolicy, RetryPolicy};
+use tokio::time::interval;
+
+use crate::config::{read_env, read_positive_env};
+
+/// バックエンドごとのタイムアウト・再試行・サーキットブレーカーの設定
+/// 環境変数は `{prefix}_TIMEOUT_MILLIS` のように、バックエンドごとの接頭辞をつける
+pub fn resilience_from_env(backend: &'static str, prefix: &str) -> Result<Arc<Resilience>> {
+    let default = ResiliencePolicy::default();
+    let env = |name: &str, default: Duration| -> Result<Duration> {
+        let millis = read_env(
+            &format!("{}_{}", prefix, name),
+            default.as_millis().try_into()?,
+        )?;
+        Ok(Duration::from_millis(millis))
+    };
+    let policy = ResiliencePolicy {
+        timeout: env("TIMEOUT_MILLIS", default.timeout)?,
+        retry: RetryPolicy {
+            max_attempts: read_env(
+                &format!("{}_RETRY_MAX_ATTEMPTS", prefix),
+                default.retry.max_attempts.into(),
+            )?
+            .try_into()?,
+            base_delay: env("RETRY_BASE_DELAY_MILLIS", default.retry.base_delay)?,
+            max_delay: env("RETRY_MAX_DELAY_MILLIS", default.retry.max_delay)?,
+        },
+        breaker: BreakerPolicy {
+            failure_threshold: read_env(
+                &format!("{}_BREAKER_FAILURE_THRESHOLD", prefix),
+                default.breaker.failure_threshold.into(),
+            )?
+            .try_into()?,
+            open_duration: env("BREAKER_OPEN_MILLIS", default.breaker.open_duration)?,
+        },
+    };
+    tracing::info!(backend, ?policy, "resilience policy");
+    Ok(Arc::new(Resilience::new(backend, policy)))
+}
+
+/// 呼び出しの集計をログに出す間隔。未設定の場合は1分ごと
+pub fn report_interval_from_env() -> Result<Duration> {
+    let interval_secs = read_positive_env("RESILIENCE_REPORT_INTERVAL_SECS", 60)?;
+    Ok(Duration::from_secs(interval_secs))
+}
+
+/// 呼び出しの集計を定期的にログに出す
+pub async fn report_periodically(resiliences: Vec<Arc<Resilience>>, period: Duration) {
+    let mut interval = interval(period);
+    loop {
+        interval.tick().await;
+        for resilience in &resiliences {
+            let metrics = resilience.metrics();
+            tracing::info!(
+                backend = metrics.backend,
+                state = ?metrics.state,
+                calls = metrics.calls,
+                retries = metrics.retries,
+                timeouts = metrics.timeouts,
+                rejected = metrics.rejected,
+                trips = metrics.trips,
+                "resilience metrics"
+            );
+        }
+    }
+}
@@ -0,0 +1,14 @@
+-- 監査ログとして対象が削除されても残すため、外部キーは張らない
+CREATE TABLE activities (
+    id VARCHAR PRIMARY KEY,
+    actor_id VARCHAR NOT NULL,
+    action VARCHAR NOT NULL,
+    target_id VARCHAR NOT NULL,
+    board_id VARCHAR,
+    before_value TEXT,
+    after_value TEXT,
+    occurred_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX activities_board_id_idx ON activities (board_id, id);
+CREATE INDEX activities_actor_id_idx ON activities (actor_id, id);
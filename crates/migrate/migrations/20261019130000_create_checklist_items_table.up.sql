@@ -0,0 +1,8 @@
+CREATE TABLE checklist_items (
+    card_id VARCHAR NOT NULL,
+    position INTEGER NOT NULL,
+    text VARCHAR NOT NULL,
+    done BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (card_id, position),
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+);
@@ -0,0 +1,2 @@
+ALTER TABLE columns DROP COLUMN version;
+DROP TABLE projection_checkpoints;
@@ -0,0 +1,24 @@
+-- 集約と同じトランザクションで保存し、リレーが配送するメッセージ
+CREATE TABLE outbox (
+    id VARCHAR PRIMARY KEY,
+    topic VARCHAR NOT NULL,
+    payload TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ,
+    last_error TEXT
+);
+CREATE INDEX outbox_created_at_idx ON outbox (created_at);
+
+-- 再試行をあきらめたメッセージ
+CREATE TABLE outbox_dead_letters (
+    id VARCHAR PRIMARY KEY,
+    topic VARCHAR NOT NULL,
+    payload TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    attempts INTEGER NOT NULL,
+    last_error TEXT,
+    error TEXT NOT NULL,
+    dead_at TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX outbox_dead_letters_dead_at_idx ON outbox_dead_letters (dead_at);
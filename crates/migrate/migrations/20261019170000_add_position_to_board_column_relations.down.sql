@@ -0,0 +1,2 @@
+DROP INDEX board_column_relations_position_idx;
+ALTER TABLE board_column_relations DROP COLUMN position;
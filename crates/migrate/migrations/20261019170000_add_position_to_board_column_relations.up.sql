@@ -0,0 +1,13 @@
+-- ボード内でのカラムの並び順(0始まり)
+ALTER TABLE board_column_relations ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+
+-- これまでの並び順は保存されていないので、既存のカラムはIDの順に並べる
+UPDATE board_column_relations r
+SET position = ordered.position
+FROM (
+    SELECT column_id, (ROW_NUMBER() OVER (PARTITION BY board_id ORDER BY column_id) - 1) AS position
+    FROM board_column_relations
+) ordered
+WHERE r.column_id = ordered.column_id;
+
+CREATE INDEX board_column_relations_position_idx ON board_column_relations (board_id, position);
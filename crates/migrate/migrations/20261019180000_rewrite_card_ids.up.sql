@@ -0,0 +1,29 @@
+-- カードのIDを、書き込み側と同じ `card-<ULID>` の形式に書き換える
+-- 並び順はまだIDの順なので、これまでのIDの順にULIDの時刻部分を1ミリ秒ずつずらして順番を保つ
+CREATE TEMPORARY TABLE card_id_map ON COMMIT DROP AS
+SELECT
+    o.id AS old_id,
+    'card-' || (
+        SELECT string_agg(substr('0123456789ABCDEFGHJKMNPQRSTVWXYZ', ((o.ts >> (5 * (9 - i))) & 31)::int + 1, 1), '' ORDER BY i)
+        FROM generate_series(0, 9) AS i
+    ) || (
+        SELECT string_agg(substr('0123456789ABCDEFGHJKMNPQRSTVWXYZ', (get_byte(decode(md5(o.id), 'hex'), i) & 31) + 1, 1), '' ORDER BY i)
+        FROM generate_series(0, 15) AS i
+    ) AS new_id
+FROM (
+    SELECT id, (EXTRACT(EPOCH FROM now()) * 1000)::bigint + ROW_NUMBER() OVER (ORDER BY id) AS ts
+    FROM cards
+    WHERE id NOT LIKE 'card-%'
+) o;
+
+ALTER TABLE comments DROP CONSTRAINT comments_card_id_fkey;
+ALTER TABLE checklist_items DROP CONSTRAINT checklist_items_card_id_fkey;
+
+UPDATE cards c SET id = m.new_id FROM card_id_map m WHERE c.id = m.old_id;
+UPDATE comments c SET card_id = m.new_id FROM card_id_map m WHERE c.card_id = m.old_id;
+UPDATE checklist_items i SET card_id = m.new_id FROM card_id_map m WHERE i.card_id = m.old_id;
+
+ALTER TABLE comments ADD CONSTRAINT comments_card_id_fkey
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE;
+ALTER TABLE checklist_items ADD CONSTRAINT checklist_items_card_id_fkey
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE;
@@ -0,0 +1,8 @@
+DROP INDEX cards_fts_idx;
+DROP INDEX columns_title_fts_idx;
+DROP INDEX boards_title_fts_idx;
+DROP INDEX cards_description_trgm_idx;
+DROP INDEX cards_title_trgm_idx;
+DROP INDEX columns_title_trgm_idx;
+DROP INDEX boards_title_trgm_idx;
+DROP EXTENSION IF EXISTS pg_trgm;
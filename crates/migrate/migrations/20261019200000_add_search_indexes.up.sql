@@ -0,0 +1,15 @@
+-- 検索用の索引
+-- 日本語は単語に分かれないので、部分一致(ILIKE)を trigram の索引で引く
+-- 英語などの単語での一致は全文検索の索引で引く
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX boards_title_trgm_idx ON boards USING gin (title gin_trgm_ops);
+CREATE INDEX columns_title_trgm_idx ON columns USING gin (title gin_trgm_ops);
+CREATE INDEX cards_title_trgm_idx ON cards USING gin (title gin_trgm_ops);
+CREATE INDEX cards_description_trgm_idx ON cards USING gin (description gin_trgm_ops);
+
+-- 検索するクエリと同じ式で作る
+CREATE INDEX boards_title_fts_idx ON boards USING gin (to_tsvector('simple', title));
+CREATE INDEX columns_title_fts_idx ON columns USING gin (to_tsvector('simple', title));
+CREATE INDEX cards_fts_idx ON cards
+    USING gin (to_tsvector('simple', title || ' ' || coalesce(description, '')));
@@ -0,0 +1,13 @@
+-- カラム内でのカードの並び順(0始まり)
+ALTER TABLE cards ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+
+-- これまでの並び順は保存されていないので、既存のカードはIDの順に並べる
+UPDATE cards c
+SET position = ordered.position
+FROM (
+    SELECT id, (ROW_NUMBER() OVER (PARTITION BY column_id ORDER BY id) - 1) AS position
+    FROM cards
+) ordered
+WHERE c.id = ordered.id;
+
+CREATE INDEX cards_position_idx ON cards (column_id, position);
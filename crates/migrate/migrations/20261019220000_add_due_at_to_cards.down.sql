@@ -0,0 +1,2 @@
+DROP INDEX cards_due_at_idx;
+ALTER TABLE cards DROP COLUMN due_at;
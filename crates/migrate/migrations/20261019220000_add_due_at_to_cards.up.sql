@@ -0,0 +1,5 @@
+-- カードの期限。NULLは期限なし
+ALTER TABLE cards ADD COLUMN due_at TIMESTAMPTZ;
+
+-- 期限で絞り込むときに使う
+CREATE INDEX cards_due_at_idx ON cards (column_id, due_at) WHERE due_at IS NOT NULL;
@@ -0,0 +1,2 @@
+DROP INDEX cards_detached_at_idx;
+ALTER TABLE cards DROP COLUMN detached_at;
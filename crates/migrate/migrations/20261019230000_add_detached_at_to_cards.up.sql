@@ -0,0 +1,6 @@
+-- カラムから取り除かれた日時。NULLはカラムに入っている
+-- ほかのカラムへ移したカードは、移した先が保存(反映)されるまで取り除かれたように見えるので、すぐには削除しない
+ALTER TABLE cards ADD COLUMN detached_at TIMESTAMPTZ;
+
+-- 保持期間を過ぎたものを削除するときに使う
+CREATE INDEX cards_detached_at_idx ON cards (detached_at) WHERE detached_at IS NOT NULL;
@@ -0,0 +1,7 @@
+-- SQLiteのリードモデル用。migrations以下と同じバージョンで同じテーブルを作る
+-- 日時はRFC3339の文字列(TEXT)で保存する
+CREATE TABLE users (
+    id VARCHAR PRIMARY KEY,
+    name VARCHAR NOT NULL,
+    email VARCHAR UNIQUE NOT NULL
+);
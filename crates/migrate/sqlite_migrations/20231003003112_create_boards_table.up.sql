@@ -0,0 +1,4 @@
+CREATE TABLE boards (
+    id VARCHAR PRIMARY KEY,
+    title VARCHAR NOT NULL
+);
@@ -0,0 +1,4 @@
+CREATE TABLE columns (
+    id VARCHAR PRIMARY KEY,
+    title VARCHAR NOT NULL
+);
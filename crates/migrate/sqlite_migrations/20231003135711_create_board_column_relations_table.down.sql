@@ -0,0 +1 @@
+DROP TABLE board_column_relations;
@@ -0,0 +1,13 @@
+CREATE TABLE comments (
+    id VARCHAR PRIMARY KEY,
+    card_id VARCHAR NOT NULL,
+    author_id VARCHAR NOT NULL,
+    body TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    edited_at TEXT,
+    version BIGINT NOT NULL DEFAULT 0,
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE,
+    FOREIGN KEY (author_id) REFERENCES users(id)
+);
+
+CREATE INDEX comments_card_id_idx ON comments (card_id, created_at);
@@ -0,0 +1,7 @@
+-- SQLiteはインデックスのある列を削除できないので、先にインデックスを削除する
+DROP INDEX cards_archived_at_idx;
+DROP INDEX columns_archived_at_idx;
+DROP INDEX boards_archived_at_idx;
+ALTER TABLE cards DROP COLUMN archived_at;
+ALTER TABLE columns DROP COLUMN archived_at;
+ALTER TABLE boards DROP COLUMN archived_at;
@@ -0,0 +1,9 @@
+-- アーカイブした日時。NULLはアーカイブされていない
+ALTER TABLE boards ADD COLUMN archived_at TEXT;
+ALTER TABLE columns ADD COLUMN archived_at TEXT;
+ALTER TABLE cards ADD COLUMN archived_at TEXT;
+
+-- 保持期間を過ぎたものを削除するときに使う
+CREATE INDEX boards_archived_at_idx ON boards (archived_at) WHERE archived_at IS NOT NULL;
+CREATE INDEX columns_archived_at_idx ON columns (archived_at) WHERE archived_at IS NOT NULL;
+CREATE INDEX cards_archived_at_idx ON cards (archived_at) WHERE archived_at IS NOT NULL;
@@ -0,0 +1,15 @@
+-- DynamoDB Streamsのシャードごとに、リードモデルへ反映済みの位置を記録する
+CREATE TABLE projection_checkpoints (
+    stream VARCHAR NOT NULL,
+    shard_id VARCHAR NOT NULL,
+    sequence_number VARCHAR,
+    -- 閉じたシャードを最後まで読み終えたか
+    finished BOOLEAN NOT NULL DEFAULT FALSE,
+    -- 最後に反映した変更が書き込み側で発生した日時
+    last_event_at TEXT,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (stream, shard_id)
+);
+
+-- 古い変更で上書きしないよう、カラムにもバージョンを持たせる
+ALTER TABLE columns ADD COLUMN version BIGINT NOT NULL DEFAULT 0;
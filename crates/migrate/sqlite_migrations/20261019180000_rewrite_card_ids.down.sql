@@ -0,0 +1,2 @@
+-- 書き換える前のIDは残していないので、戻さない
+SELECT 1;
@@ -0,0 +1,44 @@
+-- カードのIDを、書き込み側と同じ `card-<ULID>` の形式に書き換える
+-- 並び順はまだIDの順なので、これまでのIDの順にULIDの時刻部分を1ミリ秒ずつずらして順番を保つ
+-- 外部キーはコミットするときに確認する
+PRAGMA defer_foreign_keys = ON;
+
+CREATE TEMPORARY TABLE card_id_source AS
+SELECT
+    id AS old_id,
+    CAST(strftime('%s', 'now') AS INTEGER) * 1000 + ROW_NUMBER() OVER (ORDER BY id) AS ts,
+    hex(randomblob(16)) AS entropy
+FROM cards
+WHERE id NOT LIKE 'card-%';
+
+CREATE TEMPORARY TABLE card_id_map AS
+WITH RECURSIVE digits(i) AS (
+    SELECT 0
+    UNION ALL
+    SELECT i + 1 FROM digits WHERE i < 25
+)
+SELECT
+    s.old_id,
+    'card-' || (
+        SELECT group_concat(substr('0123456789ABCDEFGHJKMNPQRSTVWXYZ', ch + 1, 1), '')
+        FROM (
+            SELECT CASE
+                WHEN d.i < 10 THEN (s.ts >> (5 * (9 - d.i))) & 31
+                ELSE (instr('0123456789ABCDEF', substr(s.entropy, 2 * (d.i - 10) + 1, 1)) - 1) * 16
+                    + instr('0123456789ABCDEF', substr(s.entropy, 2 * (d.i - 10) + 2, 1)) - 1
+            END & 31 AS ch
+            FROM digits d
+            ORDER BY d.i
+        )
+    ) AS new_id
+FROM card_id_source s;
+
+UPDATE cards SET id = (SELECT new_id FROM card_id_map WHERE old_id = cards.id)
+WHERE id IN (SELECT old_id FROM card_id_map);
+UPDATE comments SET card_id = (SELECT new_id FROM card_id_map WHERE old_id = comments.card_id)
+WHERE card_id IN (SELECT old_id FROM card_id_map);
+UPDATE checklist_items SET card_id = (SELECT new_id FROM card_id_map WHERE old_id = checklist_items.card_id)
+WHERE card_id IN (SELECT old_id FROM card_id_map);
+
+DROP TABLE card_id_source;
+DROP TABLE card_id_map;
@@ -0,0 +1,11 @@
+-- ボードのメンバー。所有者は user_board_relations にある
+-- 既存のボードのメンバーは、書き込み側から反映しなおすまで空になる
+CREATE TABLE board_members (
+    board_id VARCHAR NOT NULL,
+    user_id VARCHAR NOT NULL,
+    PRIMARY KEY (board_id, user_id),
+    FOREIGN KEY (board_id) REFERENCES boards(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id)
+);
+
+CREATE INDEX board_members_user_id_idx ON board_members (user_id);
@@ -0,0 +1,3 @@
+-- SQLiteでは索引を使わずに部分一致で検索するので、何もしない
+-- Postgresのマイグレーションとバージョンを揃えるためだけに置く
+SELECT 1;
@@ -0,0 +1,2 @@
+DROP INDEX cards_position_idx;
+ALTER TABLE cards DROP COLUMN position;
@@ -0,0 +1,13 @@
+-- カラム内でのカードの並び順(0始まり)
+ALTER TABLE cards ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+
+-- これまでの並び順は保存されていないので、既存のカードはIDの順に並べる
+UPDATE cards
+SET position = (
+    SELECT COUNT(*)
+    FROM cards c
+    WHERE c.column_id = cards.column_id
+        AND c.id < cards.id
+);
+
+CREATE INDEX cards_position_idx ON cards (column_id, position);
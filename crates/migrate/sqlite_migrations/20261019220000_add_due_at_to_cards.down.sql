@@ -0,0 +1,3 @@
+-- SQLiteはインデックスのある列を削除できないので、先にインデックスを削除する
+DROP INDEX cards_due_at_idx;
+ALTER TABLE cards DROP COLUMN due_at;
@@ -1,9 +1,12 @@
 use anyhow::Result;
 use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
-use axum::{extract::Extension, response::Html, routing::get, Router, Server};
+use axum::{extract::Extension, http::HeaderMap, response::Html, routing::get, Router, Server};
 
-pub use presentation_graphql::Modules;
-use presentation_graphql::{GraphQL, Spawner};
+use presentation_graphql::{CurrentUser, Spawner};
+pub use presentation_graphql::{GraphQL, Modules};
+
+// NOTE: 認証を導入するまでは、操作したユーザーのIDをヘッダーで受け取る
+const CURRENT_USER_HEADER: &str = "x-user-id";
 
 pub struct App;
 
@@ -16,14 +19,7 @@ impl App {
     where
         S: Spawner<R>,
     {
-        let gql = GraphQL::new(spawner, m);
-
-        let app = Router::new()
-            .route(
-                "/",
-                get(|| async { Html(GraphQL::graphiql("/")) }).post(graphql_handler),
-            )
-            .layer(Extension(gql));
+        let app = router(GraphQL::new(spawner, m));
 
         println!("GraphiQL IDE: http://localhost:8000");
         Server::bind(&"127.0.0.1:8000".parse().unwrap())
@@ -33,6 +29,32 @@ impl App {
     }
 }
 
-async fn graphql_handler(gql: Extension<GraphQL>, req: GraphQLRequest) -> GraphQLResponse {
-    gql.execute(req.into_inner()).await.into()
+/// GraphiQLとGraphQLのエンドポイントを `/` に置く
+/// NOTE: サーバーを立てずにリクエストを流せるように、Routerだけを組み立てる
+pub fn router(gql: GraphQL) -> Router {
+    Router::new()
+        .route(
+            "/",
+            get(|| async { Html(GraphQL::graphiql("/")) }).post(graphql_handler),
+        )
+        .layer(Extension(gql))
+}
+
+async fn graphql_handler(
+    gql: Extension<GraphQL>,
+    headers: HeaderMap,
+    req: GraphQLRequest,
+) -> GraphQLResponse {
+    let mut req = req.into_inner();
+    if let Some(current_user) = current_user(&headers) {
+        req = req.data(current_user);
+    }
+    gql.execute(req).await.into()
+}
+
+fn current_user(headers: &HeaderMap) -> Option<CurrentUser> {
+    let value = headers.get(CURRENT_USER_HEADER)?.to_str().ok()?;
+    CurrentUser::parse(value)
+        .map_err(|e| tracing::warn!("invalid {} header: {}", CURRENT_USER_HEADER, e))
+        .ok()
 }
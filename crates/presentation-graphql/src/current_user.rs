@@ -0,0 +1,21 @@
+use domain_kanban::user::UserId;
+use domain_util::IdentifierParseError;
+
+/// リクエストを行ったユーザー
+/// NOTE: 認証はまだないので、presentation層から渡されたIDをそのまま信用している
+#[derive(Debug, Clone)]
+pub struct CurrentUser(UserId);
+
+impl CurrentUser {
+    pub fn new(user_id: UserId) -> Self {
+        Self(user_id)
+    }
+
+    pub fn parse(s: &str) -> Result<Self, IdentifierParseError> {
+        Ok(Self(s.parse()?))
+    }
+
+    pub fn user_id(&self) -> &UserId {
+        &self.0
+    }
+}
@@ -1,4 +1,27 @@
 mod board;
 mod card;
 mod column;
+mod comment;
 mod user;
+
+use std::{collections::HashMap, hash::Hash};
+
+/// `load_many` の結果を、渡したキーの順に並べる。見つからなかったキーは除く
+/// HashMapのままだと順番が決まらないので、リストを返すリゾルバーでは必ずこれを通す
+pub(crate) fn in_key_order<K: Eq + Hash, V>(keys: &[K], mut values: HashMap<K, V>) -> Vec<V> {
+    keys.iter().filter_map(|key| values.remove(key)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_key_order() {
+        let values = HashMap::from([("todo", 1), ("doing", 2), ("done", 3)]);
+
+        let result = in_key_order(&["done", "missing", "todo", "doing"], values);
+
+        assert_eq!(result, vec![3, 1, 2]);
+    }
+}
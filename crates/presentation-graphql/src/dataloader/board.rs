@@ -1,8 +1,14 @@
 use crate::scalar::Id;
 use crate::Modules;
-use crate::{model::Board, provides::HasProviderGql};
+use crate::{
+    error::repository_error,
+    model::{Board, BoardVersion, ColumnBoard, MemberBoards},
+    provides::HasProviderGql,
+};
 use async_graphql::{dataloader::Loader, Error as GqlError};
 use async_trait::async_trait;
+use domain_kanban::board::BoardRepository;
+use domain_util::Version;
 use query_resolver::BoardQuery;
 use std::collections::HashMap;
 
@@ -28,3 +34,89 @@ impl Loader<Id<Board>> for Modules {
             .collect())
     }
 }
+
+#[async_trait]
+impl Loader<ColumnBoard> for Modules {
+    type Value = Board;
+    type Error = GqlError;
+
+    async fn load(
+        &self,
+        keys: &[ColumnBoard],
+    ) -> Result<HashMap<ColumnBoard, Self::Value>, Self::Error> {
+        println!(
+            "[Dataloader] CALLED DataLoader of ColumnBoard -> Board: {:?}",
+            keys
+        );
+        let ids: Vec<_> = keys
+            .iter()
+            .map(|ColumnBoard(id)| id.clone().into())
+            .collect();
+        let board_query: Box<dyn BoardQuery> = self.query().provide_gql_result()?;
+        let result = board_query.list_by_column_ids(&ids).await?;
+        Ok(result
+            .into_iter()
+            .map(|(k, v)| (ColumnBoard(k.to_string().into()), v.into()))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl Loader<MemberBoards> for Modules {
+    type Value = Vec<Board>;
+    type Error = GqlError;
+
+    async fn load(
+        &self,
+        keys: &[MemberBoards],
+    ) -> Result<HashMap<MemberBoards, Self::Value>, Self::Error> {
+        println!(
+            "[Dataloader] CALLED DataLoader of MemberBoards -> Vec<Board>: {:?}",
+            keys
+        );
+        let ids: Vec<_> = keys
+            .iter()
+            .map(|MemberBoards(id)| id.clone().into())
+            .collect();
+        let board_query: Box<dyn BoardQuery> = self.query().provide_gql_result()?;
+        let result = board_query.list_by_member_ids(&ids).await?;
+        Ok(result
+            .into_iter()
+            .map(|(k, v)| {
+                let key = MemberBoards(k.to_string().into());
+                (key, v.into_iter().map(Into::into).collect())
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl Loader<BoardVersion> for Modules {
+    type Value = Version;
+    type Error = GqlError;
+
+    async fn load(
+        &self,
+        keys: &[BoardVersion],
+    ) -> Result<HashMap<BoardVersion, Self::Value>, Self::Error> {
+        println!(
+            "[Dataloader] CALLED DataLoader of BoardVersion -> Version: {:?}",
+            keys
+        );
+        let ids: Vec<_> = keys
+            .iter()
+            .map(|BoardVersion(id)| id.clone().into())
+            .collect();
+        let board_repository: Box<dyn BoardRepository> = self.repository().provide_gql_result()?;
+        let boards = board_repository
+            .find_many(&ids)
+            .await
+            .map_err(repository_error)?;
+        // `find_many` は渡したIDの順に返す
+        Ok(keys
+            .iter()
+            .zip(boards)
+            .filter_map(|(key, board)| Some((key.clone(), board?.version())))
+            .collect())
+    }
+}
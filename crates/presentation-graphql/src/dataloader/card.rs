@@ -1,4 +1,4 @@
-use crate::model::Column;
+use crate::model::{Column, FilteredCards};
 use crate::scalar::Id;
 use crate::Modules;
 use crate::{model::Card, provides::HasProviderGql};
@@ -7,28 +7,31 @@ use async_graphql::{dataloader::Loader, Error as GqlError};
 use async_trait::async_trait;
 use futures_util::future::{join_all, JoinAll};
 use itertools::Itertools;
-use query_resolver::CardsQuery;
+use query_resolver::{ArchivedFilter, CardCriteria, CardsQuery};
 use std::collections::HashMap;
 use std::sync::Arc;
 
 #[async_trait]
-impl Loader<(Id<Column>, usize)> for Modules {
+impl Loader<(Id<Column>, ArchivedFilter, usize)> for Modules {
     type Value = Card;
     type Error = GqlError;
 
     async fn load(
         &self,
-        keys: &[(Id<Column>, usize)],
-    ) -> Result<HashMap<(Id<Column>, usize), Self::Value>, Self::Error> {
+        keys: &[(Id<Column>, ArchivedFilter, usize)],
+    ) -> Result<HashMap<(Id<Column>, ArchivedFilter, usize), Self::Value>, Self::Error> {
         println!(
-            "[Dataloader] CALLED DataLoader of Id<Card> -> Card: {:?}",
+            "[Dataloader] CALLED DataLoader of (Id<Column>, ArchivedFilter, usize) -> Card: {:?}",
             keys
         );
-        let idmap: HashMap<_, _> = Vec::from(keys).into_iter().into_group_map();
+        let idmap: HashMap<_, _> = keys
+            .iter()
+            .map(|(cid, archived, i)| ((cid.clone(), *archived), *i))
+            .into_group_map();
         let card_query: Arc<dyn CardsQuery> = self.query().provide_arc_gql_result()?;
         let futures_iterator: Vec<_> = idmap
             .into_iter()
-            .map(|(cid, us)| list_by_orders(Arc::clone(&card_query), cid, us))
+            .map(|((cid, archived), us)| list_by_orders(Arc::clone(&card_query), cid, archived, us))
             .collect();
         let v = JoinAll::from_iter(futures_iterator)
             .await
@@ -39,18 +42,93 @@ impl Loader<(Id<Column>, usize)> for Modules {
     }
 }
 
+#[async_trait]
+impl Loader<Id<Card>> for Modules {
+    type Value = Card;
+    type Error = GqlError;
+
+    async fn load(&self, keys: &[Id<Card>]) -> Result<HashMap<Id<Card>, Self::Value>, Self::Error> {
+        println!(
+            "[Dataloader] CALLED DataLoader of Id<Card> -> Card: {:?}",
+            keys
+        );
+        let ids: Vec<_> = keys.iter().map(|i| i.clone().into()).collect();
+        let card_query: Box<dyn CardsQuery> = self.query().provide_gql_result()?;
+        let result = card_query.list_by_ids(&ids).await?;
+        Ok(result
+            .into_iter()
+            .map(|(k, v)| (k.to_string().into(), v.into()))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl Loader<FilteredCards> for Modules {
+    type Value = Vec<Card>;
+    type Error = GqlError;
+
+    async fn load(
+        &self,
+        keys: &[FilteredCards],
+    ) -> Result<HashMap<FilteredCards, Self::Value>, Self::Error> {
+        println!(
+            "[Dataloader] CALLED DataLoader of FilteredCards -> Vec<Card>: {:?}",
+            keys
+        );
+        // 条件ごとにまとめて、カラムのIDでまとめて取得する
+        let idmap: HashMap<_, _> = keys
+            .iter()
+            .map(|FilteredCards(cid, criteria)| (criteria.clone(), cid.clone()))
+            .into_group_map();
+        let card_query: Arc<dyn CardsQuery> = self.query().provide_arc_gql_result()?;
+        let futures_iterator: Vec<_> = idmap
+            .into_iter()
+            .map(|(criteria, cids)| list_by_column_ids(Arc::clone(&card_query), criteria, cids))
+            .collect();
+        let v = join_all(futures_iterator)
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(merge_all(v))
+    }
+}
+
 // id群からクエリを呼ぶ部分を抽出
 // inlineで書くと `async move` ブロックになる
 async fn list_by_orders(
     card_query: Arc<dyn CardsQuery>,
     id: Id<Column>,
+    archived: ArchivedFilter,
     indices: Vec<usize>,
-) -> Result<HashMap<(Id<Column>, usize), Card>> {
+) -> Result<HashMap<(Id<Column>, ArchivedFilter, usize), Card>> {
     let column_id = id.clone().into();
-    let hash_map = card_query.list_by_orders(&column_id, &indices).await?;
+    let hash_map = card_query
+        .list_by_orders(&column_id, &indices, archived)
+        .await?;
+    let hash_map = hash_map
+        .into_iter()
+        .map(|(u, v)| ((id.clone(), archived, u), v.into()))
+        .collect::<HashMap<_, _>>();
+    Ok(hash_map)
+}
+
+// 条件ごとにクエリを呼ぶ部分を抽出
+async fn list_by_column_ids(
+    card_query: Arc<dyn CardsQuery>,
+    criteria: CardCriteria,
+    ids: Vec<Id<Column>>,
+) -> Result<HashMap<FilteredCards, Vec<Card>>> {
+    let column_ids: Vec<_> = ids.iter().map(|id| id.clone().into()).collect();
+    let hash_map = card_query
+        .list_by_column_ids(&column_ids, &criteria)
+        .await?;
     let hash_map = hash_map
         .into_iter()
-        .map(|(u, v)| ((id.clone(), u), v.into()))
+        .map(|(cid, cards)| {
+            let key = FilteredCards(cid.to_string().into(), criteria.clone());
+            (key, cards.into_iter().map(Into::into).collect())
+        })
         .collect::<HashMap<_, _>>();
     Ok(hash_map)
 }
@@ -0,0 +1,38 @@
+use crate::model::{CardComments, Comment};
+use crate::provides::HasProviderGql;
+use crate::Modules;
+use async_graphql::{dataloader::Loader, Error as GqlError};
+use async_trait::async_trait;
+use domain_kanban::column::CardId;
+use query_resolver::CommentsQuery;
+use std::collections::HashMap;
+
+#[async_trait]
+impl Loader<CardComments> for Modules {
+    type Value = Vec<Comment>;
+    type Error = GqlError;
+
+    async fn load(
+        &self,
+        keys: &[CardComments],
+    ) -> Result<HashMap<CardComments, Self::Value>, Self::Error> {
+        println!(
+            "[Dataloader] CALLED DataLoader of CardComments -> Vec<Comment>: {:?}",
+            keys
+        );
+        // NOTE: 旧形式のカードID(c0など)にはコメントを付けられないので読み飛ばす
+        let ids: Vec<CardId> = keys
+            .iter()
+            .filter_map(|CardComments(id)| id.value().parse().ok())
+            .collect();
+        let comment_query: Box<dyn CommentsQuery> = self.query().provide_gql_result()?;
+        let result = comment_query.list_by_card_ids(&ids).await?;
+        Ok(result
+            .into_iter()
+            .map(|(k, v)| {
+                let key = CardComments(k.to_string().into());
+                (key, v.into_iter().map(Into::into).collect())
+            })
+            .collect())
+    }
+}
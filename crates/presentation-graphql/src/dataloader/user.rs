@@ -1,8 +1,14 @@
 use crate::scalar::Id;
 use crate::Modules;
-use crate::{model::User, provides::HasProviderGql};
+use crate::{
+    error::repository_error,
+    model::{User, UserVersion},
+    provides::HasProviderGql,
+};
 use async_graphql::{dataloader::Loader, Error as GqlError};
 use async_trait::async_trait;
+use domain_kanban::user::UserRepository;
+use domain_util::Version;
 use query_resolver::UsersQuery;
 use std::collections::HashMap;
 
@@ -25,3 +31,34 @@ impl Loader<Id<User>> for Modules {
             .collect())
     }
 }
+
+#[async_trait]
+impl Loader<UserVersion> for Modules {
+    type Value = Version;
+    type Error = GqlError;
+
+    async fn load(
+        &self,
+        keys: &[UserVersion],
+    ) -> Result<HashMap<UserVersion, Self::Value>, Self::Error> {
+        println!(
+            "[Dataloader] CALLED DataLoader of UserVersion -> Version: {:?}",
+            keys
+        );
+        let ids: Vec<_> = keys
+            .iter()
+            .map(|UserVersion(id)| id.clone().into())
+            .collect();
+        let user_repository: Box<dyn UserRepository> = self.repository().provide_gql_result()?;
+        let users = user_repository
+            .find_many(&ids)
+            .await
+            .map_err(repository_error)?;
+        // `find_many` は渡したIDの順に返す
+        Ok(keys
+            .iter()
+            .zip(users)
+            .filter_map(|(key, user)| Some((key.clone(), user?.version())))
+            .collect())
+    }
+}
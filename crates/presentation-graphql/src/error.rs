@@ -0,0 +1,64 @@
+// ドメイン・リポジトリのエラーを、クライアントが判別できるextensions付きのエラーにする
+use async_graphql::{Error as GqlError, ErrorExtensions};
+use domain_util::{InvariantError, RepositoryError};
+
+pub fn repository_error(e: RepositoryError) -> GqlError {
+    let message = e.to_string();
+    match e {
+        // 競合は最新のバージョンを取得しなおせば再試行できる
+        RepositoryError::Conflict { expected } => GqlError::new(message).extend_with(|_, ext| {
+            ext.set("code", "CONFLICT");
+            ext.set("retryable", true);
+            ext.set("expectedVersion", expected.value());
+        }),
+        RepositoryError::NotFound(_) => GqlError::new(message).extend_with(|_, ext| {
+            ext.set("code", "NOT_FOUND");
+            ext.set("retryable", false);
+        }),
+        // スロットリングやタイムアウトは時間をおけば再試行できる
+        RepositoryError::Transient(_) => GqlError::new(message).extend_with(|_, ext| {
+            ext.set("code", "SERVICE_UNAVAILABLE");
+            ext.set("retryable", true);
+        }),
+        // 反映されたかわからないので、最新を取得して確かめてから操作しなおす
+        RepositoryError::Indeterminate(_) => GqlError::new(message).extend_with(|_, ext| {
+            ext.set("code", "UNKNOWN_OUTCOME");
+            ext.set("retryable", false);
+        }),
+        // 保存されている内容の不整合はクライアントの入力では解決できない
+        RepositoryError::Invariant(_)
+        | RepositoryError::Serialization(_)
+        | RepositoryError::Other(_) => GqlError::new(message).extend_with(|_, ext| {
+            ext.set("code", "INTERNAL_SERVER_ERROR");
+            ext.set("retryable", false);
+        }),
+    }
+}
+
+// 指定したIDのものが保存されていない
+pub fn not_found_error(id: impl ToString) -> GqlError {
+    repository_error(RepositoryError::NotFound(id.to_string()))
+}
+
+pub fn invariant_error(e: InvariantError) -> GqlError {
+    GqlError::new(e.to_string()).extend_with(|_, ext| {
+        ext.set("code", "BAD_USER_INPUT");
+        ext.set("retryable", false);
+    })
+}
+
+// 権限のない操作
+pub fn forbidden_error(message: impl Into<String>) -> GqlError {
+    GqlError::new(message.into()).extend_with(|_, ext| {
+        ext.set("code", "FORBIDDEN");
+        ext.set("retryable", false);
+    })
+}
+
+// どの種類のノードのIDでもない
+pub fn invalid_node_id_error(id: &str) -> GqlError {
+    GqlError::new(format!("{} はノードのIDではありません", id)).extend_with(|_, ext| {
+        ext.set("code", "BAD_USER_INPUT");
+        ext.set("retryable", false);
+    })
+}
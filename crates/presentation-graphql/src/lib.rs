@@ -1,25 +1,31 @@
+mod current_user;
 mod dataloader;
+mod error;
 mod extensions;
 mod model;
+mod mutation;
 mod provides;
 mod scalar;
 mod validator;
 
 use async_graphql::{
-    dataloader::DataLoader, extensions::Logger, http::GraphiQLSource, EmptyMutation,
-    EmptySubscription, Request, Response, Schema, SchemaBuilder,
+    dataloader::DataLoader, extensions::Logger, http::GraphiQLSource, EmptySubscription, Request,
+    Response, Schema, SchemaBuilder,
 };
 use extensions::RestrictQueryAliases;
 use futures_util::future::BoxFuture;
 use model::QueryRoot as Query;
+use mutation::MutationRoot as Mutation;
+
+pub use current_user::CurrentUser;
 pub use provides::Modules;
 
-type SchemaType = Schema<Query, EmptyMutation, EmptySubscription>;
-type SchemaBuilderType = SchemaBuilder<Query, EmptyMutation, EmptySubscription>;
+type SchemaType = Schema<Query, Mutation, EmptySubscription>;
+type SchemaBuilderType = SchemaBuilder<Query, Mutation, EmptySubscription>;
 
 #[derive(Clone)]
 pub struct GraphQL {
-    schema: Schema<Query, EmptyMutation, EmptySubscription>,
+    schema: Schema<Query, Mutation, EmptySubscription>,
 }
 
 // Spawnerは利用するライブラリに依存しないよう、traitで受ける
@@ -61,7 +67,7 @@ impl GraphQL {
 }
 
 fn schema_builder() -> SchemaBuilderType {
-    Schema::build(Query, EmptyMutation, EmptySubscription)
+    Schema::build(Query, Mutation::default(), EmptySubscription)
 }
 
 fn schema() -> SchemaType {
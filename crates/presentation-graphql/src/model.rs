@@ -1,15 +1,29 @@
+mod activity;
 mod board;
 mod column;
+mod comment;
+mod filter;
+mod node;
+mod outbox;
+mod search;
 mod user;
 
+pub use self::activity::*;
 pub use self::board::*;
 pub use self::column::*;
+pub use self::comment::*;
+pub use self::filter::*;
+pub use self::node::*;
+pub use self::outbox::*;
+pub use self::search::*;
 pub use self::user::*;
+use crate::error::repository_error;
 use crate::provides::{ContextExt, HasProviderGql};
 use crate::validator;
 use crate::{provides::Modules, scalar::Id};
 use async_graphql::{Context, Object, Result as GqlResult};
-use query_resolver::UsersQuery;
+use domain_kanban::outbox::OutboxStore;
+use query_resolver::{BoardQuery, UsersQuery};
 
 pub struct QueryRoot;
 
@@ -26,15 +40,112 @@ impl QueryRoot {
         let r: Option<_> = loader.load_one(id).await?;
         Ok(r)
     }
-    async fn users_all<'a>(&self, ctx: &Context<'a>) -> GqlResult<Vec<User>> {
+    async fn board<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Board", "board")"#))] id: Id<
+            Board,
+        >,
+    ) -> GqlResult<Option<Board>> {
+        let loader = ctx.data_loader()?;
+        let r: Option<_> = loader.load_one(id).await?;
+        Ok(r)
+    }
+    async fn boards<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(default)] filter: BoardFilter,
+        order_by: Option<BoardOrder>,
+    ) -> GqlResult<Vec<Board>> {
+        let modules: &Modules = ctx.modules()?;
+        let board_query: Box<dyn BoardQuery> = modules.query().provide_gql_result()?;
+        let criteria = filter.into_criteria(order_by);
+        let result = board_query
+            .all(&criteria)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        Ok(result)
+    }
+    async fn column<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Column", "column")"#))] id: Id<
+            Column,
+        >,
+    ) -> GqlResult<Option<Column>> {
+        let loader = ctx.data_loader()?;
+        let r: Option<_> = loader.load_one(id).await?;
+        Ok(r)
+    }
+    async fn card<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Card", "card")"#))] id: Id<
+            Card,
+        >,
+    ) -> GqlResult<Option<Card>> {
+        let loader = ctx.data_loader()?;
+        let r: Option<_> = loader.load_one(id).await?;
+        Ok(r)
+    }
+    /// Relayのオブジェクト識別: IDの接頭辞で種類を判別して取得する
+    async fn node<'a>(&self, ctx: &Context<'a>, id: Id<Node>) -> GqlResult<Option<Node>> {
+        let mut result = load_nodes(ctx, &[id]).await?;
+        Ok(result.pop().flatten())
+    }
+    /// 指定した順に返す。見つからなかったIDはnullになる
+    async fn nodes<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(max_items = 100))] ids: Vec<Id<Node>>,
+    ) -> GqlResult<Vec<Option<Node>>> {
+        load_nodes(ctx, &ids).await
+    }
+    /// ボード・カラム・カードのタイトルやカードの説明を、関連度の高い順に返す
+    /// アーカイブされたものは含めない
+    async fn search<'a>(
+        &self,
+        ctx: &Context<'a>,
+        query: String,
+        #[graphql(default = 20, validator(maximum = 100))] first: usize,
+        after: Option<String>,
+    ) -> GqlResult<SearchResultConnection> {
+        search_connection(ctx, &query, first, after).await
+    }
+    async fn users_all<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(default)] filter: UserFilter,
+        order_by: Option<UserOrder>,
+    ) -> GqlResult<Vec<User>> {
         let modules: &Modules = ctx.modules()?;
         let user_query: Box<dyn UsersQuery> = modules.query().provide_gql_result()?;
+        let criteria = filter.into_criteria(order_by);
         let result = user_query
-            .all()
+            .all(&criteria)
             .await?
             .into_iter()
             .map(Into::into)
             .collect();
         Ok(result)
     }
+    /// 管理用: 配信を諦めたアウトボックスのメッセージを新しい順に返す
+    async fn outbox_dead_letters<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(default = 20)] first: usize,
+    ) -> GqlResult<Vec<DeadLetter>> {
+        let modules: &Modules = ctx.modules()?;
+        let outbox_store: Box<dyn OutboxStore> = modules.repository().provide_gql_result()?;
+        let result = outbox_store
+            .list_dead_letters(first)
+            .await
+            .map_err(repository_error)?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        Ok(result)
+    }
 }
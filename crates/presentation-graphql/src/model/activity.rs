@@ -0,0 +1,154 @@
+use crate::{
+    provides::{ContextExt, HasProviderGql},
+    scalar::Id,
+};
+use async_graphql::{
+    connection::{Connection, Edge},
+    ComplexObject, Context, Enum, Error as GqlError, Result as GqlResult, SimpleObject,
+};
+use chrono::{DateTime, Utc};
+use domain_kanban::{
+    activity::ActivityAction as DomainActivityAction, board::BoardId, user::UserId,
+};
+use query_resolver::{ActivityPage, ActivityQuery, ActivityView};
+
+use super::User;
+
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(complex)]
+pub struct Activity {
+    id: Id<Activity>,
+    #[graphql(skip)]
+    actor_id: Id<User>,
+    action: ActivityAction,
+    /// 操作対象のID
+    target_id: String,
+    before: Option<String>,
+    after: Option<String>,
+    occurred_at: DateTime<Utc>,
+}
+
+#[ComplexObject]
+impl Activity {
+    async fn actor<'ctx>(&self, ctx: &Context<'ctx>) -> GqlResult<Option<User>> {
+        let loader = ctx.data_loader()?;
+        let result = loader.load_one(self.actor_id.clone()).await?;
+        Ok(result)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum ActivityAction {
+    UserRenamed,
+    BoardRenamed,
+    ColumnsReordered,
+    CommentAdded,
+    CommentEdited,
+    CommentDeleted,
+    ChecklistItemAdded,
+    ChecklistItemToggled,
+    ChecklistItemReordered,
+    ChecklistItemDeleted,
+    Archived,
+    Restored,
+}
+
+impl From<DomainActivityAction> for ActivityAction {
+    fn from(value: DomainActivityAction) -> Self {
+        match value {
+            DomainActivityAction::UserRenamed => Self::UserRenamed,
+            DomainActivityAction::BoardRenamed => Self::BoardRenamed,
+            DomainActivityAction::ColumnsReordered => Self::ColumnsReordered,
+            DomainActivityAction::CommentAdded => Self::CommentAdded,
+            DomainActivityAction::CommentEdited => Self::CommentEdited,
+            DomainActivityAction::CommentDeleted => Self::CommentDeleted,
+            DomainActivityAction::ChecklistItemAdded => Self::ChecklistItemAdded,
+            DomainActivityAction::ChecklistItemToggled => Self::ChecklistItemToggled,
+            DomainActivityAction::ChecklistItemReordered => Self::ChecklistItemReordered,
+            DomainActivityAction::ChecklistItemDeleted => Self::ChecklistItemDeleted,
+            DomainActivityAction::Archived => Self::Archived,
+            DomainActivityAction::Restored => Self::Restored,
+        }
+    }
+}
+
+impl From<ActivityAction> for DomainActivityAction {
+    fn from(value: ActivityAction) -> Self {
+        match value {
+            ActivityAction::UserRenamed => Self::UserRenamed,
+            ActivityAction::BoardRenamed => Self::BoardRenamed,
+            ActivityAction::ColumnsReordered => Self::ColumnsReordered,
+            ActivityAction::CommentAdded => Self::CommentAdded,
+            ActivityAction::CommentEdited => Self::CommentEdited,
+            ActivityAction::CommentDeleted => Self::CommentDeleted,
+            ActivityAction::ChecklistItemAdded => Self::ChecklistItemAdded,
+            ActivityAction::ChecklistItemToggled => Self::ChecklistItemToggled,
+            ActivityAction::ChecklistItemReordered => Self::ChecklistItemReordered,
+            ActivityAction::ChecklistItemDeleted => Self::ChecklistItemDeleted,
+            ActivityAction::Archived => Self::Archived,
+            ActivityAction::Restored => Self::Restored,
+        }
+    }
+}
+
+impl TryFrom<ActivityView> for Activity {
+    type Error = GqlError;
+
+    fn try_from(value: ActivityView) -> Result<Self, Self::Error> {
+        let action: DomainActivityAction = value.action.parse()?;
+        let result = Self {
+            id: value.id.into(),
+            actor_id: value.actor_id.into(),
+            action: action.into(),
+            target_id: value.target_id,
+            before: value.before,
+            after: value.after,
+            occurred_at: value.occurred_at,
+        };
+        Ok(result)
+    }
+}
+
+pub type ActivityConnection = Connection<String, Activity>;
+
+pub(super) enum ActivityOwner<'a> {
+    Board(&'a BoardId),
+    Actor(&'a UserId),
+}
+
+// Board.activity, User.activityの共通部分
+// cursorはActivityのIDで、`after` より古いものを `first` 件返す
+pub(super) async fn activity_connection(
+    ctx: &Context<'_>,
+    owner: ActivityOwner<'_>,
+    first: usize,
+    after: Option<String>,
+    actions: Option<Vec<ActivityAction>>,
+) -> GqlResult<ActivityConnection> {
+    let activity_query: Box<dyn ActivityQuery> = ctx.modules()?.query().provide_gql_result()?;
+    let page = ActivityPage {
+        // 次のページがあるかを知るために1件多く取得する
+        first: first + 1,
+        after: after.map(|a| a.parse()).transpose()?,
+        actions: actions
+            .unwrap_or_default()
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+    };
+    let mut views = match owner {
+        ActivityOwner::Board(board_id) => activity_query.list_by_board(board_id, &page).await?,
+        ActivityOwner::Actor(actor_id) => activity_query.list_by_actor(actor_id, &page).await?,
+    };
+    let has_next_page = views.len() > first;
+    views.truncate(first);
+
+    let mut connection = Connection::new(page.after.is_some(), has_next_page);
+    for view in views {
+        let activity = Activity::try_from(view)?;
+        connection
+            .edges
+            .push(Edge::new(activity.id.value().to_owned(), activity));
+    }
+    Ok(connection)
+}
@@ -1,8 +1,12 @@
-use crate::{provides::ContextExt, scalar::Id};
+use crate::error::not_found_error;
+use crate::scalar::{version_to_int, Id};
+use crate::{dataloader::in_key_order, provides::ContextExt};
 use async_graphql::{ComplexObject, Context, Result as GqlResult, SimpleObject};
+use chrono::{DateTime, Utc};
 use query_resolver::BoardView;
 
-use super::{Column, User};
+use super::activity::{activity_connection, ActivityOwner};
+use super::{ActivityAction, ActivityConnection, Column, Node, User};
 
 #[derive(Debug, Clone, SimpleObject)]
 #[graphql(complex)]
@@ -13,6 +17,8 @@ pub struct Board {
     owner_id: Id<User>,
     #[graphql(skip)]
     column_ids: Vec<Id<Column>>,
+    /// アーカイブされていない場合はnull
+    archived_at: Option<DateTime<Utc>>,
 }
 
 impl Board {
@@ -21,34 +27,94 @@ impl Board {
         title: impl Into<String>,
         owner_id: impl Into<Id<User>>,
         column_ids: Vec<impl Into<Id<Column>>>,
+        archived_at: Option<DateTime<Utc>>,
     ) -> Self {
         Self {
             id: id.into(),
             title: title.into(),
             owner_id: owner_id.into(),
             column_ids: column_ids.into_iter().map(Into::into).collect(),
+            archived_at,
         }
     }
+
+    pub(crate) fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+
+    /// `Node` インターフェースの `id`
+    pub(crate) async fn node_id(&self, _ctx: &Context<'_>) -> GqlResult<Id<Node>> {
+        Ok(self.id.value().into())
+    }
 }
 
+// カラムの入っているボードを読み込むDataLoaderのキー
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ColumnBoard(pub Id<Column>);
+
+// ユーザーがメンバーになっているボードを読み込むDataLoaderのキー
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MemberBoards(pub Id<User>);
+
+// 書き込み側に保存されているボードのバージョンを読み込むDataLoaderのキー
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BoardVersion(pub Id<Board>);
+
 #[ComplexObject]
 impl Board {
+    /// 更新系のmutationに `expectedVersion` として渡すバージョン
+    /// 読み込みモデルは反映が遅れることがあるので、書き込み側に保存されているものを返す
+    async fn version<'ctx>(&self, ctx: &Context<'ctx>) -> GqlResult<i32> {
+        let loader = ctx.data_loader()?;
+        let version = loader
+            .load_one(BoardVersion(self.id.clone()))
+            .await?
+            .ok_or_else(|| not_found_error(self.id.value()))?;
+        version_to_int(version)
+    }
+
     async fn owner<'ctx>(&self, ctx: &Context<'ctx>) -> GqlResult<Option<User>> {
         let loader = ctx.data_loader()?;
         let result = loader.load_one(self.owner_id.clone()).await?;
         Ok(result)
     }
 
-    async fn columns<'ctx>(&self, ctx: &Context<'ctx>) -> GqlResult<Vec<Column>> {
+    /// ボード内の並び順に返す
+    async fn columns<'ctx>(
+        &self,
+        ctx: &Context<'ctx>,
+        #[graphql(default = false)] include_archived: bool,
+    ) -> GqlResult<Vec<Column>> {
         let loader = ctx.data_loader()?;
         let map = loader.load_many(self.column_ids.clone()).await?;
-        let result = map.values().cloned().collect();
+        let result = in_key_order(&self.column_ids, map)
+            .into_iter()
+            .filter(|c| include_archived || !c.is_archived())
+            .collect();
         Ok(result)
     }
+
+    /// ボードに対する操作の履歴を新しい順に返す
+    async fn activity<'ctx>(
+        &self,
+        ctx: &Context<'ctx>,
+        #[graphql(default = 20, validator(maximum = 100))] first: usize,
+        after: Option<String>,
+        actions: Option<Vec<ActivityAction>>,
+    ) -> GqlResult<ActivityConnection> {
+        let board_id = self.id.clone().into();
+        activity_connection(ctx, ActivityOwner::Board(&board_id), first, after, actions).await
+    }
 }
 
 impl From<BoardView> for Board {
     fn from(value: BoardView) -> Self {
-        Self::new(value.id, value.title, value.owner_id, value.column_ids)
+        Self::new(
+            value.id,
+            value.title,
+            value.owner_id,
+            value.column_ids,
+            value.archived_at,
+        )
     }
 }
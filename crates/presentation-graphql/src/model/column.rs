@@ -1,6 +1,10 @@
-use crate::{provides::ContextExt, scalar::Id};
+use crate::{dataloader::in_key_order, provides::ContextExt, scalar::Id};
 use async_graphql::{ComplexObject, Context, Result as GqlResult, SimpleObject};
-use query_resolver::{CardView, ColumnView};
+use chrono::{DateTime, Utc};
+use domain_kanban::column::ChecklistItem as DomainChecklistItem;
+use query_resolver::{ArchivedFilter, CardCriteria, CardView, ChecklistItemView, ColumnView};
+
+use super::{Board, CardComments, CardFilter, CardOrder, ColumnBoard, Comment, Node};
 
 #[derive(Debug, Clone, SimpleObject)]
 #[graphql(complex)]
@@ -9,6 +13,10 @@ pub struct Column {
     title: String,
     #[graphql(skip)]
     cards_cnt: usize,
+    #[graphql(skip)]
+    archived_cards_cnt: usize,
+    /// アーカイブされていない場合はnull
+    archived_at: Option<DateTime<Utc>>,
 }
 
 impl Column {
@@ -16,57 +24,199 @@ impl Column {
         id: impl Into<Id<Column>>,
         title: impl Into<String>,
         cards_cnt: impl Into<usize>,
+        archived_cards_cnt: impl Into<usize>,
+        archived_at: Option<DateTime<Utc>>,
     ) -> Self {
         Self {
             id: id.into(),
             title: title.into(),
             cards_cnt: cards_cnt.into(),
+            archived_cards_cnt: archived_cards_cnt.into(),
+            archived_at,
         }
     }
+
+    pub(crate) fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+
+    /// `Node` インターフェースの `id`
+    pub(crate) async fn node_id(&self, _ctx: &Context<'_>) -> GqlResult<Id<Node>> {
+        Ok(self.id.value().into())
+    }
 }
 
 #[ComplexObject]
 impl Column {
-    async fn cards<'a>(&self, ctx: &Context<'a>) -> GqlResult<Vec<Card>> {
+    /// `filter` と `orderBy` を指定しない場合はカラム内の並び順に返す
+    async fn cards<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(default = false)] include_archived: bool,
+        filter: Option<CardFilter>,
+        order_by: Option<CardOrder>,
+    ) -> GqlResult<Vec<Card>> {
         let loader = ctx.data_loader()?;
-        let ids: Vec<_> = (0..self.cards_cnt)
-            .map(|i| (self.id.clone(), i))
-            .collect();
-        let map = loader.load_many(ids).await?;
-        let result = Vec::from_iter(map.into_values());
+        if filter.is_some() || order_by.is_some() {
+            let criteria = filter
+                .unwrap_or_default()
+                .into_criteria(include_archived, order_by);
+            let result = loader
+                .load_one(FilteredCards(self.id.clone(), criteria))
+                .await?;
+            return Ok(result.unwrap_or_default());
+        }
+        let archived = ArchivedFilter::new(include_archived);
+        let cnt = if archived.includes_archived() {
+            self.cards_cnt + self.archived_cards_cnt
+        } else {
+            self.cards_cnt
+        };
+        let ids: Vec<_> = (0..cnt).map(|i| (self.id.clone(), archived, i)).collect();
+        let map = loader.load_many(ids.clone()).await?;
+        let result = in_key_order(&ids, map);
+        Ok(result)
+    }
+
+    /// どのボードにも入っていない場合はnull
+    async fn board<'a>(&self, ctx: &Context<'a>) -> GqlResult<Option<Board>> {
+        let loader = ctx.data_loader()?;
+        let result = loader.load_one(ColumnBoard(self.id.clone())).await?;
         Ok(result)
     }
 }
 
+// カラム内のカードを絞り込んで並べて読み込むDataLoaderのキー
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FilteredCards(pub Id<Column>, pub CardCriteria);
+
 #[derive(Debug, Clone, SimpleObject)]
+#[graphql(complex)]
 pub struct Card {
-    id: String,
+    id: Id<Card>,
     title: String,
     description: String,
+    #[graphql(skip)]
+    column_id: Id<Column>,
+    /// 表示順に並んだチェックリスト
+    checklist: Vec<ChecklistItem>,
+    /// アーカイブされていない場合はnull
+    archived_at: Option<DateTime<Utc>>,
+    /// 期限がない場合はnull
+    due_at: Option<DateTime<Utc>>,
 }
 
 impl Card {
     fn new(
-        id: impl Into<String>,
+        id: impl Into<Id<Card>>,
         title: impl Into<String>,
         description: impl Into<String>,
+        column_id: impl Into<Id<Column>>,
+        checklist: Vec<ChecklistItem>,
+        archived_at: Option<DateTime<Utc>>,
+        due_at: Option<DateTime<Utc>>,
     ) -> Self {
         Self {
             id: id.into(),
             title: title.into(),
             description: description.into(),
+            column_id: column_id.into(),
+            checklist,
+            archived_at,
+            due_at,
+        }
+    }
+
+    /// `Node` インターフェースの `id`
+    pub(crate) async fn node_id(&self, _ctx: &Context<'_>) -> GqlResult<Id<Node>> {
+        Ok(self.id.value().into())
+    }
+}
+
+#[ComplexObject]
+impl Card {
+    /// コメントを投稿された順に返す
+    async fn comments<'a>(&self, ctx: &Context<'a>) -> GqlResult<Vec<Comment>> {
+        let loader = ctx.data_loader()?;
+        let key = CardComments(self.id.clone());
+        let result = loader.load_one(key).await?;
+        Ok(result.unwrap_or_default())
+    }
+
+    async fn column<'a>(&self, ctx: &Context<'a>) -> GqlResult<Option<Column>> {
+        let loader = ctx.data_loader()?;
+        let result = loader.load_one(self.column_id.clone()).await?;
+        Ok(result)
+    }
+
+    /// チェックリストの完了数と項目数
+    async fn checklist_progress(&self) -> ChecklistProgress {
+        ChecklistProgress::from(self.checklist.as_slice())
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ChecklistItem {
+    text: String,
+    done: bool,
+}
+
+#[derive(Debug, Clone, Copy, SimpleObject)]
+pub struct ChecklistProgress {
+    done: usize,
+    total: usize,
+}
+
+impl From<&[ChecklistItem]> for ChecklistProgress {
+    fn from(value: &[ChecklistItem]) -> Self {
+        Self {
+            done: value.iter().filter(|item| item.done).count(),
+            total: value.len(),
         }
     }
 }
 
 impl From<ColumnView> for Column {
     fn from(value: ColumnView) -> Self {
-        Self::new(value.id, value.title, value.card_cnt)
+        Self::new(
+            value.id,
+            value.title,
+            value.card_cnt,
+            value.archived_card_cnt,
+            value.archived_at,
+        )
     }
 }
 
 impl From<CardView> for Card {
     fn from(value: CardView) -> Self {
-        Self::new(value.id, value.title, value.description)
+        let checklist = value.checklist.into_iter().map(Into::into).collect();
+        Self::new(
+            value.id,
+            value.title,
+            value.description,
+            value.column_id,
+            checklist,
+            value.archived_at,
+            value.due_at,
+        )
+    }
+}
+
+impl From<ChecklistItemView> for ChecklistItem {
+    fn from(value: ChecklistItemView) -> Self {
+        Self {
+            text: value.text,
+            done: value.done,
+        }
+    }
+}
+
+impl From<&DomainChecklistItem> for ChecklistItem {
+    fn from(value: &DomainChecklistItem) -> Self {
+        Self {
+            text: value.text().to_string(),
+            done: value.done(),
+        }
     }
 }
@@ -0,0 +1,57 @@
+use crate::{provides::ContextExt, scalar::Id};
+use async_graphql::{ComplexObject, Context, Result as GqlResult, SimpleObject};
+use chrono::{DateTime, Utc};
+use domain_kanban::comment::Comment as DomainComment;
+use query_resolver::CommentView;
+
+use super::{Card, User};
+
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(complex)]
+pub struct Comment {
+    id: Id<Comment>,
+    #[graphql(skip)]
+    author_id: Id<User>,
+    body: String,
+    created_at: DateTime<Utc>,
+    /// 一度も編集されていない場合はnull
+    edited_at: Option<DateTime<Utc>>,
+}
+
+#[ComplexObject]
+impl Comment {
+    async fn author<'ctx>(&self, ctx: &Context<'ctx>) -> GqlResult<Option<User>> {
+        let loader = ctx.data_loader()?;
+        let result = loader.load_one(self.author_id.clone()).await?;
+        Ok(result)
+    }
+}
+
+// カードのコメント一覧を読み込むDataLoaderのキー
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CardComments(pub Id<Card>);
+
+impl From<CommentView> for Comment {
+    fn from(value: CommentView) -> Self {
+        Self {
+            id: value.id.into(),
+            author_id: value.author_id.into(),
+            body: value.body,
+            created_at: value.created_at,
+            edited_at: value.edited_at,
+        }
+    }
+}
+
+// mutationの結果はリードモデルへの反映を待たずに返す
+impl From<&DomainComment> for Comment {
+    fn from(value: &DomainComment) -> Self {
+        Self {
+            id: value.id().to_string().into(),
+            author_id: value.author().to_string().into(),
+            body: value.body().to_string(),
+            created_at: *value.created_at(),
+            edited_at: value.edited_at().copied(),
+        }
+    }
+}
@@ -0,0 +1,238 @@
+// 一覧を返すフィールドの絞り込み条件と並び順
+use async_graphql::{Enum, InputObject};
+use chrono::{DateTime, Utc};
+use query_resolver::{
+    ArchivedFilter, BoardCriteria, BoardSort, BoardSortKey, CardCriteria, CardSort, CardSortKey,
+    CardStatus as QueryCardStatus, SortDirection as QuerySortDirection, UserCriteria, UserSort,
+    UserSortKey,
+};
+
+use crate::{scalar::Id, validator};
+
+use super::User;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Enum)]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// `usersAll` の絞り込み条件
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct UserFilter {
+    /// 名前がこの文字列で始まるもの。大文字・小文字を区別する
+    pub(crate) name_prefix: Option<String>,
+    /// メールアドレスの `@` より後ろがこのドメインのもの。大文字・小文字を区別しない
+    pub(crate) email_domain: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum UserOrderField {
+    Id,
+    Name,
+    Email,
+}
+
+/// 同じ値のものはIDの順に並べる
+#[derive(Debug, Clone, Copy, InputObject)]
+pub struct UserOrder {
+    pub(crate) field: UserOrderField,
+    #[graphql(default)]
+    pub(crate) direction: SortDirection,
+}
+
+/// `boards` の絞り込み条件
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct BoardFilter {
+    /// アーカイブされたボードも含める
+    #[graphql(default = false)]
+    pub(crate) include_archived: bool,
+    /// タイトルにこの文字列を含むもの。大文字・小文字を区別する
+    pub(crate) title_contains: Option<String>,
+    #[graphql(validator(custom = r#"validator::IdValidator::new("User", "user")"#))]
+    pub(crate) owner_id: Option<Id<User>>,
+    /// このユーザーがメンバーになっているもの。所有しているだけのボードは含めない
+    #[graphql(validator(custom = r#"validator::IdValidator::new("User", "user")"#))]
+    pub(crate) member_id: Option<Id<User>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum BoardOrderField {
+    Id,
+    Title,
+}
+
+/// 同じ値のものはIDの順に並べる
+#[derive(Debug, Clone, Copy, InputObject)]
+pub struct BoardOrder {
+    pub(crate) field: BoardOrderField,
+    #[graphql(default)]
+    pub(crate) direction: SortDirection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum CardStatus {
+    Active,
+    Archived,
+}
+
+/// `Column.cards` の絞り込み条件
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct CardFilter {
+    /// タイトルにこの文字列を含むもの。大文字・小文字を区別する
+    pub(crate) title_contains: Option<String>,
+    /// 指定した場合は `includeArchived` より優先する
+    pub(crate) status: Option<CardStatus>,
+    /// 期限がこの日時より前のもの。期限のないカードは含めない
+    pub(crate) due_before: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum CardOrderField {
+    /// カラム内の並び順
+    Position,
+    Title,
+}
+
+/// 同じ値のものはカラム内の並び順に並べる
+#[derive(Debug, Clone, Copy, InputObject)]
+pub struct CardOrder {
+    pub(crate) field: CardOrderField,
+    #[graphql(default)]
+    pub(crate) direction: SortDirection,
+}
+
+impl UserFilter {
+    pub(crate) fn into_criteria(self, order_by: Option<UserOrder>) -> UserCriteria {
+        UserCriteria {
+            name_prefix: self.name_prefix,
+            email_domain: self.email_domain,
+            sort: order_by.map(Into::into).unwrap_or_default(),
+        }
+    }
+}
+
+impl BoardFilter {
+    pub(crate) fn into_criteria(self, order_by: Option<BoardOrder>) -> BoardCriteria {
+        BoardCriteria {
+            title_contains: self.title_contains,
+            owner_id: self.owner_id.map(Into::into),
+            member_id: self.member_id.map(Into::into),
+            archived: ArchivedFilter::new(self.include_archived),
+            sort: order_by.map(Into::into).unwrap_or_default(),
+        }
+    }
+}
+
+impl CardFilter {
+    /// `status` を指定していない場合は `include_archived` で絞り込む
+    pub(crate) fn into_criteria(
+        self,
+        include_archived: bool,
+        order_by: Option<CardOrder>,
+    ) -> CardCriteria {
+        let status = match self.status {
+            Some(status) => Some(status.into()),
+            None if include_archived => None,
+            None => Some(QueryCardStatus::Active),
+        };
+        CardCriteria {
+            title_contains: self.title_contains,
+            status,
+            due_before: self.due_before,
+            sort: order_by.map(Into::into).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<SortDirection> for QuerySortDirection {
+    fn from(value: SortDirection) -> Self {
+        match value {
+            SortDirection::Asc => Self::Asc,
+            SortDirection::Desc => Self::Desc,
+        }
+    }
+}
+
+impl From<UserOrder> for UserSort {
+    fn from(value: UserOrder) -> Self {
+        let key = match value.field {
+            UserOrderField::Id => UserSortKey::Id,
+            UserOrderField::Name => UserSortKey::Name,
+            UserOrderField::Email => UserSortKey::Email,
+        };
+        Self {
+            key,
+            direction: value.direction.into(),
+        }
+    }
+}
+
+impl From<BoardOrder> for BoardSort {
+    fn from(value: BoardOrder) -> Self {
+        let key = match value.field {
+            BoardOrderField::Id => BoardSortKey::Id,
+            BoardOrderField::Title => BoardSortKey::Title,
+        };
+        Self {
+            key,
+            direction: value.direction.into(),
+        }
+    }
+}
+
+impl From<CardStatus> for QueryCardStatus {
+    fn from(value: CardStatus) -> Self {
+        match value {
+            CardStatus::Active => Self::Active,
+            CardStatus::Archived => Self::Archived,
+        }
+    }
+}
+
+impl From<CardOrder> for CardSort {
+    fn from(value: CardOrder) -> Self {
+        let key = match value.field {
+            CardOrderField::Position => CardSortKey::Position,
+            CardOrderField::Title => CardSortKey::Title,
+        };
+        Self {
+            key,
+            direction: value.direction.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_card_filter_status_defaults_to_include_archived() {
+        let active = CardFilter::default().into_criteria(false, None);
+        let all = CardFilter::default().into_criteria(true, None);
+        let archived = CardFilter {
+            status: Some(CardStatus::Archived),
+            ..Default::default()
+        }
+        .into_criteria(false, None);
+
+        assert_eq!(active.status, Some(QueryCardStatus::Active));
+        assert_eq!(all.status, None);
+        assert_eq!(archived.status, Some(QueryCardStatus::Archived));
+    }
+
+    #[test]
+    fn test_card_filter_keeps_due_before() {
+        let due_before: DateTime<Utc> = "2026-10-31T00:00:00Z".parse().unwrap();
+
+        let criteria = CardFilter {
+            due_before: Some(due_before),
+            ..Default::default()
+        }
+        .into_criteria(true, None);
+
+        assert_eq!(criteria.due_before, Some(due_before));
+    }
+}
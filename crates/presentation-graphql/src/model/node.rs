@@ -0,0 +1,139 @@
+// Relayのオブジェクト識別のためのNodeインターフェース
+// IDは `<entity_type>-<ULID>` の形式なので、接頭辞を見ればどの種類のノードかわかる
+use std::collections::HashMap;
+
+use async_graphql::{Context, Interface, Result as GqlResult};
+use domain_kanban::{
+    board::{Board as DomainBoard, BoardId},
+    column::{Card as DomainCard, CardId, Column as DomainColumn, ColumnId},
+    user::{User as DomainUser, UserId},
+};
+use domain_util::Entity;
+use futures_util::try_join;
+
+use super::{Board, Card, Column, User};
+use crate::{error::invalid_node_id_error, provides::ContextExt, scalar::Id};
+
+// NOTE: 各オブジェクトの `id` は型ごとに別のIdになっているので、`node_id` でNodeのIdにそろえる
+#[derive(Interface)]
+#[graphql(field(name = "id", method = "node_id", ty = "Id<Node>"))]
+pub enum Node {
+    User(User),
+    Board(Board),
+    Column(Column),
+    Card(Card),
+}
+
+/// 接頭辞で種類を判別したノードのID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum NodeId {
+    User(Id<User>),
+    Board(Id<Board>),
+    Column(Id<Column>),
+    Card(Id<Card>),
+}
+
+impl NodeId {
+    /// 接頭辞が種類のどれかと一致し、そのあとがULIDになっている場合のみ受け付ける
+    pub(crate) fn parse(id: &str) -> GqlResult<Self> {
+        let (entity_type, _) = id
+            .split_once('-')
+            .ok_or_else(|| invalid_node_id_error(id))?;
+        let valid = |ok: bool| ok.then_some(()).ok_or_else(|| invalid_node_id_error(id));
+        let node_id = match entity_type {
+            t if t == DomainUser::entity_type() => {
+                valid(id.parse::<UserId>().is_ok())?;
+                Self::User(id.into())
+            }
+            t if t == DomainBoard::entity_type() => {
+                valid(id.parse::<BoardId>().is_ok())?;
+                Self::Board(id.into())
+            }
+            t if t == DomainColumn::entity_type() => {
+                valid(id.parse::<ColumnId>().is_ok())?;
+                Self::Column(id.into())
+            }
+            t if t == DomainCard::entity_type() => {
+                valid(id.parse::<CardId>().is_ok())?;
+                Self::Card(id.into())
+            }
+            _ => return Err(invalid_node_id_error(id)),
+        };
+        Ok(node_id)
+    }
+}
+
+/// 種類ごとのDataLoaderで読み込む。見つからなかったIDはnullにする
+pub(crate) async fn load_nodes(
+    ctx: &Context<'_>,
+    ids: &[Id<Node>],
+) -> GqlResult<Vec<Option<Node>>> {
+    let node_ids = ids
+        .iter()
+        .map(|id| NodeId::parse(id.value()))
+        .collect::<GqlResult<Vec<_>>>()?;
+
+    let mut user_ids = vec![];
+    let mut board_ids = vec![];
+    let mut column_ids = vec![];
+    let mut card_ids = vec![];
+    for node_id in &node_ids {
+        match node_id {
+            NodeId::User(id) => user_ids.push(id.clone()),
+            NodeId::Board(id) => board_ids.push(id.clone()),
+            NodeId::Column(id) => column_ids.push(id.clone()),
+            NodeId::Card(id) => card_ids.push(id.clone()),
+        }
+    }
+
+    // 種類ごとに1回ずつ、まとめて読み込む
+    let loader = ctx.data_loader()?;
+    let (users, boards, columns, cards): (
+        HashMap<_, User>,
+        HashMap<_, Board>,
+        HashMap<_, Column>,
+        HashMap<_, Card>,
+    ) = try_join!(
+        loader.load_many(user_ids),
+        loader.load_many(board_ids),
+        loader.load_many(column_ids),
+        loader.load_many(card_ids),
+    )?;
+
+    // 同じIDが複数回指定されることもあるので、取り出さずに複製する
+    let result = node_ids
+        .into_iter()
+        .map(|node_id| match node_id {
+            NodeId::User(id) => users.get(&id).cloned().map(Node::User),
+            NodeId::Board(id) => boards.get(&id).cloned().map(Node::Board),
+            NodeId::Column(id) => columns.get(&id).cloned().map(Node::Column),
+            NodeId::Card(id) => cards.get(&id).cloned().map(Node::Card),
+        })
+        .collect();
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dispatches_on_entity_type() {
+        let id = "column-01HBCCGK3MAWDZKS74M1DEJQ54";
+
+        let result = NodeId::parse(id).unwrap();
+
+        assert_eq!(result, NodeId::Column(id.into()));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_or_malformed_ids() {
+        for id in [
+            "comment-01HBCCGK3MAWDZKS74M1DEJQ54",
+            "card-c0",
+            "01HBCCGK3MAWDZKS74M1DEJQ54",
+        ] {
+            assert!(NodeId::parse(id).is_err(), "{}", id);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use domain_kanban::outbox::DeadLetter as DomainDeadLetter;
+
+/// 配信を諦めたアウトボックスのメッセージ
+#[derive(Debug, Clone, SimpleObject)]
+pub struct DeadLetter {
+    id: String,
+    topic: String,
+    payload: String,
+    attempts: u32,
+    error: String,
+    created_at: DateTime<Utc>,
+    dead_at: DateTime<Utc>,
+}
+
+impl From<DomainDeadLetter> for DeadLetter {
+    fn from(value: DomainDeadLetter) -> Self {
+        let message = value.message();
+        Self {
+            id: message.id().to_string(),
+            topic: message.topic().to_owned(),
+            payload: message.payload().to_owned(),
+            attempts: message.attempts(),
+            error: value.error().to_owned(),
+            created_at: *message.created_at(),
+            dead_at: *value.dead_at(),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use async_graphql::{
+    connection::{Connection, Edge, EmptyFields},
+    Context, Result as GqlResult, SimpleObject, Union,
+};
+use futures_util::try_join;
+use query_resolver::{SearchHitKind, SearchPage, SearchQuery};
+
+use super::{Board, Card, Column};
+use crate::{
+    provides::{ContextExt, HasProviderGql},
+    scalar::Id,
+};
+
+#[derive(Debug, Clone, Union)]
+pub enum SearchResult {
+    Board(Board),
+    Column(Column),
+    Card(Card),
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SearchEdgeFields {
+    /// 関連度。大きいほど検索語に近い
+    score: f64,
+}
+
+pub type SearchResultConnection = Connection<String, SearchResult, EmptyFields, SearchEdgeFields>;
+
+// cursorは関連度の順で先頭から何番目か
+pub(super) async fn search_connection(
+    ctx: &Context<'_>,
+    query: &str,
+    first: usize,
+    after: Option<String>,
+) -> GqlResult<SearchResultConnection> {
+    let offset = match after {
+        Some(after) => after.parse::<usize>()? + 1,
+        None => 0,
+    };
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Connection::new(offset > 0, false));
+    }
+
+    let search_query: Box<dyn SearchQuery> = ctx.modules()?.query().provide_gql_result()?;
+    let page = SearchPage {
+        query: query.to_owned(),
+        // 次のページがあるかを知るために1件多く取得する
+        first: first + 1,
+        offset,
+    };
+    let mut hits = search_query.search(&page).await?;
+    let has_next_page = hits.len() > first;
+    hits.truncate(first);
+
+    let mut board_ids = vec![];
+    let mut column_ids = vec![];
+    let mut card_ids = vec![];
+    for hit in &hits {
+        match hit.kind {
+            SearchHitKind::Board => board_ids.push(Id::from(hit.id.as_str())),
+            SearchHitKind::Column => column_ids.push(Id::from(hit.id.as_str())),
+            SearchHitKind::Card => card_ids.push(Id::from(hit.id.as_str())),
+        }
+    }
+    let loader = ctx.data_loader()?;
+    let (boards, columns, cards): (
+        HashMap<Id<Board>, Board>,
+        HashMap<Id<Column>, Column>,
+        HashMap<Id<Card>, Card>,
+    ) = try_join!(
+        loader.load_many(board_ids),
+        loader.load_many(column_ids),
+        loader.load_many(card_ids),
+    )?;
+
+    let mut connection = Connection::new(offset > 0, has_next_page);
+    for (i, hit) in hits.into_iter().enumerate() {
+        let node = match hit.kind {
+            SearchHitKind::Board => boards
+                .get(&Id::<Board>::from(hit.id.as_str()))
+                .cloned()
+                .map(SearchResult::Board),
+            SearchHitKind::Column => columns
+                .get(&Id::<Column>::from(hit.id.as_str()))
+                .cloned()
+                .map(SearchResult::Column),
+            SearchHitKind::Card => cards
+                .get(&Id::<Card>::from(hit.id.as_str()))
+                .cloned()
+                .map(SearchResult::Card),
+        };
+        // 検索したあとに消えたものは飛ばす
+        if let Some(node) = node {
+            let fields = SearchEdgeFields { score: hit.score };
+            let cursor = (offset + i).to_string();
+            connection
+                .edges
+                .push(Edge::with_additional_fields(cursor, node, fields));
+        }
+    }
+    Ok(connection)
+}
@@ -1,9 +1,12 @@
-use crate::{provides::ContextExt, scalar::Id};
+use crate::error::not_found_error;
+use crate::scalar::{version_to_int, Id};
+use crate::{dataloader::in_key_order, provides::ContextExt};
 use async_graphql::{ComplexObject, Context, Result as GqlResult, SimpleObject};
 use domain_util::{Entity, Identifier};
 use query_resolver::UserView;
 
-use super::Board;
+use super::activity::{activity_connection, ActivityOwner};
+use super::{ActivityAction, ActivityConnection, Board, MemberBoards, Node};
 
 #[derive(Debug, Clone, SimpleObject)]
 #[graphql(complex)]
@@ -29,17 +32,72 @@ impl User {
             owned_board_ids: owned_board_ids.into_iter().map(Into::into).collect(),
         }
     }
+
+    /// `Node` インターフェースの `id`
+    pub(crate) async fn node_id(&self, _ctx: &Context<'_>) -> GqlResult<Id<Node>> {
+        Ok(self.id.value().into())
+    }
 }
 
+// 書き込み側に保存されているユーザーのバージョンを読み込むDataLoaderのキー
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UserVersion(pub Id<User>);
+
 #[ComplexObject]
 impl User {
-    async fn owned_boards<'a>(&self, ctx: &Context<'a>) -> GqlResult<Vec<Board>> {
+    /// 更新系のmutationに `expectedVersion` として渡すバージョン
+    /// 読み込みモデルは反映が遅れることがあるので、書き込み側に保存されているものを返す
+    async fn version<'a>(&self, ctx: &Context<'a>) -> GqlResult<i32> {
+        let loader = ctx.data_loader()?;
+        let version = loader
+            .load_one(UserVersion(self.id.clone()))
+            .await?
+            .ok_or_else(|| not_found_error(self.id.value()))?;
+        version_to_int(version)
+    }
+
+    async fn owned_boards<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(default = false)] include_archived: bool,
+    ) -> GqlResult<Vec<Board>> {
         println!("CALLED Resolver: User.owned_boards(): load_many");
         let loader = ctx.data_loader()?;
         let map = loader.load_many(self.owned_board_ids.clone()).await?;
-        let result = Vec::from_iter(map.into_values());
+        let result = in_key_order(&self.owned_board_ids, map)
+            .into_iter()
+            .filter(|b| include_archived || !b.is_archived())
+            .collect();
         Ok(result)
     }
+
+    /// メンバーになっているボード。所有しているだけのボードは含めない
+    async fn member_boards<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(default = false)] include_archived: bool,
+    ) -> GqlResult<Vec<Board>> {
+        let loader = ctx.data_loader()?;
+        let boards = loader.load_one(MemberBoards(self.id.clone())).await?;
+        let result = boards
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|b| include_archived || !b.is_archived())
+            .collect();
+        Ok(result)
+    }
+
+    /// ユーザーが行った操作の履歴を新しい順に返す
+    async fn activity<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(default = 20, validator(maximum = 100))] first: usize,
+        after: Option<String>,
+        actions: Option<Vec<ActivityAction>>,
+    ) -> GqlResult<ActivityConnection> {
+        let actor_id = self.id.clone().into();
+        activity_connection(ctx, ActivityOwner::Actor(&actor_id), first, after, actions).await
+    }
 }
 
 impl From<UserView> for User {
@@ -0,0 +1,43 @@
+mod archive;
+mod board;
+mod checklist;
+mod comment;
+mod user;
+
+use crate::error::{not_found_error, repository_error};
+use crate::provides::{ContextExt, HasProviderGql};
+use async_graphql::{Context, MergedObject, Result as GqlResult};
+use domain_kanban::activity::Activity;
+use domain_kanban::board::BoardId;
+use domain_kanban::column::ColumnId;
+use domain_kanban::outbox::OutboxMessage;
+use query_resolver::BoardQuery;
+
+#[derive(Default, MergedObject)]
+pub struct MutationRoot(
+    user::UserMutation,
+    board::BoardMutation,
+    comment::CommentMutation,
+    checklist::ChecklistMutation,
+    archive::ArchiveMutation,
+);
+
+// 更新系のmutationで行った操作の記録を、集約と同じトランザクションでアウトボックスに保存する
+// 記録はアウトボックスのリレーが非同期に追記する
+fn activity_outbox(activity: &Activity) -> GqlResult<Vec<OutboxMessage>> {
+    let message = OutboxMessage::activity(activity).map_err(repository_error)?;
+    Ok(vec![message])
+}
+
+// カラムの中の操作をボードのフィードに載せるため、カラムの入っているボードを探す
+// NOTE: リードモデルから探すので、反映される前のカラムは見つからない
+async fn column_board_id(ctx: &Context<'_>, column_id: &ColumnId) -> GqlResult<BoardId> {
+    let board_query: Box<dyn BoardQuery> = ctx.modules()?.query().provide_gql_result()?;
+
+    let board = board_query
+        .list_by_column_ids(&[column_id.clone()])
+        .await?
+        .remove(column_id)
+        .ok_or_else(|| not_found_error(column_id))?;
+    Ok(board.id.parse()?)
+}
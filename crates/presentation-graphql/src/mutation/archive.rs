@@ -0,0 +1,209 @@
+use super::activity_outbox;
+use crate::error::{invariant_error, not_found_error, repository_error};
+use crate::model::{Board, Card, Column};
+use crate::provides::{ContextExt, HasProviderGql};
+use crate::scalar::{version_from_int, version_to_int, Id};
+use crate::validator;
+use async_graphql::{Context, Object, Result as GqlResult, SimpleObject};
+use chrono::{DateTime, Utc};
+use domain_kanban::activity::{Activity, ActivityAction};
+use domain_kanban::board::BoardRepository;
+use domain_kanban::column::{CardId, ColumnRepository};
+use domain_util::RepositoryError;
+
+#[derive(Default)]
+pub struct ArchiveMutation;
+
+// アーカイブしたものは保持期間を過ぎると完全に削除される
+#[Object]
+impl ArchiveMutation {
+    /// ボードをアーカイブする
+    async fn archive_board<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Board", "board")"#))] id: Id<
+            Board,
+        >,
+        expected_version: i32,
+    ) -> GqlResult<ArchivePayload> {
+        set_board_archived(ctx, id, expected_version, true).await
+    }
+
+    /// アーカイブしたボードを元に戻す
+    async fn restore_board<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Board", "board")"#))] id: Id<
+            Board,
+        >,
+        expected_version: i32,
+    ) -> GqlResult<ArchivePayload> {
+        set_board_archived(ctx, id, expected_version, false).await
+    }
+
+    /// カラムをアーカイブする
+    async fn archive_column<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Column", "column")"#))] id: Id<
+            Column,
+        >,
+        expected_version: i32,
+    ) -> GqlResult<ArchivePayload> {
+        set_column_archived(ctx, id, None, expected_version, true).await
+    }
+
+    /// アーカイブしたカラムを元に戻す
+    async fn restore_column<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Column", "column")"#))] id: Id<
+            Column,
+        >,
+        expected_version: i32,
+    ) -> GqlResult<ArchivePayload> {
+        set_column_archived(ctx, id, None, expected_version, false).await
+    }
+
+    /// カードをアーカイブする。`expectedVersion` はカラムのバージョン
+    async fn archive_card<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Column", "column")"#))]
+        column_id: Id<Column>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Card", "card")"#))]
+        card_id: Id<Card>,
+        expected_version: i32,
+    ) -> GqlResult<ArchivePayload> {
+        set_column_archived(ctx, column_id, Some(card_id), expected_version, true).await
+    }
+
+    /// アーカイブしたカードを元に戻す。`expectedVersion` はカラムのバージョン
+    async fn restore_card<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Column", "column")"#))]
+        column_id: Id<Column>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Card", "card")"#))]
+        card_id: Id<Card>,
+        expected_version: i32,
+    ) -> GqlResult<ArchivePayload> {
+        set_column_archived(ctx, column_id, Some(card_id), expected_version, false).await
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ArchivePayload {
+    id: String,
+    /// 元に戻した場合はnull
+    archived_at: Option<DateTime<Utc>>,
+    /// 保存後のバージョン。カードの場合はカラムのバージョン
+    version: i32,
+}
+
+fn activity_action(archive: bool) -> ActivityAction {
+    if archive {
+        ActivityAction::Archived
+    } else {
+        ActivityAction::Restored
+    }
+}
+
+async fn set_board_archived(
+    ctx: &Context<'_>,
+    id: Id<Board>,
+    expected_version: i32,
+    archive: bool,
+) -> GqlResult<ArchivePayload> {
+    let actor = ctx.current_user()?.clone();
+    let board_repository: Box<dyn BoardRepository> =
+        ctx.modules()?.repository().provide_gql_result()?;
+
+    let mut board = board_repository
+        .find_by_id(&id.value().parse()?)
+        .await
+        .map_err(repository_error)?
+        .ok_or_else(|| not_found_error(id.value()))?;
+    let expected = version_from_int(expected_version)?;
+    if board.version() != expected {
+        return Err(repository_error(RepositoryError::Conflict { expected }));
+    }
+    if archive {
+        board.archive().map_err(invariant_error)?;
+    } else {
+        board.restore().map_err(invariant_error)?;
+    }
+    let activity =
+        Activity::new(actor, activity_action(archive), board.id()).on_board(board.id().clone());
+
+    let payload = ArchivePayload {
+        id: id.value().to_owned(),
+        archived_at: board.archived_at().copied(),
+        version: version_to_int(expected.next())?,
+    };
+    board_repository
+        .save_with_outbox(board, activity_outbox(&activity)?)
+        .await
+        .map_err(repository_error)?;
+    Ok(payload)
+}
+
+// `card_id` を指定した場合はカラム内のカードを対象にする
+async fn set_column_archived(
+    ctx: &Context<'_>,
+    column_id: Id<Column>,
+    card_id: Option<Id<Card>>,
+    expected_version: i32,
+    archive: bool,
+) -> GqlResult<ArchivePayload> {
+    let actor = ctx.current_user()?.clone();
+    let column_repository: Box<dyn ColumnRepository> =
+        ctx.modules()?.repository().provide_gql_result()?;
+
+    let mut column = column_repository
+        .find_by_id(&column_id.value().parse()?)
+        .await
+        .map_err(repository_error)?
+        .ok_or_else(|| not_found_error(column_id.value()))?;
+    let expected = version_from_int(expected_version)?;
+    if column.version() != expected {
+        return Err(repository_error(RepositoryError::Conflict { expected }));
+    }
+    let (activity, payload) = match card_id {
+        Some(card_id) => {
+            let card_id: CardId = card_id.value().parse()?;
+            let card = column.find_card_mut(&card_id).map_err(invariant_error)?;
+            if archive {
+                card.archive().map_err(invariant_error)?;
+            } else {
+                card.restore().map_err(invariant_error)?;
+            }
+            let activity = Activity::new(actor, activity_action(archive), card.id());
+            let payload = ArchivePayload {
+                id: card.id().to_string(),
+                archived_at: card.archived_at().copied(),
+                version: version_to_int(expected.next())?,
+            };
+            (activity, payload)
+        }
+        None => {
+            if archive {
+                column.archive().map_err(invariant_error)?;
+            } else {
+                column.restore().map_err(invariant_error)?;
+            }
+            let activity = Activity::new(actor, activity_action(archive), column.id());
+            let payload = ArchivePayload {
+                id: column.id().to_string(),
+                archived_at: column.archived_at().copied(),
+                version: version_to_int(expected.next())?,
+            };
+            (activity, payload)
+        }
+    };
+    column_repository
+        .save_with_outbox(column, activity_outbox(&activity)?)
+        .await
+        .map_err(repository_error)?;
+    Ok(payload)
+}
@@ -0,0 +1,130 @@
+use super::activity_outbox;
+use crate::error::{invariant_error, not_found_error, repository_error};
+use crate::model::{Board, Column};
+use crate::provides::{ContextExt, HasProviderGql};
+use crate::scalar::{version_from_int, version_to_int, Id};
+use crate::validator;
+use async_graphql::{Context, Object, Result as GqlResult, SimpleObject};
+use domain_kanban::activity::{Activity, ActivityAction};
+use domain_kanban::board::{BoardRepository, BoardTitle};
+use domain_kanban::column::ColumnId;
+use domain_util::RepositoryError;
+use itertools::Itertools;
+
+#[derive(Default)]
+pub struct BoardMutation;
+
+#[Object]
+impl BoardMutation {
+    /// ボードのタイトルを変更する
+    /// `expectedVersion` が保存済みのバージョンと異なる場合は `CONFLICT` エラーになる
+    async fn rename_board<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Board", "board")"#))] id: Id<
+            Board,
+        >,
+        title: String,
+        expected_version: i32,
+    ) -> GqlResult<RenameBoardPayload> {
+        let actor = ctx.current_user()?.clone();
+        let board_repository: Box<dyn BoardRepository> =
+            ctx.modules()?.repository().provide_gql_result()?;
+        let title = BoardTitle::new(title).map_err(invariant_error)?;
+
+        let mut board = board_repository
+            .find_by_id(&id.clone().into())
+            .await
+            .map_err(repository_error)?
+            .ok_or_else(|| not_found_error(id.value()))?;
+        let expected = version_from_int(expected_version)?;
+        if board.version() != expected {
+            return Err(repository_error(RepositoryError::Conflict { expected }));
+        }
+        let before = board.title().to_string();
+        board.update_title(title);
+        let activity = Activity::new(actor, ActivityAction::BoardRenamed, board.id())
+            .on_board(board.id().clone())
+            .with_change(before, board.title().to_string());
+
+        let payload = RenameBoardPayload {
+            id,
+            title: board.title().to_string(),
+            version: version_to_int(expected.next())?,
+        };
+        board_repository
+            .save_with_outbox(board, activity_outbox(&activity)?)
+            .await
+            .map_err(repository_error)?;
+        Ok(payload)
+    }
+
+    /// ボードのカラムを `columnIds` の順に並べ替える
+    /// `columnIds` にはボードのカラムをすべて1回ずつ指定する
+    async fn reorder_columns<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Board", "board")"#))]
+        board_id: Id<Board>,
+        #[graphql(validator(list, custom = r#"validator::IdValidator::new("Column", "column")"#))]
+        column_ids: Vec<Id<Column>>,
+        expected_version: i32,
+    ) -> GqlResult<ReorderColumnsPayload> {
+        let actor = ctx.current_user()?.clone();
+        let board_repository: Box<dyn BoardRepository> =
+            ctx.modules()?.repository().provide_gql_result()?;
+        let column_ids = column_ids.into_iter().map(Into::into).collect();
+
+        let mut board = board_repository
+            .find_by_id(&board_id.clone().into())
+            .await
+            .map_err(repository_error)?
+            .ok_or_else(|| not_found_error(board_id.value()))?;
+        let expected = version_from_int(expected_version)?;
+        if board.version() != expected {
+            return Err(repository_error(RepositoryError::Conflict { expected }));
+        }
+        let before = join_ids(board.column_ids());
+        board.reorder_columns(column_ids).map_err(invariant_error)?;
+        let activity = Activity::new(actor, ActivityAction::ColumnsReordered, board.id())
+            .on_board(board.id().clone())
+            .with_change(before, join_ids(board.column_ids()));
+
+        let payload = ReorderColumnsPayload {
+            id: board_id,
+            column_ids: board
+                .column_ids()
+                .iter()
+                .map(|id| id.to_string().into())
+                .collect(),
+            version: version_to_int(expected.next())?,
+        };
+        board_repository
+            .save_with_outbox(board, activity_outbox(&activity)?)
+            .await
+            .map_err(repository_error)?;
+        Ok(payload)
+    }
+}
+
+// 操作の記録に残すため、並び順をカンマ区切りにする
+fn join_ids(column_ids: &[ColumnId]) -> String {
+    column_ids.iter().map(ToString::to_string).join(",")
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ReorderColumnsPayload {
+    id: Id<Board>,
+    /// 並べ替えたあとのカラムのID
+    column_ids: Vec<Id<Column>>,
+    /// 保存後のバージョン
+    version: i32,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct RenameBoardPayload {
+    id: Id<Board>,
+    title: String,
+    /// 保存後のバージョン
+    version: i32,
+}
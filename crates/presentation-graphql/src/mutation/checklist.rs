@@ -0,0 +1,173 @@
+use super::{activity_outbox, column_board_id};
+use crate::error::{invariant_error, not_found_error, repository_error};
+use crate::model::{Card, ChecklistItem, ChecklistProgress, Column};
+use crate::provides::{ContextExt, HasProviderGql};
+use crate::scalar::{version_from_int, version_to_int, Id};
+use crate::validator;
+use async_graphql::{Context, Object, Result as GqlResult, SimpleObject};
+use domain_kanban::activity::{Activity, ActivityAction};
+use domain_kanban::column::{CardId, Checklist, ChecklistItemText, ColumnId, ColumnRepository};
+use domain_util::{InvariantResult, RepositoryError};
+
+#[derive(Default)]
+pub struct ChecklistMutation;
+
+// チェックリストはカラムの一部として保存するので、いずれもカラムの `expectedVersion` を受け取る
+#[Object]
+impl ChecklistMutation {
+    /// チェックリストの末尾に項目を追加する
+    async fn add_checklist_item<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Column", "column")"#))]
+        column_id: Id<Column>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Card", "card")"#))]
+        card_id: Id<Card>,
+        text: String,
+        expected_version: i32,
+    ) -> GqlResult<ChecklistPayload> {
+        let text = ChecklistItemText::new(text).map_err(invariant_error)?;
+        update_checklist(
+            ctx,
+            column_id,
+            card_id,
+            expected_version,
+            ActivityAction::ChecklistItemAdded,
+            |checklist| checklist.add(text).map(|_| None),
+        )
+        .await
+    }
+
+    /// `index` 番目の項目の完了状態を反転する
+    async fn toggle_checklist_item<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Column", "column")"#))]
+        column_id: Id<Column>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Card", "card")"#))]
+        card_id: Id<Card>,
+        index: usize,
+        expected_version: i32,
+    ) -> GqlResult<ChecklistPayload> {
+        update_checklist(
+            ctx,
+            column_id,
+            card_id,
+            expected_version,
+            ActivityAction::ChecklistItemToggled,
+            |checklist| {
+                let done = checklist.toggle(index)?;
+                Ok(Some(((!done).to_string(), done.to_string())))
+            },
+        )
+        .await
+    }
+
+    /// `srcIndex` 番目の項目を `dstIndex` 番目に移動する
+    async fn reorder_checklist_item<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Column", "column")"#))]
+        column_id: Id<Column>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Card", "card")"#))]
+        card_id: Id<Card>,
+        src_index: usize,
+        dst_index: usize,
+        expected_version: i32,
+    ) -> GqlResult<ChecklistPayload> {
+        update_checklist(
+            ctx,
+            column_id,
+            card_id,
+            expected_version,
+            ActivityAction::ChecklistItemReordered,
+            |checklist| {
+                checklist.reorder(src_index, dst_index)?;
+                Ok(Some((src_index.to_string(), dst_index.to_string())))
+            },
+        )
+        .await
+    }
+
+    /// `index` 番目の項目を削除する
+    async fn delete_checklist_item<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Column", "column")"#))]
+        column_id: Id<Column>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Card", "card")"#))]
+        card_id: Id<Card>,
+        index: usize,
+        expected_version: i32,
+    ) -> GqlResult<ChecklistPayload> {
+        update_checklist(
+            ctx,
+            column_id,
+            card_id,
+            expected_version,
+            ActivityAction::ChecklistItemDeleted,
+            |checklist| checklist.remove(index).map(|_| None),
+        )
+        .await
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ChecklistPayload {
+    card_id: Id<Card>,
+    checklist: Vec<ChecklistItem>,
+    checklist_progress: ChecklistProgress,
+    /// 保存後のカラムのバージョン
+    version: i32,
+}
+
+// 各mutationの共通部分
+// カラムを読み込んでカードのチェックリストを更新し、操作の記録と一緒に保存する
+// `update` は記録に残す変更前後の値を返す
+async fn update_checklist(
+    ctx: &Context<'_>,
+    column_id: Id<Column>,
+    card_id: Id<Card>,
+    expected_version: i32,
+    action: ActivityAction,
+    update: impl FnOnce(&mut Checklist) -> InvariantResult<Option<(String, String)>>,
+) -> GqlResult<ChecklistPayload> {
+    let actor = ctx.current_user()?.clone();
+    let column_repository: Box<dyn ColumnRepository> =
+        ctx.modules()?.repository().provide_gql_result()?;
+    let parsed_card_id: CardId = card_id.value().parse()?;
+    let parsed_column_id: ColumnId = column_id.value().parse()?;
+    let board_id = column_board_id(ctx, &parsed_column_id).await?;
+
+    let mut column = column_repository
+        .find_by_id(&parsed_column_id)
+        .await
+        .map_err(repository_error)?
+        .ok_or_else(|| not_found_error(column_id.value()))?;
+    let expected = version_from_int(expected_version)?;
+    if column.version() != expected {
+        return Err(repository_error(RepositoryError::Conflict { expected }));
+    }
+    let card = column
+        .find_card_mut(&parsed_card_id)
+        .map_err(invariant_error)?;
+    let change = update(card.checklist_mut()).map_err(invariant_error)?;
+    let activity = Activity::new(actor, action, card.id()).on_board(board_id);
+    let activity = match change {
+        Some((before, after)) => activity.with_change(before, after),
+        None => activity,
+    };
+
+    let checklist: Vec<ChecklistItem> = card.checklist().items().iter().map(Into::into).collect();
+    let payload = ChecklistPayload {
+        card_id,
+        checklist_progress: ChecklistProgress::from(checklist.as_slice()),
+        checklist,
+        version: version_to_int(expected.next())?,
+    };
+    column_repository
+        .save_with_outbox(column, activity_outbox(&activity)?)
+        .await
+        .map_err(repository_error)?;
+    Ok(payload)
+}
@@ -0,0 +1,131 @@
+use super::{activity_outbox, column_board_id};
+use crate::error::{forbidden_error, invariant_error, not_found_error, repository_error};
+use crate::model::{Card, Comment};
+use crate::provides::{ContextExt, HasProviderGql};
+use crate::scalar::Id;
+use crate::validator;
+use async_graphql::{Context, Object, Result as GqlResult};
+use domain_kanban::activity::{Activity, ActivityAction};
+use domain_kanban::board::BoardId;
+use domain_kanban::column::{CardId, ColumnId};
+use domain_kanban::comment::{Comment as DomainComment, CommentBody, CommentId, CommentRepository};
+use domain_kanban::user::UserId;
+use query_resolver::CardsQuery;
+
+#[derive(Default)]
+pub struct CommentMutation;
+
+#[Object]
+impl CommentMutation {
+    /// カードにコメントを投稿する
+    /// カードがない場合は `NOT_FOUND` エラーになる
+    async fn add_comment<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Card", "card")"#))]
+        card_id: Id<Card>,
+        body: String,
+    ) -> GqlResult<Comment> {
+        let author = ctx.current_user()?.clone();
+        let comment_repository: Box<dyn CommentRepository> =
+            ctx.modules()?.repository().provide_gql_result()?;
+        let card_id: CardId = card_id.value().parse()?;
+        let body = CommentBody::new(body).map_err(invariant_error)?;
+        let board_id = card_board_id(ctx, &card_id).await?;
+
+        let comment = DomainComment::new(card_id, author.clone(), body);
+        let activity =
+            Activity::new(author, ActivityAction::CommentAdded, comment.id()).on_board(board_id);
+
+        let result = Comment::from(&comment);
+        comment_repository
+            .save_with_outbox(comment, activity_outbox(&activity)?)
+            .await
+            .map_err(repository_error)?;
+        Ok(result)
+    }
+
+    /// コメントを編集する。投稿者のみ編集できる
+    async fn edit_comment<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Comment", "comment")"#))]
+        id: Id<Comment>,
+        body: String,
+    ) -> GqlResult<Comment> {
+        let editor = ctx.current_user()?.clone();
+        let comment_repository: Box<dyn CommentRepository> =
+            ctx.modules()?.repository().provide_gql_result()?;
+        let body = CommentBody::new(body).map_err(invariant_error)?;
+
+        let mut comment = find_own_comment(comment_repository.as_ref(), &id, &editor).await?;
+        let board_id = card_board_id(ctx, comment.card_id()).await?;
+        let before = comment.body().to_string();
+        comment.edit(&editor, body).map_err(invariant_error)?;
+        let activity = Activity::new(editor, ActivityAction::CommentEdited, comment.id())
+            .on_board(board_id)
+            .with_change(before, comment.body().to_string());
+
+        let result = Comment::from(&comment);
+        comment_repository
+            .save_with_outbox(comment, activity_outbox(&activity)?)
+            .await
+            .map_err(repository_error)?;
+        Ok(result)
+    }
+
+    /// コメントを削除し、削除したコメントのIDを返す。投稿者のみ削除できる
+    async fn delete_comment<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("Comment", "comment")"#))]
+        id: Id<Comment>,
+    ) -> GqlResult<Id<Comment>> {
+        let actor = ctx.current_user()?.clone();
+        let comment_repository: Box<dyn CommentRepository> =
+            ctx.modules()?.repository().provide_gql_result()?;
+
+        let comment = find_own_comment(comment_repository.as_ref(), &id, &actor).await?;
+        let board_id = card_board_id(ctx, comment.card_id()).await?;
+        let activity =
+            Activity::new(actor, ActivityAction::CommentDeleted, comment.id()).on_board(board_id);
+
+        comment_repository
+            .delete_with_outbox(comment.id(), activity_outbox(&activity)?)
+            .await
+            .map_err(repository_error)?;
+        Ok(id)
+    }
+}
+
+// 投稿者以外の操作は `FORBIDDEN` にする
+async fn find_own_comment(
+    comment_repository: &dyn CommentRepository,
+    id: &Id<Comment>,
+    user_id: &UserId,
+) -> GqlResult<DomainComment> {
+    let comment_id: CommentId = id.value().parse()?;
+    let comment = comment_repository
+        .find_by_id(&comment_id)
+        .await
+        .map_err(repository_error)?
+        .ok_or_else(|| not_found_error(&comment_id))?;
+    if !comment.is_written_by(user_id) {
+        return Err(forbidden_error("コメントを変更できるのは投稿者のみです"));
+    }
+    Ok(comment)
+}
+
+// コメントの操作をボードのフィードに載せるため、カードの入っているボードを探す
+// NOTE: リードモデルから探すので、反映される前のカードは見つからない
+async fn card_board_id(ctx: &Context<'_>, card_id: &CardId) -> GqlResult<BoardId> {
+    let cards_query: Box<dyn CardsQuery> = ctx.modules()?.query().provide_gql_result()?;
+
+    let card = cards_query
+        .list_by_ids(&[card_id.clone()])
+        .await?
+        .remove(card_id)
+        .ok_or_else(|| not_found_error(card_id))?;
+    let column_id: ColumnId = card.column_id.parse()?;
+    column_board_id(ctx, &column_id).await
+}
@@ -0,0 +1,66 @@
+use super::activity_outbox;
+use crate::error::{invariant_error, not_found_error, repository_error};
+use crate::model::User;
+use crate::provides::{ContextExt, HasProviderGql};
+use crate::scalar::{version_from_int, version_to_int, Id};
+use crate::validator;
+use async_graphql::{Context, Object, Result as GqlResult, SimpleObject};
+use domain_kanban::activity::{Activity, ActivityAction};
+use domain_kanban::user::{UserName, UserRepository};
+use domain_util::RepositoryError;
+
+#[derive(Default)]
+pub struct UserMutation;
+
+#[Object]
+impl UserMutation {
+    /// ユーザー名を変更する
+    /// `expectedVersion` が保存済みのバージョンと異なる場合は `CONFLICT` エラーになる
+    async fn rename_user<'a>(
+        &self,
+        ctx: &Context<'a>,
+        #[graphql(validator(custom = r#"validator::IdValidator::new("User", "user")"#))] id: Id<
+            User,
+        >,
+        name: String,
+        expected_version: i32,
+    ) -> GqlResult<RenameUserPayload> {
+        let actor = ctx.current_user()?.clone();
+        let user_repository: Box<dyn UserRepository> =
+            ctx.modules()?.repository().provide_gql_result()?;
+        let name = UserName::new(name).map_err(invariant_error)?;
+
+        let mut user = user_repository
+            .find_by_id(&id.clone().into())
+            .await
+            .map_err(repository_error)?
+            .ok_or_else(|| not_found_error(id.value()))?;
+        let expected = version_from_int(expected_version)?;
+        if user.version() != expected {
+            return Err(repository_error(RepositoryError::Conflict { expected }));
+        }
+        let before = user.user_name().to_string();
+        user.update_name(name);
+        let activity = Activity::new(actor, ActivityAction::UserRenamed, user.user_id())
+            .with_change(before, user.user_name().to_string());
+
+        let payload = RenameUserPayload {
+            id,
+            name: user.user_name().to_string(),
+            version: version_to_int(expected.next())?,
+        };
+        user_repository
+            .save_with_outbox(user, activity_outbox(&activity)?)
+            .await
+            .map_err(repository_error)?;
+        Ok(payload)
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct RenameUserPayload {
+    id: Id<User>,
+    name: String,
+    /// 保存後のバージョン
+    version: i32,
+}
@@ -1,16 +1,30 @@
 use std::{any::type_name, sync::Arc};
 
-use async_graphql::{dataloader::DataLoader, Context, Error as GqlError};
-use domain_kanban::user::UserRepository;
-use query_resolver::{BoardQuery, CardsQuery, ColumnsQuery, UsersQuery};
+use async_graphql::{dataloader::DataLoader, Context, Error as GqlError, ErrorExtensions};
+use domain_kanban::{
+    activity::ActivityRepository,
+    board::BoardRepository,
+    column::ColumnRepository,
+    comment::CommentRepository,
+    outbox::OutboxStore,
+    user::{UserId, UserRepository},
+};
+use query_resolver::{
+    ActivityQuery, BoardQuery, CardsQuery, ColumnsQuery, CommentsQuery, SearchQuery, UsersQuery,
+};
 use shaku::HasProvider;
 
+use crate::current_user::CurrentUser;
+
 pub trait QueryProvider
 where
     Self: HasProvider<dyn UsersQuery>,
     Self: HasProvider<dyn BoardQuery>,
     Self: HasProvider<dyn ColumnsQuery>,
     Self: HasProvider<dyn CardsQuery>,
+    Self: HasProvider<dyn ActivityQuery>,
+    Self: HasProvider<dyn CommentsQuery>,
+    Self: HasProvider<dyn SearchQuery>,
 {
 }
 impl<T> QueryProvider for T
@@ -19,16 +33,33 @@ where
     Self: HasProvider<dyn BoardQuery>,
     Self: HasProvider<dyn ColumnsQuery>,
     Self: HasProvider<dyn CardsQuery>,
+    Self: HasProvider<dyn ActivityQuery>,
+    Self: HasProvider<dyn CommentsQuery>,
+    Self: HasProvider<dyn SearchQuery>,
 {
 }
 
 pub trait RepositoryProvider
 where
     Self: HasProvider<dyn UserRepository>,
+    Self: HasProvider<dyn BoardRepository>,
+    Self: HasProvider<dyn ColumnRepository>,
+    Self: HasProvider<dyn ActivityRepository>,
+    Self: HasProvider<dyn CommentRepository>,
+    Self: HasProvider<dyn OutboxStore>,
 {
 }
 
-impl<T> RepositoryProvider for T where Self: HasProvider<dyn UserRepository> {}
+impl<T> RepositoryProvider for T
+where
+    Self: HasProvider<dyn UserRepository>,
+    Self: HasProvider<dyn BoardRepository>,
+    Self: HasProvider<dyn ColumnRepository>,
+    Self: HasProvider<dyn ActivityRepository>,
+    Self: HasProvider<dyn CommentRepository>,
+    Self: HasProvider<dyn OutboxStore>,
+{
+}
 
 pub struct Modules {
     pub query_providers: Box<dyn QueryProvider + Send + Sync>,
@@ -61,12 +92,24 @@ pub trait ContextExt {
     fn modules(&self) -> Result<&Modules, GqlError> {
         Ok(self.data_loader()?.loader())
     }
+    // CurrentUserはリクエストごとに設定されるので、ない場合はエラーにする
+    fn current_user(&self) -> Result<&UserId, GqlError>;
 }
 
 impl<'ctx> ContextExt for Context<'ctx> {
     fn data_loader(&self) -> Result<&DataLoader<Modules>, GqlError> {
         self.data()
     }
+    fn current_user(&self) -> Result<&UserId, GqlError> {
+        self.data_opt::<CurrentUser>()
+            .map(CurrentUser::user_id)
+            .ok_or_else(|| {
+                GqlError::new("ユーザーが指定されていません").extend_with(|_, ext| {
+                    ext.set("code", "UNAUTHENTICATED");
+                    ext.set("retryable", false);
+                })
+            })
+    }
 }
 
 // shakuのErrorをasync-graphqlにあわせる
@@ -3,6 +3,8 @@ use async_graphql::InputValueError;
 use async_graphql::Scalar;
 use async_graphql::ScalarType;
 use async_graphql::Value;
+use async_graphql::{Error as GqlError, ErrorExtensions};
+use domain_util::Version;
 use std::fmt::Debug as DebugTrait;
 use std::hash::Hash;
 use std::marker::PhantomData;
@@ -79,3 +81,48 @@ impl<T: Send + Sync> ScalarType for Id<T> {
         Value::from(v)
     }
 }
+
+// GraphQLのIntは32bitなので、バージョンはi32にしてやりとりする
+pub(crate) fn version_to_int(version: Version) -> Result<i32, GqlError> {
+    i32::try_from(version.value())
+        .map_err(|_| GqlError::new(format!("バージョン {} はIntで表せません", version)))
+}
+
+// `expectedVersion` に負の値が渡されたらクライアントの入力エラーにする
+pub(crate) fn version_from_int(value: i32) -> Result<Version, GqlError> {
+    u64::try_from(value).map(Version::new).map_err(|_| {
+        GqlError::new(format!("{} はバージョンではありません", value)).extend_with(|_, ext| {
+            ext.set("code", "BAD_USER_INPUT");
+            ext.set("retryable", false);
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_int_round_trip() {
+        // Arrange
+        let version = Version::new(42);
+
+        // Act
+        let value = version_to_int(version).unwrap();
+
+        // Assert
+        assert_eq!(value, 42);
+        assert_eq!(version_from_int(value).unwrap(), version);
+    }
+
+    #[test]
+    fn test_version_out_of_int_range() {
+        // Act
+        let too_large = version_to_int(Version::new(i32::MAX as u64 + 1));
+        let negative = version_from_int(-1);
+
+        // Assert
+        assert!(too_large.is_err());
+        assert!(negative.is_err());
+    }
+}
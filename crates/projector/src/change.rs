@@ -0,0 +1,97 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use domain_kanban::{
+    board::{Board, BoardId},
+    column::{Column, ColumnId},
+    comment::{Comment, CommentId},
+    user::{User, UserId},
+};
+
+/// リードモデルに反映する書き込み側のテーブル
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProjectedTable {
+    Users,
+    Boards,
+    Columns,
+    Comments,
+}
+
+impl ProjectedTable {
+    pub const ALL: [Self; 4] = [Self::Users, Self::Boards, Self::Columns, Self::Comments];
+
+    pub fn table_name(&self) -> &'static str {
+        match self {
+            Self::Users => "users",
+            Self::Boards => "boards",
+            Self::Columns => "columns",
+            Self::Comments => "comments",
+        }
+    }
+}
+
+/// 書き込み側で保存・削除された集約
+/// 保存された場合は、保存後の集約全体を持つ
+#[derive(Debug, Clone)]
+pub enum Change {
+    UserSaved(User),
+    UserRemoved(UserId),
+    BoardSaved(Board),
+    BoardRemoved(BoardId),
+    ColumnSaved(Column),
+    ColumnRemoved(ColumnId),
+    CommentSaved(Comment),
+    CommentRemoved(CommentId),
+}
+
+impl Change {
+    /// 削除されたアイテムのIDから作る
+    pub fn removed(table: ProjectedTable, id: &str) -> Result<Self> {
+        let to_error = |e| anyhow!("invalid id in {}: {}", table.table_name(), e);
+        let result = match table {
+            ProjectedTable::Users => Self::UserRemoved(UserId::from_str(id).map_err(to_error)?),
+            ProjectedTable::Boards => Self::BoardRemoved(BoardId::from_str(id).map_err(to_error)?),
+            ProjectedTable::Columns => {
+                Self::ColumnRemoved(ColumnId::from_str(id).map_err(to_error)?)
+            }
+            ProjectedTable::Comments => {
+                Self::CommentRemoved(CommentId::from_str(id).map_err(to_error)?)
+            }
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ChangeRecord {
+    /// シャード内での位置
+    pub sequence_number: String,
+    /// 書き込み側で変更された日時
+    pub occurred_at: DateTime<Utc>,
+    pub change: Change,
+}
+
+/// シャードごとの反映済みの位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// 書き込み側のテーブル名
+    pub stream: String,
+    pub shard_id: String,
+    /// Noneの場合は、まだ何も反映していない
+    pub sequence_number: Option<String>,
+    /// 閉じたシャードを最後まで読み終えたか
+    pub finished: bool,
+}
+
+/// 1つのシャードから読み込んだ変更
+#[derive(Debug, Clone)]
+pub struct ShardBatch {
+    pub stream: String,
+    pub shard_id: String,
+    pub records: Vec<ChangeRecord>,
+    /// シャードが閉じていて、これ以上の変更がない
+    pub finished: bool,
+    /// 読み込みきれなかった変更がある
+    pub has_more: bool,
+}
@@ -0,0 +1,431 @@
+// 書き込み側(DynamoDB)の変更を、リードモデル(Postgres)に反映する
+// DynamoDB Streamsをシャードごとに読み込み、反映した位置をチェックポイントとしてPostgresに記録するので、
+// 停止しても続きから再開できる
+mod change;
+mod sink;
+mod stream;
+
+pub use change::*;
+pub use sink::{ApplyReport, PostgresSink};
+pub use stream::DynamoDbStreamFeed;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+
+/// 書き込み側の変更を読み込む
+#[async_trait]
+pub trait ChangeFeed: Send + Sync {
+    /// チェックポイントの続きから、シャードごとに変更を読み込む
+    async fn poll(&self, checkpoints: &[Checkpoint]) -> Result<Vec<ShardBatch>>;
+}
+
+/// リードモデルに反映した変更を受け取る(キャッシュを捨てるなど)
+/// 同じ変更を2回受け取ることがある
+pub trait ChangeObserver: Send + Sync {
+    fn applied(&self, change: &Change);
+}
+
+/// 1回分の反映結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectionReport {
+    pub applied: usize,
+    /// 依存先がまだ反映されていないため、途中で止めたシャードの数
+    pub deferred_shards: usize,
+    /// 反映できていない変更のうち、最も古いものからの経過時間
+    /// 読み込んだ変更をすべて反映し終えている場合は0
+    pub lag: Duration,
+}
+
+pub struct Projector {
+    feed: Box<dyn ChangeFeed>,
+    sink: PostgresSink,
+    observers: Vec<Box<dyn ChangeObserver>>,
+}
+
+impl Projector {
+    pub fn new(feed: Box<dyn ChangeFeed>, sink: PostgresSink) -> Self {
+        Self {
+            feed,
+            sink,
+            observers: vec![],
+        }
+    }
+
+    pub fn with_observer(mut self, observer: Box<dyn ChangeObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// 読み込めた変更を反映する。同じ変更を2回反映しても結果は変わらない
+    pub async fn project_once(&self) -> Result<ProjectionReport> {
+        let checkpoints = self.sink.checkpoints().await?;
+        let batches = self.feed.poll(&checkpoints).await?;
+
+        let now = Utc::now();
+        let mut report = ProjectionReport::default();
+        for batch in &batches {
+            let applied = self.sink.apply(batch).await?;
+            // 反映したのは先頭から `applied` 件
+            for record in &batch.records[..applied.applied] {
+                for observer in &self.observers {
+                    observer.applied(&record.change);
+                }
+            }
+            report.applied += applied.applied;
+            let pending_since = match applied.deferred_since {
+                Some(deferred_since) => {
+                    report.deferred_shards += 1;
+                    Some(deferred_since)
+                }
+                // NOTE: 続きがある場合は、最後に反映した変更の時点まで追いついている
+                None if batch.has_more => applied.last_event_at,
+                None => None,
+            };
+            if let Some(pending_since) = pending_since {
+                report.lag = report.lag.max(now - pending_since);
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use chrono::DateTime;
+    use domain_kanban::{
+        board::{Board, BoardId, BoardTitle},
+        column::{CardTitle, Column, ColumnTitle},
+        comment::{Comment, CommentBody},
+        user::{Email, User, UserId, UserName},
+    };
+    use domain_util::Version;
+    use sqlx::PgPool;
+    use testcontainers_modules::{
+        postgres::Postgres,
+        testcontainers::{runners::AsyncRunner, ContainerAsync, RunnableImage},
+    };
+
+    use super::*;
+
+    const STREAM: &str = "users";
+    const SHARD_ID: &str = "shard-0";
+
+    /// 1つのシャードの変更を、チェックポイントの続きから `limit` 件ずつ返す
+    struct FakeFeed {
+        records: Vec<ChangeRecord>,
+        limit: usize,
+    }
+
+    #[async_trait]
+    impl ChangeFeed for FakeFeed {
+        async fn poll(&self, checkpoints: &[Checkpoint]) -> Result<Vec<ShardBatch>> {
+            let after = checkpoints
+                .iter()
+                .find(|c| c.stream == STREAM && c.shard_id == SHARD_ID)
+                .and_then(|c| c.sequence_number.clone());
+            let pending: Vec<_> = self
+                .records
+                .iter()
+                .filter(|r| after.as_ref().map_or(true, |a| &r.sequence_number > a))
+                .cloned()
+                .collect();
+            let batch = ShardBatch {
+                stream: STREAM.to_owned(),
+                shard_id: SHARD_ID.to_owned(),
+                has_more: pending.len() > self.limit,
+                records: pending.into_iter().take(self.limit).collect(),
+                finished: false,
+            };
+            Ok(vec![batch])
+        }
+    }
+
+    struct CountingObserver(Arc<AtomicUsize>);
+
+    impl ChangeObserver for CountingObserver {
+        fn applied(&self, _change: &Change) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    async fn arrange_pool() -> (ContainerAsync<Postgres>, PgPool) {
+        let container = RunnableImage::from(Postgres::default()).start().await;
+        let host_ip = container.get_host().await;
+        let host_port = container.get_host_port_ipv4(5432).await;
+        let uri = format!("postgres://postgres:postgres@{host_ip}:{host_port}/postgres");
+        let pool = PgPool::connect(&uri).await.unwrap();
+        sqlx::migrate!("../migrate/migrations")
+            .run(&pool)
+            .await
+            .unwrap();
+        (container, pool)
+    }
+
+    fn record(sequence_number: usize, occurred_at: DateTime<Utc>, change: Change) -> ChangeRecord {
+        ChangeRecord {
+            sequence_number: format!("{sequence_number:05}"),
+            occurred_at,
+            change,
+        }
+    }
+
+    fn user(id: &UserId, name: &str, version: u64) -> User {
+        User::new_with_version(
+            id.clone(),
+            UserName::new(name.to_owned()).unwrap(),
+            Email::new(format!("{name}@example.com")).unwrap(),
+            Version::new(version),
+        )
+        .unwrap()
+    }
+
+    async fn user_name(pool: &PgPool, id: &UserId) -> Option<String> {
+        sqlx::query_scalar!("select name from users where id = $1", id.to_string())
+            .fetch_optional(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_project_once_replay_is_idempotent() {
+        // Arrange
+        let (_c, pool) = arrange_pool().await;
+        let user_id = UserId::gen();
+        let column = Column::new(ColumnTitle::new("todo".to_owned()))
+            .add_card(CardTitle::new("first".to_owned()));
+        let comment = Comment::new(
+            column.cards()[0].id().clone(),
+            user_id.clone(),
+            CommentBody::new("いいね".to_owned()).unwrap(),
+        );
+        let now = Utc::now();
+        let feed = FakeFeed {
+            records: vec![
+                record(1, now, Change::UserSaved(user(&user_id, "alice", 1))),
+                record(2, now, Change::ColumnSaved(column.clone())),
+                record(3, now, Change::CommentSaved(comment)),
+            ],
+            limit: 10,
+        };
+        let projector = Projector::new(Box::new(feed), PostgresSink::new(pool.clone()));
+
+        // Act
+        let first = projector.project_once().await.unwrap();
+        // チェックポイントを失った場合は、同じ変更を最初から反映しなおす
+        sqlx::query!("delete from projection_checkpoints")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let replayed = projector.project_once().await.unwrap();
+
+        // Assert
+        assert_eq!(first.applied, 3);
+        assert_eq!(replayed.applied, 3);
+        assert_eq!(user_name(&pool, &user_id).await.as_deref(), Some("alice"));
+        let counts = sqlx::query!(
+            r#"
+            select
+                (select count(*) from users) as "users!",
+                (select count(*) from cards) as "cards!",
+                (select count(*) from comments) as "comments!"
+            "#
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!((counts.users, counts.cards, counts.comments), (1, 1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_project_once_resumes_from_checkpoint() {
+        // Arrange
+        let (_c, pool) = arrange_pool().await;
+        let alice = UserId::gen();
+        let bob = UserId::gen();
+        let now = Utc::now();
+        let feed = FakeFeed {
+            records: vec![
+                record(1, now, Change::UserSaved(user(&alice, "alice", 1))),
+                record(2, now, Change::UserSaved(user(&bob, "bob", 1))),
+            ],
+            limit: 1,
+        };
+        let applied = Arc::new(AtomicUsize::new(0));
+        let projector = Projector::new(Box::new(feed), PostgresSink::new(pool.clone()))
+            .with_observer(Box::new(CountingObserver(Arc::clone(&applied))));
+
+        // Act
+        let first = projector.project_once().await.unwrap();
+        let bob_after_first = user_name(&pool, &bob).await;
+        let second = projector.project_once().await.unwrap();
+        let third = projector.project_once().await.unwrap();
+
+        // Assert
+        assert_eq!(first.applied, 1);
+        assert!(bob_after_first.is_none());
+        assert_eq!(second.applied, 1);
+        assert_eq!(third.applied, 0);
+        assert_eq!(applied.load(Ordering::SeqCst), 2);
+        assert_eq!(user_name(&pool, &bob).await.as_deref(), Some("bob"));
+        let checkpoints = PostgresSink::new(pool).checkpoints().await.unwrap();
+        assert_eq!(checkpoints.len(), 1);
+        assert_eq!(checkpoints[0].sequence_number.as_deref(), Some("00002"));
+    }
+
+    #[tokio::test]
+    async fn test_project_once_skips_stale_version() {
+        // Arrange
+        let (_c, pool) = arrange_pool().await;
+        let user_id = UserId::gen();
+        let now = Utc::now();
+        // 新しいバージョンのあとに、古いバージョンの変更が届く
+        let feed = FakeFeed {
+            records: vec![
+                record(1, now, Change::UserSaved(user(&user_id, "new", 2))),
+                record(2, now, Change::UserSaved(user(&user_id, "old", 1))),
+            ],
+            limit: 10,
+        };
+        let projector = Projector::new(Box::new(feed), PostgresSink::new(pool.clone()));
+
+        // Act
+        let report = projector.project_once().await.unwrap();
+
+        // Assert
+        assert_eq!(report.applied, 2);
+        assert_eq!(user_name(&pool, &user_id).await.as_deref(), Some("new"));
+    }
+
+    #[tokio::test]
+    async fn test_project_once_reports_lag() {
+        // Arrange
+        let (_c, pool) = arrange_pool().await;
+        let user_id = UserId::gen();
+        let now = Utc::now();
+        let deferred_at = now - Duration::minutes(10);
+        // 所有者がまだ反映されていないボードは、反映を持ち越す
+        let board = Board::new(
+            BoardId::gen(),
+            BoardTitle::new("yarukoto".to_owned()).unwrap(),
+            UserId::gen(),
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let feed = FakeFeed {
+            records: vec![
+                record(1, now, Change::UserSaved(user(&user_id, "alice", 1))),
+                record(2, deferred_at, Change::BoardSaved(board)),
+            ],
+            limit: 10,
+        };
+        let projector = Projector::new(Box::new(feed), PostgresSink::new(pool.clone()));
+
+        // Act
+        let report = projector.project_once().await.unwrap();
+
+        // Assert
+        assert_eq!(report.applied, 1);
+        assert_eq!(report.deferred_shards, 1);
+        assert!(report.lag >= Duration::minutes(10));
+        let checkpoints = PostgresSink::new(pool).checkpoints().await.unwrap();
+        assert_eq!(checkpoints[0].sequence_number.as_deref(), Some("00001"));
+    }
+
+    #[tokio::test]
+    async fn test_project_once_removes_user_with_comments() {
+        // Arrange
+        let (_c, pool) = arrange_pool().await;
+        let user_id = UserId::gen();
+        let column = Column::new(ColumnTitle::new("todo".to_owned()))
+            .add_card(CardTitle::new("first".to_owned()));
+        let comment = Comment::new(
+            column.cards()[0].id().clone(),
+            user_id.clone(),
+            CommentBody::new("いいね".to_owned()).unwrap(),
+        );
+        let now = Utc::now();
+        let feed = FakeFeed {
+            records: vec![
+                record(1, now, Change::UserSaved(user(&user_id, "alice", 1))),
+                record(2, now, Change::ColumnSaved(column)),
+                record(3, now, Change::CommentSaved(comment)),
+                record(4, now, Change::UserRemoved(user_id.clone())),
+            ],
+            limit: 10,
+        };
+        let projector = Projector::new(Box::new(feed), PostgresSink::new(pool.clone()));
+
+        // Act
+        let report = projector.project_once().await.unwrap();
+
+        // Assert
+        assert_eq!(report.applied, 4);
+        assert_eq!(report.lag, Duration::zero());
+        assert!(user_name(&pool, &user_id).await.is_none());
+        let comments = sqlx::query_scalar!(r#"select count(*) as "count!" from comments"#)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(comments, 0);
+    }
+
+    #[tokio::test]
+    async fn test_project_once_keeps_comments_of_card_moved_before_destination() {
+        // Arrange
+        let (_c, pool) = arrange_pool().await;
+        let user_id = UserId::gen();
+        let mut src = Column::new(ColumnTitle::new("todo".to_owned()))
+            .add_card(CardTitle::new("moved".to_owned()));
+        let mut dst = Column::new(ColumnTitle::new("done".to_owned()));
+        let card_id = src.cards()[0].id().clone();
+        let comment = Comment::new(
+            card_id.clone(),
+            user_id.clone(),
+            CommentBody::new("いいね".to_owned()).unwrap(),
+        );
+        let before_move = (src.clone(), dst.clone());
+        let card = src.take_card(&card_id).unwrap();
+        dst.insert_card(0, card).unwrap();
+        let now = Utc::now();
+        // 移した元のカラムが、移した先より先に反映される
+        let feed = FakeFeed {
+            records: vec![
+                record(1, now, Change::UserSaved(user(&user_id, "alice", 1))),
+                record(2, now, Change::ColumnSaved(before_move.0)),
+                record(3, now, Change::ColumnSaved(before_move.1)),
+                record(4, now, Change::CommentSaved(comment)),
+                record(5, now, Change::ColumnSaved(src)),
+                record(6, now, Change::ColumnSaved(dst.clone())),
+            ],
+            limit: 10,
+        };
+        let projector = Projector::new(Box::new(feed), PostgresSink::new(pool.clone()));
+
+        // Act
+        let report = projector.project_once().await.unwrap();
+
+        // Assert
+        assert_eq!(report.applied, 6);
+        let card = sqlx::query!(
+            "select column_id, detached_at from cards where id = $1",
+            card_id.to_string()
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(card.column_id, dst.id().to_string());
+        assert!(card.detached_at.is_none());
+        let comments = sqlx::query_scalar!(r#"select count(*) as "count!" from comments"#)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(comments, 1);
+    }
+}
@@ -0,0 +1,434 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use domain_kanban::{
+    board::{Board, BoardId},
+    column::{Column, ColumnId},
+    comment::{Comment, CommentId},
+    user::{User, UserId},
+};
+use domain_util::Version;
+use sqlx::{query, PgConnection, PgPool};
+
+use crate::{Change, ChangeRecord, Checkpoint, ShardBatch};
+
+/// リードモデルのテーブルに変更を反映する
+pub struct PostgresSink {
+    pool: PgPool,
+}
+
+/// 1つのシャードの反映結果
+#[derive(Debug, Clone, Default)]
+pub struct ApplyReport {
+    pub applied: usize,
+    /// 最後に反映した変更が書き込み側で発生した日時
+    pub last_event_at: Option<DateTime<Utc>>,
+    /// 依存先が反映されるのを待っている変更が発生した日時
+    pub deferred_since: Option<DateTime<Utc>>,
+}
+
+impl PostgresSink {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn checkpoints(&self) -> Result<Vec<Checkpoint>> {
+        let checkpoints = query!(
+            r#"
+            select p.stream, p.shard_id, p.sequence_number, p.finished
+            from projection_checkpoints p
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let result = checkpoints
+            .into_iter()
+            .map(|c| Checkpoint {
+                stream: c.stream,
+                shard_id: c.shard_id,
+                sequence_number: c.sequence_number,
+                finished: c.finished,
+            })
+            .collect();
+        Ok(result)
+    }
+
+    /// シャードの変更を順に反映し、チェックポイントと合わせて1つのトランザクションで保存する
+    /// 依存先(ボードの所有者やカラム)がまだ反映されていない変更があれば、その手前で止めて次回に持ち越す
+    pub async fn apply(&self, batch: &ShardBatch) -> Result<ApplyReport> {
+        let mut tx = self.pool.begin().await?;
+        let mut report = ApplyReport::default();
+        let mut last_sequence_number = None;
+
+        for record in &batch.records {
+            if !dependencies_projected(&mut tx, &record.change).await? {
+                tracing::warn!(
+                    stream = %batch.stream,
+                    shard_id = %batch.shard_id,
+                    sequence_number = %record.sequence_number,
+                    "deferred a change until its dependencies are projected"
+                );
+                report.deferred_since = Some(record.occurred_at);
+                break;
+            }
+            apply_change(&mut tx, record).await?;
+            report.applied += 1;
+            report.last_event_at = Some(record.occurred_at);
+            last_sequence_number = Some(record.sequence_number.as_str());
+        }
+
+        let finished = batch.finished && report.deferred_since.is_none();
+        query!(
+            r#"
+            insert into projection_checkpoints
+                (stream, shard_id, sequence_number, finished, last_event_at, updated_at)
+            values ($1, $2, $3, $4, $5, now())
+            on conflict (stream, shard_id) do update
+                set sequence_number = coalesce(excluded.sequence_number, projection_checkpoints.sequence_number),
+                    finished = excluded.finished,
+                    last_event_at = coalesce(excluded.last_event_at, projection_checkpoints.last_event_at),
+                    updated_at = now()
+            "#,
+            &batch.stream,
+            &batch.shard_id,
+            last_sequence_number,
+            finished,
+            report.last_event_at,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(report)
+    }
+}
+
+async fn dependencies_projected(conn: &mut PgConnection, change: &Change) -> Result<bool> {
+    match change {
+        Change::BoardSaved(board) => board_dependencies_projected(conn, board).await,
+        Change::CommentSaved(comment) => comment_dependencies_projected(conn, comment).await,
+        _ => Ok(true),
+    }
+}
+
+async fn board_dependencies_projected(conn: &mut PgConnection, board: &Board) -> Result<bool> {
+    let column_ids: Vec<_> = board.column_ids().iter().map(ToString::to_string).collect();
+    let projected = query!(
+        r#"
+        select
+            exists(select 1 from users where id = $1) as "owner_exists!",
+            (select count(*) from columns where id = any($2)) as "column_cnt!"
+        "#,
+        board.owner().to_string(),
+        &column_ids,
+    )
+    .fetch_one(conn)
+    .await?;
+    let result =
+        projected.owner_exists && usize::try_from(projected.column_cnt)? == column_ids.len();
+    Ok(result)
+}
+
+// コメントはカードと投稿者に外部キーを張っているので、どちらも反映されてから反映する
+async fn comment_dependencies_projected(
+    conn: &mut PgConnection,
+    comment: &Comment,
+) -> Result<bool> {
+    let projected = query!(
+        r#"
+        select
+            exists(select 1 from cards where id = $1) as "card_exists!",
+            exists(select 1 from users where id = $2) as "author_exists!"
+        "#,
+        comment.card_id().to_string(),
+        comment.author().to_string(),
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(projected.card_exists && projected.author_exists)
+}
+
+async fn apply_change(conn: &mut PgConnection, record: &ChangeRecord) -> Result<()> {
+    match &record.change {
+        Change::UserSaved(user) => upsert_user(conn, user).await,
+        Change::UserRemoved(id) => remove_user(conn, id).await,
+        Change::BoardSaved(board) => upsert_board(conn, board).await,
+        Change::BoardRemoved(id) => remove_board(conn, id).await,
+        Change::ColumnSaved(column) => upsert_column(conn, column).await,
+        Change::ColumnRemoved(id) => remove_column(conn, id).await,
+        Change::CommentSaved(comment) => upsert_comment(conn, comment).await,
+        Change::CommentRemoved(id) => remove_comment(conn, id).await,
+    }
+}
+
+fn to_i64(version: Version) -> Result<i64> {
+    Ok(i64::try_from(version.value())?)
+}
+
+// NOTE: いずれも、反映済みのものより古いバージョンでは上書きしない
+async fn upsert_user(conn: &mut PgConnection, user: &User) -> Result<()> {
+    query!(
+        r#"
+        insert into users (id, name, email, version)
+        values ($1, $2, $3, $4)
+        on conflict (id) do update
+            set name = excluded.name,
+                email = excluded.email,
+                version = excluded.version
+            where users.version <= excluded.version
+        "#,
+        user.user_id().to_string(),
+        user.user_name().to_string(),
+        user.email().to_string(),
+        to_i64(user.version())?,
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn remove_user(conn: &mut PgConnection, id: &UserId) -> Result<()> {
+    let id = id.to_string();
+    // コメントは投稿者に外部キーを張っているので、先に削除する
+    query!("delete from comments where author_id = $1", &id)
+        .execute(&mut *conn)
+        .await?;
+    query!("delete from user_board_relations where user_id = $1", &id)
+        .execute(&mut *conn)
+        .await?;
+    query!("delete from board_members where user_id = $1", &id)
+        .execute(&mut *conn)
+        .await?;
+    query!("delete from users where id = $1", &id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+async fn upsert_board(conn: &mut PgConnection, board: &Board) -> Result<()> {
+    let id = board.id().to_string();
+    let upserted = query!(
+        r#"
+        insert into boards (id, title, version, archived_at)
+        values ($1, $2, $3, $4)
+        on conflict (id) do update
+            set title = excluded.title,
+                version = excluded.version,
+                archived_at = excluded.archived_at
+            where boards.version <= excluded.version
+        "#,
+        &id,
+        board.title().to_string(),
+        to_i64(board.version())?,
+        board.archived_at().cloned(),
+    )
+    .execute(&mut *conn)
+    .await?;
+    if upserted.rows_affected() == 0 {
+        return Ok(());
+    }
+
+    query!("delete from user_board_relations where board_id = $1", &id)
+        .execute(&mut *conn)
+        .await?;
+    query!(
+        "insert into user_board_relations (user_id, board_id) values ($1, $2)",
+        board.owner().to_string(),
+        &id,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    let member_ids: Vec<_> = board.members().iter().map(ToString::to_string).collect();
+    query!("delete from board_members where board_id = $1", &id)
+        .execute(&mut *conn)
+        .await?;
+    query!(
+        r#"
+        insert into board_members (board_id, user_id)
+        select distinct $1, m.user_id
+        from unnest($2::varchar[]) as m(user_id)
+        "#,
+        &id,
+        &member_ids,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    let column_ids: Vec<_> = board.column_ids().iter().map(ToString::to_string).collect();
+    query!(
+        "delete from board_column_relations where board_id = $1",
+        &id
+    )
+    .execute(&mut *conn)
+    .await?;
+    query!(
+        r#"
+        insert into board_column_relations (board_id, column_id, position)
+        select $1, c.column_id, c.position - 1
+        from unnest($2::varchar[]) with ordinality as c(column_id, position)
+        on conflict (column_id) do update
+            set board_id = excluded.board_id,
+                position = excluded.position
+        "#,
+        &id,
+        &column_ids,
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+async fn remove_board(conn: &mut PgConnection, id: &BoardId) -> Result<()> {
+    let id = id.to_string();
+    query!("delete from user_board_relations where board_id = $1", &id)
+        .execute(&mut *conn)
+        .await?;
+    query!("delete from board_members where board_id = $1", &id)
+        .execute(&mut *conn)
+        .await?;
+    query!(
+        "delete from board_column_relations where board_id = $1",
+        &id
+    )
+    .execute(&mut *conn)
+    .await?;
+    query!("delete from boards where id = $1", &id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+async fn upsert_column(conn: &mut PgConnection, column: &Column) -> Result<()> {
+    let id = column.id().to_string();
+    let upserted = query!(
+        r#"
+        insert into columns (id, title, version, archived_at)
+        values ($1, $2, $3, $4)
+        on conflict (id) do update
+            set title = excluded.title,
+                version = excluded.version,
+                archived_at = excluded.archived_at
+            where columns.version <= excluded.version
+        "#,
+        &id,
+        column.title().to_string(),
+        to_i64(column.version())?,
+        column.archived_at().cloned(),
+    )
+    .execute(&mut *conn)
+    .await?;
+    if upserted.rows_affected() == 0 {
+        return Ok(());
+    }
+
+    // カラムから取り除かれたカードは削除せず、取り除かれた日時を記録する
+    // ストリームはアイテムをまたいだ順序を保証しないので、移した先のカラムがあとから反映されることがある
+    // 削除するとコメントもcascadeで消え、移した先が反映されても戻らない。保持期間を過ぎたら削除する
+    let card_ids: Vec<_> = column.cards().iter().map(|c| c.id().to_string()).collect();
+    query!(
+        r#"
+        update cards set detached_at = now()
+        where column_id = $1 and not (id = any($2)) and detached_at is null
+        "#,
+        &id,
+        &card_ids,
+    )
+    .execute(&mut *conn)
+    .await?;
+    query!(
+        "delete from checklist_items where card_id = any($1)",
+        &card_ids
+    )
+    .execute(&mut *conn)
+    .await?;
+    for (position, card) in column.cards().iter().enumerate() {
+        let card_id = card.id().to_string();
+        query!(
+            r#"
+            insert into cards (id, title, description, column_id, archived_at, position, due_at)
+            values ($1, $2, $3, $4, $5, $6, $7)
+            on conflict (id) do update
+                set title = excluded.title,
+                    description = excluded.description,
+                    column_id = excluded.column_id,
+                    archived_at = excluded.archived_at,
+                    position = excluded.position,
+                    due_at = excluded.due_at,
+                    detached_at = null
+            "#,
+            &card_id,
+            card.title().to_string(),
+            card.description().to_string(),
+            &id,
+            card.archived_at().cloned(),
+            i32::try_from(position)?,
+            card.due_at().cloned(),
+        )
+        .execute(&mut *conn)
+        .await?;
+        for (position, item) in card.checklist().items().iter().enumerate() {
+            query!(
+                r#"
+                insert into checklist_items (card_id, position, text, done)
+                values ($1, $2, $3, $4)
+                "#,
+                &card_id,
+                i32::try_from(position)?,
+                item.text().to_string(),
+                item.done(),
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn remove_column(conn: &mut PgConnection, id: &ColumnId) -> Result<()> {
+    let id = id.to_string();
+    query!(
+        "delete from board_column_relations where column_id = $1",
+        &id
+    )
+    .execute(&mut *conn)
+    .await?;
+    query!("delete from cards where column_id = $1", &id)
+        .execute(&mut *conn)
+        .await?;
+    query!("delete from columns where id = $1", &id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+async fn upsert_comment(conn: &mut PgConnection, comment: &Comment) -> Result<()> {
+    query!(
+        r#"
+        insert into comments (id, card_id, author_id, body, created_at, edited_at, version)
+        values ($1, $2, $3, $4, $5, $6, $7)
+        on conflict (id) do update
+            set body = excluded.body,
+                edited_at = excluded.edited_at,
+                version = excluded.version
+            where comments.version <= excluded.version
+        "#,
+        comment.id().to_string(),
+        comment.card_id().to_string(),
+        comment.author().to_string(),
+        comment.body().to_string(),
+        *comment.created_at(),
+        comment.edited_at().cloned(),
+        to_i64(comment.version())?,
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn remove_comment(conn: &mut PgConnection, id: &CommentId) -> Result<()> {
+    query!("delete from comments where id = $1", id.to_string())
+        .execute(conn)
+        .await?;
+    Ok(())
+}
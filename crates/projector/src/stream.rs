@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use aws_config::SdkConfig;
+use aws_sdk_dynamodbstreams::types::{
+    AttributeValue, OperationType, Record, Shard, ShardIteratorType,
+};
+use chrono::{DateTime, Utc};
+use serde_dynamo::aws_sdk_dynamodbstreams_1::from_item;
+
+use crate::{Change, ChangeFeed, ChangeRecord, Checkpoint, ProjectedTable, ShardBatch};
+
+/// DynamoDB Streamsから変更を読み込む
+/// テーブルはStreamsを有効(NEW_IMAGEまたはNEW_AND_OLD_IMAGES)にして作成しておく必要がある
+/// LocalStackを使う場合は、`AWS_ENDPOINT_URL` を設定したSdkConfigを渡す
+pub struct DynamoDbStreamFeed {
+    dynamodb: aws_sdk_dynamodb::Client,
+    streams: aws_sdk_dynamodbstreams::Client,
+    tables: Vec<ProjectedTable>,
+    /// シャードごとに1回で読み込む最大件数
+    limit: i32,
+}
+
+impl DynamoDbStreamFeed {
+    pub fn new(config: &SdkConfig) -> Self {
+        Self {
+            dynamodb: aws_sdk_dynamodb::Client::new(config),
+            streams: aws_sdk_dynamodbstreams::Client::new(config),
+            tables: ProjectedTable::ALL.to_vec(),
+            limit: 100,
+        }
+    }
+
+    pub fn with_limit(mut self, limit: i32) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    async fn stream_arn(&self, table: ProjectedTable) -> Result<String> {
+        let output = self
+            .dynamodb
+            .describe_table()
+            .table_name(table.table_name())
+            .send()
+            .await?;
+        output
+            .table
+            .and_then(|t| t.latest_stream_arn)
+            .ok_or_else(|| anyhow!("streams are not enabled on {}", table.table_name()))
+    }
+
+    async fn list_shards(&self, stream_arn: &str) -> Result<Vec<Shard>> {
+        let mut result = vec![];
+        let mut exclusive_start_shard_id = None;
+        loop {
+            let output = self
+                .streams
+                .describe_stream()
+                .stream_arn(stream_arn)
+                .set_exclusive_start_shard_id(exclusive_start_shard_id)
+                .send()
+                .await?;
+            let description = output
+                .stream_description
+                .ok_or_else(|| anyhow!("stream not found: {}", stream_arn))?;
+            result.extend(description.shards.unwrap_or_default());
+            exclusive_start_shard_id = description.last_evaluated_shard_id;
+            if exclusive_start_shard_id.is_none() {
+                return Ok(result);
+            }
+        }
+    }
+
+    async fn poll_shard(
+        &self,
+        table: ProjectedTable,
+        stream_arn: &str,
+        shard_id: &str,
+        checkpoint: Option<&Checkpoint>,
+    ) -> Result<ShardBatch> {
+        let sequence_number = checkpoint.and_then(|c| c.sequence_number.clone());
+        let iterator_type = match sequence_number {
+            Some(_) => ShardIteratorType::AfterSequenceNumber,
+            None => ShardIteratorType::TrimHorizon,
+        };
+        let iterator = self
+            .streams
+            .get_shard_iterator()
+            .stream_arn(stream_arn)
+            .shard_id(shard_id)
+            .shard_iterator_type(iterator_type)
+            .set_sequence_number(sequence_number)
+            .send()
+            .await?
+            .shard_iterator;
+
+        let (records, next_iterator) = match iterator {
+            Some(iterator) => {
+                let output = self
+                    .streams
+                    .get_records()
+                    .shard_iterator(iterator)
+                    .limit(self.limit)
+                    .send()
+                    .await?;
+                (
+                    output.records.unwrap_or_default(),
+                    output.next_shard_iterator,
+                )
+            }
+            // NOTE: 閉じたシャードを読み終えている
+            None => (vec![], None),
+        };
+        let has_more = records.len() >= usize::try_from(self.limit)?;
+        let records = records
+            .into_iter()
+            .map(|r| to_change_record(table, r))
+            .collect::<Result<Vec<_>>>()
+            .with_context(|| format!("failed to read shard {} of {}", shard_id, stream_arn))?;
+
+        Ok(ShardBatch {
+            stream: table.table_name().to_owned(),
+            shard_id: shard_id.to_owned(),
+            records,
+            finished: next_iterator.is_none(),
+            has_more,
+        })
+    }
+}
+
+#[async_trait]
+impl ChangeFeed for DynamoDbStreamFeed {
+    async fn poll(&self, checkpoints: &[Checkpoint]) -> Result<Vec<ShardBatch>> {
+        let mut result = vec![];
+        for table in &self.tables {
+            let stream = table.table_name();
+            let find_checkpoint = |shard_id: &str| {
+                checkpoints
+                    .iter()
+                    .find(|c| c.stream == stream && c.shard_id == shard_id)
+            };
+            let stream_arn = self.stream_arn(*table).await?;
+            let shards = self.list_shards(&stream_arn).await?;
+            let shard_ids: Vec<_> = shards
+                .iter()
+                .filter_map(|s| s.shard_id.as_deref())
+                .collect();
+            for shard in &shards {
+                let Some(shard_id) = shard.shard_id.as_deref() else {
+                    continue;
+                };
+                let checkpoint = find_checkpoint(shard_id);
+                if checkpoint.map_or(false, |c| c.finished) {
+                    continue;
+                }
+                // 同じアイテムの変更順を守るため、親シャードを読み終えるまで待つ
+                // NOTE: 一覧にない親シャードは保持期間を過ぎて削除されたもの
+                let waiting_for_parent = shard.parent_shard_id.as_deref().map_or(false, |p| {
+                    shard_ids.contains(&p) && !find_checkpoint(p).map_or(false, |c| c.finished)
+                });
+                if waiting_for_parent {
+                    continue;
+                }
+                let batch = self
+                    .poll_shard(*table, &stream_arn, shard_id, checkpoint)
+                    .await?;
+                if !batch.records.is_empty() || batch.finished {
+                    result.push(batch);
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn to_change_record(table: ProjectedTable, record: Record) -> Result<ChangeRecord> {
+    let stream_record = record
+        .dynamodb
+        .ok_or_else(|| anyhow!("stream record has no dynamodb data"))?;
+    let sequence_number = stream_record
+        .sequence_number
+        .ok_or_else(|| anyhow!("stream record has no sequence number"))?;
+    let occurred_at = stream_record
+        .approximate_creation_date_time
+        .and_then(|t| DateTime::<Utc>::from_timestamp(t.secs(), t.subsec_nanos()))
+        .unwrap_or_else(Utc::now);
+    let change = match record.event_name {
+        Some(OperationType::Remove) => {
+            let keys = stream_record.keys.unwrap_or_default();
+            Change::removed(table, &id_of(&keys)?)?
+        }
+        _ => {
+            let image = stream_record.new_image.ok_or_else(|| {
+                anyhow!("stream record has no new image (StreamViewType must include NEW_IMAGE)")
+            })?;
+            to_saved_change(table, image)?
+        }
+    };
+    Ok(ChangeRecord {
+        sequence_number,
+        occurred_at,
+        change,
+    })
+}
+
+fn to_saved_change(
+    table: ProjectedTable,
+    image: HashMap<String, AttributeValue>,
+) -> Result<Change> {
+    let result = match table {
+        ProjectedTable::Users => Change::UserSaved(from_item(image)?),
+        ProjectedTable::Boards => Change::BoardSaved(from_item(image)?),
+        ProjectedTable::Columns => Change::ColumnSaved(from_item(image)?),
+        ProjectedTable::Comments => Change::CommentSaved(from_item(image)?),
+    };
+    Ok(result)
+}
+
+fn id_of(keys: &HashMap<String, AttributeValue>) -> Result<String> {
+    match keys.get("id") {
+        Some(AttributeValue::S(id)) => Ok(id.clone()),
+        _ => Err(anyhow!("stream record has no string id key")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodbstreams::types::StreamRecord;
+    use domain_kanban::{
+        column::CardId,
+        comment::CommentId,
+        user::{Email, User, UserId, UserName},
+    };
+
+    use super::*;
+
+    fn user() -> User {
+        User::new(
+            UserName::new("stream".to_owned()).unwrap(),
+            Email::new("stream@example.com".to_owned()).unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn string(value: impl ToString) -> AttributeValue {
+        AttributeValue::S(value.to_string())
+    }
+
+    #[test]
+    fn test_to_change_record_saved() {
+        // Arrange
+        let user = user();
+        let image = HashMap::from([
+            ("id".to_owned(), string(user.user_id())),
+            ("name".to_owned(), string(user.user_name())),
+            ("email".to_owned(), string(user.email())),
+            ("version".to_owned(), AttributeValue::N("3".to_owned())),
+        ]);
+        let record = Record::builder()
+            .event_name(OperationType::Modify)
+            .dynamodb(
+                StreamRecord::builder()
+                    .sequence_number("100")
+                    .set_new_image(Some(image))
+                    .build(),
+            )
+            .build();
+
+        // Act
+        let result = to_change_record(ProjectedTable::Users, record).unwrap();
+
+        // Assert
+        assert_eq!(result.sequence_number, "100");
+        match result.change {
+            Change::UserSaved(saved) => {
+                assert_eq!(saved.user_id(), user.user_id());
+                assert_eq!(saved.version().value(), 3);
+            }
+            other => panic!("unexpected change: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_to_change_record_removed() {
+        // Arrange
+        let user = user();
+        let record = Record::builder()
+            .event_name(OperationType::Remove)
+            .dynamodb(
+                StreamRecord::builder()
+                    .sequence_number("200")
+                    .keys("id", string(user.user_id()))
+                    .build(),
+            )
+            .build();
+
+        // Act
+        let result = to_change_record(ProjectedTable::Users, record).unwrap();
+
+        // Assert
+        match result.change {
+            Change::UserRemoved(id) => assert_eq!(&id, user.user_id()),
+            other => panic!("unexpected change: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_to_change_record_comment_saved() {
+        // Arrange
+        let id = CommentId::gen();
+        let card_id = CardId::gen();
+        let author = UserId::gen();
+        let image = HashMap::from([
+            ("id".to_owned(), string(&id)),
+            ("card_id".to_owned(), string(&card_id)),
+            ("author".to_owned(), string(&author)),
+            ("body".to_owned(), string("looks good")),
+            ("created_at".to_owned(), string("2026-10-19T00:00:00Z")),
+            ("version".to_owned(), AttributeValue::N("1".to_owned())),
+        ]);
+        let record = Record::builder()
+            .event_name(OperationType::Insert)
+            .dynamodb(
+                StreamRecord::builder()
+                    .sequence_number("300")
+                    .set_new_image(Some(image))
+                    .build(),
+            )
+            .build();
+
+        // Act
+        let result = to_change_record(ProjectedTable::Comments, record).unwrap();
+
+        // Assert
+        match result.change {
+            Change::CommentSaved(saved) => {
+                assert_eq!(saved.id(), &id);
+                assert_eq!(saved.card_id(), &card_id);
+                assert_eq!(saved.author(), &author);
+                assert_eq!(saved.version().value(), 1);
+            }
+            other => panic!("unexpected change: {:?}", other),
+        }
+    }
+}
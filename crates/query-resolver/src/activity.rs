@@ -0,0 +1,43 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_kanban::{
+    activity::{ActivityAction, ActivityId},
+    board::BoardId,
+    user::UserId,
+};
+use shaku::Interface;
+
+/// いずれも新しい順に返す
+#[async_trait]
+pub trait ActivityQuery: Interface {
+    async fn list_by_board(
+        &self,
+        board_id: &BoardId,
+        page: &ActivityPage,
+    ) -> Result<Vec<ActivityView>>;
+    async fn list_by_actor(
+        &self,
+        actor_id: &UserId,
+        page: &ActivityPage,
+    ) -> Result<Vec<ActivityView>>;
+}
+
+pub struct ActivityPage {
+    pub first: usize,
+    /// このIDより古いものを返す
+    pub after: Option<ActivityId>,
+    /// 空のときは全てのアクションを返す
+    pub actions: Vec<ActivityAction>,
+}
+
+pub struct ActivityView {
+    pub id: String,
+    pub actor_id: String,
+    pub action: String,
+    pub target_id: String,
+    pub board_id: Option<String>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
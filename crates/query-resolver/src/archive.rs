@@ -0,0 +1,22 @@
+/// 一覧を取得するときにアーカイブ済みのものを含めるか
+/// デフォルトでは含めない
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ArchivedFilter {
+    #[default]
+    Exclude,
+    Include,
+}
+
+impl ArchivedFilter {
+    pub fn new(include_archived: bool) -> Self {
+        if include_archived {
+            Self::Include
+        } else {
+            Self::Exclude
+        }
+    }
+
+    pub fn includes_archived(&self) -> bool {
+        matches!(self, Self::Include)
+    }
+}
@@ -2,19 +2,30 @@ use std::collections::HashMap;
 
 use anyhow::Result;
 use async_trait::async_trait;
-use domain_kanban::board::BoardId;
+use chrono::{DateTime, Utc};
+use domain_kanban::{board::BoardId, column::ColumnId, user::UserId};
 use shaku::Interface;
 
+use crate::BoardCriteria;
+
+/// IDを指定して取得するときは、アーカイブ済みのものも返す
 #[async_trait]
 pub trait BoardQuery: Interface {
     async fn find_by_id(&self, id: &BoardId) -> Result<BoardView>;
     async fn list_by_ids(&self, ids: &[BoardId]) -> Result<HashMap<BoardId, BoardView>>;
-    async fn all(&self) -> Result<Vec<BoardView>>;
+    async fn all(&self, criteria: &BoardCriteria) -> Result<Vec<BoardView>>;
+    /// カラムの入っているボード。どのボードにも入っていないカラムは含めない
+    async fn list_by_column_ids(&self, ids: &[ColumnId]) -> Result<HashMap<ColumnId, BoardView>>;
+    /// ユーザーがメンバーになっているボードをIDの順に返す。所有しているだけのボードは含めない
+    async fn list_by_member_ids(&self, ids: &[UserId]) -> Result<HashMap<UserId, Vec<BoardView>>>;
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct BoardView {
     pub id: String,
     pub title: String,
     pub owner_id: String,
     pub column_ids: Vec<String>,
+    pub version: u64,
+    pub archived_at: Option<DateTime<Utc>>,
 }
@@ -0,0 +1,92 @@
+// よく読まれるが変更の少ないビューを、クエリの実装の手前でキャッシュする
+// 書き込み側で保存したときと、リードモデルに変更が反映されたときに、変更された集約のエントリを捨てる
+mod module;
+mod query;
+mod repository;
+mod store;
+
+pub use module::*;
+pub use query::*;
+pub use repository::*;
+
+use std::{str::FromStr, time::Duration};
+
+use domain_kanban::{
+    board::BoardId,
+    column::{CardId, ColumnId},
+    user::UserId,
+};
+
+use crate::{ArchivedFilter, BoardView, CardView, ColumnView, UserView};
+use store::TtlCache;
+
+/// キャッシュの件数の上限(ビューの種類ごと)と有効期限
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// 0の場合は何もキャッシュしない
+    pub capacity: usize,
+    pub ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            ttl: Duration::from_secs(30),
+        }
+    }
+}
+
+/// キャッシュしたビュー。クエリとリポジトリのデコレーターで共有する
+#[derive(Debug)]
+pub struct QueryCache {
+    users: TtlCache<UserId, UserView>,
+    boards: TtlCache<BoardId, BoardView>,
+    columns: TtlCache<ColumnId, ColumnView>,
+    cards: TtlCache<(ColumnId, ArchivedFilter, usize), CardView>,
+    cards_by_id: TtlCache<CardId, CardView>,
+}
+
+impl QueryCache {
+    pub fn new(config: CacheConfig) -> Self {
+        let CacheConfig { capacity, ttl } = config;
+        Self {
+            users: TtlCache::new(capacity, ttl),
+            boards: TtlCache::new(capacity, ttl),
+            columns: TtlCache::new(capacity, ttl),
+            cards: TtlCache::new(capacity, ttl),
+            cards_by_id: TtlCache::new(capacity, ttl),
+        }
+    }
+
+    pub fn invalidate_user(&self, id: &UserId) {
+        self.users.remove(id);
+    }
+
+    /// ユーザーのビューは所有するボードのIDを持つので、所有者のエントリも捨てる
+    pub fn invalidate_board(&self, id: &BoardId) {
+        let owner_id = self
+            .boards
+            .remove(id)
+            .and_then(|board| UserId::from_str(&board.owner_id).ok());
+        if let Some(owner_id) = owner_id {
+            self.invalidate_user(&owner_id);
+        }
+    }
+
+    /// カードはカラムの一部なので、カラム内のカードのエントリも捨てる
+    pub fn invalidate_column(&self, id: &ColumnId) {
+        self.columns.remove(id);
+        self.cards
+            .remove_where(|(column_id, _, _), _| column_id == id);
+        let column_id = id.to_string();
+        self.cards_by_id
+            .remove_where(|_, card| card.column_id == column_id);
+    }
+}
+
+impl Default for QueryCache {
+    fn default() -> Self {
+        Self::new(CacheConfig::default())
+    }
+}
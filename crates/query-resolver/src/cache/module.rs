@@ -0,0 +1,152 @@
+use std::{error::Error, sync::Arc};
+
+use domain_kanban::{
+    activity::ActivityRepository, board::BoardRepository, column::ColumnRepository,
+    comment::CommentRepository, outbox::OutboxStore, user::UserRepository,
+};
+use shaku::HasProvider;
+
+use super::{
+    CachedBoardQuery, CachedCardsQuery, CachedColumnsQuery, CachedUsersQuery,
+    InvalidatingBoardRepository, InvalidatingColumnRepository, InvalidatingUserRepository,
+    QueryCache,
+};
+use crate::{
+    ActivityQuery, BoardQuery, CardsQuery, ColumnsQuery, CommentsQuery, SearchQuery, UsersQuery,
+};
+
+/// クエリのモジュールを包み、キャッシュするクエリを提供する
+/// キャッシュしないクエリは、包んだモジュールのものをそのまま提供する
+pub struct CachedQueryModule<M> {
+    inner: M,
+    cache: Arc<QueryCache>,
+}
+
+impl<M> CachedQueryModule<M> {
+    pub fn new(inner: M, cache: Arc<QueryCache>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+impl<M: HasProvider<dyn UsersQuery>> HasProvider<dyn UsersQuery> for CachedQueryModule<M> {
+    fn provide(&self) -> Result<Box<dyn UsersQuery>, Box<dyn Error>> {
+        let inner = HasProvider::<dyn UsersQuery>::provide(&self.inner)?;
+        let query = CachedUsersQuery::new(inner, Arc::clone(&self.cache));
+        Ok(Box::new(query))
+    }
+}
+
+impl<M: HasProvider<dyn BoardQuery>> HasProvider<dyn BoardQuery> for CachedQueryModule<M> {
+    fn provide(&self) -> Result<Box<dyn BoardQuery>, Box<dyn Error>> {
+        let inner = HasProvider::<dyn BoardQuery>::provide(&self.inner)?;
+        let query = CachedBoardQuery::new(inner, Arc::clone(&self.cache));
+        Ok(Box::new(query))
+    }
+}
+
+impl<M: HasProvider<dyn ColumnsQuery>> HasProvider<dyn ColumnsQuery> for CachedQueryModule<M> {
+    fn provide(&self) -> Result<Box<dyn ColumnsQuery>, Box<dyn Error>> {
+        let inner = HasProvider::<dyn ColumnsQuery>::provide(&self.inner)?;
+        let query = CachedColumnsQuery::new(inner, Arc::clone(&self.cache));
+        Ok(Box::new(query))
+    }
+}
+
+impl<M: HasProvider<dyn CardsQuery>> HasProvider<dyn CardsQuery> for CachedQueryModule<M> {
+    fn provide(&self) -> Result<Box<dyn CardsQuery>, Box<dyn Error>> {
+        let inner = HasProvider::<dyn CardsQuery>::provide(&self.inner)?;
+        let query = CachedCardsQuery::new(inner, Arc::clone(&self.cache));
+        Ok(Box::new(query))
+    }
+}
+
+impl<M: HasProvider<dyn ActivityQuery>> HasProvider<dyn ActivityQuery> for CachedQueryModule<M> {
+    fn provide(&self) -> Result<Box<dyn ActivityQuery>, Box<dyn Error>> {
+        self.inner.provide()
+    }
+}
+
+impl<M: HasProvider<dyn CommentsQuery>> HasProvider<dyn CommentsQuery> for CachedQueryModule<M> {
+    fn provide(&self) -> Result<Box<dyn CommentsQuery>, Box<dyn Error>> {
+        self.inner.provide()
+    }
+}
+
+impl<M: HasProvider<dyn SearchQuery>> HasProvider<dyn SearchQuery> for CachedQueryModule<M> {
+    fn provide(&self) -> Result<Box<dyn SearchQuery>, Box<dyn Error>> {
+        self.inner.provide()
+    }
+}
+
+/// リポジトリのモジュールを包み、保存した集約のキャッシュを捨てるリポジトリを提供する
+/// クエリのキャッシュに載らない集約のリポジトリは、包んだモジュールのものをそのまま提供する
+pub struct InvalidatingRepositoryModule<M> {
+    inner: M,
+    cache: Arc<QueryCache>,
+}
+
+impl<M> InvalidatingRepositoryModule<M> {
+    pub fn new(inner: M, cache: Arc<QueryCache>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+impl<M> HasProvider<dyn UserRepository> for InvalidatingRepositoryModule<M>
+where
+    M: HasProvider<dyn UserRepository>,
+{
+    fn provide(&self) -> Result<Box<dyn UserRepository>, Box<dyn Error>> {
+        let inner = HasProvider::<dyn UserRepository>::provide(&self.inner)?;
+        let repository = InvalidatingUserRepository::new(inner, Arc::clone(&self.cache));
+        Ok(Box::new(repository))
+    }
+}
+
+impl<M> HasProvider<dyn BoardRepository> for InvalidatingRepositoryModule<M>
+where
+    M: HasProvider<dyn BoardRepository>,
+{
+    fn provide(&self) -> Result<Box<dyn BoardRepository>, Box<dyn Error>> {
+        let inner = HasProvider::<dyn BoardRepository>::provide(&self.inner)?;
+        let repository = InvalidatingBoardRepository::new(inner, Arc::clone(&self.cache));
+        Ok(Box::new(repository))
+    }
+}
+
+impl<M> HasProvider<dyn ColumnRepository> for InvalidatingRepositoryModule<M>
+where
+    M: HasProvider<dyn ColumnRepository>,
+{
+    fn provide(&self) -> Result<Box<dyn ColumnRepository>, Box<dyn Error>> {
+        let inner = HasProvider::<dyn ColumnRepository>::provide(&self.inner)?;
+        let repository = InvalidatingColumnRepository::new(inner, Arc::clone(&self.cache));
+        Ok(Box::new(repository))
+    }
+}
+
+impl<M> HasProvider<dyn CommentRepository> for InvalidatingRepositoryModule<M>
+where
+    M: HasProvider<dyn CommentRepository>,
+{
+    fn provide(&self) -> Result<Box<dyn CommentRepository>, Box<dyn Error>> {
+        self.inner.provide()
+    }
+}
+
+impl<M> HasProvider<dyn ActivityRepository> for InvalidatingRepositoryModule<M>
+where
+    M: HasProvider<dyn ActivityRepository>,
+{
+    fn provide(&self) -> Result<Box<dyn ActivityRepository>, Box<dyn Error>> {
+        self.inner.provide()
+    }
+}
+
+impl<M> HasProvider<dyn OutboxStore> for InvalidatingRepositoryModule<M>
+where
+    M: HasProvider<dyn OutboxStore>,
+{
+    fn provide(&self) -> Result<Box<dyn OutboxStore>, Box<dyn Error>> {
+        self.inner.provide()
+    }
+}
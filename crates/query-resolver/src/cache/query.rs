@@ -0,0 +1,403 @@
+use std::{collections::HashMap, hash::Hash, sync::Arc};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use domain_kanban::{
+    board::BoardId,
+    column::{CardId, ColumnId},
+    user::UserId,
+};
+
+use super::{store::TtlCache, QueryCache};
+use crate::{
+    ArchivedFilter, BoardCriteria, BoardQuery, BoardView, CardCriteria, CardView, CardsQuery,
+    ColumnView, ColumnsQuery, UserCriteria, UserView, UsersQuery,
+};
+
+/// IDで取得したユーザーをキャッシュする。一覧(`all`)はキャッシュしない
+pub struct CachedUsersQuery<Q: ?Sized = dyn UsersQuery> {
+    inner: Box<Q>,
+    cache: Arc<QueryCache>,
+}
+
+impl<Q: ?Sized> CachedUsersQuery<Q> {
+    pub fn new(inner: Box<Q>, cache: Arc<QueryCache>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl<Q: UsersQuery + ?Sized> UsersQuery for CachedUsersQuery<Q> {
+    async fn find_by_id(&self, id: &UserId) -> Result<UserView> {
+        if let Some(view) = self.cache.users.get(id) {
+            return Ok(view);
+        }
+        let view = self.inner.find_by_id(id).await?;
+        self.cache.users.insert(id.clone(), view.clone());
+        Ok(view)
+    }
+
+    async fn list_by_ids(&self, ids: &[UserId]) -> Result<HashMap<UserId, UserView>> {
+        let (mut result, misses) = lookup(&self.cache.users, ids);
+        if !misses.is_empty() {
+            let fetched = self.inner.list_by_ids(&misses).await?;
+            fill(&self.cache.users, &mut result, fetched);
+        }
+        Ok(result)
+    }
+
+    async fn all(&self, criteria: &UserCriteria) -> Result<Vec<UserView>> {
+        self.inner.all(criteria).await
+    }
+}
+
+/// IDで取得したボードをキャッシュする。一覧(`all`)と、カラム・メンバーからたどるものはキャッシュしない
+pub struct CachedBoardQuery<Q: ?Sized = dyn BoardQuery> {
+    inner: Box<Q>,
+    cache: Arc<QueryCache>,
+}
+
+impl<Q: ?Sized> CachedBoardQuery<Q> {
+    pub fn new(inner: Box<Q>, cache: Arc<QueryCache>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl<Q: BoardQuery + ?Sized> BoardQuery for CachedBoardQuery<Q> {
+    async fn find_by_id(&self, id: &BoardId) -> Result<BoardView> {
+        if let Some(view) = self.cache.boards.get(id) {
+            return Ok(view);
+        }
+        let view = self.inner.find_by_id(id).await?;
+        self.cache.boards.insert(id.clone(), view.clone());
+        Ok(view)
+    }
+
+    async fn list_by_ids(&self, ids: &[BoardId]) -> Result<HashMap<BoardId, BoardView>> {
+        let (mut result, misses) = lookup(&self.cache.boards, ids);
+        if !misses.is_empty() {
+            let fetched = self.inner.list_by_ids(&misses).await?;
+            fill(&self.cache.boards, &mut result, fetched);
+        }
+        Ok(result)
+    }
+
+    async fn all(&self, criteria: &BoardCriteria) -> Result<Vec<BoardView>> {
+        self.inner.all(criteria).await
+    }
+
+    async fn list_by_column_ids(&self, ids: &[ColumnId]) -> Result<HashMap<ColumnId, BoardView>> {
+        self.inner.list_by_column_ids(ids).await
+    }
+
+    async fn list_by_member_ids(&self, ids: &[UserId]) -> Result<HashMap<UserId, Vec<BoardView>>> {
+        self.inner.list_by_member_ids(ids).await
+    }
+}
+
+pub struct CachedColumnsQuery<Q: ?Sized = dyn ColumnsQuery> {
+    inner: Box<Q>,
+    cache: Arc<QueryCache>,
+}
+
+impl<Q: ?Sized> CachedColumnsQuery<Q> {
+    pub fn new(inner: Box<Q>, cache: Arc<QueryCache>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl<Q: ColumnsQuery + ?Sized> ColumnsQuery for CachedColumnsQuery<Q> {
+    async fn find_by_id(&self, id: &ColumnId) -> Result<ColumnView> {
+        if let Some(view) = self.cache.columns.get(id) {
+            return Ok(view);
+        }
+        let view = self.inner.find_by_id(id).await?;
+        self.cache.columns.insert(id.clone(), view.clone());
+        Ok(view)
+    }
+
+    async fn list_by_ids(&self, ids: &[ColumnId]) -> Result<HashMap<ColumnId, ColumnView>> {
+        let (mut result, misses) = lookup(&self.cache.columns, ids);
+        if !misses.is_empty() {
+            let fetched = self.inner.list_by_ids(&misses).await?;
+            fill(&self.cache.columns, &mut result, fetched);
+        }
+        Ok(result)
+    }
+}
+
+/// カードはカラムとアーカイブの絞り込みごとの順番と、IDのそれぞれでキャッシュする
+/// 条件を指定した一覧(`list_by_column_ids`)はキャッシュしない
+pub struct CachedCardsQuery<Q: ?Sized = dyn CardsQuery> {
+    inner: Box<Q>,
+    cache: Arc<QueryCache>,
+}
+
+impl<Q: ?Sized> CachedCardsQuery<Q> {
+    pub fn new(inner: Box<Q>, cache: Arc<QueryCache>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl<Q: CardsQuery + ?Sized> CardsQuery for CachedCardsQuery<Q> {
+    async fn find_by_order(
+        &self,
+        column_id: &ColumnId,
+        order: &usize,
+        archived: ArchivedFilter,
+    ) -> Result<CardView> {
+        let key = (column_id.clone(), archived, *order);
+        if let Some(view) = self.cache.cards.get(&key) {
+            return Ok(view);
+        }
+        let view = self.inner.find_by_order(column_id, order, archived).await?;
+        self.cache.cards.insert(key, view.clone());
+        Ok(view)
+    }
+
+    async fn list_by_orders(
+        &self,
+        column_id: &ColumnId,
+        orders: &[usize],
+        archived: ArchivedFilter,
+    ) -> Result<HashMap<usize, CardView>> {
+        let keys: Vec<_> = orders
+            .iter()
+            .map(|order| (column_id.clone(), archived, *order))
+            .collect();
+        let (cached, misses) = lookup(&self.cache.cards, &keys);
+        let mut result: HashMap<_, _> = cached
+            .into_iter()
+            .map(|((_, _, order), view)| (order, view))
+            .collect();
+        if !misses.is_empty() {
+            let misses: Vec<_> = misses.into_iter().map(|(_, _, order)| order).collect();
+            let fetched = self
+                .inner
+                .list_by_orders(column_id, &misses, archived)
+                .await?;
+            for (order, view) in fetched {
+                let key = (column_id.clone(), archived, order);
+                self.cache.cards.insert(key, view.clone());
+                result.insert(order, view);
+            }
+        }
+        Ok(result)
+    }
+
+    async fn list_by_column_ids(
+        &self,
+        ids: &[ColumnId],
+        criteria: &CardCriteria,
+    ) -> Result<HashMap<ColumnId, Vec<CardView>>> {
+        self.inner.list_by_column_ids(ids, criteria).await
+    }
+
+    async fn list_by_ids(&self, ids: &[CardId]) -> Result<HashMap<CardId, CardView>> {
+        let (mut result, misses) = lookup(&self.cache.cards_by_id, ids);
+        if !misses.is_empty() {
+            let fetched = self.inner.list_by_ids(&misses).await?;
+            fill(&self.cache.cards_by_id, &mut result, fetched);
+        }
+        Ok(result)
+    }
+}
+
+// キャッシュにあったものと、内側のクエリで取得する必要のあるキー(重複なし)に分ける
+fn lookup<K, V>(cache: &TtlCache<K, V>, keys: &[K]) -> (HashMap<K, V>, Vec<K>)
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    let mut hits = HashMap::new();
+    let mut misses = Vec::new();
+    for key in keys {
+        if hits.contains_key(key) || misses.contains(key) {
+            continue;
+        }
+        match cache.get(key) {
+            Some(view) => {
+                hits.insert(key.clone(), view);
+            }
+            None => misses.push(key.clone()),
+        }
+    }
+    (hits, misses)
+}
+
+// 内側のクエリで取得したものをキャッシュし、結果に加える
+fn fill<K, V>(cache: &TtlCache<K, V>, result: &mut HashMap<K, V>, fetched: HashMap<K, V>)
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    for (key, view) in fetched {
+        cache.insert(key.clone(), view.clone());
+        result.insert(key, view);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use anyhow::anyhow;
+
+    use super::*;
+
+    /// 内側のクエリとして、受け取ったIDを記録する
+    #[derive(Default)]
+    struct RecordingBoardQuery {
+        calls: Mutex<Vec<Vec<BoardId>>>,
+    }
+
+    fn board_view(id: &BoardId) -> BoardView {
+        BoardView {
+            id: id.to_string(),
+            title: "board".to_owned(),
+            owner_id: UserId::gen().to_string(),
+            column_ids: vec![],
+            version: 1,
+            archived_at: None,
+        }
+    }
+
+    #[async_trait]
+    impl BoardQuery for RecordingBoardQuery {
+        async fn find_by_id(&self, id: &BoardId) -> Result<BoardView> {
+            self.calls.lock().unwrap().push(vec![id.clone()]);
+            Ok(board_view(id))
+        }
+        async fn list_by_ids(&self, ids: &[BoardId]) -> Result<HashMap<BoardId, BoardView>> {
+            self.calls.lock().unwrap().push(ids.to_vec());
+            Ok(ids.iter().map(|id| (id.clone(), board_view(id))).collect())
+        }
+        async fn all(&self, _criteria: &BoardCriteria) -> Result<Vec<BoardView>> {
+            Err(anyhow!("not used"))
+        }
+        async fn list_by_column_ids(
+            &self,
+            _ids: &[ColumnId],
+        ) -> Result<HashMap<ColumnId, BoardView>> {
+            Err(anyhow!("not used"))
+        }
+        async fn list_by_member_ids(
+            &self,
+            _ids: &[UserId],
+        ) -> Result<HashMap<UserId, Vec<BoardView>>> {
+            Err(anyhow!("not used"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_by_ids_fetches_only_misses() {
+        // Arrange
+        let cache = Arc::new(QueryCache::default());
+        let query = CachedBoardQuery::new(Box::new(RecordingBoardQuery::default()), cache);
+        let cached = BoardId::gen();
+        let missing = BoardId::gen();
+        query.find_by_id(&cached).await.unwrap();
+
+        // Act
+        let result = query
+            .list_by_ids(&[cached.clone(), missing.clone(), missing.clone()])
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(result.len(), 2);
+        let calls = query.inner.calls.lock().unwrap();
+        assert_eq!(*calls, vec![vec![cached], vec![missing]]);
+    }
+
+    #[tokio::test]
+    async fn test_invalidated_board_is_fetched_again() {
+        // Arrange
+        let cache = Arc::new(QueryCache::default());
+        let query =
+            CachedBoardQuery::new(Box::new(RecordingBoardQuery::default()), Arc::clone(&cache));
+        let id = BoardId::gen();
+        query.find_by_id(&id).await.unwrap();
+        query.find_by_id(&id).await.unwrap();
+
+        // Act
+        cache.invalidate_board(&id);
+        query.find_by_id(&id).await.unwrap();
+
+        // Assert
+        assert_eq!(query.inner.calls.lock().unwrap().len(), 2);
+    }
+
+    /// 内側のクエリとして、IDで取得した回数を記録する。カードはすべて `column_id` に入っている
+    struct RecordingCardsQuery {
+        column_id: ColumnId,
+        calls: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl CardsQuery for RecordingCardsQuery {
+        async fn find_by_order(
+            &self,
+            _column_id: &ColumnId,
+            _order: &usize,
+            _archived: ArchivedFilter,
+        ) -> Result<CardView> {
+            Err(anyhow!("not used"))
+        }
+        async fn list_by_orders(
+            &self,
+            _column_id: &ColumnId,
+            _orders: &[usize],
+            _archived: ArchivedFilter,
+        ) -> Result<HashMap<usize, CardView>> {
+            Err(anyhow!("not used"))
+        }
+        async fn list_by_column_ids(
+            &self,
+            _ids: &[ColumnId],
+            _criteria: &CardCriteria,
+        ) -> Result<HashMap<ColumnId, Vec<CardView>>> {
+            Err(anyhow!("not used"))
+        }
+        async fn list_by_ids(&self, ids: &[CardId]) -> Result<HashMap<CardId, CardView>> {
+            *self.calls.lock().unwrap() += 1;
+            let view = |id: &CardId| CardView {
+                id: id.to_string(),
+                title: "card".to_owned(),
+                description: "".to_owned(),
+                column_id: self.column_id.to_string(),
+                checklist: vec![],
+                archived_at: None,
+                due_at: None,
+            };
+            Ok(ids.iter().map(|id| (id.clone(), view(id))).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalidated_column_drops_its_cards_by_id() {
+        // Arrange
+        let cache = Arc::new(QueryCache::default());
+        let column_id = ColumnId::gen();
+        let inner = RecordingCardsQuery {
+            column_id: column_id.clone(),
+            calls: Mutex::new(0),
+        };
+        let query = CachedCardsQuery::new(Box::new(inner), Arc::clone(&cache));
+        let id = CardId::gen();
+        query.list_by_ids(&[id.clone()]).await.unwrap();
+        query.list_by_ids(&[id.clone()]).await.unwrap();
+
+        // Act
+        cache.invalidate_column(&ColumnId::gen());
+        query.list_by_ids(&[id.clone()]).await.unwrap();
+        cache.invalidate_column(&column_id);
+        query.list_by_ids(&[id]).await.unwrap();
+
+        // Assert
+        assert_eq!(*query.inner.calls.lock().unwrap(), 2);
+    }
+}
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use domain_kanban::{
+    board::{Board, BoardId, BoardRepository},
+    column::{Column, ColumnId, ColumnRepository},
+    outbox::OutboxMessage,
+    user::{User, UserId, UserRepository},
+};
+use domain_util::RepositoryError;
+
+use super::QueryCache;
+
+// NOTE: タイムアウトした場合などは保存されたかわからないので、結果にかかわらずキャッシュを捨てる
+
+/// 保存したユーザーのキャッシュを捨てる
+pub struct InvalidatingUserRepository<R: ?Sized = dyn UserRepository> {
+    inner: Box<R>,
+    cache: Arc<QueryCache>,
+}
+
+impl<R: ?Sized> InvalidatingUserRepository<R> {
+    pub fn new(inner: Box<R>, cache: Arc<QueryCache>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl<R: UserRepository + ?Sized> UserRepository for InvalidatingUserRepository<R> {
+    async fn save(&self, user: User) -> Result<(), RepositoryError> {
+        let id = user.user_id().clone();
+        let result = self.inner.save(user).await;
+        self.cache.invalidate_user(&id);
+        result
+    }
+
+    async fn save_with_outbox(
+        &self,
+        user: User,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        let id = user.user_id().clone();
+        let result = self.inner.save_with_outbox(user, messages).await;
+        self.cache.invalidate_user(&id);
+        result
+    }
+
+    async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, RepositoryError> {
+        self.inner.find_by_id(id).await
+    }
+
+    async fn find_many(&self, ids: &[UserId]) -> Result<Vec<Option<User>>, RepositoryError> {
+        self.inner.find_many(ids).await
+    }
+
+    async fn save_all(&self, users: Vec<User>) -> Result<(), RepositoryError> {
+        let ids: Vec<_> = users.iter().map(|u| u.user_id().clone()).collect();
+        let result = self.inner.save_all(users).await;
+        for id in &ids {
+            self.cache.invalidate_user(id);
+        }
+        result
+    }
+}
+
+/// 保存したボードと、その所有者のキャッシュを捨てる
+/// NOTE: ボードの所有者は変わらないので、保存するボードの所有者のみ捨てる。所有者を移せるようにする場合は、前の所有者を明示的に渡して捨てること
+pub struct InvalidatingBoardRepository<R: ?Sized = dyn BoardRepository> {
+    inner: Box<R>,
+    cache: Arc<QueryCache>,
+}
+
+impl<R: ?Sized> InvalidatingBoardRepository<R> {
+    pub fn new(inner: Box<R>, cache: Arc<QueryCache>) -> Self {
+        Self { inner, cache }
+    }
+
+    fn invalidate(&self, id: &BoardId, owner_id: &UserId) {
+        self.cache.invalidate_board(id);
+        self.cache.invalidate_user(owner_id);
+    }
+}
+
+#[async_trait]
+impl<R: BoardRepository + ?Sized> BoardRepository for InvalidatingBoardRepository<R> {
+    async fn save(&self, board: Board) -> Result<(), RepositoryError> {
+        let (id, owner_id) = (board.id().clone(), board.owner().clone());
+        let result = self.inner.save(board).await;
+        self.invalidate(&id, &owner_id);
+        result
+    }
+
+    async fn save_with_outbox(
+        &self,
+        board: Board,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        let (id, owner_id) = (board.id().clone(), board.owner().clone());
+        let result = self.inner.save_with_outbox(board, messages).await;
+        self.invalidate(&id, &owner_id);
+        result
+    }
+
+    async fn find_by_id(&self, id: &BoardId) -> Result<Option<Board>, RepositoryError> {
+        self.inner.find_by_id(id).await
+    }
+
+    async fn find_many(&self, ids: &[BoardId]) -> Result<Vec<Option<Board>>, RepositoryError> {
+        self.inner.find_many(ids).await
+    }
+}
+
+/// 保存したカラムと、その中のカードのキャッシュを捨てる
+pub struct InvalidatingColumnRepository<R: ?Sized = dyn ColumnRepository> {
+    inner: Box<R>,
+    cache: Arc<QueryCache>,
+}
+
+impl<R: ?Sized> InvalidatingColumnRepository<R> {
+    pub fn new(inner: Box<R>, cache: Arc<QueryCache>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl<R: ColumnRepository + ?Sized> ColumnRepository for InvalidatingColumnRepository<R> {
+    async fn save(&self, column: Column) -> Result<(), RepositoryError> {
+        let id = column.id().clone();
+        let result = self.inner.save(column).await;
+        self.cache.invalidate_column(&id);
+        result
+    }
+
+    async fn save_with_outbox(
+        &self,
+        column: Column,
+        messages: Vec<OutboxMessage>,
+    ) -> Result<(), RepositoryError> {
+        let id = column.id().clone();
+        let result = self.inner.save_with_outbox(column, messages).await;
+        self.cache.invalidate_column(&id);
+        result
+    }
+
+    async fn find_by_id(&self, id: &ColumnId) -> Result<Option<Column>, RepositoryError> {
+        self.inner.find_by_id(id).await
+    }
+}
@@ -0,0 +1,173 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// 件数の上限と有効期限のあるキャッシュ
+/// 有効期限はどのエントリも同じなので、追加した順に期限が切れる。上限を超えたら古いものから捨てる
+#[derive(Debug)]
+pub(crate) struct TtlCache<K, V> {
+    capacity: usize,
+    ttl: Duration,
+    inner: Mutex<Inner<K, V>>,
+}
+
+#[derive(Debug)]
+struct Inner<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    /// 追加した順のキー。上書き・削除されたものも残るので、`seq` で古いものを見分ける
+    order: VecDeque<(u64, K)>,
+    next_seq: u64,
+}
+
+#[derive(Debug)]
+struct Entry<V> {
+    seq: u64,
+    value: V,
+    expires_at: Instant,
+}
+
+impl<K, V> TtlCache<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    /// `capacity` が0の場合は何もキャッシュしない
+    pub(crate) fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            inner: Mutex::new(Inner {
+                entries: HashMap::new(),
+                order: VecDeque::new(),
+                next_seq: 0,
+            }),
+        }
+    }
+
+    pub(crate) fn get(&self, key: &K) -> Option<V> {
+        self.get_at(key, Instant::now())
+    }
+
+    pub(crate) fn insert(&self, key: K, value: V) {
+        self.insert_at(key, value, Instant::now())
+    }
+
+    pub(crate) fn remove(&self, key: &K) -> Option<V> {
+        self.lock().entries.remove(key).map(|e| e.value)
+    }
+
+    /// 条件にあうエントリをすべて削除する
+    pub(crate) fn remove_where(&self, f: impl Fn(&K, &V) -> bool) {
+        self.lock().entries.retain(|k, e| !f(k, &e.value));
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    fn get_at(&self, key: &K, now: Instant) -> Option<V> {
+        let mut inner = self.lock();
+        match inner.entries.get(key) {
+            Some(entry) if entry.expires_at > now => Some(entry.value.clone()),
+            Some(_) => {
+                inner.entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert_at(&self, key: K, value: V, now: Instant) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut inner = self.lock();
+        let seq = inner.next_seq;
+        inner.next_seq += 1;
+        let entry = Entry {
+            seq,
+            value,
+            expires_at: now + self.ttl,
+        };
+        inner.entries.insert(key.clone(), entry);
+        inner.order.push_back((seq, key));
+        inner.evict(self.capacity, now);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner<K, V>> {
+        // 更新中にパニックすることはないので、poisonされていてもそのまま使う
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<K: Eq + Hash, V> Inner<K, V> {
+    // 期限切れのものと、上限を超えた分を古い順に捨てる
+    fn evict(&mut self, capacity: usize, now: Instant) {
+        while let Some((seq, key)) = self.order.front() {
+            let current = self.entries.get(key).filter(|e| e.seq == *seq);
+            match current {
+                // 上書き・削除済み
+                None => {}
+                Some(entry) if entry.expires_at <= now || self.entries.len() > capacity => {
+                    self.entries.remove(key);
+                }
+                Some(_) => break,
+            }
+            self.order.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expired_entries_are_not_returned() {
+        // Arrange
+        let cache = TtlCache::new(10, Duration::from_secs(30));
+        let now = Instant::now();
+        cache.insert_at("board-01", 1, now);
+
+        // Act
+        let fresh = cache.get_at(&"board-01", now + Duration::from_secs(29));
+        let expired = cache.get_at(&"board-01", now + Duration::from_secs(30));
+
+        // Assert
+        assert_eq!(fresh, Some(1));
+        assert_eq!(expired, None);
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn test_oldest_entries_are_evicted_over_capacity() {
+        // Arrange
+        let cache = TtlCache::new(2, Duration::from_secs(30));
+        let now = Instant::now();
+        cache.insert_at("a", 1, now);
+        cache.insert_at("b", 2, now);
+        // 上書きしたものは、上書きした時点で追加したものとして扱う
+        cache.insert_at("a", 3, now);
+
+        // Act
+        cache.insert_at("c", 4, now);
+
+        // Assert
+        assert_eq!(cache.get_at(&"a", now), Some(3));
+        assert_eq!(cache.get_at(&"b", now), None);
+        assert_eq!(cache.get_at(&"c", now), Some(4));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_zero_capacity_disables_cache() {
+        let cache = TtlCache::new(0, Duration::from_secs(30));
+
+        cache.insert("a", 1);
+
+        assert_eq!(cache.get(&"a"), None);
+    }
+}
@@ -2,21 +2,53 @@ use std::collections::HashMap;
 
 use anyhow::Result;
 use async_trait::async_trait;
-use domain_kanban::column::ColumnId;
+use chrono::{DateTime, Utc};
+use domain_kanban::column::{CardId, ColumnId};
 use shaku::Interface;
 
+use crate::{ArchivedFilter, CardCriteria};
+
+/// `order` は `archived` で絞り込んだあとのカラム内での順番
 #[async_trait]
 pub trait CardsQuery: Interface {
-    async fn find_by_order(&self, column_id: &ColumnId, order: &usize) -> Result<CardView>;
+    async fn find_by_order(
+        &self,
+        column_id: &ColumnId,
+        order: &usize,
+        archived: ArchivedFilter,
+    ) -> Result<CardView>;
     async fn list_by_orders(
         &self,
         column_id: &ColumnId,
         orders: &[usize],
+        archived: ArchivedFilter,
     ) -> Result<HashMap<usize, CardView>>;
+    /// カラムごとに `criteria` で絞り込んで並べる。カードのないカラムは含めない
+    async fn list_by_column_ids(
+        &self,
+        ids: &[ColumnId],
+        criteria: &CardCriteria,
+    ) -> Result<HashMap<ColumnId, Vec<CardView>>>;
+    /// アーカイブされたものも含めて返す。見つからなかったIDは含めない
+    async fn list_by_ids(&self, ids: &[CardId]) -> Result<HashMap<CardId, CardView>>;
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct CardView {
     pub id: String,
     pub title: String,
     pub description: String,
+    /// カードの入っているカラム
+    pub column_id: String,
+    /// 表示順に並んだチェックリスト
+    pub checklist: Vec<ChecklistItemView>,
+    pub archived_at: Option<DateTime<Utc>>,
+    /// 期限。`None` は期限なし
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChecklistItemView {
+    pub text: String,
+    pub done: bool,
 }
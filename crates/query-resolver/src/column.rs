@@ -2,17 +2,23 @@ use std::collections::HashMap;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use domain_kanban::column::ColumnId;
 use shaku::Interface;
 
+/// IDを指定して取得するときは、アーカイブ済みのものも返す
 #[async_trait]
 pub trait ColumnsQuery: Interface {
     async fn find_by_id(&self, id: &ColumnId) -> Result<ColumnView>;
     async fn list_by_ids(&self, ids: &[ColumnId]) -> Result<HashMap<ColumnId, ColumnView>>;
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct ColumnView {
     pub id: String,
     pub title: String,
+    /// アーカイブされていないカードの数
     pub card_cnt: usize,
+    pub archived_card_cnt: usize,
+    pub archived_at: Option<DateTime<Utc>>,
 }
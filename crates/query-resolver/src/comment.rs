@@ -0,0 +1,25 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain_kanban::column::CardId;
+use shaku::Interface;
+
+#[async_trait]
+pub trait CommentsQuery: Interface {
+    /// カードごとのコメントを投稿された順に返す。コメントがないカードはキーに含まれない
+    async fn list_by_card_ids(
+        &self,
+        card_ids: &[CardId],
+    ) -> Result<HashMap<CardId, Vec<CommentView>>>;
+}
+
+pub struct CommentView {
+    pub id: String,
+    pub card_id: String,
+    pub author_id: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,338 @@
+// 一覧を取得するときの絞り込みと並び順
+// SQLで絞り込むバックエンドも、ここの `matches` / `sort` と同じ結果になるようにする
+// 文字列の比較は大文字・小文字を区別し、並び順はバイト順(Postgresでは `collate "C"`)にする
+use std::cmp::Ordering;
+
+use chrono::{DateTime, Utc};
+use domain_kanban::user::UserId;
+
+use crate::{ArchivedFilter, BoardView, CardView, UserView};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    pub fn is_desc(&self) -> bool {
+        matches!(self, Self::Desc)
+    }
+
+    fn apply(&self, ordering: Ordering) -> Ordering {
+        match self {
+            Self::Asc => ordering,
+            Self::Desc => ordering.reverse(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct UserCriteria {
+    pub name_prefix: Option<String>,
+    /// `@` より後ろと一致するもの。ドメインは大文字・小文字を区別しない
+    pub email_domain: Option<String>,
+    pub sort: UserSort,
+}
+
+/// 同じ値のものはIDの順に並べる
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct UserSort {
+    pub key: UserSortKey,
+    pub direction: SortDirection,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum UserSortKey {
+    #[default]
+    Id,
+    Name,
+    Email,
+}
+
+impl UserSortKey {
+    /// SQLのパラメーターとして渡す値
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::Name => "name",
+            Self::Email => "email",
+        }
+    }
+}
+
+impl UserCriteria {
+    pub fn matches(&self, user: &UserView) -> bool {
+        let name_matches = self
+            .name_prefix
+            .as_ref()
+            .map_or(true, |prefix| user.name.starts_with(prefix.as_str()));
+        let email_matches = self.email_domain.as_ref().map_or(true, |domain| {
+            user.email
+                .split_once('@')
+                .map_or(false, |(_, d)| d.eq_ignore_ascii_case(domain))
+        });
+        name_matches && email_matches
+    }
+
+    pub fn sort(&self, users: &mut [UserView]) {
+        let UserSort { key, direction } = self.sort;
+        users.sort_by(|a, b| {
+            let ordering = match key {
+                UserSortKey::Id => Ordering::Equal,
+                UserSortKey::Name => a.name.cmp(&b.name),
+                UserSortKey::Email => a.email.cmp(&b.email),
+            };
+            direction.apply(ordering.then_with(|| a.id.cmp(&b.id)))
+        });
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BoardCriteria {
+    pub title_contains: Option<String>,
+    pub owner_id: Option<UserId>,
+    /// メンバーになっているボード。所有しているだけのボードは含めない
+    pub member_id: Option<UserId>,
+    pub archived: ArchivedFilter,
+    pub sort: BoardSort,
+}
+
+/// 同じ値のものはIDの順に並べる
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct BoardSort {
+    pub key: BoardSortKey,
+    pub direction: SortDirection,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BoardSortKey {
+    #[default]
+    Id,
+    Title,
+}
+
+impl BoardSortKey {
+    /// SQLのパラメーターとして渡す値
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::Title => "title",
+        }
+    }
+}
+
+impl BoardCriteria {
+    /// `member_ids` はボードのメンバーのID
+    pub fn matches(&self, board: &BoardView, member_ids: &[UserId]) -> bool {
+        let archived_matches = self.archived.includes_archived() || board.archived_at.is_none();
+        let title_matches = self
+            .title_contains
+            .as_ref()
+            .map_or(true, |s| board.title.contains(s.as_str()));
+        let owner_matches = self
+            .owner_id
+            .as_ref()
+            .map_or(true, |id| board.owner_id == id.to_string());
+        let member_matches = self
+            .member_id
+            .as_ref()
+            .map_or(true, |id| member_ids.contains(id));
+        archived_matches && title_matches && owner_matches && member_matches
+    }
+
+    pub fn sort(&self, boards: &mut [BoardView]) {
+        let BoardSort { key, direction } = self.sort;
+        boards.sort_by(|a, b| {
+            let ordering = match key {
+                BoardSortKey::Id => Ordering::Equal,
+                BoardSortKey::Title => a.title.cmp(&b.title),
+            };
+            direction.apply(ordering.then_with(|| a.id.cmp(&b.id)))
+        });
+    }
+}
+
+/// アーカイブされているかどうか
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CardStatus {
+    Active,
+    Archived,
+}
+
+impl CardStatus {
+    pub fn is_archived(&self) -> bool {
+        matches!(self, Self::Archived)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CardCriteria {
+    pub title_contains: Option<String>,
+    /// 指定しない場合はアーカイブされたものも含める
+    pub status: Option<CardStatus>,
+    /// 期限がこの日時より前のもの。期限のないものは含めない
+    pub due_before: Option<DateTime<Utc>>,
+    pub sort: CardSort,
+}
+
+/// 同じ値のものはカラム内の並び順に並べる
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CardSort {
+    pub key: CardSortKey,
+    pub direction: SortDirection,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CardSortKey {
+    /// カラム内の並び順
+    #[default]
+    Position,
+    Title,
+}
+
+impl CardSortKey {
+    /// SQLのパラメーターとして渡す値
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Position => "position",
+            Self::Title => "title",
+        }
+    }
+}
+
+impl CardCriteria {
+    pub fn matches(&self, card: &CardView) -> bool {
+        let title_matches = self
+            .title_contains
+            .as_ref()
+            .map_or(true, |s| card.title.contains(s.as_str()));
+        let status_matches = self
+            .status
+            .map_or(true, |s| s.is_archived() == card.archived_at.is_some());
+        let due_matches = self.due_before.as_ref().map_or(true, |before| {
+            card.due_at.as_ref().map_or(false, |due_at| due_at < before)
+        });
+        title_matches && status_matches && due_matches
+    }
+
+    /// `cards` はカラム内の並び順に並んでいること
+    pub fn sort(&self, cards: &mut [CardView]) {
+        let CardSort { key, direction } = self.sort;
+        // 安定ソートなので、同じ値のものは並び順のまま残る
+        match key {
+            CardSortKey::Position if direction.is_desc() => cards.reverse(),
+            CardSortKey::Position => {}
+            CardSortKey::Title => cards.sort_by(|a, b| direction.apply(a.title.cmp(&b.title))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(id: &str, name: &str, email: &str) -> UserView {
+        UserView {
+            id: id.to_owned(),
+            name: name.to_owned(),
+            email: email.to_owned(),
+            owned_board_ids: vec![],
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn test_user_criteria_matches() {
+        let criteria = UserCriteria {
+            name_prefix: Some("al".to_owned()),
+            email_domain: Some("Example.com".to_owned()),
+            ..Default::default()
+        };
+
+        assert!(criteria.matches(&user("user-1", "alice", "alice@example.com")));
+        assert!(!criteria.matches(&user("user-2", "Alice", "alice@example.com")));
+        assert!(!criteria.matches(&user("user-3", "alice", "alice@example.org")));
+        assert!(!criteria.matches(&user("user-4", "alice", "alice@sub.example.com")));
+    }
+
+    #[test]
+    fn test_user_sort_breaks_ties_by_id() {
+        // Arrange
+        let criteria = UserCriteria {
+            sort: UserSort {
+                key: UserSortKey::Name,
+                direction: SortDirection::Desc,
+            },
+            ..Default::default()
+        };
+        let mut users = vec![
+            user("user-1", "bob", "a@example.com"),
+            user("user-2", "alice", "b@example.com"),
+            user("user-3", "bob", "c@example.com"),
+        ];
+
+        // Act
+        criteria.sort(&mut users);
+
+        // Assert
+        let ids: Vec<_> = users.iter().map(|u| u.id.as_str()).collect();
+        assert_eq!(ids, vec!["user-3", "user-1", "user-2"]);
+    }
+
+    #[test]
+    fn test_card_sort_by_title_keeps_position_for_ties() {
+        // Arrange
+        let card = |id: &str, title: &str| CardView {
+            id: id.to_owned(),
+            title: title.to_owned(),
+            description: "".to_owned(),
+            column_id: "column-1".to_owned(),
+            checklist: vec![],
+            archived_at: None,
+            due_at: None,
+        };
+        let criteria = CardCriteria {
+            sort: CardSort {
+                key: CardSortKey::Title,
+                direction: SortDirection::Asc,
+            },
+            ..Default::default()
+        };
+        let mut cards = vec![
+            card("card-3", "b"),
+            card("card-1", "a"),
+            card("card-2", "b"),
+        ];
+
+        // Act
+        criteria.sort(&mut cards);
+
+        // Assert
+        let ids: Vec<_> = cards.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["card-1", "card-3", "card-2"]);
+    }
+
+    #[test]
+    fn test_card_criteria_due_before() {
+        let card = |due_at: Option<&str>| CardView {
+            id: "card-1".to_owned(),
+            title: "a".to_owned(),
+            description: "".to_owned(),
+            column_id: "column-1".to_owned(),
+            checklist: vec![],
+            archived_at: None,
+            due_at: due_at.map(|d| d.parse().unwrap()),
+        };
+        let criteria = CardCriteria {
+            due_before: Some("2026-10-20T00:00:00Z".parse().unwrap()),
+            ..Default::default()
+        };
+
+        assert!(criteria.matches(&card(Some("2026-10-19T23:59:59Z"))));
+        assert!(!criteria.matches(&card(Some("2026-10-20T00:00:00Z"))));
+        assert!(!criteria.matches(&card(None)));
+        assert!(CardCriteria::default().matches(&card(None)));
+    }
+}
@@ -1,9 +1,20 @@
+mod activity;
+mod archive;
 mod board;
+pub mod cache;
 mod card;
 mod column;
+mod comment;
+mod criteria;
+mod search;
 mod user;
 
+pub use activity::*;
+pub use archive::*;
 pub use board::*;
 pub use card::*;
 pub use column::*;
+pub use comment::*;
+pub use criteria::*;
+pub use search::*;
 pub use user::*;
@@ -0,0 +1,122 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use shaku::Interface;
+
+/// ボード・カラム・カードを横断して検索する。アーカイブされたものは含めない
+#[async_trait]
+pub trait SearchQuery: Interface {
+    /// 関連度の高い順に返す。同じ関連度のものはIDの順に並べる
+    async fn search(&self, page: &SearchPage) -> Result<Vec<SearchHit>>;
+}
+
+pub struct SearchPage {
+    /// 前後の空白を除いた、空でない検索語
+    pub query: String,
+    pub first: usize,
+    /// 関連度の順で、先頭から読み飛ばす件数
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchHitKind {
+    Board,
+    Column,
+    Card,
+}
+
+impl SearchHitKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Board => "board",
+            Self::Column => "column",
+            Self::Card => "card",
+        }
+    }
+}
+
+impl std::str::FromStr for SearchHitKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "board" => Ok(Self::Board),
+            "column" => Ok(Self::Column),
+            "card" => Ok(Self::Card),
+            _ => Err(anyhow::anyhow!("unknown search hit kind: {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub kind: SearchHitKind,
+    pub id: String,
+    /// 大きいほど関連度が高い
+    pub score: f64,
+}
+
+/// 全文検索の索引を持たないバックエンドの関連度
+/// 大文字・小文字を区別しない部分一致で、タイトルに含むものを説明に含むものより上にする
+/// 含まない場合はNone
+pub fn substring_score(query: &str, title: &str, description: Option<&str>) -> Option<f64> {
+    let query = query.to_lowercase();
+    let title = title.to_lowercase();
+    if title.contains(&query) {
+        // タイトルのうち検索語の占める割合が大きいほど上にする
+        let coverage = query.chars().count() as f64 / title.chars().count() as f64;
+        return Some(1.0 + coverage);
+    }
+    description
+        .filter(|d| d.to_lowercase().contains(&query))
+        .map(|_| 0.5)
+}
+
+/// 関連度の高い順、同じ関連度ならIDの順に並べて `page` の範囲を返す
+pub fn paginate_hits(mut hits: Vec<SearchHit>, page: &SearchPage) -> Vec<SearchHit> {
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.id.cmp(&b.id)));
+    hits.into_iter()
+        .skip(page.offset)
+        .take(page.first)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substring_score() {
+        let exact = substring_score("ゴミ出し", "ゴミ出し", None);
+        let partial = substring_score("ゴミ", "ゴミ出しの日", None);
+        let description = substring_score("trash", "ゴミ出し", Some("Take out the Trash"));
+        let missing = substring_score("洗濯", "ゴミ出し", Some("燃えるゴミ"));
+
+        assert_eq!(exact, Some(2.0));
+        assert!(partial.unwrap() > 1.0 && partial.unwrap() < 2.0);
+        assert_eq!(description, Some(0.5));
+        assert_eq!(missing, None);
+    }
+
+    #[test]
+    fn test_paginate_hits() {
+        // Arrange
+        let hit = |id: &str, score: f64| SearchHit {
+            kind: SearchHitKind::Card,
+            id: id.to_owned(),
+            score,
+        };
+        let hits = vec![hit("card-2", 0.5), hit("card-3", 1.5), hit("card-1", 0.5)];
+        let page = SearchPage {
+            query: "q".to_owned(),
+            first: 2,
+            offset: 1,
+        };
+
+        // Act
+        let result = paginate_hits(hits, &page);
+
+        // Assert
+        let ids: Vec<_> = result.iter().map(|h| h.id.as_str()).collect();
+        assert_eq!(ids, vec!["card-1", "card-2"]);
+    }
+}
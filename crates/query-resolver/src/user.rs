@@ -5,16 +5,20 @@ use async_trait::async_trait;
 use domain_kanban::user::UserId;
 use shaku::Interface;
 
+use crate::UserCriteria;
+
 #[async_trait]
 pub trait UsersQuery: Interface {
     async fn find_by_id(&self, id: &UserId) -> Result<UserView>;
     async fn list_by_ids(&self, ids: &[UserId]) -> Result<HashMap<UserId, UserView>>;
-    async fn all(&self) -> Result<Vec<UserView>>;
+    async fn all(&self, criteria: &UserCriteria) -> Result<Vec<UserView>>;
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct UserView {
     pub id: String,
     pub name: String,
     pub email: String,
     pub owned_board_ids: Vec<String>,
+    pub version: u64,
 }
@@ -0,0 +1,178 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::BreakerPolicy;
+
+/// サーキットブレーカーの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// 通常どおり呼び出す
+    Closed,
+    /// バックエンドが落ちているとみなし、呼び出さずに失敗させる
+    Open,
+    /// 復旧したかを確かめるために、1つだけ呼び出している
+    HalfOpen,
+}
+
+#[derive(Debug)]
+enum State {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen { since: Instant },
+}
+
+/// 呼び出しの成否を記録したときに起きた状態の変化
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Transition {
+    Opened,
+    Closed,
+}
+
+/// 連続して一時的なエラーになったら開き、しばらくの間は呼び出さずに失敗させる
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    policy: BreakerPolicy,
+    state: Mutex<State>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(policy: BreakerPolicy) -> Self {
+        Self {
+            policy,
+            state: Mutex::new(State::Closed { failures: 0 }),
+        }
+    }
+
+    pub(crate) fn state(&self) -> BreakerState {
+        match *self.lock() {
+            State::Closed { .. } => BreakerState::Closed,
+            State::Open { .. } => BreakerState::Open,
+            State::HalfOpen { .. } => BreakerState::HalfOpen,
+        }
+    }
+
+    /// 呼び出してよいか。開いている間はfalseを返す
+    /// 開いてから `open_duration` が経った後は、1つだけ試しに呼び出させる
+    pub(crate) fn try_acquire(&self) -> bool {
+        self.try_acquire_at(Instant::now())
+    }
+
+    pub(crate) fn on_success(&self) -> Option<Transition> {
+        let mut state = self.lock();
+        let transition = match *state {
+            State::Closed { .. } => None,
+            State::Open { .. } | State::HalfOpen { .. } => Some(Transition::Closed),
+        };
+        *state = State::Closed { failures: 0 };
+        transition
+    }
+
+    /// 一時的なエラーになったことを記録する
+    pub(crate) fn on_failure(&self) -> Option<Transition> {
+        self.on_failure_at(Instant::now())
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> bool {
+        let mut state = self.lock();
+        match *state {
+            State::Closed { .. } => true,
+            State::Open { until } if now >= until => {
+                *state = State::HalfOpen { since: now };
+                true
+            }
+            State::Open { .. } => false,
+            // 試しの呼び出しが結果を記録せずに中断された場合に備えて、時間が経ったらもう一度試す
+            State::HalfOpen { since } if now.duration_since(since) >= self.policy.open_duration => {
+                *state = State::HalfOpen { since: now };
+                true
+            }
+            State::HalfOpen { .. } => false,
+        }
+    }
+
+    fn on_failure_at(&self, now: Instant) -> Option<Transition> {
+        let mut state = self.lock();
+        let failures = match *state {
+            State::Closed { failures } => failures + 1,
+            State::HalfOpen { .. } => self.policy.failure_threshold,
+            State::Open { .. } => return None,
+        };
+        if failures < self.policy.failure_threshold {
+            *state = State::Closed { failures };
+            return None;
+        }
+        *state = State::Open {
+            until: now + self.policy.open_duration,
+        };
+        Some(Transition::Opened)
+    }
+
+    pub(crate) fn open_duration(&self) -> Duration {
+        self.policy.open_duration
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        // 状態の更新中にパニックすることはないので、poisonされていてもそのまま使う
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(BreakerPolicy {
+            failure_threshold: 3,
+            open_duration: Duration::from_secs(10),
+        })
+    }
+
+    #[test]
+    fn test_opens_after_consecutive_failures() {
+        // Arrange
+        let breaker = breaker();
+        let now = Instant::now();
+
+        // Act
+        let first = breaker.on_failure_at(now);
+        breaker.on_success();
+        let after_success = [breaker.on_failure_at(now), breaker.on_failure_at(now)];
+        let third = breaker.on_failure_at(now);
+
+        // Assert
+        assert_eq!(first, None);
+        assert_eq!(after_success, [None, None]);
+        assert_eq!(third, Some(Transition::Opened));
+        assert_eq!(breaker.state(), BreakerState::Open);
+        assert!(!breaker.try_acquire_at(now + Duration::from_secs(9)));
+    }
+
+    #[test]
+    fn test_half_open_allows_one_probe() {
+        // Arrange
+        let breaker = breaker();
+        let now = Instant::now();
+        for _ in 0..3 {
+            breaker.on_failure_at(now);
+        }
+        let later = now + Duration::from_secs(10);
+
+        // Act
+        let probe = breaker.try_acquire_at(later);
+        let concurrent = breaker.try_acquire_at(later);
+        let reopened = breaker.on_failure_at(later);
+        let next_probe = breaker.try_acquire_at(later + Duration::from_secs(10));
+        let closed = breaker.on_success();
+
+        // Assert
+        assert!(probe);
+        assert!(!concurrent);
+        assert_eq!(reopened, Some(Transition::Opened));
+        assert!(next_probe);
+        assert_eq!(closed, Some(Transition::Closed));
+        assert_eq!(breaker.state(), BreakerState::Closed);
+    }
+}
@@ -0,0 +1,318 @@
+// バックエンド(Postgres・DynamoDB)の呼び出しに、タイムアウト・再試行・サーキットブレーカーを適用する
+// 一時的なエラー(`RepositoryError::Transient`)と結果が不明なエラー(`RepositoryError::Indeterminate`)のみを再試行・遮断の対象にし、
+// それ以外のエラーはそのまま返す
+mod breaker;
+mod policy;
+
+pub use breaker::BreakerState;
+pub use policy::*;
+
+use std::{
+    future::Future,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use breaker::{CircuitBreaker, Transition};
+use domain_util::RepositoryError;
+use tokio::time::{sleep, timeout};
+
+/// バックエンドごとに1つ作り、そのバックエンドへの全ての呼び出しで共有する
+#[derive(Debug)]
+pub struct Resilience {
+    backend: &'static str,
+    policy: ResiliencePolicy,
+    breaker: CircuitBreaker,
+    counters: Counters,
+}
+
+/// これまでの呼び出しの集計
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResilienceMetrics {
+    pub backend: &'static str,
+    pub state: BreakerState,
+    pub calls: u64,
+    pub retries: u64,
+    pub timeouts: u64,
+    /// ブレーカーが開いていたために呼び出さなかった回数
+    pub rejected: u64,
+    /// ブレーカーが開いた回数
+    pub trips: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    calls: AtomicU64,
+    retries: AtomicU64,
+    timeouts: AtomicU64,
+    rejected: AtomicU64,
+    trips: AtomicU64,
+}
+
+impl Resilience {
+    pub fn new(backend: &'static str, policy: ResiliencePolicy) -> Self {
+        Self {
+            backend,
+            policy,
+            breaker: CircuitBreaker::new(policy.breaker),
+            counters: Counters::default(),
+        }
+    }
+
+    pub fn backend(&self) -> &'static str {
+        self.backend
+    }
+
+    pub fn policy(&self) -> &ResiliencePolicy {
+        &self.policy
+    }
+
+    /// 冪等な呼び出し。タイムアウトなど、反映されたかわからない場合も再試行する
+    pub async fn read<T, F, Fut>(&self, call: F) -> Result<T, RepositoryError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, RepositoryError>>,
+    {
+        self.execute(true, call).await
+    }
+
+    /// 書き込み。タイムアウトや接続の切断など、反映されたかわからない場合は再試行しない
+    /// 反映されていないことがわかっている一時的なエラー(スロットリングなど)のみ再試行する
+    pub async fn write<T, F, Fut>(&self, call: F) -> Result<T, RepositoryError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, RepositoryError>>,
+    {
+        self.execute(false, call).await
+    }
+
+    pub fn metrics(&self) -> ResilienceMetrics {
+        ResilienceMetrics {
+            backend: self.backend,
+            state: self.breaker.state(),
+            calls: self.counters.calls.load(Ordering::Relaxed),
+            retries: self.counters.retries.load(Ordering::Relaxed),
+            timeouts: self.counters.timeouts.load(Ordering::Relaxed),
+            rejected: self.counters.rejected.load(Ordering::Relaxed),
+            trips: self.counters.trips.load(Ordering::Relaxed),
+        }
+    }
+
+    async fn execute<T, F, Fut>(&self, idempotent: bool, mut call: F) -> Result<T, RepositoryError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, RepositoryError>>,
+    {
+        self.counters.calls.fetch_add(1, Ordering::Relaxed);
+        let mut attempts = 0;
+        loop {
+            if !self.breaker.try_acquire() {
+                self.counters.rejected.fetch_add(1, Ordering::Relaxed);
+                return Err(RepositoryError::Transient(format!(
+                    "{} is unavailable (circuit breaker is open)",
+                    self.backend
+                )));
+            }
+            attempts += 1;
+            let result = match timeout(self.policy.timeout, call()).await {
+                Ok(result) => result,
+                Err(_) => {
+                    self.counters.timeouts.fetch_add(1, Ordering::Relaxed);
+                    Err(RepositoryError::Indeterminate(format!(
+                        "{} did not respond within {:?}",
+                        self.backend, self.policy.timeout
+                    )))
+                }
+            };
+            let error = match result {
+                Err(e @ (RepositoryError::Transient(_) | RepositoryError::Indeterminate(_))) => e,
+                // 一時的でないエラーは、バックエンドが応答できている
+                result => {
+                    self.record(self.breaker.on_success());
+                    return result;
+                }
+            };
+            self.record(self.breaker.on_failure());
+            let retryable = idempotent || !matches!(error, RepositoryError::Indeterminate(_));
+            if !retryable || attempts >= self.policy.retry.max_attempts {
+                return Err(error);
+            }
+            self.counters.retries.fetch_add(1, Ordering::Relaxed);
+            let delay = self.policy.retry.backoff(attempts);
+            tracing::debug!(backend = self.backend, attempts, ?delay, %error, "retrying");
+            sleep(delay).await;
+        }
+    }
+
+    fn record(&self, transition: Option<Transition>) {
+        match transition {
+            Some(Transition::Opened) => {
+                let trips = self.counters.trips.fetch_add(1, Ordering::Relaxed) + 1;
+                tracing::warn!(
+                    backend = self.backend,
+                    trips,
+                    open_for = ?self.breaker.open_duration(),
+                    "circuit breaker opened"
+                );
+            }
+            Some(Transition::Closed) => {
+                tracing::info!(backend = self.backend, "circuit breaker closed");
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::atomic::AtomicU32, time::Duration};
+
+    use super::*;
+
+    fn resilience() -> Resilience {
+        Resilience::new(
+            "test",
+            ResiliencePolicy {
+                timeout: Duration::from_millis(50),
+                retry: RetryPolicy {
+                    max_attempts: 3,
+                    base_delay: Duration::from_millis(1),
+                    max_delay: Duration::from_millis(5),
+                },
+                breaker: BreakerPolicy {
+                    failure_threshold: 3,
+                    open_duration: Duration::from_secs(60),
+                },
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_read_retries_transient_errors() {
+        // Arrange
+        let resilience = resilience();
+        let calls = AtomicU32::new(0);
+
+        // Act
+        let result = resilience
+            .read(|| async {
+                match calls.fetch_add(1, Ordering::SeqCst) {
+                    0 => Err(RepositoryError::Transient("throttled".to_owned())),
+                    _ => Ok("ok"),
+                }
+            })
+            .await;
+
+        // Assert
+        assert_eq!(result, Ok("ok"));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let metrics = resilience.metrics();
+        assert_eq!(metrics.retries, 1);
+        assert_eq!(metrics.state, BreakerState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_other_errors_are_not_retried() {
+        // Arrange
+        let resilience = resilience();
+        let calls = AtomicU32::new(0);
+
+        // Act
+        let result: Result<(), _> = resilience
+            .read(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(RepositoryError::NotFound("user-01".to_owned()))
+            })
+            .await;
+
+        // Assert
+        assert_eq!(result, Err(RepositoryError::NotFound("user-01".to_owned())));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_write_does_not_retry_timeouts() {
+        // Arrange
+        let resilience = resilience();
+        let applied = AtomicU32::new(0);
+
+        // Act
+        // 反映されたあとで、応答が返る前にタイムアウトする
+        let result = resilience
+            .write(|| async {
+                applied.fetch_add(1, Ordering::SeqCst);
+                sleep(Duration::from_secs(1)).await;
+                Ok(())
+            })
+            .await;
+
+        // Assert
+        assert!(matches!(result, Err(RepositoryError::Indeterminate(_))));
+        assert_eq!(applied.load(Ordering::SeqCst), 1);
+        let metrics = resilience.metrics();
+        assert_eq!(metrics.timeouts, 1);
+        assert_eq!(metrics.retries, 0);
+    }
+
+    #[tokio::test]
+    async fn test_indeterminate_errors_are_retried_only_for_reads() {
+        // Arrange
+        let resilience = resilience();
+        let reads = AtomicU32::new(0);
+        let writes = AtomicU32::new(0);
+
+        // Act
+        let read = resilience
+            .read(|| async {
+                match reads.fetch_add(1, Ordering::SeqCst) {
+                    0 => Err(RepositoryError::Indeterminate(
+                        "connection reset".to_owned(),
+                    )),
+                    _ => Ok("ok"),
+                }
+            })
+            .await;
+        let write: Result<(), _> = resilience
+            .write(|| async {
+                writes.fetch_add(1, Ordering::SeqCst);
+                Err(RepositoryError::Indeterminate(
+                    "connection reset".to_owned(),
+                ))
+            })
+            .await;
+
+        // Assert
+        assert_eq!(read, Ok("ok"));
+        assert_eq!(reads.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            write,
+            Err(RepositoryError::Indeterminate(
+                "connection reset".to_owned()
+            ))
+        );
+        assert_eq!(writes.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_open_breaker_fails_fast() {
+        // Arrange
+        let resilience = resilience();
+        let calls = AtomicU32::new(0);
+        let unavailable = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(RepositoryError::Transient("connection refused".to_owned()))
+        };
+
+        // Act
+        let first = resilience.read(unavailable).await;
+        let second = resilience.read(unavailable).await;
+
+        // Assert
+        assert!(first.is_err());
+        assert!(matches!(second, Err(RepositoryError::Transient(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        let metrics = resilience.metrics();
+        assert_eq!(metrics.state, BreakerState::Open);
+        assert_eq!(metrics.trips, 1);
+        assert_eq!(metrics.rejected, 1);
+    }
+}
@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+/// バックエンドの呼び出しに適用するタイムアウト・再試行・遮断の設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResiliencePolicy {
+    /// 1回の呼び出しのタイムアウト
+    pub timeout: Duration,
+    pub retry: RetryPolicy,
+    pub breaker: BreakerPolicy,
+}
+
+impl Default for ResiliencePolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(3),
+            retry: RetryPolicy::default(),
+            breaker: BreakerPolicy::default(),
+        }
+    }
+}
+
+/// 一時的なエラーを再試行する間隔
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// 最初の呼び出しを含めた試行回数。1なら再試行しない
+    pub max_attempts: u32,
+    /// 最初の再試行までの待ち時間の上限。再試行するたびに倍にする
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// `retries` 回目の再試行までの待ち時間
+    /// 同時に失敗した呼び出しが一斉に再試行しないように、上限までの間でランダムにする(full jitter)
+    pub fn backoff(&self, retries: u32) -> Duration {
+        let exponent = retries.saturating_sub(1).min(16);
+        let cap = self
+            .base_delay
+            .saturating_mul(2_u32.pow(exponent))
+            .min(self.max_delay);
+        cap.mul_f64(rand::thread_rng().gen::<f64>())
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(50),
+            max_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+/// サーキットブレーカーを開く条件と、開いたままにする時間
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BreakerPolicy {
+    /// 連続してこの回数だけ一時的なエラーになったら開く
+    pub failure_threshold: u32,
+    /// 開いてから、次に試しに呼び出すまでの時間
+    pub open_duration: Duration,
+}
+
+impl Default for BreakerPolicy {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(10),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(300),
+        };
+
+        for retries in 1..10 {
+            let delay = policy.backoff(retries);
+            let cap = Duration::from_millis(100 * 2_u64.pow(retries - 1)).min(policy.max_delay);
+            assert!(delay <= cap, "{:?} > {:?}", delay, cap);
+        }
+    }
+}
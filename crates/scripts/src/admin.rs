@@ -0,0 +1,452 @@
+//! 管理用の操作。ドメインのコンストラクタとリポジトリを通して、不変条件を守ったまま変更する
+//!
+//! NOTE: 一覧とカードの場所はリードモデルから読むので、書き込み側の変更が反映されるまでは古いことがある
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use domain_kanban::{
+    board::{Board, BoardId, BoardRepository, BoardTitle},
+    column::{
+        Card, CardDescription, CardId, CardTitle, Column, ColumnId, ColumnRepository, ColumnTitle,
+    },
+    comment::{Comment, CommentId, CommentRepository},
+    user::{Email, User, UserId, UserName, UserRepository},
+};
+use domain_util::{Entity, RepositoryError};
+use query_resolver::{
+    ArchivedFilter, BoardCriteria, BoardQuery, BoardView, CardsQuery, UserCriteria, UserView,
+    UsersQuery,
+};
+use shaku::HasProvider;
+
+pub struct Admin {
+    user_repository: Box<dyn UserRepository>,
+    board_repository: Box<dyn BoardRepository>,
+    column_repository: Box<dyn ColumnRepository>,
+    comment_repository: Box<dyn CommentRepository>,
+    users_query: Box<dyn UsersQuery>,
+    board_query: Box<dyn BoardQuery>,
+    cards_query: Box<dyn CardsQuery>,
+}
+
+impl Admin {
+    pub fn new<Q, R>(query_module: &Q, repository_module: &R) -> Result<Self>
+    where
+        Q: HasProvider<dyn UsersQuery> + HasProvider<dyn BoardQuery> + HasProvider<dyn CardsQuery>,
+        R: HasProvider<dyn UserRepository>
+            + HasProvider<dyn BoardRepository>
+            + HasProvider<dyn ColumnRepository>
+            + HasProvider<dyn CommentRepository>,
+    {
+        Ok(Self {
+            user_repository: provide(repository_module)?,
+            board_repository: provide(repository_module)?,
+            column_repository: provide(repository_module)?,
+            comment_repository: provide(repository_module)?,
+            users_query: provide(query_module)?,
+            board_query: provide(query_module)?,
+            cards_query: provide(query_module)?,
+        })
+    }
+
+    /// 同じメールアドレスのユーザーがいる場合は作らない
+    ///
+    /// NOTE: 重複はリードモデルで確かめるので、反映される前に同じメールアドレスで作ると重複しうる
+    /// 書き込み側がPostgresの場合は `users.email` のUNIQUE制約で保存に失敗するが、
+    /// 制約は大文字・小文字を区別し、DynamoDBの書き込み側には制約がない
+    pub async fn create_user(&self, name: &str, email: &str) -> Result<User> {
+        let user = User::new(
+            UserName::new(name.to_owned())?,
+            Email::new(email.to_owned())?,
+        )?;
+        if let Some(existing) = self.find_user_by_email(email).await? {
+            return Err(anyhow!("{} はすでに {} が使っています", email, existing.id));
+        }
+        self.user_repository.save(user.clone()).await?;
+        Ok(user)
+    }
+
+    pub async fn rename_user(&self, id: &str, name: &str) -> Result<User> {
+        let id: UserId = id.parse()?;
+        let name = UserName::new(name.to_owned())?;
+        let mut user = self.find_user(&id).await?;
+        user.update_name(name);
+        self.user_repository.save(user.clone()).await?;
+        Ok(user)
+    }
+
+    pub async fn list_users(&self) -> Result<Vec<UserView>> {
+        self.users_query.all(&UserCriteria::default()).await
+    }
+
+    /// メールアドレスは大文字・小文字を区別せずに比べる
+    pub async fn find_user_by_email(&self, email: &str) -> Result<Option<UserView>> {
+        let users = self.list_users().await?;
+        let result = users
+            .into_iter()
+            .find(|u| u.email.eq_ignore_ascii_case(email));
+        Ok(result)
+    }
+
+    /// `column_titles` の順にカラムを作る
+    pub async fn create_board(
+        &self,
+        owner_id: &str,
+        title: &str,
+        column_titles: &[String],
+    ) -> Result<Board> {
+        let owner_id: UserId = owner_id.parse()?;
+        self.find_user(&owner_id).await?;
+        let columns: Vec<_> = column_titles
+            .iter()
+            .map(|t| Column::new(ColumnTitle::new(t.clone())))
+            .collect();
+        let column_ids = columns.iter().map(|c| c.id().clone()).collect();
+        // カラムを保存する前に、ボードの不変条件を確かめる
+        let board = Board::new(
+            BoardId::gen(),
+            BoardTitle::new(title.to_owned())?,
+            owner_id,
+            vec![],
+            column_ids,
+        )?;
+
+        // ボードが存在しないカラムを指さないように、カラムから保存する
+        for column in columns {
+            self.column_repository.save(column).await?;
+        }
+        self.board_repository.save(board.clone()).await?;
+        Ok(board)
+    }
+
+    pub async fn list_boards(&self, include_archived: bool) -> Result<Vec<BoardView>> {
+        let criteria = BoardCriteria {
+            archived: ArchivedFilter::new(include_archived),
+            ..Default::default()
+        };
+        self.board_query.all(&criteria).await
+    }
+
+    pub async fn archive_board(&self, id: &str) -> Result<Board> {
+        let id: BoardId = id.parse()?;
+        let mut board = self
+            .board_repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| not_found(&id))?;
+        board.archive()?;
+        self.board_repository.save(board.clone()).await?;
+        Ok(board)
+    }
+
+    pub async fn add_card(
+        &self,
+        column_id: &str,
+        title: &str,
+        description: Option<&str>,
+    ) -> Result<Card> {
+        let column_id: ColumnId = column_id.parse()?;
+        let column = self
+            .find_column(&column_id)
+            .await?
+            .add_card_with_description(
+                CardTitle::new(title.to_owned()),
+                CardDescription::new(description.unwrap_or_default().to_owned()),
+            );
+        let card = column.cards().last().cloned().expect("card was just added");
+        self.column_repository.save(column).await?;
+        Ok(card)
+    }
+
+    /// カードの期限をRFC 3339の日時で設定する。`due_at` がない場合は期限をなくす
+    pub async fn set_card_due(&self, card_id: &str, due_at: Option<&str>) -> Result<Card> {
+        let card_id: CardId = card_id.parse()?;
+        let due_at = due_at.map(DateTime::parse_from_rfc3339).transpose()?;
+        let mut column = self.find_card_column(&card_id).await?;
+        let card = column.find_card_mut(&card_id)?;
+        card.set_due_at(due_at.map(|d| d.with_timezone(&Utc)));
+        let card = card.clone();
+        self.column_repository.save(column).await?;
+        Ok(card)
+    }
+
+    /// カードを `to_column_id` の `position` 番目に移す。`position` がない場合は末尾に移す
+    pub async fn move_card(
+        &self,
+        card_id: &str,
+        to_column_id: &str,
+        position: Option<usize>,
+    ) -> Result<Card> {
+        let card_id: CardId = card_id.parse()?;
+        let to_column_id: ColumnId = to_column_id.parse()?;
+        let mut src = self.find_card_column(&card_id).await?;
+        let card = src.take_card(&card_id)?;
+
+        if src.id() == &to_column_id {
+            let position = position.unwrap_or(src.cards().len());
+            src.insert_card(position, card.clone())?;
+            self.column_repository.save(src).await?;
+            return Ok(card);
+        }
+
+        let mut dst = self.find_column(&to_column_id).await?;
+        let position = position.unwrap_or(dst.cards().len());
+        dst.insert_card(position, card.clone())?;
+        // NOTE: 2つのカラムは別々に保存するので、途中で失敗したときにカードが消えないよう移動先から保存する
+        // どちらも読み込んだときのバージョンで保存するので、その間にほかで変更されていれば失敗する
+        self.column_repository.save(dst).await?;
+        let src_id = src.id().clone();
+        self.column_repository.save(src).await.map_err(|e| {
+            anyhow!(
+                "{} を {} に保存しましたが、{} から取り除けませんでした。両方のカラムにあります: {}",
+                card_id,
+                to_column_id,
+                src_id,
+                e
+            )
+        })?;
+        Ok(card)
+    }
+
+    /// IDの接頭辞で種類を判別し、書き込み側に保存されている内容を返す
+    pub async fn inspect(&self, id: &str) -> Result<String> {
+        let (entity_type, _) = id
+            .split_once('-')
+            .ok_or_else(|| anyhow!("{} はIDではありません", id))?;
+        let result = match entity_type {
+            t if t == User::entity_type() => format!("{:#?}", self.find_user(&id.parse()?).await?),
+            t if t == Board::entity_type() => {
+                let id: BoardId = id.parse()?;
+                let board = self.board_repository.find_by_id(&id).await?;
+                format!("{:#?}", board.ok_or_else(|| not_found(&id))?)
+            }
+            t if t == Column::entity_type() => {
+                format!("{:#?}", self.find_column(&id.parse()?).await?)
+            }
+            t if t == Card::entity_type() => {
+                let id: CardId = id.parse()?;
+                let column = self.find_card_column(&id).await?;
+                let card = column.cards().iter().find(|c| c.id() == &id);
+                let card = card.ok_or_else(|| not_found(&id))?;
+                format!("{:#?}\nin {}", card, column.id())
+            }
+            t if t == Comment::entity_type() => {
+                let id: CommentId = id.parse()?;
+                let comment = self.comment_repository.find_by_id(&id).await?;
+                format!("{:#?}", comment.ok_or_else(|| not_found(&id))?)
+            }
+            _ => return Err(anyhow!("{} の種類 `{}` は扱えません", id, entity_type)),
+        };
+        Ok(result)
+    }
+
+    async fn find_user(&self, id: &UserId) -> Result<User> {
+        let user = self.user_repository.find_by_id(id).await?;
+        user.ok_or_else(|| not_found(id))
+    }
+
+    async fn find_column(&self, id: &ColumnId) -> Result<Column> {
+        let column = self.column_repository.find_by_id(id).await?;
+        column.ok_or_else(|| not_found(id))
+    }
+
+    // カードの入っているカラムはリードモデルで探す
+    async fn find_card_column(&self, card_id: &CardId) -> Result<Column> {
+        let cards = self
+            .cards_query
+            .list_by_ids(std::slice::from_ref(card_id))
+            .await?;
+        let card = cards.get(card_id).ok_or_else(|| not_found(card_id))?;
+        self.find_column(&card.column_id.parse()?).await
+    }
+}
+
+fn not_found(id: &impl ToString) -> anyhow::Error {
+    RepositoryError::NotFound(id.to_string()).into()
+}
+
+pub(crate) fn provide<I: ?Sized>(module: &impl HasProvider<I>) -> Result<Box<I>> {
+    module.provide().map_err(|e| anyhow!(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use domain_kanban::outbox::OutboxMessage;
+    use infrastructure_memory::{modules, sample, Tables};
+
+    use super::*;
+
+    fn admin() -> Admin {
+        let (query_module, repository_module) = modules(Tables::seeded());
+        Admin::new(&query_module, &repository_module).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_user_checks_invariants() {
+        // Arrange
+        let admin = admin();
+        let existing = &sample::data().users[0];
+
+        // Act
+        let created = admin.create_user("admin", "admin@example.com").await;
+        let too_long = admin
+            .create_user("123456789012345678901", "long@example.com")
+            .await;
+        let duplicated = admin
+            .create_user("dup", &existing.email.to_uppercase())
+            .await;
+
+        // Assert
+        let created = created.unwrap();
+        let users = admin.list_users().await.unwrap();
+        assert!(users.iter().any(|u| u.id == created.user_id().to_string()));
+        assert!(too_long.is_err());
+        assert!(duplicated.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_board_and_move_card() {
+        // Arrange
+        let admin = admin();
+        let owner = &sample::data().users[0];
+        let columns = vec!["todo".to_owned(), "done".to_owned()];
+        let board = admin
+            .create_board(&owner.id.to_string(), "admin", &columns)
+            .await
+            .unwrap();
+        let todo = board.column_ids()[0].to_string();
+        let done = board.column_ids()[1].to_string();
+        let first = admin.add_card(&done, "first", None).await.unwrap();
+        let card = admin.add_card(&todo, "moved", Some("desc")).await.unwrap();
+
+        // Act
+        admin
+            .move_card(&card.id().to_string(), &done, Some(0))
+            .await
+            .unwrap();
+
+        // Assert
+        let done = admin.find_column(&done.parse().unwrap()).await.unwrap();
+        let ids: Vec<_> = done.cards().iter().map(|c| c.id().clone()).collect();
+        assert_eq!(ids, vec![card.id().clone(), first.id().clone()]);
+        let todo = admin.find_column(&todo.parse().unwrap()).await.unwrap();
+        assert!(todo.cards().is_empty());
+        let inspected = admin.inspect(&card.id().to_string()).await.unwrap();
+        assert!(inspected.contains("desc"));
+    }
+
+    /// `fail_on` のカラムを保存しようとすると、ほかで変更されていたものとして失敗する
+    struct ConflictingColumnRepository {
+        inner: Box<dyn ColumnRepository>,
+        fail_on: ColumnId,
+    }
+
+    #[async_trait]
+    impl ColumnRepository for ConflictingColumnRepository {
+        async fn save(&self, column: Column) -> Result<(), RepositoryError> {
+            if column.id() == &self.fail_on {
+                return Err(RepositoryError::Conflict {
+                    expected: column.version(),
+                });
+            }
+            self.inner.save(column).await
+        }
+        async fn save_with_outbox(
+            &self,
+            column: Column,
+            _messages: Vec<OutboxMessage>,
+        ) -> Result<(), RepositoryError> {
+            self.save(column).await
+        }
+        async fn find_by_id(&self, id: &ColumnId) -> Result<Option<Column>, RepositoryError> {
+            self.inner.find_by_id(id).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_move_card_names_both_columns_when_src_save_fails() {
+        // Arrange
+        let (query_module, repository_module) = modules(Tables::seeded());
+        let mut admin = Admin::new(&query_module, &repository_module).unwrap();
+        let owner = &sample::data().users[0];
+        let columns = vec!["todo".to_owned(), "done".to_owned()];
+        let board = admin
+            .create_board(&owner.id.to_string(), "admin", &columns)
+            .await
+            .unwrap();
+        let (todo, done) = (&board.column_ids()[0], &board.column_ids()[1]);
+        let card = admin
+            .add_card(&todo.to_string(), "moved", None)
+            .await
+            .unwrap();
+        admin.column_repository = Box::new(ConflictingColumnRepository {
+            inner: provide(&repository_module).unwrap(),
+            fail_on: todo.clone(),
+        });
+
+        // Act
+        let result = admin
+            .move_card(&card.id().to_string(), &done.to_string(), None)
+            .await;
+
+        // Assert
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains(&todo.to_string()));
+        assert!(message.contains(&done.to_string()));
+        let done = admin.find_column(done).await.unwrap();
+        assert_eq!(done.cards()[0].id(), card.id());
+    }
+
+    #[tokio::test]
+    async fn test_set_card_due() {
+        // Arrange
+        let admin = admin();
+        let owner = &sample::data().users[0];
+        let columns = vec!["todo".to_owned()];
+        let board = admin
+            .create_board(&owner.id.to_string(), "admin", &columns)
+            .await
+            .unwrap();
+        let todo = board.column_ids()[0].to_string();
+        let card = admin.add_card(&todo, "due", None).await.unwrap();
+        let card_id = card.id().to_string();
+
+        // Act
+        let due = admin
+            .set_card_due(&card_id, Some("2026-10-31T18:00:00+09:00"))
+            .await
+            .unwrap();
+        let invalid = admin.set_card_due(&card_id, Some("2026-10-31")).await;
+        let cleared = admin.set_card_due(&card_id, None).await.unwrap();
+
+        // Assert
+        let expected: DateTime<Utc> = "2026-10-31T09:00:00Z".parse().unwrap();
+        assert_eq!(due.due_at(), Some(&expected));
+        assert!(invalid.is_err());
+        assert!(cleared.due_at().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_create_board_respects_max_column_count() {
+        let admin = admin();
+        let owner = &sample::data().users[0];
+        let columns: Vec<_> = (0..11).map(|i| format!("column {i}")).collect();
+
+        let result = admin
+            .create_board(&owner.id.to_string(), "too many", &columns)
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_inspect_rejects_unknown_type() {
+        let admin = admin();
+
+        assert!(admin
+            .inspect("team-01HBCCGK3MG5HA7GJG25BGV6PJ")
+            .await
+            .is_err());
+        assert!(admin.inspect("not an id").await.is_err());
+    }
+}
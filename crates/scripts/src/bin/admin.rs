@@ -0,0 +1,148 @@
+//! ユーザー・ボード・カードを管理する
+//!
+//! 書き込み先はDynamoDB(接続先は AWS_ENDPOINT_URL などの環境変数)、
+//! 一覧はPostgresのリードモデル(接続先は DATABASE_URL)から読む
+use std::{env, process::ExitCode, sync::Arc};
+
+use anyhow::Result;
+use infrastructure_dynamodb::{
+    default_sdk_config, dynamo_db_client, ClientImpl, ClientImplParameters, RepositoryModule,
+};
+use infrastructure_rdb::{Configuration, PgPoolImpl, PgPoolImplParameters, QueryModule};
+use resilience::{Resilience, ResiliencePolicy};
+use scripts::{
+    admin::Admin,
+    trello::{TrelloBoard, TrelloImporter},
+};
+
+const USAGE: &str = "\
+usage:
+  admin user create <name> <email>
+  admin user rename <user-id> <name>
+  admin user list
+  admin board create <owner-id> <title> [<column-title>...]
+  admin board list [--all]
+  admin board archive <board-id>
+  admin card add <column-id> <title> [<description>]
+  admin card move <card-id> <column-id> [<position>]
+  admin card due <card-id> [<rfc3339-datetime>]
+  admin inspect <id>
+  admin import trello <file> <owner-id> [--dry-run]
+";
+
+#[tokio::main]
+async fn main() -> Result<ExitCode> {
+    let args: Vec<_> = env::args().skip(1).collect();
+    let args: Vec<_> = args.iter().map(String::as_str).collect();
+    if args.is_empty() || args == ["--help"] {
+        print!("{}", USAGE);
+        return Ok(ExitCode::SUCCESS);
+    }
+    let (query_module, repository_module) = connect().await?;
+    let admin = Admin::new(&query_module, &repository_module)?;
+
+    match args.as_slice() {
+        ["user", "create", name, email] => {
+            let user = admin.create_user(name, email).await?;
+            println!("created {}", user.user_id());
+        }
+        ["user", "rename", id, name] => {
+            let user = admin.rename_user(id, name).await?;
+            println!(
+                "renamed {} to {}",
+                user.user_id(),
+                user.user_name().to_string()
+            );
+        }
+        ["user", "list"] => {
+            for user in admin.list_users().await? {
+                println!("{}\t{}\t{}", user.id, user.name, user.email);
+            }
+        }
+        ["board", "create", owner_id, title, column_titles @ ..] => {
+            let column_titles: Vec<_> = column_titles.iter().map(|t| t.to_string()).collect();
+            let board = admin.create_board(owner_id, title, &column_titles).await?;
+            println!("created {}", board.id());
+            for column_id in board.column_ids() {
+                println!("  {}", column_id);
+            }
+        }
+        ["board", "list", rest @ ..] if rest.is_empty() || rest == ["--all"] => {
+            for board in admin.list_boards(!rest.is_empty()).await? {
+                let archived = if board.archived_at.is_some() {
+                    "\tarchived"
+                } else {
+                    ""
+                };
+                println!(
+                    "{}\t{}\t{}{}",
+                    board.id, board.title, board.owner_id, archived
+                );
+            }
+        }
+        ["board", "archive", id] => {
+            let board = admin.archive_board(id).await?;
+            println!("archived {}", board.id());
+        }
+        ["card", "add", column_id, title, description @ ..] if description.len() <= 1 => {
+            let card = admin
+                .add_card(column_id, title, description.first().copied())
+                .await?;
+            println!("created {}", card.id());
+        }
+        ["card", "move", card_id, column_id, position @ ..] if position.len() <= 1 => {
+            let position = position.first().map(|p| p.parse()).transpose()?;
+            let card = admin.move_card(card_id, column_id, position).await?;
+            println!("moved {} to {}", card.id(), column_id);
+        }
+        ["card", "due", card_id, due_at @ ..] if due_at.len() <= 1 => {
+            let card = admin.set_card_due(card_id, due_at.first().copied()).await?;
+            match card.due_at() {
+                Some(due_at) => println!("{} is due at {}", card.id(), due_at),
+                None => println!("{} has no due date", card.id()),
+            }
+        }
+        ["inspect", id] => {
+            println!("{}", admin.inspect(id).await?);
+        }
+        ["import", "trello", path, owner_id, rest @ ..]
+            if rest.is_empty() || rest == ["--dry-run"] =>
+        {
+            let export = TrelloBoard::from_json(&std::fs::read_to_string(path)?)?;
+            let importer = TrelloImporter::new(&query_module, &repository_module)?;
+            let dry_run = !rest.is_empty();
+            print!("{}", importer.import(&export, owner_id, dry_run).await?);
+            if dry_run {
+                println!("dry run: nothing was saved");
+            }
+        }
+        _ => {
+            eprint!("{}", USAGE);
+            return Ok(ExitCode::FAILURE);
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+async fn connect() -> Result<(QueryModule, RepositoryModule)> {
+    let configuration = match env::var("DATABASE_URL") {
+        Ok(uri) => Configuration::new(1, uri),
+        Err(_) => Configuration::default(),
+    };
+    let query_module = QueryModule::builder()
+        .with_component_parameters::<PgPoolImpl>(PgPoolImplParameters {
+            pool: configuration.connect().await?,
+            resilience: Arc::new(Resilience::new("postgres", ResiliencePolicy::default())),
+        })
+        .build();
+
+    let sdk_config = default_sdk_config().await;
+    let repository_module = RepositoryModule::builder()
+        .with_component_parameters::<ClientImpl>(ClientImplParameters {
+            client: dynamo_db_client(&sdk_config),
+            resilience: Arc::new(Resilience::new("dynamodb", ResiliencePolicy::default())),
+        })
+        .build();
+
+    Ok((query_module, repository_module))
+}
@@ -0,0 +1,19 @@
+use anyhow::Result;
+use infrastructure_dynamodb::{default_sdk_config, dynamo_db_client, table};
+
+// 接続先は AWS_ENDPOINT_URL などの環境変数で指定する
+#[tokio::main]
+async fn main() -> Result<()> {
+    let sdk_config = default_sdk_config().await;
+    let client = dynamo_db_client(&sdk_config);
+    let created = table::bootstrap(&client).await?;
+    for table in table::TABLES {
+        let status = if created.contains(&table.name) {
+            "created"
+        } else {
+            "exists"
+        };
+        println!("{}: {}", table.name, status);
+    }
+    Ok(())
+}
@@ -1,21 +1,78 @@
-use anyhow::Result;
+//! GraphQLのスキーマを書き出し、書き出し済みのスキーマとの差分を報告する
+//!
+//! usage: gen_schema [--check] [--schema <path>] [--allowlist <path>]
+//!   --check      書き出さずに差分だけを報告する(CI向け)
+//!   --schema     比べて書き出すスキーマ。デフォルトは ./schema.graphql
+//!   --allowlist  許可する破壊的変更の一覧。デフォルトは ./schema.allowlist
+//!
+//! 許可されていない破壊的変更がある場合は、書き出さずに終了コード1で終わる
+use std::{path::PathBuf, process::ExitCode};
+
+use anyhow::{anyhow, Result};
 use presentation_graphql::GraphQL;
-use std::io::Write;
+use scripts::schema_diff::{diff, parse_allowlist, report, unallowed_breaking};
+
+struct Args {
+    check: bool,
+    schema_path: PathBuf,
+    allowlist_path: PathBuf,
+}
+
+impl Args {
+    fn parse() -> Result<Self> {
+        let mut result = Self {
+            check: false,
+            schema_path: "./schema.graphql".into(),
+            allowlist_path: "./schema.allowlist".into(),
+        };
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--check" => result.check = true,
+                "--schema" => result.schema_path = value_of(&arg, args.next())?.into(),
+                "--allowlist" => result.allowlist_path = value_of(&arg, args.next())?.into(),
+                _ => return Err(anyhow!("unknown argument: {arg}")),
+            }
+        }
+        Ok(result)
+    }
+}
 
-fn main() -> Result<()> {
-    let schema_output_path = "./schema.graphql";
+fn value_of(name: &str, value: Option<String>) -> Result<String> {
+    value.ok_or_else(|| anyhow!("{name} requires a value"))
+}
+
+fn main() -> Result<ExitCode> {
+    let args = Args::parse()?;
     let sdl = GraphQL::sdl();
 
-    let mut sdl_file = if std::path::Path::new(schema_output_path).exists() {
-        std::fs::OpenOptions::new()
-            .write(true)
-            .open(schema_output_path)
-    } else {
-        std::fs::File::create(schema_output_path)
-    }?;
+    if args.schema_path.exists() {
+        let committed = std::fs::read_to_string(&args.schema_path)?;
+        let allowlist = if args.allowlist_path.exists() {
+            parse_allowlist(&std::fs::read_to_string(&args.allowlist_path)?)
+        } else {
+            Default::default()
+        };
+        let changes = diff(&committed, &sdl)?;
+        print!("{}", report(&changes, &allowlist));
 
-    write!(&mut sdl_file, "{}", &sdl).expect("write failed");
+        let breaking = unallowed_breaking(&changes, &allowlist);
+        if !breaking.is_empty() {
+            eprintln!(
+                "{} breaking change(s) are not allowlisted. add the paths to {} to accept them",
+                breaking.len(),
+                args.allowlist_path.display()
+            );
+            return Ok(ExitCode::FAILURE);
+        }
+    } else {
+        println!("{} does not exist yet", args.schema_path.display());
+    }
 
-    println!("write schema in {}", schema_output_path);
-    Ok(())
+    if !args.check {
+        // NOTE: 既存のファイルより短くなっても末尾が残らないように、作りなおして書く
+        std::fs::write(&args.schema_path, &sdl)?;
+        println!("write schema in {}", args.schema_path.display());
+    }
+    Ok(ExitCode::SUCCESS)
 }
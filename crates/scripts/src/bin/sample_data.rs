@@ -65,22 +65,22 @@ async fn data(transaction: &mut Transaction<'_, Postgres>) -> Result<()> {
             -- カードを挿入
             INSERT INTO cards (id, title, description, column_id)
             VALUES
-                ('c0', '掃除', '', 'column-01HBCCGK3MAWDZKS74M1DEJQ54'),
-                ('c1', '洗濯', '', 'column-01HBCCGK3MAWDZKS74M1DEJQ54'),
-                ('c2', '食事', '', 'column-01HBCCGK3MAWDZKS74M1DEJQ54'),
-                ('c3', 'ゴミ出し', '', 'column-01HBCCGK3MR41MEZWGJERC5PHD'),
-                ('c4', 'ランタン', '', 'column-01HBCCGK3MDQRSF7X7EGKBMAY8'),
-                ('c5', '本棚', 'いろいろ', 'column-01HBCCGK3MDQRSF7X7EGKBMAY8'),
-                ('c6', '床下三兄弟', '', 'column-01HBCCGK3M9BMDD7Z16JQNX3QC'),
-                ('c7', 'ダソッキー', '', 'column-01HBCCGK3M9BMDD7Z16JQNX3QC'),
-                ('c8', 'ゴハッチュウ', '', 'column-01HBCCGK3M9BMDD7Z16JQNX3QC'),
-                ('c9', 'センサーライト', '', 'column-01HBCCGK3MMEFTBS3SJ73CK96K'),
-                ('c10', '飲み物', '', 'column-01HBCCGK3MMEFTBS3SJ73CK96K'),
-                ('c11', '米', '', 'column-01HBCCGK3MMEFTBS3SJ73CK96K'),
-                ('c12', '常備薬', '', 'column-01HBCCGK3MMEFTBS3SJ73CK96K'),
-                ('c13', '石鹸', '', 'column-01HBCCGK3MMEFTBS3SJ73CK96K'),
-                ('c14', '買い物', '', 'column-01HBCCGK3MDD8M1T47N4MDB6AA'),
-                ('c15', '洗い物', '', 'column-01HBCCGK3MDD8M1T47N4MDB6AA');
+                ('card-01HBFZE37M0SKHS01HFYHV2YCX', '掃除', '', 'column-01HBCCGK3MAWDZKS74M1DEJQ54'),
+                ('card-01HBFZE37N7TRMCT2JAYYXCG7V', '洗濯', '', 'column-01HBCCGK3MAWDZKS74M1DEJQ54'),
+                ('card-01HBFZE37PQJ4J7E61X4WJ8NPY', '食事', '', 'column-01HBCCGK3MAWDZKS74M1DEJQ54'),
+                ('card-01HBFZE37QQPEATF1DDMD3T7X7', 'ゴミ出し', '', 'column-01HBCCGK3MR41MEZWGJERC5PHD'),
+                ('card-01HBFZE37R2S0RQ7946KJ6BRAG', 'ランタン', '', 'column-01HBCCGK3MDQRSF7X7EGKBMAY8'),
+                ('card-01HBFZE37S10H6R0RHK6BJN139', '本棚', 'いろいろ', 'column-01HBCCGK3MDQRSF7X7EGKBMAY8'),
+                ('card-01HBFZE37T6ZW1FZ39B83T1JKE', '床下三兄弟', '', 'column-01HBCCGK3M9BMDD7Z16JQNX3QC'),
+                ('card-01HBFZE37VK8ER2QQPT4XRZAGR', 'ダソッキー', '', 'column-01HBCCGK3M9BMDD7Z16JQNX3QC'),
+                ('card-01HBFZE37W6BKRZBE1MR3CP3XK', 'ゴハッチュウ', '', 'column-01HBCCGK3M9BMDD7Z16JQNX3QC'),
+                ('card-01HBFZE37XPE5ACQPKH6VMT3XH', 'センサーライト', '', 'column-01HBCCGK3MMEFTBS3SJ73CK96K'),
+                ('card-01HBFZE37Y71Q6PSD6MJ5R2J7P', '飲み物', '', 'column-01HBCCGK3MMEFTBS3SJ73CK96K'),
+                ('card-01HBFZE37Z8WVSDPGXNHSFYQ9A', '米', '', 'column-01HBCCGK3MMEFTBS3SJ73CK96K'),
+                ('card-01HBFZE38006MXX2KQ9F7KDNCB', '常備薬', '', 'column-01HBCCGK3MMEFTBS3SJ73CK96K'),
+                ('card-01HBFZE38153FWWJHBQQ142CRB', '石鹸', '', 'column-01HBCCGK3MMEFTBS3SJ73CK96K'),
+                ('card-01HBFZE382AWD6RC3N26SBJGVT', '買い物', '', 'column-01HBCCGK3MDD8M1T47N4MDB6AA'),
+                ('card-01HBFZE3836WF2FKC1613J54W9', '洗い物', '', 'column-01HBCCGK3MDD8M1T47N4MDB6AA');
         "#
     )
     .execute(&mut **transaction)
@@ -102,16 +102,16 @@ async fn data(transaction: &mut Transaction<'_, Postgres>) -> Result<()> {
     sqlx::query!(
         r#"
             -- ボードとカラムの関連付けを挿入
-            INSERT INTO board_column_relations (board_id, column_id)
+            INSERT INTO board_column_relations (board_id, column_id, position)
             VALUES
-                ('board-01HBCCGK3MH83RJ4Y8AVECQ5W9', 'column-01HBCCGK3MAWDZKS74M1DEJQ54'),
-                ('board-01HBCCGK3MH83RJ4Y8AVECQ5W9', 'column-01HBCCGK3MR41MEZWGJERC5PHD'),
-                ('board-01HBCCGK3MH83RJ4Y8AVECQ5W9', 'column-01HBCCGK3MDQRSF7X7EGKBMAY8'),
-                ('board-01HBCCGK3M3039H2QQEYD94TMS', 'column-01HBCCGK3MDD8M1T47N4MDB6AA'),
-                ('board-01HBCCGK3M3039H2QQEYD94TMS', 'column-01HBCCGK3MMEFTBS3SJ73CK96K'),
-                ('board-01HBCCGK3M3039H2QQEYD94TMS', 'column-01HBCCGK3M9BMDD7Z16JQNX3QC'),
-                ('board-01HBCCGK3M3039H2QQEYD94TMS', 'column-01HBCCGK3MTAFEVEFAQMFE2W43'),
-                ('board-01HBCCGK3M3039H2QQEYD94TMS', 'column-01HBCCGK3M3SA44D9SCJVR5D8X');
+                ('board-01HBCCGK3MH83RJ4Y8AVECQ5W9', 'column-01HBCCGK3MAWDZKS74M1DEJQ54', 0),
+                ('board-01HBCCGK3MH83RJ4Y8AVECQ5W9', 'column-01HBCCGK3MR41MEZWGJERC5PHD', 1),
+                ('board-01HBCCGK3MH83RJ4Y8AVECQ5W9', 'column-01HBCCGK3MDQRSF7X7EGKBMAY8', 2),
+                ('board-01HBCCGK3M3039H2QQEYD94TMS', 'column-01HBCCGK3MDD8M1T47N4MDB6AA', 0),
+                ('board-01HBCCGK3M3039H2QQEYD94TMS', 'column-01HBCCGK3MMEFTBS3SJ73CK96K', 1),
+                ('board-01HBCCGK3M3039H2QQEYD94TMS', 'column-01HBCCGK3M9BMDD7Z16JQNX3QC', 2),
+                ('board-01HBCCGK3M3039H2QQEYD94TMS', 'column-01HBCCGK3MTAFEVEFAQMFE2W43', 3),
+                ('board-01HBCCGK3M3039H2QQEYD94TMS', 'column-01HBCCGK3M3SA44D9SCJVR5D8X', 4);
         "#
     )
     .execute(&mut **transaction)
@@ -0,0 +1,3 @@
+pub mod admin;
+pub mod schema_diff;
+pub mod trello;
@@ -0,0 +1,516 @@
+//! 2つのSDLを比べて、変更ごとにクライアントへの影響を分類する
+//! 分類はgraphql-jsの `findBreakingChanges` / `findDangerousChanges` にならう
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use anyhow::Result;
+use async_graphql::parser::{
+    parse_schema,
+    types::{
+        BaseType, FieldDefinition, InputValueDefinition, Type, TypeKind, TypeSystemDefinition,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// 既存のクエリが失敗するようになる
+    Breaking,
+    /// 既存のクエリは通るが、クライアントの扱いによっては問題になる
+    Dangerous,
+    Safe,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Breaking => write!(f, "BREAKING"),
+            Self::Dangerous => write!(f, "DANGEROUS"),
+            Self::Safe => write!(f, "SAFE"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub severity: Severity,
+    /// `Type` / `Type.field` / `Type.field(arg:)` の形式。許可リストにはこれを書く
+    pub path: String,
+    pub message: String,
+}
+
+impl Change {
+    fn new(severity: Severity, path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// `old` から `new` への変更を、型・フィールドの名前順に返す
+pub fn diff(old: &str, new: &str) -> Result<Vec<Change>> {
+    let old = SchemaShape::parse(old)?;
+    let new = SchemaShape::parse(new)?;
+    let mut changes = vec![];
+    for (name, old_type) in &old.types {
+        match new.types.get(name) {
+            None => changes.push(Change::new(Severity::Breaking, name, "type removed")),
+            Some(new_type) => diff_type(name, old_type, new_type, &mut changes),
+        }
+    }
+    for name in new.types.keys().filter(|n| !old.types.contains_key(*n)) {
+        changes.push(Change::new(Severity::Safe, name, "type added"));
+    }
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(changes)
+}
+
+/// 許可リストの書式: 1行に1つ `Change::path` を書く。 `#` から後ろはコメント
+pub fn parse_allowlist(text: &str) -> BTreeSet<String> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+/// 重大度ごとにまとめた読みやすい形にする。許可された破壊的変更には印をつける
+pub fn report(changes: &[Change], allowlist: &BTreeSet<String>) -> String {
+    if changes.is_empty() {
+        return "no schema changes\n".to_owned();
+    }
+    let mut out = String::new();
+    for severity in [Severity::Breaking, Severity::Dangerous, Severity::Safe] {
+        let group: Vec<_> = changes.iter().filter(|c| c.severity == severity).collect();
+        if group.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("{} ({})\n", severity, group.len()));
+        for change in group {
+            let allowed = if severity == Severity::Breaking && allowlist.contains(&change.path) {
+                " [allowlisted]"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "  - {}: {}{}\n",
+                change.path, change.message, allowed
+            ));
+        }
+    }
+    out
+}
+
+/// 許可リストにない破壊的変更
+pub fn unallowed_breaking<'a>(
+    changes: &'a [Change],
+    allowlist: &BTreeSet<String>,
+) -> Vec<&'a Change> {
+    changes
+        .iter()
+        .filter(|c| c.severity == Severity::Breaking && !allowlist.contains(&c.path))
+        .collect()
+}
+
+// 比較に必要な部分だけを取り出したスキーマ。説明やディレクティブは比べない
+struct SchemaShape {
+    types: BTreeMap<String, TypeShape>,
+}
+
+enum TypeShape {
+    Scalar,
+    Object {
+        fields: BTreeMap<String, FieldShape>,
+        interfaces: BTreeSet<String>,
+    },
+    Interface {
+        fields: BTreeMap<String, FieldShape>,
+    },
+    Union {
+        members: BTreeSet<String>,
+    },
+    Enum {
+        values: BTreeSet<String>,
+    },
+    InputObject {
+        fields: BTreeMap<String, InputShape>,
+    },
+}
+
+struct FieldShape {
+    ty: Type,
+    args: BTreeMap<String, InputShape>,
+}
+
+struct InputShape {
+    ty: Type,
+    default_value: Option<String>,
+}
+
+impl SchemaShape {
+    fn parse(sdl: &str) -> Result<Self> {
+        let document = parse_schema(sdl)?;
+        let mut types = BTreeMap::new();
+        for definition in document.definitions {
+            let TypeSystemDefinition::Type(definition) = definition else {
+                continue;
+            };
+            let definition = definition.node;
+            let shape = match definition.kind {
+                TypeKind::Scalar => TypeShape::Scalar,
+                TypeKind::Object(object) => TypeShape::Object {
+                    fields: fields(object.fields.into_iter().map(|f| f.node)),
+                    interfaces: object
+                        .implements
+                        .into_iter()
+                        .map(|i| i.node.to_string())
+                        .collect(),
+                },
+                TypeKind::Interface(interface) => TypeShape::Interface {
+                    fields: fields(interface.fields.into_iter().map(|f| f.node)),
+                },
+                TypeKind::Union(union) => TypeShape::Union {
+                    members: union
+                        .members
+                        .into_iter()
+                        .map(|m| m.node.to_string())
+                        .collect(),
+                },
+                TypeKind::Enum(enum_type) => TypeShape::Enum {
+                    values: enum_type
+                        .values
+                        .into_iter()
+                        .map(|v| v.node.value.node.to_string())
+                        .collect(),
+                },
+                TypeKind::InputObject(input) => TypeShape::InputObject {
+                    fields: inputs(input.fields.into_iter().map(|f| f.node)),
+                },
+            };
+            types.insert(definition.name.node.to_string(), shape);
+        }
+        Ok(Self { types })
+    }
+}
+
+fn fields(definitions: impl Iterator<Item = FieldDefinition>) -> BTreeMap<String, FieldShape> {
+    definitions
+        .map(|f| {
+            let shape = FieldShape {
+                ty: f.ty.node,
+                args: inputs(f.arguments.into_iter().map(|a| a.node)),
+            };
+            (f.name.node.to_string(), shape)
+        })
+        .collect()
+}
+
+fn inputs(definitions: impl Iterator<Item = InputValueDefinition>) -> BTreeMap<String, InputShape> {
+    definitions
+        .map(|i| {
+            let shape = InputShape {
+                ty: i.ty.node,
+                default_value: i.default_value.map(|v| v.node.to_string()),
+            };
+            (i.name.node.to_string(), shape)
+        })
+        .collect()
+}
+
+impl TypeShape {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Scalar => "scalar",
+            Self::Object { .. } => "object",
+            Self::Interface { .. } => "interface",
+            Self::Union { .. } => "union",
+            Self::Enum { .. } => "enum",
+            Self::InputObject { .. } => "input object",
+        }
+    }
+}
+
+fn diff_type(name: &str, old: &TypeShape, new: &TypeShape, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (TypeShape::Scalar, TypeShape::Scalar) => {}
+        (
+            TypeShape::Object {
+                fields: old_fields,
+                interfaces: old_interfaces,
+            },
+            TypeShape::Object {
+                fields: new_fields,
+                interfaces: new_interfaces,
+            },
+        ) => {
+            diff_fields(name, old_fields, new_fields, changes);
+            for removed in old_interfaces.difference(new_interfaces) {
+                let message = format!("no longer implements {removed}");
+                changes.push(Change::new(Severity::Breaking, name, message));
+            }
+            // インターフェースで分岐しているクライアントの結果が変わりうる
+            for added in new_interfaces.difference(old_interfaces) {
+                let message = format!("now implements {added}");
+                changes.push(Change::new(Severity::Dangerous, name, message));
+            }
+        }
+        (
+            TypeShape::Interface { fields: old_fields },
+            TypeShape::Interface { fields: new_fields },
+        ) => {
+            diff_fields(name, old_fields, new_fields, changes);
+        }
+        (
+            TypeShape::Union {
+                members: old_members,
+            },
+            TypeShape::Union {
+                members: new_members,
+            },
+        ) => {
+            for removed in old_members.difference(new_members) {
+                let path = format!("{name}.{removed}");
+                changes.push(Change::new(
+                    Severity::Breaking,
+                    path,
+                    "union member removed",
+                ));
+            }
+            // 網羅的に分岐しているクライアントが知らない型を受け取りうる
+            for added in new_members.difference(old_members) {
+                let path = format!("{name}.{added}");
+                changes.push(Change::new(Severity::Dangerous, path, "union member added"));
+            }
+        }
+        (TypeShape::Enum { values: old_values }, TypeShape::Enum { values: new_values }) => {
+            for removed in old_values.difference(new_values) {
+                let path = format!("{name}.{removed}");
+                changes.push(Change::new(Severity::Breaking, path, "enum value removed"));
+            }
+            for added in new_values.difference(old_values) {
+                let path = format!("{name}.{added}");
+                changes.push(Change::new(Severity::Dangerous, path, "enum value added"));
+            }
+        }
+        (
+            TypeShape::InputObject { fields: old_fields },
+            TypeShape::InputObject { fields: new_fields },
+        ) => {
+            let path_of = |field: &str| format!("{name}.{field}");
+            diff_inputs(path_of, "input field", old_fields, new_fields, changes);
+        }
+        _ => {
+            let message = format!("kind changed from {} to {}", old.kind(), new.kind());
+            changes.push(Change::new(Severity::Breaking, name, message));
+        }
+    }
+}
+
+fn diff_fields(
+    type_name: &str,
+    old: &BTreeMap<String, FieldShape>,
+    new: &BTreeMap<String, FieldShape>,
+    changes: &mut Vec<Change>,
+) {
+    for (name, old_field) in old {
+        let path = format!("{type_name}.{name}");
+        let Some(new_field) = new.get(name) else {
+            changes.push(Change::new(Severity::Breaking, path, "field removed"));
+            continue;
+        };
+        if old_field.ty != new_field.ty {
+            let severity = if is_safe_output_change(&old_field.ty, &new_field.ty) {
+                Severity::Safe
+            } else {
+                Severity::Breaking
+            };
+            let message = format!("type changed from {} to {}", old_field.ty, new_field.ty);
+            changes.push(Change::new(severity, path.clone(), message));
+        }
+        let path_of = |arg: &str| format!("{path}({arg}:)");
+        diff_inputs(
+            path_of,
+            "argument",
+            &old_field.args,
+            &new_field.args,
+            changes,
+        );
+    }
+    for name in new.keys().filter(|n| !old.contains_key(*n)) {
+        let path = format!("{type_name}.{name}");
+        changes.push(Change::new(Severity::Safe, path, "field added"));
+    }
+}
+
+// 引数と入力オブジェクトのフィールドは、どちらもクライアントが値を渡す側なので同じ規則で比べる
+fn diff_inputs(
+    path_of: impl Fn(&str) -> String,
+    label: &str,
+    old: &BTreeMap<String, InputShape>,
+    new: &BTreeMap<String, InputShape>,
+    changes: &mut Vec<Change>,
+) {
+    for (name, old_input) in old {
+        let path = path_of(name);
+        let Some(new_input) = new.get(name) else {
+            changes.push(Change::new(
+                Severity::Breaking,
+                path,
+                format!("{label} removed"),
+            ));
+            continue;
+        };
+        if old_input.ty != new_input.ty {
+            let severity = if is_safe_input_change(&old_input.ty, &new_input.ty) {
+                Severity::Safe
+            } else {
+                Severity::Breaking
+            };
+            let message = format!("type changed from {} to {}", old_input.ty, new_input.ty);
+            changes.push(Change::new(severity, path.clone(), message));
+        }
+        if old_input.default_value != new_input.default_value {
+            let message = format!(
+                "default value changed from {} to {}",
+                old_input.default_value.as_deref().unwrap_or("none"),
+                new_input.default_value.as_deref().unwrap_or("none"),
+            );
+            changes.push(Change::new(Severity::Dangerous, path, message));
+        }
+    }
+    for (name, new_input) in new.iter().filter(|(n, _)| !old.contains_key(*n)) {
+        let path = path_of(name);
+        // 既存のクエリは値を渡していないので、必須のものが増えると失敗する
+        if !new_input.ty.nullable && new_input.default_value.is_none() {
+            let message = format!("required {label} added");
+            changes.push(Change::new(Severity::Breaking, path, message));
+        } else {
+            let message = format!("optional {label} added");
+            changes.push(Change::new(Severity::Safe, path, message));
+        }
+    }
+}
+
+// 返す値は、nullを返さなくなるのは問題ないが、nullを返すようになるとクライアントが壊れうる
+fn is_safe_output_change(old: &Type, new: &Type) -> bool {
+    if !old.nullable && new.nullable {
+        return false;
+    }
+    match (&old.base, &new.base) {
+        (BaseType::Named(old), BaseType::Named(new)) => old == new,
+        (BaseType::List(old), BaseType::List(new)) => is_safe_output_change(old, new),
+        _ => false,
+    }
+}
+
+// 渡す値は、nullを受け付けるようになるのは問題ないが、必須になると既存のクエリが失敗しうる
+fn is_safe_input_change(old: &Type, new: &Type) -> bool {
+    if old.nullable && !new.nullable {
+        return false;
+    }
+    match (&old.base, &new.base) {
+        (BaseType::Named(old), BaseType::Named(new)) => old == new,
+        (BaseType::List(old), BaseType::List(new)) => is_safe_input_change(old, new),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = r#"
+        type Card {
+            id: Id!
+            title: String!
+            description: String
+            dueDate: String
+        }
+        type Query {
+            card(id: Id!): Card
+            cards(first: Int = 20): [Card!]!
+        }
+        enum CardStatus { ACTIVE ARCHIVED }
+        scalar Id
+    "#;
+
+    fn severities(changes: &[Change]) -> Vec<(&str, Severity)> {
+        changes
+            .iter()
+            .map(|c| (c.path.as_str(), c.severity))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_classifies_changes() {
+        // Arrange
+        let new = r#"
+            type Card {
+                id: Id!
+                title: String
+                description: String!
+                checklist: [String!]!
+            }
+            type Query {
+                card(id: Id!): Card
+                cards(first: Int = 50, status: CardStatus!, after: String): [Card!]!
+            }
+            enum CardStatus { ACTIVE ARCHIVED DELETED }
+            scalar Id
+        "#;
+
+        // Act
+        let changes = diff(OLD, new).unwrap();
+
+        // Assert
+        assert_eq!(
+            severities(&changes),
+            vec![
+                ("Card.checklist", Severity::Safe),
+                ("Card.description", Severity::Safe),
+                ("Card.dueDate", Severity::Breaking),
+                ("Card.title", Severity::Breaking),
+                ("CardStatus.DELETED", Severity::Dangerous),
+                ("Query.cards(after:)", Severity::Safe),
+                ("Query.cards(first:)", Severity::Dangerous),
+                ("Query.cards(status:)", Severity::Breaking),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_input_nullability_rules() {
+        let old = "type Query { card(id: Id!, title: String): String } scalar Id";
+        let new = "type Query { card(id: Id, title: String!): String } scalar Id";
+
+        let changes = diff(old, new).unwrap();
+
+        assert_eq!(
+            severities(&changes),
+            vec![
+                ("Query.card(id:)", Severity::Safe),
+                ("Query.card(title:)", Severity::Breaking),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_allowlist_suppresses_breaking_changes() {
+        // Arrange
+        let new = OLD.replace("dueDate: String", "");
+        let allowlist = parse_allowlist("# cards have no due date\nCard.dueDate  # removed\n\n");
+
+        // Act
+        let changes = diff(OLD, &new).unwrap();
+
+        // Assert
+        assert!(unallowed_breaking(&changes, &allowlist).is_empty());
+        assert_eq!(
+            report(&changes, &allowlist),
+            "BREAKING (1)\n  - Card.dueDate: field removed [allowlisted]\n"
+        );
+    }
+}
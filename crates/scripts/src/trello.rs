@@ -0,0 +1,490 @@
+//! TrelloのボードをエクスポートしたJSONを取り込む
+//!
+//! - リストはカラムに、カードは説明・期限つきのカードにする。アーカイブ(closed)されたものは取り込まない
+//! - メンバーは、メールアドレスが一致するユーザーをボードのメンバーにする
+//!   NOTE: Trelloのエクスポートにはメールアドレスが含まれないことが多い。含まれないメンバーは飛ばす
+//! - IDはTrelloのIDから決めるので、取り込みなおしても重複しない。取り込んだあとに増えたものだけを加える
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use domain_kanban::{
+    board::{Board, BoardId, BoardRepository, BoardTitle},
+    column::{CardDescription, CardId, CardTitle, Column, ColumnId, ColumnRepository, ColumnTitle},
+    user::{UserId, UserRepository},
+};
+use domain_util::{Entity, Identifier, RepositoryError};
+use query_resolver::{CardsQuery, UserCriteria, UsersQuery};
+use serde::Deserialize;
+use shaku::HasProvider;
+use ulid::Ulid;
+
+use crate::admin::provide;
+
+/// エクスポートのうち、取り込みに使うところ
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrelloBoard {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub lists: Vec<TrelloList>,
+    #[serde(default)]
+    pub cards: Vec<TrelloCard>,
+    #[serde(default)]
+    pub members: Vec<TrelloMember>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrelloList {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub closed: bool,
+    #[serde(default)]
+    pub pos: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrelloCard {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub desc: String,
+    pub id_list: String,
+    #[serde(default)]
+    pub closed: bool,
+    #[serde(default)]
+    pub pos: f64,
+    #[serde(default)]
+    pub due: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrelloMember {
+    pub id: String,
+    #[serde(default)]
+    pub full_name: String,
+    #[serde(default)]
+    pub username: String,
+    pub email: Option<String>,
+}
+
+impl TrelloBoard {
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportAction {
+    Create,
+    /// 取り込み済み
+    Exists,
+    /// 取り込まない。理由を持つ
+    Skip(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportItem {
+    /// board, column, card, member のどれか
+    pub kind: &'static str,
+    /// Trelloでの名前
+    pub name: String,
+    /// 取り込み先のID。取り込まない場合はNone
+    pub id: Option<String>,
+    pub action: ImportAction,
+}
+
+/// 取り込む(dry-runでは取り込むはずだった)ものと、飛ばしたもの
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    pub items: Vec<ImportItem>,
+}
+
+impl ImportReport {
+    fn push(&mut self, kind: &'static str, name: &str, id: Option<String>, action: ImportAction) {
+        self.items.push(ImportItem {
+            kind,
+            name: name.to_owned(),
+            id,
+            action,
+        });
+    }
+
+    pub fn count(&self, f: impl Fn(&ImportAction) -> bool) -> usize {
+        self.items.iter().filter(|item| f(&item.action)).count()
+    }
+}
+
+impl Display for ImportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for item in &self.items {
+            match (&item.action, &item.id) {
+                (ImportAction::Skip(reason), _) => {
+                    writeln!(f, "skip    {} {:?}: {}", item.kind, item.name, reason)?
+                }
+                (action, id) => {
+                    let label = if action == &ImportAction::Create {
+                        "create"
+                    } else {
+                        "exists"
+                    };
+                    let id = id.as_deref().unwrap_or_default();
+                    writeln!(f, "{:<7} {} {} {:?}", label, item.kind, id, item.name)?
+                }
+            }
+        }
+        writeln!(
+            f,
+            "{} to create, {} already imported, {} skipped",
+            self.count(|a| a == &ImportAction::Create),
+            self.count(|a| a == &ImportAction::Exists),
+            self.count(|a| matches!(a, ImportAction::Skip(_))),
+        )
+    }
+}
+
+pub struct TrelloImporter {
+    user_repository: Box<dyn UserRepository>,
+    board_repository: Box<dyn BoardRepository>,
+    column_repository: Box<dyn ColumnRepository>,
+    users_query: Box<dyn UsersQuery>,
+    cards_query: Box<dyn CardsQuery>,
+}
+
+// 保存するものと報告
+struct ImportPlan {
+    report: ImportReport,
+    /// 作ったか変えた場合のみ
+    board: Option<Board>,
+    columns: Vec<Column>,
+}
+
+impl TrelloImporter {
+    pub fn new<Q, R>(query_module: &Q, repository_module: &R) -> Result<Self>
+    where
+        Q: HasProvider<dyn UsersQuery> + HasProvider<dyn CardsQuery>,
+        R: HasProvider<dyn UserRepository>
+            + HasProvider<dyn BoardRepository>
+            + HasProvider<dyn ColumnRepository>,
+    {
+        Ok(Self {
+            user_repository: provide(repository_module)?,
+            board_repository: provide(repository_module)?,
+            column_repository: provide(repository_module)?,
+            users_query: provide(query_module)?,
+            cards_query: provide(query_module)?,
+        })
+    }
+
+    /// `owner_id` のユーザーが所有するボードとして取り込む
+    /// `dry_run` のときは保存せずに、何を取り込むかだけを返す
+    pub async fn import(
+        &self,
+        export: &TrelloBoard,
+        owner_id: &str,
+        dry_run: bool,
+    ) -> Result<ImportReport> {
+        let plan = self.plan(export, &owner_id.parse()?).await?;
+        if !dry_run {
+            // ボードが存在しないカラムを指さないように、カラムから保存する
+            for column in plan.columns {
+                self.column_repository.save(column).await?;
+            }
+            if let Some(board) = plan.board {
+                self.board_repository.save(board).await?;
+            }
+        }
+        Ok(plan.report)
+    }
+
+    async fn plan(&self, export: &TrelloBoard, owner_id: &UserId) -> Result<ImportPlan> {
+        if self.user_repository.find_by_id(owner_id).await?.is_none() {
+            return Err(RepositoryError::NotFound(owner_id.to_string()).into());
+        }
+        let mut report = ImportReport::default();
+
+        let board_id: BoardId = imported_id(&export.id)?;
+        let (mut board, mut board_changed) =
+            match self.board_repository.find_by_id(&board_id).await? {
+                Some(board) => (board, false),
+                None => {
+                    let title = BoardTitle::new(export.name.clone())?;
+                    let board = Board::new(board_id, title, owner_id.clone(), vec![], vec![])?;
+                    (board, true)
+                }
+            };
+        let action = if board_changed {
+            ImportAction::Create
+        } else {
+            ImportAction::Exists
+        };
+        report.push("board", &export.name, Some(board.id().to_string()), action);
+
+        // Trelloのリストの並び順でカラムを加え、上限を超えた分は飛ばす
+        let mut lists: Vec<_> = export.lists.iter().collect();
+        lists.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+        // TrelloのリストIDごとのカラムと、変えたかどうか
+        let mut columns: HashMap<&str, (Column, bool)> = HashMap::new();
+        for list in lists {
+            if list.closed {
+                report.push("column", &list.name, None, skip("closed in Trello"));
+                continue;
+            }
+            let column_id: ColumnId = imported_id(&list.id)?;
+            let existing = self.column_repository.find_by_id(&column_id).await?;
+            // NOTE: 前回カラムだけ保存してボードの保存に失敗した場合は、ボードにないカラムが残っている
+            if !board.column_ids().contains(&column_id) {
+                if let Err(e) = board.add_column(column_id.clone()) {
+                    report.push("column", &list.name, None, skip(e));
+                    continue;
+                }
+                board_changed = true;
+            }
+            let (column, action) = match existing {
+                Some(column) => ((column, false), ImportAction::Exists),
+                None => {
+                    let column =
+                        Column::new_with_id(column_id, ColumnTitle::new(list.name.clone()));
+                    ((column, true), ImportAction::Create)
+                }
+            };
+            report.push(
+                "column",
+                &list.name,
+                Some(column.0.id().to_string()),
+                action,
+            );
+            columns.insert(list.id.as_str(), column);
+        }
+
+        let mut cards: Vec<_> = export.cards.iter().collect();
+        cards.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+        let card_ids = cards
+            .iter()
+            .map(|card| imported_id(&card.id))
+            .collect::<Result<Vec<CardId>>>()?;
+        // 取り込んだあとにほかのカラムへ移したカードも、取り込み済みにする
+        let imported_cards = self.cards_query.list_by_ids(&card_ids).await?;
+        for (card, card_id) in cards.into_iter().zip(card_ids) {
+            if card.closed {
+                report.push("card", &card.name, None, skip("closed in Trello"));
+                continue;
+            }
+            let Some((column, changed)) = columns.get_mut(card.id_list.as_str()) else {
+                report.push("card", &card.name, None, skip("its list is not imported"));
+                continue;
+            };
+            let exists = imported_cards.contains_key(&card_id)
+                || column.cards().iter().any(|c| c.id() == &card_id);
+            let action = if exists {
+                ImportAction::Exists
+            } else {
+                *column = column.clone().add_card_with_id(
+                    card_id.clone(),
+                    CardTitle::new(card.name.clone()),
+                    CardDescription::new(card.desc.clone()),
+                );
+                column.find_card_mut(&card_id)?.set_due_at(card.due);
+                *changed = true;
+                ImportAction::Create
+            };
+            report.push("card", &card.name, Some(card_id.to_string()), action);
+        }
+
+        let users = self.users_query.all(&UserCriteria::default()).await?;
+        for member in &export.members {
+            let name = if member.full_name.is_empty() {
+                &member.username
+            } else {
+                &member.full_name
+            };
+            let Some(email) = &member.email else {
+                report.push("member", name, None, skip("no email in the export"));
+                continue;
+            };
+            let Some(user) = users.iter().find(|u| u.email.eq_ignore_ascii_case(email)) else {
+                report.push("member", name, None, skip(format!("no user with {email}")));
+                continue;
+            };
+            let user_id: UserId = user.id.parse()?;
+            let action = if board.add_member(user_id) {
+                board_changed = true;
+                ImportAction::Create
+            } else {
+                ImportAction::Exists
+            };
+            report.push("member", name, Some(user.id.clone()), action);
+        }
+
+        Ok(ImportPlan {
+            report,
+            board: board_changed.then_some(board),
+            columns: columns
+                .into_values()
+                .filter_map(|(column, changed)| changed.then_some(column))
+                .collect(),
+        })
+    }
+}
+
+fn skip(reason: impl Display) -> ImportAction {
+    ImportAction::Skip(reason.to_string())
+}
+
+/// TrelloのID(24桁の16進数のObjectId)から取り込み先のIDを決める
+/// ObjectIdの先頭4バイトは作成日時の秒なので、ULIDの日時にする。残りの8バイトはそのまま使う
+fn imported_id<T: Entity>(trello_id: &str) -> Result<Identifier<T>> {
+    let invalid = || anyhow!("{} はTrelloのIDではありません", trello_id);
+    if trello_id.len() != 24 || !trello_id.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let seconds = u64::from_str_radix(&trello_id[..8], 16).map_err(|_| invalid())?;
+    let rest = u128::from_str_radix(&trello_id[8..], 16).map_err(|_| invalid())?;
+    Ok(Identifier::new(Ulid::from_parts(seconds * 1000, rest)))
+}
+
+#[cfg(test)]
+mod tests {
+    use infrastructure_memory::{modules, sample, Tables};
+
+    use super::*;
+
+    fn trello_id(n: usize) -> String {
+        format!("5f1a2b3c4d5e6f7a8b9c{:04x}", n)
+    }
+
+    fn export(list_count: usize, member_email: &str) -> TrelloBoard {
+        let lists: Vec<_> = (0..list_count)
+            .map(|i| {
+                serde_json::json!({
+                    "id": trello_id(100 + i),
+                    "name": format!("list {i}"),
+                    "closed": false,
+                    "pos": 1000 - i,
+                })
+            })
+            .collect();
+        let json = serde_json::json!({
+            "id": trello_id(1),
+            "name": "Trelloのボード",
+            "lists": lists,
+            "cards": [
+                {"id": trello_id(200), "name": "second", "desc": "", "idList": trello_id(100), "closed": false, "pos": 2},
+                {"id": trello_id(201), "name": "first", "desc": "説明", "idList": trello_id(100), "closed": false, "pos": 1, "due": "2026-10-31T09:00:00.000Z"},
+                {"id": trello_id(202), "name": "closed", "desc": "", "idList": trello_id(100), "closed": true, "pos": 3}
+            ],
+            "members": [
+                {"id": trello_id(300), "fullName": "matched", "email": member_email.to_uppercase()},
+                {"id": trello_id(301), "fullName": "no email", "username": "noemail"}
+            ]
+        });
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_imported_id() {
+        let a: Result<BoardId> = imported_id("5f1a2b3c4d5e6f7a8b9c0d1e");
+        let b: Result<BoardId> = imported_id("5f1a2b3c4d5e6f7a8b9c0d1e");
+        let invalid: Result<BoardId> = imported_id("not-a-trello-id");
+
+        assert_eq!(a.unwrap(), b.unwrap());
+        assert!(invalid.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_then_import_then_reimport() {
+        // Arrange
+        let (query_module, repository_module) = modules(Tables::seeded());
+        let importer = TrelloImporter::new(&query_module, &repository_module).unwrap();
+        let board_repository: Box<dyn BoardRepository> = repository_module.provide().unwrap();
+        let users = &sample::data().users;
+        let (owner, member) = (users[0].id.to_string(), &users[1]);
+        let export = export(2, &member.email);
+        let board_id: BoardId = imported_id(&export.id).unwrap();
+
+        // Act
+        let dry_run = importer.import(&export, &owner, true).await.unwrap();
+        let saved_after_dry_run = board_repository.find_by_id(&board_id).await.unwrap();
+        let imported = importer.import(&export, &owner, false).await.unwrap();
+        let reimported = importer.import(&export, &owner, false).await.unwrap();
+
+        // Assert
+        assert!(saved_after_dry_run.is_none());
+        assert_eq!(dry_run.items, imported.items);
+        // board, 2 columns, 2 cards, 1 member
+        assert_eq!(imported.count(|a| a == &ImportAction::Create), 6);
+        assert_eq!(imported.count(|a| matches!(a, ImportAction::Skip(_))), 2);
+        assert_eq!(reimported.count(|a| a == &ImportAction::Create), 0);
+        assert_eq!(reimported.count(|a| a == &ImportAction::Exists), 6);
+
+        let board = board_repository
+            .find_by_id(&board_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(board.members(), [member.id.clone()]);
+        // posの順に並べる
+        let column_repository: Box<dyn ColumnRepository> = repository_module.provide().unwrap();
+        let first_list: ColumnId = imported_id(&trello_id(100)).unwrap();
+        assert_eq!(board.column_ids().last(), Some(&first_list));
+        let column = column_repository
+            .find_by_id(&first_list)
+            .await
+            .unwrap()
+            .unwrap();
+        let titles: Vec<_> = column
+            .cards()
+            .iter()
+            .map(|c| c.title().to_string())
+            .collect();
+        assert_eq!(titles, vec!["first", "second"]);
+        let due_at: Vec<_> = column.cards().iter().map(|c| c.due_at().cloned()).collect();
+        assert_eq!(
+            due_at,
+            vec![Some("2026-10-31T09:00:00Z".parse().unwrap()), None]
+        );
+        let users_query: Box<dyn UsersQuery> = query_module.provide().unwrap();
+        assert_eq!(
+            users_query
+                .all(&UserCriteria::default())
+                .await
+                .unwrap()
+                .len(),
+            users.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_respects_max_column_count() {
+        // Arrange
+        let (query_module, repository_module) = modules(Tables::seeded());
+        let importer = TrelloImporter::new(&query_module, &repository_module).unwrap();
+        let owner = sample::data().users[0].id.to_string();
+        let export = export(Board::MAX_COLUMN_COUNT + 1, "nobody@example.com");
+
+        // Act
+        let report = importer.import(&export, &owner, false).await.unwrap();
+
+        // Assert
+        let columns = |action: fn(&ImportAction) -> bool| {
+            report
+                .items
+                .iter()
+                .filter(|item| item.kind == "column" && action(&item.action))
+                .count()
+        };
+        assert_eq!(
+            columns(|a| a == &ImportAction::Create),
+            Board::MAX_COLUMN_COUNT
+        );
+        assert_eq!(columns(|a| matches!(a, ImportAction::Skip(_))), 1);
+    }
+}